@@ -1,220 +1,5844 @@
-use std::collections::{BTreeMap, VecDeque};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    fmt,
+    ops::Range,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
 
 use crate::{
-    event::{AppEvent, Event, EventHandler},
-    gh_cli::{self, GithubJob},
+    client::GithubClient,
+    commands::COMMANDS,
+    config::{self, Config, RunOutcome},
+    crash_report,
+    dispatch_history::{DispatchHistory, DispatchRecord},
+    event::{
+        AppEvent, DEFAULT_REFRESH_INTERVAL, Event, EventHandler, JobDataEvent, OutcomeEvent,
+        PolledEvent, Throttle, ViewDataEvent,
+    },
+    export,
+    filters::{FilterMessage, FilterState},
+    flaky_tests::{self, FlakyTestScan},
+    gh_cli::{
+        self, ActionsBilling, ActionsSetting, Annotation, Artifact, CheckAnnotations, CommitState,
+        CommitStatus, EnvironmentStatus, GitRef, GithubJob, GithubRunner, GithubWorkflow,
+        GithubWorkflowRun, HistoryRun, LOG_TAIL_BYTES, PullRequest, RefKind, RepoInfo, RunDetails,
+        SsoAuthorization, WorkflowData, WorkflowFileDiff,
+    },
+    gh_extensions::{self, ExtensionAction},
+    instance_lock::{Holder, InstanceLock},
+    keymap::Keymap,
+    links::{self, ActionsLink, ActionsView},
+    log_dump,
+    logs::{self, LineKind, LogGroup, LogLine},
+    notifications,
+    pinned_jobs::PinnedJobs,
+    refresh_history::RefreshHistory,
+    run_history::RunHistory,
+    snapshot::Snapshot,
+    text_input::TextInput,
+    theme::{Theme, ThemePreset},
+    timeline::Timeline,
+    views::{View, ViewMessage, ViewStack},
+    workflow_mutes::WorkflowMutes,
 };
-use clap::Parser;
+use chrono::{DateTime, TimeDelta, Utc};
 use ratatui::{
     DefaultTerminal,
     crossterm::{
         self,
-        event::{KeyCode, KeyEvent, KeyModifiers},
+        event::{KeyCode, KeyEvent, KeyEventKind},
     },
 };
 const MAX_DISPLAYED_JOBS: usize = 300;
+/// How many matching branches and tags the dispatch form suggests at once.
+const MAX_REF_SUGGESTIONS: usize = 6;
+/// How many lines from the end of a job's log are kept for the triage view.
+const TRIAGE_LOG_TAIL_LINES: usize = 200;
+/// How many lines around the first error of a failed job's log the details panel shows.
+const FAILURE_EXCERPT_LINES: usize = 12;
+/// How many lines are left above a match of the log search jumped to.
+const LOG_SEARCH_CONTEXT_LINES: usize = 3;
+/// How many days GitHub keeps the logs and artifacts of runs unless a repository says
+/// otherwise, assumed when its setting can't be read.
+const DEFAULT_RETENTION_DAYS: i64 = 90;
+/// How long before GitHub deletes logs and artifacts they get a warning.
+const EXPIRY_WARNING: TimeDelta = TimeDelta::days(7);
+/// How long a toast stays on screen.
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+/// How long a warning or an error stays on screen, giving more time to read it.
+const ERROR_TOAST_DURATION: Duration = Duration::from_secs(8);
+/// How many toasts are stacked at most, the oldest making way for a new one.
+const MAX_TOASTS: usize = 3;
+/// How long a backfill of the run history waits between two pages, so that it takes a small
+/// share of the rate limit budget next to the refreshes.
+const BACKFILL_PAGE_INTERVAL: Duration = Duration::from_secs(3);
+/// How often a watched run is refreshed.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// How often an instance attached to another one checks for what that one saved.
+const ATTACHMENT_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// How long before a dispatched run's jobs start a dispatch from the history can have been
+/// sent to be taken as the one that started it.
+const DISPATCH_MATCH_WINDOW: Duration = Duration::from_secs(10 * 60);
+/// The file the export prompt starts out with.
+const DEFAULT_EXPORT_PATH: &str = "lazyactions-jobs.md";
+/// The file the suspected flaky tests are exported to.
+const FLAKY_TESTS_EXPORT_PATH: &str = "lazyactions-flaky-tests.md";
+/// How many earlier successful runs of a step are needed before it can be called slow.
+const MIN_STEP_SAMPLES: usize = 3;
+/// How many times its median duration a step must take to be called slow.
+const SLOW_STEP_FACTOR: i32 = 2;
+/// How much longer than its median a step must take to be called slow, so steps of a few
+/// seconds aren't flagged for the noise in their timing.
+const SLOW_STEP_MARGIN: TimeDelta = TimeDelta::seconds(30);
 
 #[derive(Debug)]
 pub struct App {
     pub running: bool,
     pub job_details: VecDeque<GithubJob>,
+    /// The runs fetched last, newest first within each repository.
+    pub runs: Vec<GithubWorkflowRun>,
+    pub runners: Vec<GithubRunner>,
+    /// Recent commits of the current branch across all repositories, newest first.
+    pub commits: Vec<CommitStatus>,
     pub current_job_index: usize,
     pub events: EventHandler,
     pub app_state: AppState,
-    pub gh_cli: crate::gh_cli::GhCli,
+    pub gh_cli: Arc<dyn GithubClient>,
     pub args: crate::Args,
+    pub config: Config,
+    pub keymap: Keymap,
+    pub theme: Theme,
+    pub theme_preset: ThemePreset,
+    pub dispatch_history: DispatchHistory,
+    pub workflow_mutes: WorkflowMutes,
+    pub pinned_jobs: PinnedJobs,
+    pub run_history: RunHistory,
+    /// The status of each run as of the last fetch, used to notice runs concluding.
+    pub run_statuses: HashMap<u64, String>,
+    /// Failed jobs acknowledged during triage, which are left out of later triage queues.
+    pub acked_jobs: HashSet<u64>,
+    pub timeline: Timeline,
+    pub refresh_history: RefreshHistory,
+    /// The jobs and runs of the latest refresh, put aside while an earlier one is shown.
+    pub live_data: Option<(VecDeque<GithubJob>, Vec<GithubWorkflowRun>)>,
+    /// The exit code of the process once the app quits.
+    pub exit_code: ExitCode,
+    /// Whether the jobs are saved for the next session to start with.
+    pub persist_session: bool,
+    /// The claim of this instance on polling its repositories, holding those no other instance
+    /// polls, `None` when the claim couldn't be made.
+    pub instance_lock: Option<InstanceLock>,
+    /// The instance this one shows the jobs of some or all of its repositories of instead of
+    /// polling GitHub for them itself.
+    pub attachment: Option<Attachment>,
+}
+
+/// How an instance started for repositories that another one already polls with the same
+/// filters follows what that one fetches, from the snapshots it saves after each refresh.
+#[derive(Debug)]
+pub struct Attachment {
+    /// The process ID of the instance polling, if it could be read.
+    pub pid: Option<u32>,
+    /// The repositories followed, as `owner/name`, the others being polled by this instance.
+    pub repos: Vec<String>,
+    /// What the runs polled are narrowed down by, to claim the repositories followed with once
+    /// the other instance has exited.
+    pub filters: String,
+    /// Whether this instance polls the rest of its repositories itself.
+    pub polling: bool,
+    /// When the snapshots shown were saved, `None` until they are first loaded.
+    pub loaded_at: Option<DateTime<Utc>>,
+    /// When to check for newer snapshots, or for the other instance having exited.
+    pub next_check: Instant,
+    /// How often to poll once the other instance has exited.
+    pub refresh_interval: Duration,
 }
 
 #[derive(Debug)]
 pub struct AppState {
     pub column_index: usize,
-    pub row_index: usize,
+    /// The selected row and scroll offset of each job column.
+    pub columns: [ColumnPosition; 3],
+    /// Where the list the third column isn't showing, failed or otherwise concluded jobs, was
+    /// left, swapped back in when it is shown again.
+    pub hidden_column: ColumnPosition,
+    /// Where the jobs of each column were last drawn, updated by every render.
+    pub column_layouts: RefCell<[ColumnLayout; 3]>,
     pub show_details: bool,
-    pub in_progress_jobs: BTreeMap<String, Vec<usize>>,
-    pub success_jobs: BTreeMap<String, Vec<usize>>,
-    pub failure_jobs: BTreeMap<String, Vec<usize>>,
+    /// Whether the details panel lists the links of the job, numbered, instead of its details.
+    pub show_links: bool,
+    pub show_api_inspector: bool,
+    pub in_progress_jobs: BTreeMap<JobGroup, Vec<ColumnRow>>,
+    pub success_jobs: BTreeMap<JobGroup, Vec<ColumnRow>>,
+    pub failure_jobs: BTreeMap<JobGroup, Vec<ColumnRow>>,
+    /// The jobs that concluded neither with success nor failure, e.g. cancelled or skipped.
+    pub other_jobs: BTreeMap<JobGroup, Vec<ColumnRow>>,
+    /// Whether the third column lists `other_jobs` instead of `failure_jobs`.
+    pub show_other_jobs: bool,
     pub loading_status: String,
+    /// When the jobs shown were saved, while they still come from the previous session.
+    pub stale_since: Option<DateTime<Utc>>,
+    /// When the jobs shown were last fetched in this session.
+    pub updated_at: Option<DateTime<Utc>>,
+    /// When the earlier refresh shown instead of the latest one was fetched.
+    pub time_travel: Option<DateTime<Utc>>,
+    /// The new run of the current branch to select the first job of once it is listed.
+    pub followed_run: Option<u64>,
+    pub selected_job: Option<GithubJob>,
+    /// The step selected in the details panel.
+    pub selected_step: usize,
+    /// The annotation selected in the details panel, which `A` opens.
+    pub selected_annotation: usize,
+    pub repo_index: usize,
+    pub saved_selections: HashMap<String, SelectionState>,
+    pub dispatch: Option<DispatchState>,
+    pub views: ViewStack,
+    pub job_summary: Option<JobSummaryState>,
+    pub json_inspector: Option<JsonInspectorState>,
+    /// The filters of the job columns, and the prompt the search is typed into.
+    pub filter: FilterState,
+    /// The matrices listed with their jobs, by run id and name, the others being collapsed.
+    pub expanded_matrices: HashSet<(u64, String)>,
+    pub workflow_sidebar: Option<WorkflowSidebarState>,
+    pub branch_picker: Option<BranchPickerState>,
+    pub command_palette: Option<CommandPaletteState>,
+    /// The job marked to have its log compared with the next one picked.
+    pub compare_mark: Option<GithubJob>,
+    pub triage: Option<TriageState>,
+    pub actions_settings: Option<ActionsSettingsState>,
+    pub rerun_confirmation: Option<RerunConfirmation>,
+    pub cancel_confirmation: Option<CancelConfirmation>,
+    pub approval_confirmation: Option<ApprovalConfirmation>,
+    pub full_log_confirmation: Option<FullLogConfirmation>,
+    pub deletion_confirmation: Option<DeletionConfirmation>,
+    pub yank_menu: Option<YankMenuState>,
+    pub export_prompt: Option<ExportPrompt>,
+    pub extension_menu: Option<ExtensionMenuState>,
+    pub badges: Option<BadgePopupState>,
+    pub extension_output: Option<ExtensionOutputState>,
+    /// The arguments of `gh` to run in the foreground once the UI steps aside, for a command
+    /// of an extension that takes over the terminal.
+    pub foreground_command: Option<Vec<String>>,
+    /// Why GitHub couldn't be reached on launch, shown in place of the errors of refreshes
+    /// until one succeeds.
+    pub connectivity_problem: Option<String>,
+    /// The SAML single sign-on that refreshes were refused for, waiting to be authorized.
+    pub sso_authorization: Option<SsoAuthorization>,
+    /// The organization whose single sign-on prompt was closed, not to be asked about again
+    /// on every refresh.
+    pub sso_dismissed: Option<String>,
+    /// The messages reporting the outcomes of actions, shown over the UI for a few seconds each,
+    /// which a refresh can't overwrite. The latest is last.
+    pub toasts: Vec<Toast>,
+    /// The log excerpts around the first error of failed jobs, by job id, `None` while the
+    /// log is being fetched.
+    pub failure_excerpts: HashMap<u64, Option<Result<Vec<String>, String>>>,
+    /// The jobs of which only the end of the log was fetched, as it is larger than
+    /// [`LOG_TAIL_BYTES`], with the size of the whole log when GitHub told it.
+    pub partial_logs: HashMap<u64, Option<u64>>,
+    /// The environment each job deployed to, if any, by job id, `None` while its deployments
+    /// are being looked up.
+    pub job_environments: HashMap<u64, Option<Result<Option<String>, String>>>,
+    /// Whether the refs of runs are tags or branches, by repository in lowercase and ref name,
+    /// `None` while being looked up.
+    pub ref_kinds: HashMap<(String, String), Option<Result<RefKind, String>>>,
+    /// The annotations of failed jobs, by job id, `None` while they are being fetched.
+    pub annotations: HashMap<u64, Option<Result<Vec<Annotation>, String>>>,
+    /// The annotations of the checks other apps failed on the commits of jobs, by repository in
+    /// lowercase and SHA, `None` while they are being fetched.
+    pub check_annotations: HashMap<(String, String), Option<Result<CheckAnnotations, String>>>,
+    /// The paths of the files changed by the commits of jobs, fetched once the filter has a
+    /// `path:` term.
+    pub changed_paths: ChangedPaths,
+    /// The artifacts of runs, by run id, `None` while they are being fetched.
+    pub artifacts: HashMap<u64, Option<Result<Vec<Artifact>, String>>>,
+    /// How many days each repository keeps the logs and artifacts of its runs, by lowercased
+    /// repository, `None` while being fetched or when it can't be read.
+    pub retention_days: HashMap<String, Option<u64>>,
+    /// What the owners of the repositories used in their billing cycle, by owner, as last
+    /// polled.
+    pub billing: HashMap<String, Result<ActionsBilling, String>>,
+    /// The backfill of the run history under way, `None` when there is none or it was paused.
+    pub backfill: Option<Backfill>,
+}
+
+impl AppState {
+    /// The rows listed in a column, grouped by tool.
+    pub fn column_jobs(&self, column: usize) -> &BTreeMap<JobGroup, Vec<ColumnRow>> {
+        match column {
+            0 => &self.in_progress_jobs,
+            1 => &self.success_jobs,
+            _ if self.show_other_jobs => &self.other_jobs,
+            _ => &self.failure_jobs,
+        }
+    }
+
+    /// The selected row of the selected job column.
+    pub fn selected_row(&self) -> Option<&ColumnRow> {
+        self.column_jobs(self.column_index)
+            .values()
+            .flatten()
+            .nth(self.position().row_index)
+    }
+
+    /// The position in the selected job column.
+    pub fn position(&self) -> &ColumnPosition {
+        &self.columns[self.column_index]
+    }
+
+    fn position_mut(&mut self) -> &mut ColumnPosition {
+        &mut self.columns[self.column_index]
+    }
+}
+
+/// Where a job column was left, kept for every column so that hopping between them doesn't
+/// lose the place in any of them.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ColumnPosition {
+    pub row_index: usize,
     pub scroll_offset: usize,
-    pub selected_job: Option<GithubJob>
 }
 
-impl Default for App {
-    fn default() -> Self {
-        let args_obj = crate::Args::parse();
-        let gh_cli_instance = gh_cli::GhCli::new(args_obj.branch, args_obj.user, args_obj.latest);
+/// What the jobs of a column are grouped by, the pinned jobs coming first.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum JobGroup {
+    Pinned,
+    /// The first part of the names of the jobs, e.g. `test` for `test / unit`.
+    Tool(String),
+}
+
+impl fmt::Display for JobGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobGroup::Pinned => write!(f, "pinned"),
+            JobGroup::Tool(tool) => write!(f, "{}", tool),
+        }
+    }
+}
+
+/// A row of a job column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnRow {
+    /// A job, as an index into `job_details`.
+    Job(usize),
+    /// The jobs of a matrix, e.g. `test (ubuntu, 1.75)` and `test (macos, 1.75)`, listed as
+    /// one row, followed by a row for each of them when expanded.
+    Matrix(MatrixRow),
+}
+
+impl ColumnRow {
+    /// The job selected with the row, the first one of a matrix.
+    pub fn job_index(&self) -> usize {
+        match self {
+            ColumnRow::Job(index) => *index,
+            ColumnRow::Matrix(matrix) => matrix.jobs[0],
+        }
+    }
+
+    /// The jobs listed by the row and no other, which are none of an expanded matrix as its
+    /// jobs have rows of their own.
+    pub fn jobs(&self) -> &[usize] {
+        match self {
+            ColumnRow::Job(index) => std::slice::from_ref(index),
+            ColumnRow::Matrix(matrix) if matrix.expanded => &[],
+            ColumnRow::Matrix(matrix) => &matrix.jobs,
+        }
+    }
+}
+
+/// The jobs of a matrix that are listed in the same column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixRow {
+    pub run_id: u64,
+    /// The name the jobs share, without the values of the matrix.
+    pub name: String,
+    /// The jobs, as indices into `job_details`, in the order they are listed.
+    pub jobs: Vec<usize>,
+    pub expanded: bool,
+}
+
+/// The rows the jobs of a column take once long lines are wrapped, as last rendered, so
+/// scrolling and selection can account for jobs taking a varying number of rows.
+#[derive(Debug, Default, Clone)]
+pub struct ColumnLayout {
+    /// The rows of each job in the column's order, including the header of its group for
+    /// the first job of a group.
+    pub jobs: Vec<Range<usize>>,
+    /// How many rows the whole column takes.
+    pub total_rows: usize,
+    /// How many rows fit on screen.
+    pub viewport_rows: usize,
+}
+
+impl ColumnLayout {
+    /// The furthest the column can be scrolled while still filling the screen.
+    pub fn max_scroll(&self) -> usize {
+        self.total_rows.saturating_sub(self.viewport_rows)
+    }
+
+    /// The scroll offset closest to `offset` at which the job in `row` is fully visible, or
+    /// its start is when it is taller than the screen.
+    pub fn offset_showing(&self, row: usize, offset: usize) -> usize {
+        let offset = offset.min(self.max_scroll());
+        match self.jobs.get(row) {
+            Some(rows) if rows.start < offset => rows.start,
+            Some(rows) if rows.end > offset + self.viewport_rows => {
+                rows.end.saturating_sub(self.viewport_rows).min(rows.start)
+            }
+            _ => offset,
+        }
+    }
+}
+
+/// A short message reporting the outcome of an action.
+#[derive(Debug)]
+pub struct Toast {
+    pub message: String,
+    pub level: ToastLevel,
+    pub expires_at: Instant,
+}
+
+/// How much a toast matters, which colors it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToastLevel {
+    /// Something started or changed, e.g. a setting toggled.
+    Info,
+    /// An action went through.
+    Success,
+    /// Something to keep in mind, that didn't stop anything.
+    Warning,
+    /// An action failed.
+    Error,
+}
+
+/// The prompt for the file the jobs in the columns are exported to.
+#[derive(Debug)]
+pub struct ExportPrompt {
+    pub path: TextInput,
+    /// Why the last export failed, shown until the path is changed.
+    pub error: Option<String>,
+}
+
+/// A rerun of a whole run, or of its failed jobs, waiting to be confirmed.
+#[derive(Debug)]
+pub struct RerunConfirmation {
+    /// The repository of the run, as `owner/name`.
+    pub repo: String,
+    pub run_id: u64,
+    pub workflow_name: String,
+    /// Whether only the failed jobs are rerun.
+    pub failed_only: bool,
+}
+
+impl RerunConfirmation {
+    /// What is rerun, e.g. `the failed jobs of CI run 1234`.
+    pub fn description(&self) -> String {
+        let jobs = if self.failed_only {
+            "the failed jobs"
+        } else {
+            "all jobs"
+        };
+        format!("{} of {} run {}", jobs, self.workflow_name, self.run_id)
+    }
+}
+
+/// A cancellation of a run that is still going, waiting to be confirmed.
+#[derive(Debug)]
+pub struct CancelConfirmation {
+    /// The repository of the run, as `owner/name`.
+    pub repo: String,
+    pub run_id: u64,
+    pub workflow_name: String,
+}
+
+impl CancelConfirmation {
+    /// What is cancelled, e.g. `CI run 1234`.
+    pub fn description(&self) -> String {
+        format!("{} run {}", self.workflow_name, self.run_id)
+    }
+}
+
+/// An approval of the deployments a run waits for, waiting to be confirmed.
+#[derive(Debug)]
+pub struct ApprovalConfirmation {
+    /// The repository of the run, as `owner/name`.
+    pub repo: String,
+    pub run_id: u64,
+    pub workflow_name: String,
+    pub head_branch: String,
+}
+
+impl ApprovalConfirmation {
+    /// The run waiting for approval, e.g. `Release run 1234 on main`.
+    pub fn description(&self) -> String {
+        format!(
+            "{} run {} on {}",
+            self.workflow_name, self.run_id, self.head_branch
+        )
+    }
+}
+
+/// How old the runs deleted along with the selected one are by default, in days.
+const DEFAULT_DELETION_AGE_DAYS: u32 = 30;
+/// The oldest the runs deleted along with the selected one can be asked to be, in days.
+const MAX_DELETION_AGE_DAYS: u32 = 9999;
+
+/// A deletion of a run, or of every completed run of its workflow older than some days,
+/// waiting to be confirmed.
+#[derive(Debug)]
+pub struct DeletionConfirmation {
+    /// The repository of the run, as `owner/name`.
+    pub repo: String,
+    pub run_id: u64,
+    pub workflow_name: String,
+    /// The path of the workflow file, e.g. `.github/workflows/ci.yml`.
+    pub path: String,
+    /// How many days old the runs of the workflow deleted instead are, `None` to delete the
+    /// run only.
+    pub older_than_days: Option<u32>,
+}
+
+impl DeletionConfirmation {
+    /// What is deleted, e.g. `CI run 1234` or `every run of CI older than 30 days`.
+    pub fn description(&self) -> String {
+        match self.older_than_days {
+            Some(days) => format!(
+                "every run of {} older than {} day{}",
+                self.workflow_name,
+                days,
+                if days == 1 { "" } else { "s" }
+            ),
+            None => format!("{} run {}", self.workflow_name, self.run_id),
+        }
+    }
+}
+
+/// Loading the whole of a log of which only the end was fetched, waiting to be confirmed.
+#[derive(Debug)]
+pub struct FullLogConfirmation {
+    pub job: GithubJob,
+    /// The size of the whole log, when GitHub told it.
+    pub total_bytes: Option<u64>,
+}
+
+/// The menu of what to copy about the selected job, taken when the menu is opened.
+#[derive(Debug)]
+pub struct YankMenuState {
+    pub items: Vec<YankItem>,
+    pub selected: usize,
+}
+
+/// Something the yank menu can copy, picked with its key.
+#[derive(Debug)]
+pub struct YankItem {
+    pub key: char,
+    /// What is copied, e.g. `the job URL`.
+    pub label: &'static str,
+    pub value: String,
+}
+
+/// A page about the selected job listed in its details, opened with its number.
+#[derive(Debug, PartialEq, Eq)]
+pub struct JobLink {
+    /// What the page is, e.g. `Commit`.
+    pub label: &'static str,
+    pub url: String,
+}
+
+/// State of the popup listing the markdown status badges of the workflows of a repository.
+#[derive(Debug)]
+pub struct BadgePopupState {
+    /// The repository of the workflows, as `owner/name`.
+    pub repo: String,
+    /// The workflows with a badge, `None` while they are being fetched.
+    pub workflows: Option<Result<Vec<GithubWorkflow>, String>>,
+    pub selected: usize,
+    /// The branch whose latest run the badges show, any branch's when empty.
+    pub branch: TextInput,
+}
+
+impl BadgePopupState {
+    /// The markdown of the badge of a workflow.
+    pub fn badge(&self, workflow: &GithubWorkflow) -> String {
+        let branch = Some(self.branch.value().trim()).filter(|branch| !branch.is_empty());
+        links::workflow_badge(&self.repo, &workflow.name, &workflow.path, branch)
+    }
+}
+
+/// State of the menu of the commands of the installed `gh` extensions.
+#[derive(Debug)]
+pub struct ExtensionMenuState {
+    /// The commands offered, `None` while the installed extensions are being listed.
+    pub actions: Option<Result<Vec<&'static ExtensionAction>, String>>,
+    pub selected: usize,
+    /// The repository the commands are run for, as `owner/name`.
+    pub repo: String,
+    pub branch: String,
+}
+
+/// State of the popup showing the output of a command of a `gh` extension.
+#[derive(Debug)]
+pub struct ExtensionOutputState {
+    pub label: &'static str,
+    /// The output, `None` while the command runs.
+    pub output: Option<Result<String, String>>,
+    pub scroll: u16,
+}
+
+/// State of the sidebar listing the workflows to pick those whose jobs are shown.
+#[derive(Debug, Default)]
+pub struct WorkflowSidebarState {
+    pub selected: usize,
+}
+
+/// State of the command palette, which runs an action picked by name.
+#[derive(Debug, Default)]
+pub struct CommandPaletteState {
+    /// The search narrowing down the commands, matched fuzzily.
+    pub query: TextInput,
+    pub selected: usize,
+}
+
+impl CommandPaletteState {
+    /// The commands matching the query, those starting with it first.
+    pub fn choices(&self) -> Vec<(&'static str, AppEvent)> {
+        let query = self.query.value().trim();
+        let mut matches: Vec<(&'static str, AppEvent)> = COMMANDS
+            .iter()
+            .copied()
+            .filter(|(name, _)| fuzzy_match(query, name))
+            .collect();
+        matches.sort_by_key(|(name, _)| !name.to_lowercase().starts_with(&query.to_lowercase()));
+        matches
+    }
+}
+
+/// State of the popup picking the branch whose runs are fetched.
+#[derive(Debug, Default)]
+pub struct BranchPickerState {
+    /// The repository the branches belong to, as `owner/name`.
+    pub repo: String,
+    /// The branches of the repository, `None` while they are being fetched.
+    pub branches: Option<Result<Vec<String>, String>>,
+    /// The search narrowing down the branches, matched fuzzily.
+    pub query: TextInput,
+    pub selected: usize,
+}
+
+impl BranchPickerState {
+    /// The choices matching the query, best matches first: `None` for every branch, listed
+    /// until something is typed, then the matching branches.
+    pub fn choices(&self) -> Vec<Option<&str>> {
+        let Some(Ok(branches)) = &self.branches else {
+            return Vec::new();
+        };
+        let query = self.query.value().trim();
+        // Branches starting with what was typed come before those merely containing it
+        let mut matches: Vec<&str> = branches
+            .iter()
+            .map(String::as_str)
+            .filter(|branch| fuzzy_match(query, branch))
+            .collect();
+        matches.sort_by_key(|branch| !branch.starts_with(query));
+        let all_branches = query.is_empty().then_some(None);
+        all_branches
+            .into_iter()
+            .chain(matches.into_iter().map(Some))
+            .collect()
+    }
+}
+
+/// State of the popup showing the Actions settings of the active repository.
+#[derive(Debug)]
+pub struct ActionsSettingsState {
+    /// The repository the settings belong to, as `owner/name`.
+    pub repo: String,
+    /// The settings, `None` while they are being fetched.
+    pub settings: Option<Vec<ActionsSetting>>,
+    pub error: Option<String>,
+}
+
+/// State of the dashboard following a single run, which replaces the job columns.
+#[derive(Debug)]
+pub struct WatchState {
+    /// The repository of the run, as `owner/name`.
+    pub repo: String,
+    pub run_id: u64,
+    /// The run and its jobs, `None` until they are first fetched.
+    pub details: Option<RunDetails>,
+    pub error: Option<String>,
+    pub scroll: usize,
+    /// When the run is next due to be refreshed, `None` while a refresh is in flight.
+    pub next_poll: Option<Instant>,
+    /// Whether to quit once the run concludes, as with `--watch`.
+    pub exit_on_conclusion: bool,
+}
+
+/// State of the triage view, which goes through failed jobs one at a time.
+#[derive(Debug)]
+pub struct TriageState {
+    /// The failed jobs left to triage.
+    pub queue: Vec<GithubJob>,
+    pub position: usize,
+    /// How many jobs were in the queue when triage started.
+    pub total: usize,
+    /// The summary of the current job as markdown, `None` while it is being fetched.
+    pub summary: Option<String>,
+    /// The last lines of the current job's log, `None` while it is being fetched.
+    pub log_tail: Option<Vec<String>>,
+    pub error: Option<String>,
+}
+
+impl TriageState {
+    /// The job being triaged.
+    pub fn current(&self) -> Option<&GithubJob> {
+        self.queue.get(self.position)
+    }
+}
+
+/// State of the view comparing the logs of two jobs side by side.
+#[derive(Debug)]
+pub struct LogCompareState {
+    pub panes: [LogPane; 2],
+    /// The first row shown, shared by both panes so they scroll together.
+    pub scroll: usize,
+    /// How the workflow file changed from the commit of the job that started first to that
+    /// of the other, `None` while it is being fetched.
+    pub workflow_diff: Option<Result<WorkflowFileDiff, String>>,
+    /// The search through both logs, `None` until `/` is pressed.
+    pub search: Option<LogSearch>,
+}
+
+/// A search through the logs of the comparison.
+#[derive(Debug, Default)]
+pub struct LogSearch {
+    pub query: TextInput,
+    /// Whether the query is being typed.
+    pub editing: bool,
+    /// The lines matching the query, as `(pane, line)`, from the top of the logs down.
+    pub matches: Vec<(usize, usize)>,
+    /// The match jumped to last.
+    pub current: usize,
+    /// Where the logs were scrolled to when the search started, the first match after which
+    /// is jumped to as the query is typed.
+    pub start_scroll: usize,
+}
+
+impl LogCompareState {
+    /// How many rows the longest of the logs takes.
+    pub fn row_count(&self) -> usize {
+        self.panes
+            .iter()
+            .map(|pane| pane.rows().len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Finds the lines of both logs matching the query of the search again.
+    pub fn update_search(&mut self) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        let query = search.query.value();
+        let line_count = self
+            .panes
+            .iter()
+            .map(|pane| pane.lines.as_ref().map_or(0, Vec::len))
+            .max()
+            .unwrap_or(0);
+        search.matches = (0..line_count)
+            .flat_map(|line| (0..self.panes.len()).map(move |pane| (pane, line)))
+            .filter(|&(pane, line)| {
+                self.panes[pane]
+                    .lines
+                    .as_ref()
+                    .and_then(|lines| lines.get(line))
+                    .is_some_and(|line| !logs::find_matches(&line.text, query).is_empty())
+            })
+            .collect();
+        search.current = search
+            .matches
+            .iter()
+            .position(|&(pane, line)| self.panes[pane].row_of(line) >= search.start_scroll)
+            .unwrap_or(0);
+        self.scroll_to_match();
+    }
+
+    /// Jumps `delta` matches from the current one, wrapping around the ends of the logs.
+    pub fn jump_to_match(&mut self, delta: isize) {
+        let Some(search) = self
+            .search
+            .as_mut()
+            .filter(|search| !search.matches.is_empty())
+        else {
+            return;
+        };
+        search.current =
+            (search.current as isize + delta).rem_euclid(search.matches.len() as isize) as usize;
+        self.scroll_to_match();
+    }
+
+    /// Scrolls to the current match, unfolding the group it is in and leaving a few lines of
+    /// context above it.
+    fn scroll_to_match(&mut self) {
+        if let Some(&(pane, line)) = self
+            .search
+            .as_ref()
+            .and_then(|search| search.matches.get(search.current))
+        {
+            let pane = &mut self.panes[pane];
+            pane.collapsed.retain(|&header| {
+                !pane
+                    .groups
+                    .iter()
+                    .any(|group| group.header == header && (header + 1..group.end).contains(&line))
+            });
+            self.scroll = pane.row_of(line).saturating_sub(LOG_SEARCH_CONTEXT_LINES);
+        }
+    }
+
+    /// Unfolds every group of both logs, or folds them all back when none is folded.
+    pub fn toggle_groups(&mut self) {
+        let any_collapsed = self.panes.iter().any(|pane| !pane.collapsed.is_empty());
+        for pane in &mut self.panes {
+            pane.collapsed = if any_collapsed {
+                BTreeSet::new()
+            } else {
+                pane.groups.iter().map(|group| group.header).collect()
+            };
+        }
+        self.scroll = self.scroll.min(self.row_count().saturating_sub(1));
+    }
+}
+
+/// One side of the log comparison.
+#[derive(Debug)]
+pub struct LogPane {
+    pub job: GithubJob,
+    /// The log without timestamps, `None` while it is being fetched.
+    pub lines: Option<Vec<LogLine>>,
+    /// The `##[group]` sections of the log.
+    pub groups: Vec<LogGroup>,
+    /// The headers of the groups shown folded.
+    pub collapsed: BTreeSet<usize>,
+    pub error: Option<String>,
+}
+
+impl LogPane {
+    pub fn new(job: GithubJob) -> Self {
+        Self {
+            job,
+            lines: None,
+            groups: Vec::new(),
+            collapsed: BTreeSet::new(),
+            error: None,
+        }
+    }
+
+    /// Takes in the fetched log. Its groups start folded as on GitHub, but for those with an
+    /// error.
+    pub fn set_log(&mut self, log: &str) {
+        let lines: Vec<LogLine> = logs::log_lines(log)
+            .iter()
+            .map(|line| LogLine::parse(line))
+            .collect();
+        self.groups = logs::groups(&lines);
+        self.collapsed = self
+            .groups
+            .iter()
+            .filter(|group| !group.has_error)
+            .map(|group| group.header)
+            .collect();
+        self.lines = Some(lines);
+    }
+
+    /// The lines shown, by index: every line but the ends of the groups and the insides of
+    /// those folded.
+    pub fn rows(&self) -> Vec<usize> {
+        let Some(lines) = &self.lines else {
+            return Vec::new();
+        };
+        let mut rows = Vec::with_capacity(lines.len());
+        let mut index = 0;
+        while index < lines.len() {
+            if lines[index].kind != LineKind::GroupEnd {
+                rows.push(index);
+            }
+            index = match self.groups.iter().find(|group| group.header == index) {
+                Some(group) if self.collapsed.contains(&index) => group.end.max(index + 1),
+                _ => index + 1,
+            };
+        }
+        rows
+    }
+
+    /// The row showing `line`, or the header of the folded group hiding it.
+    pub fn row_of(&self, line: usize) -> usize {
+        let rows = self.rows();
+        match rows.binary_search(&line) {
+            Ok(row) => row,
+            Err(row) => row.saturating_sub(1),
+        }
+    }
+}
+
+/// State of the popup showing the summary a job published.
+#[derive(Debug)]
+pub struct JobSummaryState {
+    pub job_id: u64,
+    pub job_name: String,
+    /// The summary as markdown, `None` while it is being fetched.
+    pub markdown: Option<String>,
+    pub error: Option<String>,
+    pub scroll: u16,
+}
+
+/// State of the popup showing the raw JSON behind the selected job.
+#[derive(Debug)]
+pub struct JsonInspectorState {
+    pub job_id: u64,
+    pub job_name: String,
+    /// The pretty-printed JSON, `None` while it is being fetched.
+    pub json: Option<Result<String, String>>,
+    pub scroll: u16,
+}
+
+/// State of the deploy dashboard, which replaces the job columns while it is shown.
+#[derive(Debug, Default)]
+pub struct DeployViewState {
+    /// The environments of the active repository, `None` until they are first fetched.
+    pub environments: Option<Vec<EnvironmentStatus>>,
+    pub selected: usize,
+    pub error: Option<String>,
+}
+
+/// State of the usage view, which sums up the runner minutes of the active repository's runs
+/// in place of the job columns.
+#[derive(Debug)]
+pub struct UsageViewState {
+    /// The organization or user whose Actions minutes are billed.
+    pub owner: String,
+    /// What the account used in its billing cycle, `None` until it is fetched, and an error
+    /// unless the billing settings of the account are accessible.
+    pub billing: Option<Result<ActionsBilling, String>>,
+    pub scroll: usize,
+}
+
+/// State of the workspace overview, which summarizes every monitored repository in place of
+/// the job columns.
+#[derive(Debug, Default)]
+pub struct OverviewState {
+    /// The index of the selected repository.
+    pub selected: usize,
+}
+
+/// A repository as summarized by the workspace overview.
+#[derive(Debug)]
+pub struct RepoOverview<'a> {
+    pub repo: String,
+    pub in_progress: usize,
+    pub success: usize,
+    pub failure: usize,
+    pub other: usize,
+    /// The failed job that completed last.
+    pub last_failure: Option<&'a GithubJob>,
+}
+
+/// State of the run view, which lists the runs with the jobs of the selected one in place of
+/// the job columns. Runs and jobs are selected by id, so refreshes keep the selection.
+#[derive(Debug, Default)]
+pub struct RunViewState {
+    pub run_id: Option<u64>,
+    pub job_id: Option<u64>,
+    /// Whether `Up`/`Down` move through the jobs rather than the runs.
+    pub jobs_focused: bool,
+    /// The run whose jobs are all being fetched.
+    pub expanding: Option<u64>,
+}
+
+/// State of the timeline view, which replaces the job columns while it is shown.
+#[derive(Debug, Default)]
+pub struct TimelineViewState {
+    /// The selected entry, counted from the newest.
+    pub selected: usize,
+}
+
+/// A backfill paging older runs of a repository into the run history, a page at a time.
+#[derive(Debug)]
+pub struct Backfill {
+    /// The repository, as `owner/name`.
+    pub repo: String,
+    /// The page of runs being fetched.
+    pub page: u64,
+}
+
+/// State of the tests of a workflow suspected of being flaky, listed in place of the job
+/// columns.
+#[derive(Debug)]
+pub struct FlakyTestsState {
+    /// The repository of the workflow, as `owner/name`.
+    pub repo: String,
+    pub workflow_name: String,
+    /// The path of the workflow file, e.g. `.github/workflows/ci.yml`.
+    pub path: String,
+    /// What scanning the test reports found, `None` while they are being scanned.
+    pub scan: Option<Result<FlakyTestScan, String>>,
+    pub selected: usize,
+}
+
+/// State of the history of a workflow, which lists its runs newest first in place of the job
+/// columns.
+#[derive(Debug)]
+pub struct WorkflowHistoryState {
+    /// The repository of the workflow, as `owner/name`.
+    pub repo: String,
+    pub workflow_name: String,
+    /// The path of the workflow file, e.g. `.github/workflows/ci.yml`.
+    pub path: String,
+    /// The runs fetched so far, newest first.
+    pub runs: Vec<HistoryRun>,
+    /// How many runs the workflow has, `None` until the first page is fetched.
+    pub total_count: Option<u64>,
+    /// How many pages of runs were fetched.
+    pub pages: u64,
+    /// Whether the next page of runs is being fetched.
+    pub loading: bool,
+    pub selected: usize,
+    pub error: Option<String>,
+}
+
+impl WorkflowHistoryState {
+    /// Whether the workflow has older runs than those fetched.
+    pub fn has_older_runs(&self) -> bool {
+        self.total_count
+            .is_some_and(|total_count| (self.runs.len() as u64) < total_count)
+    }
+}
+
+/// State of the workflow dispatch popup.
+#[derive(Debug, Default)]
+pub struct DispatchState {
+    /// The workflows of the active repository, `None` while they are being fetched.
+    pub workflows: Option<Vec<GithubWorkflow>>,
+    pub selected: usize,
+    pub error: Option<String>,
+    /// Whether previous dispatches are listed instead of the workflows.
+    pub show_history: bool,
+    pub history_selected: usize,
+    pub form: Option<DispatchForm>,
+    /// The branches and tags to pick the ref from, `None` while they are being fetched.
+    pub refs: Option<Result<Vec<GitRef>, String>>,
+}
+
+impl DispatchState {
+    /// The branches and tags matching the ref typed in the form, best matches first.
+    pub fn ref_suggestions(&self) -> Vec<&GitRef> {
+        let (Some(form), Some(Ok(refs))) = (&self.form, &self.refs) else {
+            return Vec::new();
+        };
+        let query = form.git_ref.value().trim();
+        // Refs starting with what was typed come before those merely containing it
+        let mut suggestions: Vec<&GitRef> = refs
+            .iter()
+            .filter(|git_ref| fuzzy_match(query, &git_ref.name) && git_ref.name != query)
+            .collect();
+        suggestions.sort_by_key(|git_ref| !git_ref.name.starts_with(query));
+        suggestions.truncate(MAX_REF_SUGGESTIONS);
+        suggestions
+    }
+}
+
+/// The form used to enter the ref and inputs of a workflow dispatch.
+#[derive(Debug)]
+pub struct DispatchForm {
+    pub workflow: GithubWorkflow,
+    pub git_ref: TextInput,
+    /// Workflow inputs as comma separated `key=value` pairs.
+    pub inputs: TextInput,
+    pub focused_field: DispatchField,
+    /// The highlighted suggestion for the ref, if any.
+    pub ref_selected: Option<usize>,
+    pub error: Option<String>,
+}
+
+/// The fields of a [`DispatchForm`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispatchField {
+    Ref,
+    Inputs,
+}
+
+/// The navigation state saved for a repository while another one is shown.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SelectionState {
+    pub column_index: usize,
+    pub columns: [ColumnPosition; 3],
+    pub hidden_column: ColumnPosition,
+}
+
+impl App {
+    /// Constructs a new instance of [`App`] monitoring the repositories given on the command
+    /// line, with the settings of the config file and the jobs of the previous session.
+    pub fn new(args: crate::Args, config: Config) -> Self {
+        let gh_cli: Arc<dyn GithubClient> = Arc::new(gh_cli::GhCli::new(
+            args.branch,
+            args.user,
+            args.latest,
+            args.repos.clone(),
+            args.backend,
+        ));
+        // Refreshing without a pause in between would only burn through the rate limit
+        let refresh_interval = args
+            .interval
+            .or(config.interval)
+            .map_or(DEFAULT_REFRESH_INTERVAL, |seconds| {
+                Duration::from_secs(seconds.max(1))
+            });
+        // A claim that can't be made, e.g. in a read-only cache, only lets instances both poll
+        let repos: Vec<String> = gh_cli.repos().iter().map(RepoInfo::full_name).collect();
+        let filters = poll_filters(gh_cli.as_ref(), args.latest);
+        let (instance_lock, held) = match InstanceLock::acquire(&repos, &filters) {
+            Ok(claims) => (Some(claims.lock), claims.held),
+            Err(_) => (None, Vec::new()),
+        };
+        // Only the repositories another instance fetches the same runs of can be followed
+        let (followed, shared): (Vec<Holder>, Vec<Holder>) = held
+            .into_iter()
+            .partition(|holder| holder.same_filters && !args.standalone);
+        let followed_repos: Vec<String> =
+            followed.iter().map(|holder| holder.repo.clone()).collect();
+        gh_cli.set_followed_repos(&followed_repos);
+        let polling = followed_repos.len() < repos.len();
+        let events = if polling {
+            EventHandler::new(gh_cli.clone(), refresh_interval)
+        } else {
+            EventHandler::without_polling(gh_cli.clone())
+        };
+        let mut app = Self::with_client(args, config, gh_cli, events);
+        app.instance_lock = instance_lock;
+        app.dispatch_history = DispatchHistory::load();
+        app.workflow_mutes = WorkflowMutes::load();
+        app.pinned_jobs = PinnedJobs::load();
+        app.run_history = RunHistory::load();
+        app.persist_session = true;
+        let compatibility_notes = app.gh_cli.compatibility_notes().join(" ");
+        if !compatibility_notes.is_empty() {
+            app.show_toast(compatibility_notes, ToastLevel::Info);
+        }
+        for warning in app.gh_cli.startup_warnings().to_vec() {
+            app.show_toast(warning, ToastLevel::Warning);
+        }
+        // A proxy or firewall in the way would otherwise only show as failing refreshes
+        app.events
+            .request(|gh_cli| PolledEvent::ConnectivityChecked(gh_cli.diagnose_connectivity()));
+        // Several repositories are summarized side by side before drilling into one
+        if app.gh_cli.repos().len() > 1 && app.args.watch.is_none() {
+            app.app_state
+                .views
+                .update(ViewMessage::Open(View::Overview(OverviewState::default())));
+        }
+        if let Some(run_id) = app.args.watch
+            && let Some(repo_info) = app.gh_cli.repos().first()
+        {
+            // Watching from the command line only succeeds if the run does
+            app.exit_code = ExitCode::FAILURE;
+            app.start_watch(repo_info.full_name(), run_id, true);
+        }
+        app.load_snapshots();
+        if let Some(holder) = followed.first() {
+            if polling {
+                app.show_toast(
+                    format!(
+                        "Showing what {} fetches for {}, polling the other repositories.",
+                        instance_name(holder.pid),
+                        followed_repos.join(", ")
+                    ),
+                    ToastLevel::Info,
+                );
+            } else {
+                app.app_state.loading_status = attached_status(holder.pid);
+            }
+            app.attachment = Some(Attachment {
+                pid: holder.pid,
+                repos: followed_repos,
+                filters,
+                polling,
+                loaded_at: None,
+                next_check: Instant::now(),
+                refresh_interval,
+            });
+        }
+        if let Some(holder) = shared.first() {
+            let repos: Vec<&str> = shared.iter().map(|holder| holder.repo.as_str()).collect();
+            app.show_toast(
+                format!(
+                    "Polling {} although {} already polls {}, both spend the rate limit.",
+                    repos.join(", "),
+                    instance_name(holder.pid),
+                    if holder.same_filters {
+                        "them"
+                    } else {
+                        "them with other filters"
+                    }
+                ),
+                ToastLevel::Warning,
+            );
+        }
+        app
+    }
+
+    /// Shows what the instance this one is attached to last saved, if it saved anything
+    /// newer, or takes over polling the repositories it no longer polls with the same filters,
+    /// e.g. once it has exited.
+    fn follow_attachment(&mut self) {
+        let Some(attachment) = self
+            .attachment
+            .as_mut()
+            .filter(|attachment| attachment.next_check <= Instant::now())
+        else {
+            return;
+        };
+        attachment.next_check = Instant::now() + ATTACHMENT_CHECK_INTERVAL;
+        if let Ok(claims) = InstanceLock::acquire(&attachment.repos, &attachment.filters) {
+            let followed: Vec<String> = claims
+                .held
+                .into_iter()
+                .filter(|holder| holder.same_filters)
+                .map(|holder| holder.repo)
+                .collect();
+            if followed.len() < attachment.repos.len() {
+                let (polling, refresh_interval) = (attachment.polling, attachment.refresh_interval);
+                attachment.repos = followed.clone();
+                attachment.polling = true;
+                match self.instance_lock.as_mut() {
+                    Some(lock) => lock.extend(claims.lock),
+                    None => self.instance_lock = Some(claims.lock),
+                }
+                self.gh_cli.set_followed_repos(&followed);
+                if polling {
+                    self.events.refresh();
+                } else {
+                    self.events.start_polling(refresh_interval);
+                }
+                if followed.is_empty() {
+                    self.attachment = None;
+                    self.show_toast(
+                        "The instance this one was attached to exited, polling GitHub again."
+                            .to_string(),
+                        ToastLevel::Info,
+                    );
+                    return;
+                }
+            }
+        }
+        let Some(attachment) = self.attachment.as_mut() else {
+            return;
+        };
+        let snapshots: Vec<Snapshot> = attachment
+            .repos
+            .iter()
+            .filter_map(|repo| Snapshot::load(repo))
+            .collect();
+        let Some(saved_at) = snapshots.iter().map(|snapshot| snapshot.saved_at).max() else {
+            return;
+        };
+        if attachment
+            .loaded_at
+            .is_some_and(|loaded_at| loaded_at >= saved_at)
+        {
+            return;
+        }
+        attachment.loaded_at = Some(saved_at);
+        let (pid, polling) = (attachment.pid, attachment.polling);
+        let mut workflow_data = WorkflowData {
+            runs: Vec::new(),
+            jobs: Vec::new(),
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        };
+        for snapshot in snapshots {
+            workflow_data.runs.extend(snapshot.data.runs);
+            workflow_data.jobs.extend(snapshot.data.jobs);
+        }
+        // The repositories polled by this instance are updated by its own refreshes
+        self.keep_shown_runs(&mut workflow_data, false);
+        let (updated_at, loading_status) = (
+            self.app_state.updated_at,
+            self.app_state.loading_status.clone(),
+        );
+        self.update_github_data(workflow_data);
+        if polling {
+            self.app_state.updated_at = updated_at;
+            self.app_state.loading_status = loading_status;
+        } else {
+            self.app_state.updated_at = Some(saved_at);
+            self.app_state.loading_status = attached_status(pid);
+        }
+    }
+
+    /// Adds the runs shown of the repositories followed, or of those polled by this instance
+    /// when not `followed`, to `workflow_data` as unchanged, for them and their jobs to stay
+    /// listed as the other repositories are updated.
+    fn keep_shown_runs(&self, workflow_data: &mut WorkflowData, followed: bool) {
+        let Some(attachment) = &self.attachment else {
+            return;
+        };
+        for run in self.runs.iter().filter(|run| {
+            let is_followed = attachment
+                .repos
+                .iter()
+                .any(|repo| repo.eq_ignore_ascii_case(&run.repo));
+            is_followed == followed
+        }) {
+            workflow_data.unchanged_runs.push(run.id);
+            workflow_data.runs.push(run.clone());
+        }
+    }
+
+    /// Constructs an [`App`] talking to GitHub through `gh_cli` and receiving events from
+    /// `events`, which starts out empty and leaves nothing on disk, e.g. to drive it with
+    /// fixtures.
+    pub fn with_client(
+        args: crate::Args,
+        config: Config,
+        gh_cli: Arc<dyn GithubClient>,
+        events: EventHandler,
+    ) -> Self {
+        let theme_preset = args.theme.unwrap_or(config.theme.preset);
         Self {
             running: true,
             job_details: VecDeque::new(),
+            runs: Vec::new(),
+            runners: Vec::new(),
+            commits: Vec::new(),
             current_job_index: 0,
-            gh_cli: gh_cli_instance.clone(),
-            events: EventHandler::new(gh_cli_instance),
+            gh_cli,
+            events,
             app_state: AppState {
                 column_index: 0,
-                row_index: 0,
+                columns: Default::default(),
+                hidden_column: ColumnPosition::default(),
+                column_layouts: Default::default(),
                 show_details: false,
+                show_links: false,
+                show_api_inspector: false,
                 in_progress_jobs: BTreeMap::new(),
                 success_jobs: BTreeMap::new(),
                 failure_jobs: BTreeMap::new(),
+                other_jobs: BTreeMap::new(),
+                show_other_jobs: false,
                 loading_status: "Initializing...".to_string(),
-                scroll_offset: 0,
+                stale_since: None,
+                time_travel: None,
+                updated_at: None,
+                followed_run: None,
                 selected_job: None,
+                selected_step: 0,
+                selected_annotation: 0,
+                repo_index: 0,
+                saved_selections: HashMap::new(),
+                dispatch: None,
+                views: ViewStack::default(),
+                job_summary: None,
+                json_inspector: None,
+                filter: FilterState::default(),
+                expanded_matrices: HashSet::new(),
+                workflow_sidebar: None,
+                branch_picker: None,
+                command_palette: None,
+                compare_mark: None,
+                triage: None,
+                actions_settings: None,
+                rerun_confirmation: None,
+                cancel_confirmation: None,
+                approval_confirmation: None,
+                full_log_confirmation: None,
+                deletion_confirmation: None,
+                extension_menu: None,
+                badges: None,
+                extension_output: None,
+                foreground_command: None,
+                yank_menu: None,
+                export_prompt: None,
+                connectivity_problem: None,
+                sso_authorization: None,
+                sso_dismissed: None,
+                toasts: Vec::new(),
+                failure_excerpts: HashMap::new(),
+                partial_logs: HashMap::new(),
+                job_environments: HashMap::new(),
+                ref_kinds: HashMap::new(),
+                changed_paths: HashMap::new(),
+                annotations: HashMap::new(),
+                check_annotations: HashMap::new(),
+                artifacts: HashMap::new(),
+                retention_days: HashMap::new(),
+                billing: HashMap::new(),
+                backfill: None,
             },
-            args: args_obj,
+            args,
+            keymap: Keymap::new(&config.keymap),
+            theme: Theme::new(theme_preset, &config.theme.colors),
+            theme_preset,
+            config,
+            dispatch_history: DispatchHistory::default(),
+            workflow_mutes: WorkflowMutes::default(),
+            pinned_jobs: PinnedJobs::default(),
+            run_history: RunHistory::default(),
+            run_statuses: HashMap::new(),
+            acked_jobs: HashSet::new(),
+            timeline: Timeline::default(),
+            refresh_history: RefreshHistory::default(),
+            live_data: None,
+            exit_code: ExitCode::SUCCESS,
+            persist_session: false,
+            instance_lock: None,
+            attachment: None,
+        }
+    }
+
+    /// Shows the jobs saved by the previous session until the first fetch replaces them.
+    fn load_snapshots(&mut self) {
+        let snapshots: Vec<Snapshot> = self
+            .gh_cli
+            .repos()
+            .iter()
+            .filter_map(|repo_info| Snapshot::load(&repo_info.full_name()))
+            .collect();
+        self.app_state.stale_since = snapshots.iter().map(|snapshot| snapshot.saved_at).min();
+        for snapshot in snapshots {
+            self.runs.extend(snapshot.data.runs);
+            self.job_details.extend(snapshot.data.jobs);
+        }
+        while self.job_details.len() > MAX_DISPLAYED_JOBS {
+            self.job_details.pop_front();
+        }
+        self.group_jobs();
+    }
+
+    /// Saves the runs and jobs of every repository this instance polls for the next session
+    /// to start with, and for the instances following it.
+    fn save_snapshots(&self, runs: &[GithubWorkflowRun]) {
+        if !self.persist_session {
+            return;
+        }
+        let saved_at = Utc::now();
+        for repo_info in self.gh_cli.repos() {
+            let repo = repo_info.full_name();
+            // The snapshots of the repositories another instance polls are its to write
+            if let Some(lock) = &self.instance_lock
+                && !lock.holds(&repo)
+            {
+                continue;
+            }
+            let snapshot = Snapshot {
+                saved_at,
+                data: WorkflowData {
+                    runs: runs
+                        .iter()
+                        .filter(|run| run.repo.eq_ignore_ascii_case(&repo))
+                        .cloned()
+                        .collect(),
+                    jobs: self
+                        .job_details
+                        .iter()
+                        .filter(|job| job.repo.eq_ignore_ascii_case(&repo))
+                        .cloned()
+                        .collect(),
+                    unchanged_runs: Vec::new(),
+                    warnings: Vec::new(),
+                    fetched_runs: HashMap::new(),
+                },
+            };
+            // Without a snapshot the next start merely waits for its first fetch
+            let _ = snapshot.save(&repo);
+        }
+    }
+
+    /// Run the application's main loop, returning the exit code of the process.
+    pub fn run(mut self, mut terminal: DefaultTerminal) -> color_eyre::Result<ExitCode> {
+        while self.running {
+            terminal.draw(|frame| frame.render_widget(&self, frame.area()))?;
+            self.handle_events()?;
+            if let Some(args) = self.app_state.foreground_command.take() {
+                self.run_in_foreground(&mut terminal, &args);
+            }
+        }
+        Ok(self.exit_code)
+    }
+
+    /// Waits for the next event and hands it to the handler of its family.
+    pub fn handle_events(&mut self) -> color_eyre::Result<()> {
+        let event = self.events.next_event()?;
+        crash_report::record(&event);
+        match event {
+            Event::Tick => self.tick(),
+            Event::Crossterm(event) => {
+                // Windows reports releasing a key as well as pressing it
+                if let crossterm::event::Event::Key(key_event) = event
+                    && key_event.kind != KeyEventKind::Release
+                {
+                    self.handle_key_event(key_event)?
+                }
+            }
+            Event::App(app_event) => self.route_app_event(app_event),
+            Event::Polled(event) => self.handle_polled_event(event),
+            Event::Outcome(event) => self.handle_outcome_event(event),
+            Event::JobData(event) => self.handle_job_data_event(event),
+            Event::ViewData(event) => self.handle_view_data_event(event),
+        }
+        Ok(())
+    }
+
+    /// Handles the data polled on a schedule, and the older runs backfilled along with it.
+    fn handle_polled_event(&mut self, event: PolledEvent) {
+        match event {
+            PolledEvent::Action => {
+                // This event now only signals that a data fetch has been *triggered*.
+                // You can update a loading status in the UI here.
+                self.app_state.loading_status = "Fetching data...".to_string();
+            }
+            PolledEvent::GitHubDataFetched(result) => {
+                // This is where the actual data (or error) arrives.
+                match result {
+                    Ok(mut workflow_data) => {
+                        self.app_state.connectivity_problem = None;
+                        // The latest jobs keep being updated while an earlier refresh is shown
+                        let time_travel = self.app_state.time_travel;
+                        self.return_to_present();
+                        let warnings = workflow_data.warnings.clone();
+                        let fetched_runs = std::mem::take(&mut workflow_data.fetched_runs);
+                        // The runs of the repositories another instance polls aren't fetched
+                        self.keep_shown_runs(&mut workflow_data, true);
+                        // The repositories of another organization may still have been fetched
+                        for warning in &warnings {
+                            self.prompt_sso_authorization(warning);
+                        }
+                        self.update_github_data(workflow_data);
+                        // The jobs of these runs are kept from now on rather than refetched
+                        self.gh_cli.remember_fetched_runs(fetched_runs);
+                        if let Some(fetched_at) = time_travel {
+                            let index = self.refresh_history.position(fetched_at).unwrap_or(0);
+                            self.show_past_refresh(index);
+                        }
+                        self.app_state.loading_status = match warnings.len() {
+                            0 => "Data updated.".to_string(),
+                            1 => format!("Data updated with a warning: {}", warnings[0]),
+                            count => format!(
+                                "Data updated with {} warnings: {}",
+                                count,
+                                warnings.join("; ")
+                            ),
+                        };
+                        // Keep the deploy dashboard as fresh as the job columns
+                        if self.app_state.views.state::<DeployViewState>().is_some() {
+                            self.fetch_environments();
+                        }
+                    }
+                    Err(e) => {
+                        self.prompt_sso_authorization(&e);
+                        self.app_state.loading_status = format!(
+                            "Error: {}",
+                            self.app_state.connectivity_problem.as_ref().unwrap_or(&e)
+                        );
+                    }
+                }
+            }
+            PolledEvent::ConnectivityChecked(result) => {
+                if let Err(problem) = result {
+                    self.app_state.loading_status = format!("Error: {}", problem);
+                    self.show_toast(problem.clone(), ToastLevel::Error);
+                    self.app_state.connectivity_problem = Some(problem);
+                }
+            }
+            PolledEvent::RunnersFetched(result) => {
+                // Runner status needs admin access to the repo, so failures are not surfaced.
+                self.runners = result.unwrap_or_default();
+            }
+            PolledEvent::CommitsFetched(result) => {
+                // A failed refresh keeps the previous strip rather than blanking the header
+                if let Ok(commits) = result {
+                    self.commits = commits;
+                }
+            }
+            PolledEvent::HistoryBackfilled(repo, page, result) => {
+                let awaited = self
+                    .app_state
+                    .backfill
+                    .as_ref()
+                    .is_some_and(|backfill| backfill.repo == repo && backfill.page == page);
+                match result {
+                    Ok(fetched) => {
+                        if let Err(e) = self.run_history.record(&repo, page, fetched) {
+                            self.show_toast(
+                                format!("Could not save the run history: {:?}", e),
+                                ToastLevel::Error,
+                            );
+                        }
+                        // A page fetched before pausing is kept without going on
+                        if awaited {
+                            self.continue_backfill();
+                        }
+                    }
+                    Err(e) if awaited => {
+                        self.app_state.backfill = None;
+                        self.show_toast(e, ToastLevel::Error);
+                    }
+                    Err(_) => {}
+                }
+            }
+            PolledEvent::BillingFetched(owner, result) => {
+                if let Some(usage_view) = self
+                    .app_state
+                    .views
+                    .state_mut::<UsageViewState>()
+                    .filter(|usage_view| usage_view.owner == owner)
+                {
+                    usage_view.billing = Some(result.clone());
+                }
+                self.app_state.billing.insert(owner, result);
+            }
+        }
+    }
+
+    /// Handles the outcome of what was done on GitHub or on disk, e.g. a rerun, told with a toast.
+    fn handle_outcome_event(&mut self, event: OutcomeEvent) {
+        match event {
+            OutcomeEvent::WorkflowDispatched(result) => match result {
+                Ok(record) => {
+                    self.show_toast(
+                        format!(
+                            "Dispatched {} on {}. The run will appear after the next refresh.",
+                            record.workflow.name, record.git_ref
+                        ),
+                        ToastLevel::Success,
+                    );
+                    if let Err(e) = self.dispatch_history.push(record) {
+                        self.show_toast(
+                            format!("Could not save dispatch history: {}", e),
+                            ToastLevel::Error,
+                        );
+                    }
+                }
+                Err(e) => self.show_toast(e, ToastLevel::Error),
+            },
+            OutcomeEvent::JobRerun(result) => match result {
+                Ok(job_name) => self.show_toast(
+                    format!(
+                        "Rerunning {}. It will appear after the next refresh.",
+                        job_name
+                    ),
+                    ToastLevel::Success,
+                ),
+                Err(e) => self.show_toast(e, ToastLevel::Error),
+            },
+            OutcomeEvent::RunRerun(result) => match result {
+                Ok(description) => self.show_toast(
+                    format!(
+                        "Rerunning {}. It will appear after the next refresh.",
+                        description
+                    ),
+                    ToastLevel::Success,
+                ),
+                Err(e) => self.show_toast(e, ToastLevel::Error),
+            },
+            OutcomeEvent::RunCancelled(result) => match result {
+                Ok(description) => self.show_toast(
+                    format!(
+                        "Cancelling {}. It will show as cancelled after the next refresh.",
+                        description
+                    ),
+                    ToastLevel::Success,
+                ),
+                Err(e) => self.show_toast(e, ToastLevel::Error),
+            },
+            OutcomeEvent::ExtensionRun(label, result) => {
+                if let Some(popup) = self
+                    .app_state
+                    .extension_output
+                    .as_mut()
+                    .filter(|popup| popup.label == label)
+                {
+                    popup.output = Some(result);
+                }
+            }
+            OutcomeEvent::RunsDeleted(result) => match result {
+                Ok(description) => {
+                    self.show_toast(format!("Deleted {}.", description), ToastLevel::Success);
+                    self.events.refresh();
+                }
+                Err(e) => self.show_toast(e, ToastLevel::Error),
+            },
+            OutcomeEvent::DeploymentsApproved(result) => match result {
+                Ok((description, environments)) => {
+                    self.show_toast(
+                        format!(
+                            "Approved the deployment of {} to {}.",
+                            description,
+                            environments.join(", ")
+                        ),
+                        ToastLevel::Success,
+                    );
+                    if self.app_state.views.state::<DeployViewState>().is_some() {
+                        self.fetch_environments();
+                    }
+                }
+                Err(e) => self.show_toast(e, ToastLevel::Error),
+            },
+            OutcomeEvent::LinkedRunFound(result) => match result {
+                Ok((repo, run_id)) => self.show_run(repo, run_id),
+                Err(e) => self.show_toast(e, ToastLevel::Error),
+            },
+            OutcomeEvent::LogsSaved(result) => match result {
+                Ok((description, path)) => self.show_toast(
+                    format!("Saved {} to {}", description, path.display()),
+                    ToastLevel::Success,
+                ),
+                Err(e) => self.show_toast(e, ToastLevel::Error),
+            },
+        }
+    }
+
+    /// Handles the data requested about a job, e.g. its log, when its details are opened.
+    fn handle_job_data_event(&mut self, event: JobDataEvent) {
+        match event {
+            JobDataEvent::AnnotationsFetched(job_id, result) => {
+                if let Some(annotations) = self.app_state.annotations.get_mut(&job_id) {
+                    *annotations = Some(result);
+                }
+            }
+            JobDataEvent::CheckAnnotationsFetched(commit, result) => {
+                self.app_state
+                    .check_annotations
+                    .insert(commit, Some(result));
+            }
+            JobDataEvent::ArtifactsFetched(run_id, result) => {
+                if let Some(artifacts) = self.app_state.artifacts.get_mut(&run_id) {
+                    *artifacts = Some(result);
+                }
+            }
+            // Reading the setting takes admin access, without which the default is assumed
+            JobDataEvent::RetentionFetched(repo, result) => {
+                self.app_state.retention_days.insert(repo, result.ok());
+            }
+            JobDataEvent::ChangedPathsFetched(commit, result) => {
+                self.app_state.changed_paths.insert(commit, Some(result));
+                self.regroup_jobs();
+            }
+            JobDataEvent::JobEnvironmentFetched(job_id, result) => {
+                if let Some(environment) = self.app_state.job_environments.get_mut(&job_id) {
+                    *environment = Some(result);
+                }
+            }
+            JobDataEvent::RefKindFetched(git_ref, result) => {
+                self.app_state.ref_kinds.insert(git_ref, Some(result));
+            }
+            JobDataEvent::WorkflowFileDiffFetched((earlier_id, later_id), result) => {
+                if let Some(compare) =
+                    self.app_state
+                        .views
+                        .state_mut::<LogCompareState>()
+                        .filter(|compare| {
+                            let ids = compare.panes.each_ref().map(|pane| pane.job.id);
+                            ids.contains(&earlier_id) && ids.contains(&later_id)
+                        })
+                {
+                    compare.workflow_diff = Some(result);
+                }
+            }
+            JobDataEvent::RunJobsFetched(run_id, result) => {
+                if let Some(run_view) = self.app_state.views.state_mut::<RunViewState>() {
+                    run_view.expanding = None;
+                }
+                match result {
+                    Ok(jobs) => {
+                        self.job_details.retain(|job| job.run_id != run_id);
+                        self.job_details.extend(jobs);
+                        if let Some(run) = self.runs.iter_mut().find(|run| run.id == run_id) {
+                            run.total_jobs = None;
+                        }
+                        self.group_jobs();
+                    }
+                    Err(e) => self.show_toast(e, ToastLevel::Error),
+                }
+            }
+            JobDataEvent::JobLogFetched(job_id, Err(e))
+                if self.app_state.partial_logs.contains_key(&job_id) =>
+            {
+                // The end of the log stays in place when the whole of it fails to load
+                self.show_toast(e, ToastLevel::Error);
+            }
+            JobDataEvent::JobLogFetched(job_id, result) => {
+                match &result {
+                    Ok(log) if log.partial => {
+                        self.app_state.partial_logs.insert(job_id, log.total_bytes);
+                    }
+                    _ => {
+                        self.app_state.partial_logs.remove(&job_id);
+                    }
+                }
+                let result = result.map(|log| log.text);
+                if let Some(excerpt) = self.app_state.failure_excerpts.get_mut(&job_id) {
+                    *excerpt = Some(match &result {
+                        Ok(log) => Ok(logs::failure_excerpt(
+                            &logs::log_lines(log),
+                            FAILURE_EXCERPT_LINES,
+                        )),
+                        Err(e) => Err(e.clone()),
+                    });
+                }
+                if let Some(triage) = self
+                    .app_state
+                    .triage
+                    .as_mut()
+                    .filter(|triage| triage.current().is_some_and(|job| job.id == job_id))
+                {
+                    match &result {
+                        Ok(log) => {
+                            let lines = logs::log_lines(log);
+                            let start = lines.len().saturating_sub(TRIAGE_LOG_TAIL_LINES);
+                            triage.log_tail = Some(lines[start..].to_vec());
+                        }
+                        Err(e) => triage.error = Some(e.clone()),
+                    }
+                }
+                let panes = self
+                    .app_state
+                    .views
+                    .state_mut::<LogCompareState>()
+                    .into_iter()
+                    .flat_map(|compare| compare.panes.iter_mut());
+                for pane in panes.filter(|pane| pane.job.id == job_id) {
+                    match &result {
+                        Ok(log) => pane.set_log(log),
+                        Err(e) => pane.error = Some(e.clone()),
+                    }
+                }
+                if let Some(compare) = self.app_state.views.state_mut::<LogCompareState>()
+                    && compare.search.is_some()
+                {
+                    compare.update_search();
+                }
+            }
+            JobDataEvent::JobSummaryFetched(job_id, result) => {
+                if let Some(triage) = self
+                    .app_state
+                    .triage
+                    .as_mut()
+                    .filter(|triage| triage.current().is_some_and(|job| job.id == job_id))
+                {
+                    match &result {
+                        Ok(markdown) => triage.summary = Some(markdown.clone()),
+                        Err(e) => triage.error = Some(e.clone()),
+                    }
+                }
+                if let Some(summary) = self
+                    .app_state
+                    .job_summary
+                    .as_mut()
+                    .filter(|summary| summary.job_id == job_id)
+                {
+                    match result {
+                        Ok(markdown) => summary.markdown = Some(markdown),
+                        Err(e) => summary.error = Some(e),
+                    }
+                }
+            }
+            JobDataEvent::RawJsonFetched(job_id, result) => {
+                if let Some(inspector) = self
+                    .app_state
+                    .json_inspector
+                    .as_mut()
+                    .filter(|inspector| inspector.job_id == job_id)
+                {
+                    inspector.json = Some(result);
+                }
+            }
+        }
+    }
+
+    /// Handles the data requested by a view or popup when it opens, e.g. the runs of a workflow.
+    fn handle_view_data_event(&mut self, event: ViewDataEvent) {
+        match event {
+            ViewDataEvent::WorkflowsFetched(result) => {
+                if let Some(badges) = self.app_state.badges.as_mut() {
+                    // Workflows GitHub generates, e.g. for Dependabot, have no file nor badge
+                    badges.workflows = Some(result.clone().map(|workflows| {
+                        workflows
+                            .into_iter()
+                            .filter(|workflow| workflow.path.starts_with(".github/workflows/"))
+                            .collect()
+                    }));
+                }
+                if let Some(dispatch) = self.app_state.dispatch.as_mut() {
+                    match result {
+                        Ok(workflows) => dispatch.workflows = Some(workflows),
+                        Err(e) => dispatch.error = Some(e),
+                    }
+                }
+            }
+            ViewDataEvent::RefsFetched(result) => {
+                if let Some(dispatch) = self.app_state.dispatch.as_mut() {
+                    dispatch.refs = Some(result);
+                }
+            }
+            ViewDataEvent::BranchesFetched(repo, result) => {
+                if let Some(picker) = self
+                    .app_state
+                    .branch_picker
+                    .as_mut()
+                    .filter(|picker| picker.repo == repo)
+                {
+                    picker.branches = Some(result);
+                }
+            }
+            ViewDataEvent::RunFetched(run_id, result) => {
+                if let Some(watch) = self
+                    .app_state
+                    .views
+                    .state_mut::<WatchState>()
+                    .filter(|watch| watch.run_id == run_id)
+                {
+                    watch.next_poll = Some(Instant::now() + WATCH_POLL_INTERVAL);
+                    match result {
+                        Ok(details) => {
+                            let concluded = details.run.status == "completed";
+                            let succeeded = details.run.conclusion.as_deref() == Some("success");
+                            // Only a run seen in progress has just concluded
+                            let was_running = watch
+                                .details
+                                .as_ref()
+                                .is_some_and(|previous| previous.run.status != "completed");
+                            let open_url = (was_running
+                                && self.config.open_on_failure.enabled
+                                && RunOutcome::from_conclusion(details.run.conclusion.as_deref())
+                                    == Some(RunOutcome::Failure))
+                            .then(|| self.config.open_on_failure.url_for(&details.run));
+                            watch.details = Some(*details);
+                            watch.error = None;
+                            if let Some(url) = open_url
+                                && let Err(e) = links::open_in_browser(&url)
+                            {
+                                watch.error = Some(format!("Failed to open {}: {}", url, e));
+                            }
+                            if concluded && watch.exit_on_conclusion {
+                                if succeeded {
+                                    self.exit_code = ExitCode::SUCCESS;
+                                }
+                                self.quit();
+                            }
+                        }
+                        Err(e) => watch.error = Some(e),
+                    }
+                }
+            }
+            ViewDataEvent::ExtensionsFetched(result) => {
+                if let Some(menu) = self.app_state.extension_menu.as_mut() {
+                    menu.actions =
+                        Some(result.map(|installed| gh_extensions::available_actions(&installed)));
+                }
+            }
+            ViewDataEvent::WorkflowHistoryFetched((repo, path), page, result) => {
+                if let Some(history) = self
+                    .app_state
+                    .views
+                    .state_mut::<WorkflowHistoryState>()
+                    .filter(|history| history.repo == repo && history.path == path)
+                {
+                    history.loading = false;
+                    match result {
+                        Ok(fetched) => {
+                            history.pages = page;
+                            history.total_count = Some(fetched.total_count);
+                            history.error = None;
+                            // Runs started since the previous page push some listed runs onto
+                            // this one
+                            for run in fetched.runs {
+                                if !history
+                                    .runs
+                                    .iter()
+                                    .any(|listed| listed.run.id == run.run.id)
+                                {
+                                    history.runs.push(run);
+                                }
+                            }
+                        }
+                        Err(e) => history.error = Some(e),
+                    }
+                }
+            }
+            ViewDataEvent::FlakyTestsScanned((repo, path), result) => {
+                if let Some(flaky_tests) = self
+                    .app_state
+                    .views
+                    .state_mut::<FlakyTestsState>()
+                    .filter(|flaky_tests| flaky_tests.repo == repo && flaky_tests.path == path)
+                {
+                    flaky_tests.scan = Some(result);
+                }
+            }
+            ViewDataEvent::EnvironmentsFetched(repo, result) => {
+                // Drop results for a repository that is no longer shown
+                let is_active = self.active_repo().as_deref() == Some(repo.as_str());
+                if let Some(deploy_view) = self
+                    .app_state
+                    .views
+                    .state_mut::<DeployViewState>()
+                    .filter(|_| is_active)
+                {
+                    match result {
+                        Ok(environments) => {
+                            deploy_view.selected = deploy_view
+                                .selected
+                                .min(environments.len().saturating_sub(1));
+                            deploy_view.environments = Some(environments);
+                            deploy_view.error = None;
+                        }
+                        Err(e) => deploy_view.error = Some(e),
+                    }
+                }
+            }
+            ViewDataEvent::ActionsSettingsFetched(repo, result) => {
+                if let Some(popup) = self
+                    .app_state
+                    .actions_settings
+                    .as_mut()
+                    .filter(|popup| popup.repo == repo)
+                {
+                    match result {
+                        Ok(settings) => popup.settings = Some(settings),
+                        Err(e) => popup.error = Some(e),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Handles an application event in the view shown, which leaves those it has no use for,
+    /// e.g. quitting, to the job columns.
+    fn route_app_event(&mut self, app_event: AppEvent) {
+        match self.app_state.views.shown() {
+            Some(View::Timeline(_)) => self.handle_timeline_event(app_event),
+            Some(View::WorkflowHistory(_)) => self.handle_workflow_history_event(app_event),
+            Some(View::FlakyTests(_)) => self.handle_flaky_tests_event(app_event),
+            Some(View::Watch(_)) => self.handle_watch_event(app_event),
+            Some(View::LogCompare(_)) => self.handle_log_compare_event(app_event),
+            Some(View::Overview(_)) => self.handle_overview_event(app_event),
+            Some(View::Deploy(_)) => self.handle_deploy_view_event(app_event),
+            Some(View::Usage(_)) => self.handle_usage_view_event(app_event),
+            // The details panel of a job of the run view is handled like the job columns'
+            Some(View::Runs(_)) if !self.app_state.show_details => {
+                self.handle_run_view_event(app_event)
+            }
+            Some(View::Runs(_)) | None => self.handle_app_event(app_event),
+        }
+    }
+
+    /// Handles an application event in the timeline, which lists status changes, each linking
+    /// to its job.
+    fn handle_timeline_event(&mut self, app_event: AppEvent) {
+        match app_event {
+            AppEvent::NavigateUp => self.change_timeline_selection(-1),
+            AppEvent::NavigateDown => self.change_timeline_selection(1),
+            AppEvent::PageUp => self.change_timeline_selection(-25),
+            AppEvent::PageDown => self.change_timeline_selection(25),
+            AppEvent::Top => self.change_timeline_selection(isize::MIN),
+            AppEvent::Bottom => self.change_timeline_selection(isize::MAX),
+            AppEvent::OpenGitHub => self.open_timeline_job(),
+            AppEvent::Back | AppEvent::ToggleTimeline => {
+                self.app_state.views.update(ViewMessage::Close);
+            }
+            AppEvent::NavigateLeft | AppEvent::NavigateRight | AppEvent::ToggleDetails => {}
+            app_event => self.handle_app_event(app_event),
+        }
+    }
+
+    /// Handles an application event in the history of a workflow, which lists its runs,
+    /// fetching older ones at the bottom.
+    fn handle_workflow_history_event(&mut self, app_event: AppEvent) {
+        match app_event {
+            AppEvent::NavigateUp => self.change_history_selection(-1),
+            AppEvent::NavigateDown => self.change_history_selection(1),
+            AppEvent::PageUp => self.change_history_selection(-10),
+            AppEvent::PageDown => self.change_history_selection(10),
+            AppEvent::Top => self.change_history_selection(isize::MIN),
+            AppEvent::Bottom => self.change_history_selection(isize::MAX),
+            AppEvent::OpenGitHub => self.open_history_run(),
+            AppEvent::Back | AppEvent::ToggleWorkflowHistory => {
+                self.app_state.views.update(ViewMessage::Close);
+            }
+            AppEvent::NavigateLeft | AppEvent::NavigateRight | AppEvent::ToggleDetails => {}
+            app_event => self.handle_app_event(app_event),
+        }
+    }
+
+    /// Handles an application event in the list of suspected flaky tests, each linking to the
+    /// latest run it failed in.
+    fn handle_flaky_tests_event(&mut self, app_event: AppEvent) {
+        match app_event {
+            AppEvent::NavigateUp => self.change_flaky_test_selection(-1),
+            AppEvent::NavigateDown => self.change_flaky_test_selection(1),
+            AppEvent::PageUp => self.change_flaky_test_selection(-10),
+            AppEvent::PageDown => self.change_flaky_test_selection(10),
+            AppEvent::Top => self.change_flaky_test_selection(isize::MIN),
+            AppEvent::Bottom => self.change_flaky_test_selection(isize::MAX),
+            AppEvent::OpenGitHub => self.open_flaky_test_run(),
+            AppEvent::OpenExport => self.export_flaky_tests(),
+            AppEvent::Back | AppEvent::ToggleFlakyTests => {
+                self.app_state.views.update(ViewMessage::Close);
+            }
+            AppEvent::NavigateLeft | AppEvent::NavigateRight | AppEvent::ToggleDetails => {}
+            app_event => self.handle_app_event(app_event),
+        }
+    }
+
+    /// Handles an application event while watching a run, whose jobs and steps are listed as
+    /// one scrolling page.
+    fn handle_watch_event(&mut self, app_event: AppEvent) {
+        match app_event {
+            AppEvent::NavigateUp => self.scroll_watch(-1),
+            AppEvent::NavigateDown => self.scroll_watch(1),
+            AppEvent::PageUp => self.scroll_watch(-25),
+            AppEvent::PageDown => self.scroll_watch(25),
+            AppEvent::Top => self.scroll_watch(isize::MIN),
+            AppEvent::Bottom => self.scroll_watch(isize::MAX),
+            AppEvent::OpenGitHub => self.open_watched_run(),
+            // A run watched from the command line is the whole UI
+            AppEvent::Back | AppEvent::WatchRun
+                if self
+                    .app_state
+                    .views
+                    .state::<WatchState>()
+                    .is_some_and(|watch| watch.exit_on_conclusion) =>
+            {
+                self.quit()
+            }
+            AppEvent::Back | AppEvent::WatchRun => {
+                self.app_state.views.update(ViewMessage::Close);
+            }
+            AppEvent::NavigateLeft
+            | AppEvent::NavigateRight
+            | AppEvent::ToggleDetails
+            | AppEvent::CompareLogs => {}
+            app_event => self.handle_app_event(app_event),
+        }
+    }
+
+    /// Handles an application event in a log comparison, whose logs scroll together.
+    fn handle_log_compare_event(&mut self, app_event: AppEvent) {
+        match app_event {
+            AppEvent::NavigateUp => self.scroll_log_compare(-1),
+            AppEvent::NavigateDown => self.scroll_log_compare(1),
+            AppEvent::PageUp => self.scroll_log_compare(-25),
+            AppEvent::PageDown => self.scroll_log_compare(25),
+            AppEvent::Top => self.scroll_log_compare(isize::MIN),
+            AppEvent::Bottom => self.scroll_log_compare(isize::MAX),
+            AppEvent::OpenFilter => self.open_log_search(),
+            AppEvent::NextMatch => self.jump_to_log_match(1),
+            AppEvent::PreviousMatch => self.jump_to_log_match(-1),
+            // Back ends the search before closing the comparison
+            AppEvent::Back
+                if self
+                    .app_state
+                    .views
+                    .state::<LogCompareState>()
+                    .is_some_and(|compare| compare.search.is_some()) =>
+            {
+                if let Some(compare) = self.app_state.views.state_mut::<LogCompareState>() {
+                    compare.search = None;
+                }
+            }
+            AppEvent::Back | AppEvent::CompareLogs => {
+                self.app_state.views.update(ViewMessage::Close);
+            }
+            AppEvent::OpenGitHub => self.open_workflow_diff(),
+            AppEvent::ToggleDetails => {
+                if let Some(compare) = self.app_state.views.state_mut::<LogCompareState>() {
+                    compare.toggle_groups();
+                }
+            }
+            AppEvent::NavigateLeft | AppEvent::NavigateRight => {}
+            app_event => self.handle_app_event(app_event),
+        }
+    }
+
+    /// Handles an application event in the workspace overview, which lists the repositories,
+    /// each opening its dashboard.
+    fn handle_overview_event(&mut self, app_event: AppEvent) {
+        match app_event {
+            AppEvent::NavigateUp | AppEvent::PrevRepo => self.change_overview_selection(-1),
+            AppEvent::NavigateDown | AppEvent::NextRepo => self.change_overview_selection(1),
+            AppEvent::Top => self.change_overview_selection(isize::MIN),
+            AppEvent::Bottom => self.change_overview_selection(isize::MAX),
+            AppEvent::ToggleDetails => self.open_overview_repo(),
+            AppEvent::OpenGitHub => self.open_overview_actions_page(),
+            AppEvent::Back | AppEvent::ToggleOverview => {
+                self.app_state.views.update(ViewMessage::Close);
+            }
+            AppEvent::NavigateLeft
+            | AppEvent::NavigateRight
+            | AppEvent::PageUp
+            | AppEvent::PageDown => {}
+            app_event => self.handle_app_event(app_event),
+        }
+    }
+
+    /// Handles an application event in the deploy dashboard, which has its own rows and links,
+    /// and no columns or details.
+    fn handle_deploy_view_event(&mut self, app_event: AppEvent) {
+        match app_event {
+            AppEvent::NavigateUp => self.change_deploy_selection(-1),
+            AppEvent::NavigateDown => self.change_deploy_selection(1),
+            AppEvent::Top => self.change_deploy_selection(isize::MIN),
+            AppEvent::Bottom => self.change_deploy_selection(isize::MAX),
+            AppEvent::OpenGitHub => self.open_deploy_run(),
+            AppEvent::ApproveDeployment => self.confirm_deploy_view_approval(),
+            AppEvent::Back | AppEvent::ToggleDeployView => {
+                self.app_state.views.update(ViewMessage::Close);
+            }
+            AppEvent::NavigateLeft
+            | AppEvent::NavigateRight
+            | AppEvent::ToggleDetails
+            | AppEvent::PageUp
+            | AppEvent::PageDown => {}
+            app_event => self.handle_app_event(app_event),
+        }
+    }
+
+    /// Handles an application event in the usage view, which only scrolls.
+    fn handle_usage_view_event(&mut self, app_event: AppEvent) {
+        match app_event {
+            AppEvent::NavigateUp => self.scroll_usage_view(-1),
+            AppEvent::NavigateDown => self.scroll_usage_view(1),
+            AppEvent::Top => self.scroll_usage_view(isize::MIN),
+            AppEvent::Back | AppEvent::ToggleUsageView => {
+                self.app_state.views.update(ViewMessage::Close);
+            }
+            AppEvent::NavigateLeft
+            | AppEvent::NavigateRight
+            | AppEvent::ToggleDetails
+            | AppEvent::PageUp
+            | AppEvent::PageDown
+            | AppEvent::Bottom => {}
+            app_event => self.handle_app_event(app_event),
+        }
+    }
+
+    /// Handles an application event in the run view, which moves through its own runs and jobs,
+    /// and opens a job's details.
+    fn handle_run_view_event(&mut self, app_event: AppEvent) {
+        match app_event {
+            AppEvent::NavigateUp => self.change_run_view_selection(-1),
+            AppEvent::NavigateDown => self.change_run_view_selection(1),
+            AppEvent::PageUp => self.change_run_view_selection(-10),
+            AppEvent::PageDown => self.change_run_view_selection(10),
+            AppEvent::Top => self.change_run_view_selection(isize::MIN),
+            AppEvent::Bottom => self.change_run_view_selection(isize::MAX),
+            AppEvent::NavigateLeft => self.focus_run_view_jobs(false),
+            AppEvent::NavigateRight => self.focus_run_view_jobs(true),
+            AppEvent::ToggleDetails
+                if self
+                    .app_state
+                    .views
+                    .state::<RunViewState>()
+                    .is_some_and(|run_view| !run_view.jobs_focused) =>
+            {
+                self.expand_run();
+                self.focus_run_view_jobs(true);
+            }
+            AppEvent::ToggleDetails => {
+                if self.selected_run_view_job().is_some() {
+                    self.toggle_details_panel();
+                }
+            }
+            AppEvent::OpenGitHub => self.open_run_view_selection(),
+            AppEvent::Back | AppEvent::ToggleRunView => self.toggle_run_view(),
+            app_event => self.handle_app_event(app_event),
+        }
+    }
+
+    /// Handles an application event for the job columns.
+    fn handle_app_event(&mut self, app_event: AppEvent) {
+        match app_event {
+            AppEvent::Quit => self.quit(),
+            // With a search applied, Esc clears it rather than quitting
+            AppEvent::Back if !self.app_state.filter.filters().query.is_empty() => {
+                self.update_filters(FilterMessage::SetQuery(String::new()));
+            }
+            AppEvent::Back if self.app_state.time_travel.is_some() => self.return_to_present(),
+            AppEvent::Back if self.app_state.show_links => self.app_state.show_links = false,
+            AppEvent::Back => self.quit(),
+            AppEvent::NavigateRight if self.app_state.show_details => self.change_annotation(1),
+            AppEvent::NavigateLeft if self.app_state.show_details => self.change_annotation(-1),
+            AppEvent::NavigateRight => self.change_column_index(1),
+            AppEvent::NavigateLeft => self.change_column_index(-1),
+            AppEvent::NavigateUp => self.change_row_index(-1),
+            AppEvent::NavigateDown => self.change_row_index(1),
+            AppEvent::Top => self.change_row_index(isize::MIN),
+            AppEvent::Bottom => self.change_row_index(isize::MAX),
+            AppEvent::ToggleDetails => {
+                if self.app_state.show_details || !self.toggle_selected_matrix() {
+                    self.toggle_details_panel();
+                }
+            }
+            AppEvent::PageDown => self.scroll_page(1),
+            AppEvent::PageUp => self.scroll_page(-1),
+            AppEvent::OpenGitHub => self.open_github(),
+            AppEvent::NextRepo => self.change_repo_index(1),
+            AppEvent::PrevRepo => self.change_repo_index(-1),
+            AppEvent::ToggleApiInspector => self.toggle_api_inspector(),
+            AppEvent::OpenDispatch => self.open_dispatch(),
+            AppEvent::ToggleDeployView => self.toggle_deploy_view(),
+            AppEvent::ToggleOverview => self.toggle_overview(),
+            AppEvent::CopyCommitSha => self.copy_commit_sha(),
+            AppEvent::OpenYankMenu => self.open_yank_menu(),
+            AppEvent::ToggleWorkflowMute => self.toggle_workflow_mute(),
+            AppEvent::ApproveDeployment => self.confirm_approval(),
+            AppEvent::ToggleTimeline => {
+                self.app_state
+                    .views
+                    .update(ViewMessage::Open(View::Timeline(
+                        TimelineViewState::default(),
+                    )));
+                self.timeline.unseen = 0;
+            }
+            AppEvent::OpenJobSummary => self.open_job_summary(),
+            AppEvent::InspectJson => self.open_json_inspector(),
+            AppEvent::LoadFullLog => self.confirm_full_log(),
+            AppEvent::ToggleWorkflowHistory => self.open_workflow_history(),
+            AppEvent::ToggleFlakyTests => self.open_flaky_tests(),
+            AppEvent::Refresh => self.events.refresh(),
+            AppEvent::OpenAnnotation => self.open_annotation(),
+            AppEvent::TogglePin => self.toggle_pin(),
+            AppEvent::PreviousRefresh => self.step_through_refreshes(-1),
+            AppEvent::NextRefresh => self.step_through_refreshes(1),
+            AppEvent::DeleteRun => self.confirm_deletion(),
+            AppEvent::OpenExtensions => self.open_extension_menu(),
+            AppEvent::OpenBadges => self.open_badges(),
+            AppEvent::OpenFilter => self.app_state.filter.editing = true,
+            AppEvent::ClearFilters => self.clear_filters(),
+            AppEvent::RestoreFilters => self.restore_filters(),
+            AppEvent::CompareLogs => self.compare_logs(),
+            AppEvent::OpenTriage => self.open_triage(),
+            AppEvent::ShowActionsSettings => self.open_actions_settings(),
+            AppEvent::OpenPullRequest => self.open_pull_request(),
+            AppEvent::OpenCalledWorkflow => self.open_called_workflow(),
+            AppEvent::RerunRun => self.confirm_rerun(false),
+            AppEvent::RerunFailedJobs => self.confirm_rerun(true),
+            AppEvent::CancelRun => self.confirm_cancellation(),
+            AppEvent::ToggleOtherJobs => self.toggle_other_jobs(),
+            AppEvent::ToggleRecentOnly => self.toggle_recent_only(),
+            AppEvent::ToggleScheduled => self.toggle_scheduled(),
+            AppEvent::ToggleUsageView => self.toggle_usage_view(),
+            AppEvent::ToggleLinks => self.toggle_links(),
+            // Only the log comparison has matches to jump between
+            AppEvent::NextMatch | AppEvent::PreviousMatch => {}
+            AppEvent::OpenClipboardLink => self.open_clipboard_link(),
+            AppEvent::CycleTheme => self.cycle_theme(),
+            AppEvent::CycleSort => self.cycle_sort(),
+            AppEvent::SaveLog => self.save_logs(false),
+            AppEvent::SaveRunLogs => self.save_logs(true),
+            AppEvent::ToggleBackfill => self.toggle_backfill(),
+            AppEvent::ToggleTimestamps => self.toggle_timestamps(),
+            AppEvent::ToggleRunView => self.toggle_run_view(),
+            AppEvent::OpenBranchPicker => self.open_branch_picker(),
+            AppEvent::OpenCommandPalette => {
+                self.app_state.command_palette = Some(CommandPaletteState::default())
+            }
+            AppEvent::OpenExport => {
+                self.app_state.export_prompt = Some(ExportPrompt {
+                    path: TextInput::new(DEFAULT_EXPORT_PATH).with_validator(|path| {
+                        export::ExportFormat::from_path(Path::new(path))
+                            .map(|_| ())
+                            .map_err(|e| e.to_string())
+                    }),
+                    error: None,
+                })
+            }
+            AppEvent::ToggleWorkflowSidebar => {
+                self.app_state.workflow_sidebar = Some(WorkflowSidebarState::default())
+            }
+            AppEvent::WatchRun => {
+                if let Some(job) = self.job_details.get(self.current_job_index) {
+                    self.start_watch(job.repo.clone(), job.run_id, false);
+                }
+            }
+        }
+    }
+    fn change_column_index(&mut self, delta: isize) {
+        if self.app_state.show_details {
+            return;
+        }
+        let num_columns = 3;
+        let new_index = (self.app_state.column_index as isize + delta).rem_euclid(num_columns);
+
+        self.app_state.column_index = new_index as usize;
+
+        self.update_current_job_index_from_state();
+    }
+    /// Switches the third column between the failed jobs and the jobs that concluded
+    /// otherwise, each list picking up where it was left.
+    fn toggle_other_jobs(&mut self) {
+        self.app_state.show_other_jobs = !self.app_state.show_other_jobs;
+        std::mem::swap(
+            &mut self.app_state.columns[2],
+            &mut self.app_state.hidden_column,
+        );
+        // The list may have shrunk since it was left
+        let job_count = self
+            .app_state
+            .column_jobs(2)
+            .values()
+            .map(Vec::len)
+            .sum::<usize>();
+        let position = &mut self.app_state.columns[2];
+        position.row_index = position.row_index.min(job_count.saturating_sub(1));
+        self.update_current_job_index_from_state();
+    }
+
+    /// Hides the failed and otherwise concluded jobs older than the configured number of
+    /// hours, or shows them again.
+    fn toggle_recent_only(&mut self) {
+        self.update_filters(FilterMessage::ToggleRecentOnly);
+        let message = if self.app_state.filter.filters().recent_only {
+            format!(
+                "Showing the jobs that didn't succeed in the last {}h only.",
+                self.config.recent_hours()
+            )
+        } else {
+            "Showing the jobs that didn't succeed however long ago.".to_string()
+        };
+        self.show_toast(message, ToastLevel::Info);
+    }
+
+    /// Hides the jobs of the runs triggered on a schedule, or shows them again.
+    fn toggle_scheduled(&mut self) {
+        self.update_filters(FilterMessage::ToggleScheduled);
+        let message = if self.app_state.filter.filters().hide_scheduled {
+            "Hiding the scheduled runs."
+        } else {
+            "Showing the scheduled runs again."
+        };
+        self.show_toast(message.to_string(), ToastLevel::Info);
+    }
+
+    /// Clears every filter of the job columns, which can be restored.
+    fn clear_filters(&mut self) {
+        if !self.update_filters(FilterMessage::ClearAll) {
+            return;
+        }
+        let message = match self.keymap.keys_for(AppEvent::RestoreFilters) {
+            Some(keys) => format!("Cleared the filters, `{}` brings them back.", keys),
+            None => "Cleared the filters.".to_string(),
+        };
+        self.show_toast(message, ToastLevel::Info);
+    }
+
+    /// Brings back the filters of the job columns as they were before they last changed.
+    fn restore_filters(&mut self) {
+        if self.app_state.filter.restore() {
+            self.regroup_jobs();
+            self.show_toast(
+                "Restored the previous filters.".to_string(),
+                ToastLevel::Info,
+            );
+        } else {
+            self.show_toast(
+                "There are no earlier filters to restore.".to_string(),
+                ToastLevel::Warning,
+            );
+        }
+    }
+
+    /// Switches to the next built-in theme, keeping the colors overridden in the config file.
+    fn cycle_theme(&mut self) {
+        self.theme_preset = self.theme_preset.next();
+        self.theme = Theme::new(self.theme_preset, &self.config.theme.colors);
+        self.show_toast(
+            format!("Theme: {}", self.theme_preset.name()),
+            ToastLevel::Info,
+        );
+    }
+
+    /// Lists the jobs in the next order, saved to the config file for the next sessions.
+    fn cycle_sort(&mut self) {
+        self.config.sort = self.config.sort.next();
+        self.regroup_jobs();
+        match self.config.save_setting("sort", self.config.sort) {
+            Ok(()) => self.show_toast(
+                format!("Jobs listed {}", self.config.sort.name()),
+                ToastLevel::Info,
+            ),
+            Err(e) => self.show_toast(
+                format!("Could not save the sort order: {:?}", e),
+                ToastLevel::Error,
+            ),
+        }
+    }
+
+    fn toggle_timestamps(&mut self) {
+        self.config.absolute_timestamps = !self.config.absolute_timestamps;
+        let saved = self
+            .config
+            .save_setting("absolute_timestamps", self.config.absolute_timestamps);
+        if let Err(e) = saved {
+            self.show_toast(
+                format!("Could not save the timestamp format: {:?}", e),
+                ToastLevel::Error,
+            );
+        }
+    }
+
+    fn change_repo_index(&mut self, delta: isize) {
+        let num_repos = self.gh_cli.repos().len();
+        if num_repos < 2 {
+            return;
+        }
+        // Remember where we were in the repository we are leaving
+        if let Some(repo) = self.active_repo() {
+            self.app_state.saved_selections.insert(
+                repo,
+                SelectionState {
+                    column_index: self.app_state.column_index,
+                    columns: self.app_state.columns,
+                    hidden_column: self.app_state.hidden_column,
+                },
+            );
+        }
+
+        let new_index = (self.app_state.repo_index as isize + delta).rem_euclid(num_repos as isize);
+        self.app_state.repo_index = new_index as usize;
+
+        let selection = self
+            .active_repo()
+            .and_then(|repo| self.app_state.saved_selections.get(&repo).copied())
+            .unwrap_or_default();
+        self.app_state.column_index = selection.column_index;
+        self.app_state.columns = selection.columns;
+        self.app_state.hidden_column = selection.hidden_column;
+
+        self.group_jobs();
+        if let Some(deploy_view) = self.app_state.views.state_mut::<DeployViewState>() {
+            *deploy_view = DeployViewState::default();
+            self.fetch_environments();
+        }
+    }
+
+    /// The `owner/name` of the repository currently shown.
+    pub fn active_repo(&self) -> Option<String> {
+        self.gh_cli
+            .repos()
+            .get(self.app_state.repo_index)
+            .map(|repo| repo.full_name())
+    }
+
+    /// The tracked commits of the repository currently shown, newest first.
+    pub fn active_commits(&self) -> Vec<&CommitStatus> {
+        let active_repo = self.active_repo().unwrap_or_default();
+        self.commits
+            .iter()
+            .filter(|commit| commit.repo.eq_ignore_ascii_case(&active_repo))
+            .collect()
+    }
+
+    /// The commit that turned the current branch red, when its newest concluded commit failed.
+    pub fn breaking_commit(&self) -> Option<&CommitStatus> {
+        self.active_commits()
+            .into_iter()
+            .filter(|commit| commit.state != CommitState::Running)
+            .take_while(|commit| commit.state == CommitState::Failed)
+            .last()
+    }
+
+    /// The pages about a job, those it has: itself, its run, its commit, its pull request, its
+    /// workflow file and the reusable workflow it comes from.
+    pub fn job_links(&self, job: &GithubJob) -> Vec<JobLink> {
+        let mut links = vec![
+            JobLink {
+                label: "Job",
+                url: job.html_url.clone(),
+            },
+            JobLink {
+                label: "Run",
+                url: job.run_url.clone(),
+            },
+        ];
+        if !job.head_sha.is_empty() {
+            links.push(JobLink {
+                label: "Commit",
+                url: format!("https://github.com/{}/commit/{}", job.repo, job.head_sha),
+            });
+        }
+        if let Some(pull_request) = &job.pull_request {
+            links.push(JobLink {
+                label: "Pull request",
+                url: pull_request_url(&job.repo, pull_request),
+            });
+        }
+        if let Some(run) = self.runs.iter().find(|run| run.id == job.run_id)
+            && !run.path.is_empty()
+        {
+            links.push(JobLink {
+                label: "Workflow file",
+                url: format!(
+                    "https://github.com/{}/blob/{}/{}",
+                    job.repo, job.head_sha, run.path
+                ),
+            });
+        }
+        if let Some(called_workflow) = &job.called_workflow {
+            links.push(JobLink {
+                label: "Called workflow",
+                url: called_workflow.html_url.clone(),
+            });
+        }
+        links
+    }
+
+    /// Lists the links of the selected job in its details, opening them if they are closed,
+    /// or goes back to its details.
+    fn toggle_links(&mut self) {
+        if self.job_details.get(self.current_job_index).is_none() {
+            return;
+        }
+        if !self.app_state.show_details {
+            self.toggle_details_panel();
+            self.app_state.show_links = true;
+        } else {
+            self.app_state.show_links = !self.app_state.show_links;
+        }
+    }
+
+    /// Opens the link numbered `number` in the details of the selected job.
+    fn open_job_link(&mut self, number: usize) {
+        let Some(job) = self.job_details.get(self.current_job_index) else {
+            return;
+        };
+        let Some(link) = self.job_links(job).into_iter().nth(number.wrapping_sub(1)) else {
+            return;
+        };
+        self.open_url(&link.url);
+    }
+
+    fn open_github(&mut self) {
+        if let Some(job) = self.job_details.get(self.current_job_index) {
+            let url = job.html_url.clone();
+            self.open_url(&url);
+        }
+    }
+
+    /// Opens the reusable workflow the selected job comes from, which may live in another
+    /// repository.
+    fn open_called_workflow(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index) else {
+            return;
+        };
+        let Some(called_workflow) = &job.called_workflow else {
+            self.show_toast(
+                format!("{} doesn't come from a reusable workflow.", job.name),
+                ToastLevel::Warning,
+            );
+            return;
+        };
+        let url = called_workflow.html_url.clone();
+        self.open_url(&url);
+    }
+
+    /// Asks to confirm a rerun of the watched run, or else the run of the selected job.
+    fn confirm_rerun(&mut self, failed_only: bool) {
+        let confirmation = match self.app_state.views.state::<WatchState>() {
+            Some(watch) => RerunConfirmation {
+                repo: watch.repo.clone(),
+                run_id: watch.run_id,
+                workflow_name: watch
+                    .details
+                    .as_ref()
+                    .map_or_else(|| "the".to_string(), |details| details.run.name.clone()),
+                failed_only,
+            },
+            None => {
+                let Some(job) = self.job_details.get(self.current_job_index) else {
+                    return;
+                };
+                RerunConfirmation {
+                    repo: job.repo.clone(),
+                    run_id: job.run_id,
+                    workflow_name: job.workflow_name.clone(),
+                    failed_only,
+                }
+            }
+        };
+        self.app_state.rerun_confirmation = Some(confirmation);
+    }
+
+    /// Asks to confirm cancelling the watched run, or else the run of the selected job, unless
+    /// it already completed.
+    fn confirm_cancellation(&mut self) {
+        let run = match self.app_state.views.state::<WatchState>() {
+            Some(watch) => watch.details.as_ref().map(|details| &details.run),
+            None => self
+                .job_details
+                .get(self.current_job_index)
+                .and_then(|job| {
+                    self.runs
+                        .iter()
+                        .find(|run| run.id == job.run_id && run.repo == job.repo)
+                }),
+        };
+        let Some(run) = run else {
+            return;
+        };
+        let confirmation = CancelConfirmation {
+            repo: run.repo.clone(),
+            run_id: run.id,
+            workflow_name: run.name.clone(),
+        };
+        if run.status == "completed" {
+            self.show_toast(
+                format!("{} already completed.", confirmation.description()),
+                ToastLevel::Warning,
+            );
+            return;
+        }
+        self.app_state.cancel_confirmation = Some(confirmation);
+    }
+
+    /// Handles key events while cancelling a run is waiting to be confirmed.
+    fn handle_cancel_confirmation_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                let Some(confirmation) = self.app_state.cancel_confirmation.take() else {
+                    return;
+                };
+                let description = confirmation.description();
+                self.show_toast(format!("Cancelling {}...", description), ToastLevel::Info);
+                self.events.request(move |gh_cli| {
+                    OutcomeEvent::RunCancelled(
+                        gh_cli
+                            .cancel_run(&confirmation.repo, confirmation.run_id)
+                            .map(|_| description.clone())
+                            .map_err(|e| format!("Error cancelling {}: {:?}", description, e)),
+                    )
+                });
+            }
+            KeyCode::Char('n' | 'q') | KeyCode::Esc => {
+                self.app_state.cancel_confirmation = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Asks to confirm approving the deployments the run of the selected job waits for.
+    fn confirm_approval(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index) else {
+            return;
+        };
+        if job.status != "waiting" {
+            self.show_toast(
+                format!(
+                    "{} isn't waiting for a deployment to be approved.",
+                    job.name
+                ),
+                ToastLevel::Warning,
+            );
+            return;
+        }
+        self.app_state.approval_confirmation = Some(ApprovalConfirmation {
+            repo: job.repo.clone(),
+            run_id: job.run_id,
+            workflow_name: job.workflow_name.clone(),
+            head_branch: job.head_branch.clone(),
+        });
+    }
+
+    /// Asks to confirm approving the first run waiting to deploy to the environment selected
+    /// in the deploy dashboard.
+    fn confirm_deploy_view_approval(&mut self) {
+        let Some(repo) = self.active_repo() else {
+            return;
+        };
+        let approval = self
+            .app_state
+            .views
+            .state::<DeployViewState>()
+            .and_then(|deploy_view| {
+                deploy_view
+                    .environments
+                    .as_ref()?
+                    .get(deploy_view.selected)?
+                    .pending_approvals
+                    .first()
+            });
+        let Some(approval) = approval else {
+            self.app_state.loading_status =
+                "No run is waiting to deploy to this environment.".to_string();
+            return;
+        };
+        self.app_state.approval_confirmation = Some(ApprovalConfirmation {
+            repo,
+            run_id: approval.run_id,
+            workflow_name: approval.run_name.clone(),
+            head_branch: approval.head_branch.clone(),
+        });
+    }
+
+    /// Handles key events while approving deployments is waiting to be confirmed.
+    fn handle_approval_confirmation_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                let Some(confirmation) = self.app_state.approval_confirmation.take() else {
+                    return;
+                };
+                let description = confirmation.description();
+                self.show_toast(format!("Approving {}...", description), ToastLevel::Info);
+                self.events.request(move |gh_cli| {
+                    OutcomeEvent::DeploymentsApproved(
+                        gh_cli
+                            .approve_pending_deployments(&confirmation.repo, confirmation.run_id)
+                            .map(|environments| (description.clone(), environments))
+                            .map_err(|e| format!("Error approving {}: {}", description, e)),
+                    )
+                });
+            }
+            KeyCode::Char('n' | 'q') | KeyCode::Esc => {
+                self.app_state.approval_confirmation = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles key events while the export prompt is open.
+    fn handle_export_key_event(&mut self, key_event: KeyEvent) {
+        let Some(prompt) = self.app_state.export_prompt.as_mut() else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Enter => {
+                if let Err(e) = prompt.path.submit() {
+                    prompt.error = Some(e);
+                    return;
+                }
+                let path = PathBuf::from(prompt.path.value());
+                let jobs = (0..3)
+                    .flat_map(|column| self.app_state.column_jobs(column).values().flatten())
+                    .flat_map(ColumnRow::jobs)
+                    .filter_map(|&index| self.job_details.get(index));
+                match export::export_jobs(&path, jobs) {
+                    Ok(exported) => {
+                        self.app_state.export_prompt = None;
+                        self.show_toast(
+                            format!("Exported {} jobs to {}", exported, path.display()),
+                            ToastLevel::Success,
+                        );
+                    }
+                    Err(e) => {
+                        if let Some(prompt) = self.app_state.export_prompt.as_mut() {
+                            prompt.error = Some(format!("{:#}", e));
+                        }
+                    }
+                }
+            }
+            KeyCode::Esc => self.app_state.export_prompt = None,
+            _ => {
+                if prompt.path.handle_key(key_event) {
+                    prompt.error = None;
+                }
+            }
+        }
+    }
+
+    /// Handles key events while a rerun is waiting to be confirmed.
+    fn handle_rerun_confirmation_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                let Some(confirmation) = self.app_state.rerun_confirmation.take() else {
+                    return;
+                };
+                let description = confirmation.description();
+                self.show_toast(
+                    format!("Requesting a rerun of {}...", description),
+                    ToastLevel::Info,
+                );
+                self.events.request(move |gh_cli| {
+                    let result = if confirmation.failed_only {
+                        gh_cli.rerun_failed_jobs(&confirmation.repo, confirmation.run_id)
+                    } else {
+                        gh_cli.rerun_run(&confirmation.repo, confirmation.run_id)
+                    };
+                    OutcomeEvent::RunRerun(
+                        result
+                            .map(|_| description.clone())
+                            .map_err(|e| format!("Error rerunning {}: {:?}", description, e)),
+                    )
+                });
+            }
+            KeyCode::Char('n' | 'q') | KeyCode::Esc => {
+                self.app_state.rerun_confirmation = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Asks to confirm deleting the run of the selected job.
+    fn confirm_deletion(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index) else {
+            return;
+        };
+        let Some(run) = self.runs.iter().find(|run| run.id == job.run_id) else {
+            let message = format!("The run of {} is no longer listed.", job.name);
+            self.show_toast(message, ToastLevel::Error);
+            return;
+        };
+        self.app_state.deletion_confirmation = Some(DeletionConfirmation {
+            repo: run.repo.clone(),
+            run_id: run.id,
+            workflow_name: run.name.clone(),
+            path: run.path.clone(),
+            older_than_days: None,
+        });
+    }
+
+    /// Handles key events while a deletion is waiting to be confirmed, `o` switching between
+    /// deleting the run and deleting the older runs of its workflow, whose age is typed.
+    fn handle_deletion_confirmation_key_event(&mut self, key_event: KeyEvent) {
+        let Some(confirmation) = self.app_state.deletion_confirmation.as_mut() else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                if confirmation.older_than_days == Some(0) {
+                    return;
+                }
+                let Some(confirmation) = self.app_state.deletion_confirmation.take() else {
+                    return;
+                };
+                let description = confirmation.description();
+                self.show_toast(format!("Deleting {}...", description), ToastLevel::Info);
+                self.events.request(move |gh_cli| {
+                    let result = match confirmation.older_than_days {
+                        Some(days) => gh_cli
+                            .delete_runs_before(
+                                &confirmation.repo,
+                                &confirmation.path,
+                                Utc::now() - TimeDelta::days(days.into()),
+                            )
+                            .map(|deleted| {
+                                format!(
+                                    "{} run{} of {}",
+                                    deleted,
+                                    if deleted == 1 { "" } else { "s" },
+                                    confirmation.workflow_name
+                                )
+                            }),
+                        None => gh_cli
+                            .delete_run(&confirmation.repo, confirmation.run_id)
+                            .map(|_| description.clone()),
+                    };
+                    OutcomeEvent::RunsDeleted(
+                        result.map_err(|e| format!("Error deleting {}: {:?}", description, e)),
+                    )
+                });
+            }
+            KeyCode::Char('o') => {
+                confirmation.older_than_days = match confirmation.older_than_days {
+                    Some(_) => None,
+                    None => Some(DEFAULT_DELETION_AGE_DAYS),
+                };
+            }
+            KeyCode::Char(digit @ '0'..='9') => {
+                if let Some(days) = confirmation.older_than_days.as_mut() {
+                    let typed = *days * 10 + digit.to_digit(10).unwrap_or(0);
+                    if typed <= MAX_DELETION_AGE_DAYS {
+                        *days = typed;
+                    }
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(days) = confirmation.older_than_days.as_mut() {
+                    *days /= 10;
+                }
+            }
+            KeyCode::Char('n' | 'q') | KeyCode::Esc => {
+                self.app_state.deletion_confirmation = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Asks to confirm loading the whole log of the selected job, of which only the end was
+    /// fetched as it is large.
+    fn confirm_full_log(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index).cloned() else {
+            return;
+        };
+        match self.app_state.partial_logs.get(&job.id) {
+            Some(&total_bytes) => {
+                self.app_state.full_log_confirmation =
+                    Some(FullLogConfirmation { job, total_bytes })
+            }
+            None => self.show_toast(
+                format!("There is no more of the log of {} to load.", job.name),
+                ToastLevel::Info,
+            ),
+        }
+    }
+
+    /// Handles key events while loading a whole log is waiting to be confirmed.
+    fn handle_full_log_confirmation_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Char('y') | KeyCode::Enter => {
+                let Some(FullLogConfirmation { job, .. }) =
+                    self.app_state.full_log_confirmation.take()
+                else {
+                    return;
+                };
+                self.events.request(move |gh_cli| {
+                    JobDataEvent::JobLogFetched(
+                        job.id,
+                        gh_cli
+                            .fetch_job_log(&job, None)
+                            .map_err(|e| format!("Error fetching the full log: {:?}", e)),
+                    )
+                });
+            }
+            KeyCode::Char('n' | 'q') | KeyCode::Esc => {
+                self.app_state.full_log_confirmation = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Asks for the token to be authorized for the SAML single sign-on of an organization when
+    /// `error` says it isn't, rather than leaving a `403` that looks like a bug.
+    fn prompt_sso_authorization(&mut self, error: &str) {
+        if let Some(authorization) = SsoAuthorization::from_error(error)
+            && self.app_state.sso_dismissed.as_ref() != Some(&authorization.org)
+        {
+            self.app_state.sso_authorization = Some(authorization);
+        }
+    }
+
+    /// Handles key events while the single sign-on prompt is open: the authorization page is
+    /// opened in the browser, then the refresh retried.
+    fn handle_sso_authorization_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Char('o') => {
+                if let Some(authorization) = self.app_state.sso_authorization.clone() {
+                    self.open_url(&authorization.url);
+                }
+            }
+            KeyCode::Char('r') => {
+                self.app_state.sso_authorization = None;
+                self.events.refresh();
+            }
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.app_state.sso_dismissed = self
+                    .app_state
+                    .sso_authorization
+                    .take()
+                    .map(|authorization| authorization.org);
+            }
+            _ => {}
+        }
+    }
+
+    /// Opens a page in the browser, telling in a toast when it can't.
+    fn open_url(&mut self, url: &str) {
+        if let Err(e) = links::open_in_browser(url) {
+            self.show_toast(format!("Could not open {}: {}", url, e), ToastLevel::Error);
+        }
+    }
+
+    /// Shows a message over the UI for a few seconds.
+    fn show_toast(&mut self, message: String, level: ToastLevel) {
+        let duration = match level {
+            ToastLevel::Warning | ToastLevel::Error => ERROR_TOAST_DURATION,
+            ToastLevel::Info | ToastLevel::Success => TOAST_DURATION,
+        };
+        // The same message again only stays up longer
+        self.app_state
+            .toasts
+            .retain(|toast| toast.message != message);
+        if self.app_state.toasts.len() == MAX_TOASTS {
+            self.app_state.toasts.remove(0);
+        }
+        self.app_state.toasts.push(Toast {
+            message,
+            level,
+            expires_at: Instant::now() + duration,
+        });
+    }
+
+    /// Saves the whole log of the selected job, or the logs of every job of its run, to a file
+    /// in the current directory in the background.
+    fn save_logs(&mut self, whole_run: bool) {
+        let Some(job) = self.job_details.get(self.current_job_index).cloned() else {
+            return;
+        };
+        let (description, path) = if whole_run {
+            (
+                format!("the logs of the run of {}", job.name),
+                log_dump::run_logs_file(&job.repo, job.run_id),
+            )
+        } else {
+            (
+                format!("the log of {}", job.name),
+                log_dump::job_log_file(&job.repo, job.run_id, &job.name),
+            )
+        };
+        self.show_toast(format!("Saving {}...", description), ToastLevel::Info);
+        self.events.request(move |gh_cli| {
+            let bytes = if whole_run {
+                gh_cli.download_run_logs(&job.repo, job.run_id)
+            } else {
+                gh_cli.download_job_log(&job.repo, job.id)
+            };
+            OutcomeEvent::LogsSaved(
+                bytes
+                    .and_then(|bytes| log_dump::save(&path, &bytes))
+                    .map(|path| (description.clone(), path))
+                    .map_err(|e| format!("Could not save {}: {:?}", description, e)),
+            )
+        });
+    }
+
+    /// Starts paging older runs of the repository shown into the run history, going on from
+    /// where the latest backfill stopped, or pauses the backfill under way.
+    fn toggle_backfill(&mut self) {
+        if let Some(backfill) = self.app_state.backfill.take() {
+            self.show_toast(
+                format!("Paused backfilling the history of {}.", backfill.repo),
+                ToastLevel::Info,
+            );
+            return;
+        }
+        let Some(repo) = self.active_repo() else {
+            return;
+        };
+        // A complete history is brought up to date from the newest runs on
+        let page = match self.run_history.repo(&repo) {
+            Some(history) if !history.is_complete() => history.pages + 1,
+            _ => 1,
+        };
+        self.show_toast(
+            format!("Backfilling the history of {}...", repo),
+            ToastLevel::Info,
+        );
+        self.app_state.backfill = Some(Backfill { repo, page });
+        self.fetch_backfill_page(Duration::ZERO);
+    }
+
+    /// Fetches the next page of the backfill under way, or ends it once the history goes back
+    /// as far as it can.
+    fn continue_backfill(&mut self) {
+        let Some(backfill) = self.app_state.backfill.as_mut() else {
+            return;
+        };
+        match self.run_history.repo(&backfill.repo) {
+            Some(history) if history.is_complete() => {
+                let message = format!(
+                    "Backfilled the history of {}: {} runs.",
+                    backfill.repo,
+                    history.runs.len()
+                );
+                self.app_state.backfill = None;
+                self.show_toast(message, ToastLevel::Success);
+            }
+            _ => {
+                backfill.page += 1;
+                self.fetch_backfill_page(BACKFILL_PAGE_INTERVAL);
+            }
+        }
+    }
+
+    /// Fetches the page of the backfill under way after waiting `interval`, or longer while
+    /// the rate limit budget is low.
+    fn fetch_backfill_page(&self, interval: Duration) {
+        let Some(backfill) = &self.app_state.backfill else {
+            return;
+        };
+        let (repo, page) = (backfill.repo.clone(), backfill.page);
+        self.events.request(move |gh_cli| {
+            thread::sleep(Throttle::for_rate_limit(gh_cli.rate_limit().as_ref()).delay(interval));
+            PolledEvent::HistoryBackfilled(
+                repo.clone(),
+                page,
+                gh_cli
+                    .fetch_repo_history(&repo, page)
+                    .map_err(|e| format!("Error backfilling the history of {}: {:?}", repo, e)),
+            )
+        });
+    }
+
+    /// Copies the full SHA of the selected job's commit to the clipboard.
+    fn copy_commit_sha(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index) else {
+            return;
+        };
+        if job.head_sha.is_empty() {
+            self.show_toast(
+                format!(
+                    "The commit of {} is known after the next refresh.",
+                    job.name
+                ),
+                ToastLevel::Warning,
+            );
+            return;
+        }
+        let sha = job.head_sha.clone();
+        match links::write_clipboard(&sha) {
+            Ok(()) => self.show_toast(
+                format!("Copied {} to the clipboard.", sha),
+                ToastLevel::Success,
+            ),
+            Err(e) => self.show_toast(e.to_string(), ToastLevel::Error),
+        }
+    }
+
+    /// Mutes the workflow of the selected job on its branch until a newer commit runs, or
+    /// unmutes it.
+    fn toggle_workflow_mute(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index).cloned() else {
+            return;
+        };
+        match self.workflow_mutes.toggle(&job) {
+            Ok(true) => self.show_toast(
+                format!(
+                    "Muted the failures of {} on {} until a newer commit runs.",
+                    job.workflow_name, job.head_branch
+                ),
+                ToastLevel::Success,
+            ),
+            Ok(false) => self.show_toast(
+                format!("Unmuted {} on {}.", job.workflow_name, job.head_branch),
+                ToastLevel::Success,
+            ),
+            Err(e) => self.show_toast(
+                format!("Could not save the muted workflows: {}", e),
+                ToastLevel::Error,
+            ),
+        }
+        self.regroup_jobs();
+    }
+
+    /// Pins the selected job to the top of its column, or unpins it.
+    fn toggle_pin(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index).cloned() else {
+            return;
+        };
+        match self.pinned_jobs.toggle(&job) {
+            Ok(true) => self.show_toast(
+                format!(
+                    "Pinned {}, you'll be told when its status changes.",
+                    job.name
+                ),
+                ToastLevel::Success,
+            ),
+            Ok(false) => self.show_toast(format!("Unpinned {}.", job.name), ToastLevel::Success),
+            Err(e) => self.show_toast(
+                format!("Could not save the pinned jobs: {}", e),
+                ToastLevel::Error,
+            ),
+        }
+        self.regroup_jobs();
+    }
+
+    /// Opens the menu of what to copy about the selected job.
+    fn open_yank_menu(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index) else {
+            return;
+        };
+        let items = vec![
+            YankItem {
+                key: 'u',
+                label: "the job URL",
+                value: job.html_url.clone(),
+            },
+            YankItem {
+                key: 'r',
+                label: "the run ID",
+                value: job.run_id.to_string(),
+            },
+            YankItem {
+                key: 'g',
+                label: "the gh command",
+                value: format!("gh run view {} --repo {}", job.run_id, job.repo),
+            },
+            YankItem {
+                key: 'v',
+                label: "the view link",
+                value: self.actions_view(&job.repo).url(),
+            },
+            YankItem {
+                key: 'd',
+                label: "the download",
+                value: format!("gh run download {} --repo {}", job.run_id, job.repo),
+            },
+            YankItem {
+                key: 'l',
+                label: "the log download",
+                value: format!(
+                    "gh run view {} --repo {} --log > run-{}.log",
+                    job.run_id, job.repo, job.run_id
+                ),
+            },
+        ];
+        self.app_state.yank_menu = Some(YankMenuState { items, selected: 0 });
+    }
+
+    /// The Actions page of a repository that best matches the job columns: the branch and
+    /// user whose runs are fetched, the workflow if every job shown is of the same one, and
+    /// the status of the selected column. The fuzzy filter has no equivalent there.
+    fn actions_view(&self, repo: &str) -> ActionsView {
+        let state = &self.app_state;
+        let workflow_files: HashSet<&str> = [
+            &state.in_progress_jobs,
+            &state.success_jobs,
+            &state.failure_jobs,
+            &state.other_jobs,
+        ]
+        .into_iter()
+        .flat_map(|groups| groups.values().flatten())
+        .flat_map(ColumnRow::jobs)
+        .filter_map(|&index| {
+            let run_id = self.job_details.get(index)?.run_id;
+            let run = self.runs.iter().find(|run| run.id == run_id)?;
+            run.path.rsplit('/').next()
+        })
+        .collect();
+        let status = match state.column_index {
+            0 => Some("in_progress"),
+            1 => Some("success"),
+            _ if state.show_other_jobs => None,
+            _ => Some("failure"),
+        };
+        ActionsView {
+            repo: repo.to_string(),
+            workflow_file: match workflow_files.into_iter().collect::<Vec<_>>()[..] {
+                [file] => Some(file.to_string()),
+                _ => None,
+            },
+            branch: self.gh_cli.branch(),
+            actor: self.gh_cli.actor().map(str::to_string),
+            status,
+        }
+    }
+
+    /// Handles key events while the yank menu is open.
+    fn handle_yank_menu_key_event(&mut self, key_event: KeyEvent) {
+        let Some(menu) = self.app_state.yank_menu.as_mut() else {
+            return;
+        };
+        let picked = match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                menu.selected = menu.selected.saturating_sub(1);
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                menu.selected = (menu.selected + 1).min(menu.items.len().saturating_sub(1));
+                None
+            }
+            KeyCode::Enter => Some(menu.selected),
+            KeyCode::Esc | KeyCode::Char('q' | 'y') => {
+                self.app_state.yank_menu = None;
+                None
+            }
+            KeyCode::Char(c) => menu.items.iter().position(|item| item.key == c),
+            _ => None,
+        };
+        let Some(index) = picked else {
+            return;
+        };
+        let Some(item) = self
+            .app_state
+            .yank_menu
+            .take()
+            .and_then(|menu| menu.items.into_iter().nth(index))
+        else {
+            return;
+        };
+        match links::write_clipboard(&item.value) {
+            Ok(()) => self.show_toast(
+                format!("Copied {} to the clipboard: {}", item.label, item.value),
+                ToastLevel::Success,
+            ),
+            Err(e) => self.show_toast(e.to_string(), ToastLevel::Error),
+        }
+    }
+
+    /// Opens the pull request the selected job ran for, rather than the job itself.
+    fn open_pull_request(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index) else {
+            return;
+        };
+        let Some(pull_request) = &job.pull_request else {
+            self.show_toast(
+                format!("{} didn't run for a pull request.", job.name),
+                ToastLevel::Warning,
+            );
+            return;
+        };
+        let url = pull_request_url(&job.repo, pull_request);
+        self.open_url(&url);
+    }
+
+    fn change_row_index(&mut self, delta: isize) {
+        // While the details panel is open, Up/Down move through the steps of the job
+        if self.app_state.show_details {
+            let step_count = self
+                .job_details
+                .get(self.current_job_index)
+                .map_or(0, |job| job.steps.len());
+            self.app_state.selected_step = (self.app_state.selected_step as isize)
+                .saturating_add(delta)
+                .clamp(0, step_count.saturating_sub(1) as isize)
+                as usize;
+            return;
+        }
+        let current_column_jobs = self.get_jobs_for_current_column();
+        if current_column_jobs.is_empty() {
+            self.app_state.position_mut().row_index = 0;
+            self.current_job_index = 0;
+            return;
+        }
+
+        let mut new_row_index =
+            (self.app_state.position().row_index as isize).saturating_add(delta);
+
+        // Ensure the row index stays within bounds
+        if new_row_index < 0 {
+            new_row_index = 0;
+        }
+        let row_count = current_column_jobs.values().flatten().count();
+        self.app_state.position_mut().row_index =
+            (new_row_index as usize).min(row_count.saturating_sub(1));
+        self.scroll_to_selection();
+
+        // Update current_job_index based on the new row and column
+        self.update_current_job_index_from_state();
+    }
+    /// Scrolls the selected column by whole screens, selecting a job on screen if the
+    /// selected one scrolled out of view.
+    fn scroll_page(&mut self, pages: isize) {
+        let column_index = self.app_state.column_index;
+        let layout = self.app_state.column_layouts.borrow()[column_index].clone();
+        let position = &mut self.app_state.columns[column_index];
+        let page = layout.viewport_rows.max(1) as isize;
+        // Page from what is on screen, which the renderer scrolled to show the selection
+        let shown = layout.offset_showing(position.row_index, position.scroll_offset);
+        position.scroll_offset = (shown as isize)
+            .saturating_add(pages.saturating_mul(page))
+            .clamp(0, layout.max_scroll() as isize) as usize;
+
+        let view = position.scroll_offset..position.scroll_offset + layout.viewport_rows;
+        let visible = |rows: &Range<usize>| rows.start >= view.start && rows.end <= view.end;
+        if layout
+            .jobs
+            .get(position.row_index)
+            .is_some_and(|rows| !visible(rows))
+        {
+            let on_screen = if pages > 0 {
+                layout.jobs.iter().position(visible)
+            } else {
+                layout.jobs.iter().rposition(visible)
+            };
+            // A job taller than the screen is never fully visible, so settle for one in view
+            let in_view = |rows: &Range<usize>| rows.start < view.end && rows.end > view.start;
+            if let Some(row) = on_screen.or_else(|| layout.jobs.iter().position(in_view)) {
+                position.row_index = row;
+            }
+            self.update_current_job_index_from_state();
+        }
+    }
+
+    /// Scrolls the selected column just enough for the selected job to be visible.
+    fn scroll_to_selection(&mut self) {
+        let column_index = self.app_state.column_index;
+        let position = &mut self.app_state.columns[column_index];
+        position.scroll_offset = self.app_state.column_layouts.borrow()[column_index]
+            .offset_showing(position.row_index, position.scroll_offset);
+    }
+
+    fn update_current_job_index_from_state(&mut self) {
+        let current_column_jobs_indices = self.get_jobs_for_current_column();
+        let indices: Vec<usize> = current_column_jobs_indices
+            .values()
+            .flatten()
+            .map(ColumnRow::job_index)
+            .collect();
+        if let Some(original_index) = indices.get(self.app_state.position().row_index) {
+            self.current_job_index = *original_index;
+        } else {
+            // No job selected, default to first available or 0
+            self.current_job_index = indices.first().copied().unwrap_or(0);
+        }
+    }
+
+    fn get_jobs_for_current_column(&self) -> &BTreeMap<JobGroup, Vec<ColumnRow>> {
+        self.app_state.column_jobs(self.app_state.column_index)
+    }
+
+    /// Expands the selected matrix, or collapses it if it is expanded. Returns whether a
+    /// matrix is selected.
+    fn toggle_selected_matrix(&mut self) -> bool {
+        let Some(ColumnRow::Matrix(matrix)) = self.app_state.selected_row() else {
+            return false;
+        };
+        let key = (matrix.run_id, matrix.name.clone());
+        if !self.app_state.expanded_matrices.remove(&key) {
+            self.app_state.expanded_matrices.insert(key);
+        }
+        // The rows above are unchanged, so the matrix stays selected
+        self.group_jobs();
+        true
+    }
+
+    fn toggle_details_panel(&mut self) {
+        self.app_state.show_details = !self.app_state.show_details;
+        self.app_state.show_links = false;
+        // Start at the step that failed, if any
+        self.app_state.selected_step = self
+            .job_details
+            .get(self.current_job_index)
+            .and_then(|job| {
+                job.steps
+                    .iter()
+                    .position(|step| step.conclusion.as_deref() == Some("failure"))
+            })
+            .unwrap_or(0);
+        self.app_state.selected_annotation = 0;
+        if self.app_state.show_details {
+            self.fetch_failure_excerpt();
+            self.fetch_annotations();
+            self.fetch_check_annotations();
+            self.fetch_artifacts();
+            self.fetch_job_environment();
+            self.fetch_ref_kind();
+        }
+    }
+
+    /// Fetches the annotations of the selected job in the background if it failed, such as
+    /// the errors a compiler reported on the lines of the files. Each job's are fetched once,
+    /// as a failed job doesn't change anymore.
+    fn fetch_annotations(&mut self) {
+        let Some(job) = self
+            .job_details
+            .get(self.current_job_index)
+            .filter(|job| job.conclusion.as_deref() == Some("failure"))
+            .cloned()
+        else {
+            return;
+        };
+        if self.app_state.annotations.contains_key(&job.id) {
+            return;
+        }
+        self.app_state.annotations.insert(job.id, None);
+        self.events.request(move |gh_cli| {
+            let result = gh_cli
+                .fetch_annotations(&job)
+                .map_err(|e| format!("Error fetching annotations: {:?}", e));
+            JobDataEvent::AnnotationsFetched(job.id, result)
+        });
+    }
+
+    /// Fetches in the background the annotations of the checks other apps failed on the commit
+    /// of the selected job once it completed, such as the lines Codecov found uncovered, whether
+    /// or not the job failed itself. Each commit's are fetched once for all of its jobs.
+    fn fetch_check_annotations(&mut self) {
+        let Some(job) = self
+            .job_details
+            .get(self.current_job_index)
+            .filter(|job| job.status == "completed" && !job.head_sha.is_empty())
+        else {
+            return;
+        };
+        let commit = (job.repo.to_ascii_lowercase(), job.head_sha.clone());
+        if self.app_state.check_annotations.contains_key(&commit) {
+            return;
+        }
+        self.app_state
+            .check_annotations
+            .insert(commit.clone(), None);
+        self.events.request(move |gh_cli| {
+            let (repo, sha) = &commit;
+            let result = gh_cli
+                .fetch_check_annotations(repo, sha)
+                .map_err(|e| format!("Error fetching the checks of other apps: {:?}", e));
+            JobDataEvent::CheckAnnotationsFetched(commit, result)
+        });
+    }
+
+    /// Fetches the artifacts of the run of the selected job in the background once the job
+    /// completed, so that those about to expire can be downloaded in time. Each run's are
+    /// fetched once.
+    fn fetch_artifacts(&mut self) {
+        let Some(job) = self
+            .job_details
+            .get(self.current_job_index)
+            .filter(|job| job.status == "completed")
+        else {
+            return;
+        };
+        if self.app_state.artifacts.contains_key(&job.run_id) {
+            return;
+        }
+        self.app_state.artifacts.insert(job.run_id, None);
+        let (repo, run_id) = (job.repo.clone(), job.run_id);
+        self.events.request(move |gh_cli| {
+            JobDataEvent::ArtifactsFetched(
+                run_id,
+                gh_cli
+                    .fetch_artifacts(&repo, run_id)
+                    .map_err(|e| format!("Error fetching artifacts: {:?}", e)),
+            )
+        });
+    }
+
+    /// Fetches in the background how long the repositories of the jobs keep their logs, once
+    /// per repository.
+    fn fetch_retention_days(&mut self) {
+        let repos: BTreeSet<String> = self
+            .job_details
+            .iter()
+            .map(|job| job.repo.to_ascii_lowercase())
+            .filter(|repo| !self.app_state.retention_days.contains_key(repo))
+            .collect();
+        for repo in repos {
+            self.app_state.retention_days.insert(repo.clone(), None);
+            self.events.request(move |gh_cli| {
+                let result = gh_cli
+                    .fetch_retention_days(&repo)
+                    .map_err(|e| format!("Error fetching the retention of {}: {:?}", repo, e));
+                JobDataEvent::RetentionFetched(repo, result)
+            });
+        }
+    }
+
+    /// When GitHub deletes the log of a job, counted from when it started.
+    pub fn log_expiry(&self, job: &GithubJob) -> DateTime<Utc> {
+        let days = self
+            .app_state
+            .retention_days
+            .get(&job.repo.to_ascii_lowercase())
+            .copied()
+            .flatten()
+            .map_or(DEFAULT_RETENTION_DAYS, |days| days as i64);
+        job.started_at + TimeDelta::days(days)
+    }
+
+    /// The artifacts of the run of a job, once fetched.
+    pub fn run_artifacts(&self, run_id: u64) -> &[Artifact] {
+        self.app_state
+            .artifacts
+            .get(&run_id)
+            .and_then(|artifacts| artifacts.as_ref()?.as_ref().ok())
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// What of a completed job GitHub deletes first within [`EXPIRY_WARNING`] of `now`, its
+    /// log or the artifacts of its run, and when.
+    pub fn expiring_soon(
+        &self,
+        job: &GithubJob,
+        now: DateTime<Utc>,
+    ) -> Option<(&'static str, DateTime<Utc>)> {
+        if job.status != "completed" {
+            return None;
+        }
+        let artifacts = self
+            .run_artifacts(job.run_id)
+            .iter()
+            .filter(|artifact| !artifact.expired)
+            .filter_map(|artifact| artifact.expires_at)
+            .min()
+            .map(|expires_at| ("artifacts", expires_at));
+        [Some(("logs", self.log_expiry(job))), artifacts]
+            .into_iter()
+            .flatten()
+            .filter(|(_, expires_at)| is_expiring_soon(*expires_at, now))
+            .min_by_key(|(_, expires_at)| *expires_at)
+    }
+
+    /// The annotations of the selected job once fetched, followed by those of the checks other
+    /// apps failed on its commit.
+    pub fn selected_annotations(&self) -> Vec<&Annotation> {
+        self.job_details
+            .get(self.current_job_index)
+            .map_or_else(Vec::new, |job| self.job_annotations(job))
+    }
+
+    /// The annotations of a job once fetched, followed by those of the checks other apps
+    /// failed on its commit.
+    pub fn job_annotations(&self, job: &GithubJob) -> Vec<&Annotation> {
+        let own = self
+            .app_state
+            .annotations
+            .get(&job.id)
+            .and_then(|annotations| annotations.as_ref()?.as_ref().ok())
+            .map_or(&[][..], Vec::as_slice);
+        let checks = self
+            .check_annotations(job)
+            .map_or(&[][..], |checks| checks.annotations.as_slice());
+        own.iter().chain(checks).collect()
+    }
+
+    /// The annotations of the checks other apps failed on the commit of a job, once fetched.
+    pub fn check_annotations(&self, job: &GithubJob) -> Option<&CheckAnnotations> {
+        self.app_state
+            .check_annotations
+            .get(&(job.repo.to_ascii_lowercase(), job.head_sha.clone()))
+            .and_then(|checks| checks.as_ref()?.as_ref().ok())
+    }
+
+    /// Moves the selection through the annotations of the selected job, wrapping around.
+    fn change_annotation(&mut self, delta: isize) {
+        let count = self.selected_annotations().len() as isize;
+        if count > 0 {
+            self.app_state.selected_annotation =
+                (self.app_state.selected_annotation as isize + delta).rem_euclid(count) as usize;
+        }
+    }
+
+    /// Opens the file the selected annotation is on, at the annotated lines.
+    fn open_annotation(&mut self) {
+        let Some(url) = self
+            .selected_annotations()
+            .get(self.app_state.selected_annotation)
+            .map(|annotation| annotation.html_url())
+        else {
+            self.show_toast(
+                "The selected job has no annotations to open.".to_string(),
+                ToastLevel::Warning,
+            );
+            return;
+        };
+        self.open_url(&url);
+    }
+
+    /// Looks up the environment the selected job deployed to in the background. Each job is
+    /// looked up once it has started, as that is when its deployment is created, and once its
+    /// commit is known, which jobs saved by older versions lack.
+    fn fetch_job_environment(&mut self) {
+        let Some(job) = self
+            .job_details
+            .get(self.current_job_index)
+            .filter(|job| !matches!(job.status.as_str(), "queued" | "waiting"))
+            .filter(|job| !job.head_sha.is_empty())
+            .cloned()
+        else {
+            return;
+        };
+        if self.app_state.job_environments.contains_key(&job.id) {
+            return;
+        }
+        self.app_state.job_environments.insert(job.id, None);
+        self.events.request(move |gh_cli| {
+            JobDataEvent::JobEnvironmentFetched(
+                job.id,
+                gh_cli
+                    .fetch_job_environment(&job)
+                    .map_err(|e| format!("Error fetching deployments: {:?}", e)),
+            )
+        });
+    }
+
+    /// Looks up whether the ref of the selected job's run is a tag or a branch in the
+    /// background, for its OIDC subject, unless its event tells. Each ref is looked up once.
+    fn fetch_ref_kind(&mut self) {
+        let Some(job) = self
+            .job_details
+            .get(self.current_job_index)
+            .filter(|job| job.known_ref_kind().is_none() && !job.head_branch.is_empty())
+        else {
+            return;
+        };
+        let git_ref = (job.repo.to_ascii_lowercase(), job.head_branch.clone());
+        if self.app_state.ref_kinds.contains_key(&git_ref) {
+            return;
+        }
+        self.app_state.ref_kinds.insert(git_ref.clone(), None);
+        self.events.request(move |gh_cli| {
+            let result = gh_cli
+                .fetch_ref_kind(&git_ref.0, &git_ref.1)
+                .map_err(|e| format!("Error looking up {}: {:?}", git_ref.1, e));
+            JobDataEvent::RefKindFetched(git_ref, result)
+        });
+    }
+
+    /// The subject claim of the OIDC tokens a job can request, `None` until both the
+    /// environment it deployed to and the kind of its ref are known, the latter not mattering
+    /// when it did deploy.
+    pub fn oidc_subject(&self, job: &GithubJob) -> Option<Result<String, String>> {
+        let environment = match self.app_state.job_environments.get(&job.id)? {
+            Some(Ok(environment)) => environment.as_deref(),
+            Some(Err(e)) => return Some(Err(e.clone())),
+            None => return None,
+        };
+        let kind = match (environment, job.known_ref_kind()) {
+            (Some(_), _) => RefKind::Branch,
+            (None, Some(kind)) => kind,
+            (None, None) => {
+                let git_ref = (job.repo.to_ascii_lowercase(), job.head_branch.clone());
+                match self.app_state.ref_kinds.get(&git_ref)? {
+                    Some(Ok(kind)) => *kind,
+                    Some(Err(e)) => return Some(Err(e.clone())),
+                    None => return None,
+                }
+            }
+        };
+        Some(Ok(job.oidc_subject(environment, kind)))
+    }
+
+    /// The dispatch from the local history that most likely started a job's run: the latest
+    /// one of its workflow and ref sent shortly before the job started. GitHub doesn't
+    /// report the inputs of a run, so those of runs dispatched elsewhere are unknown.
+    pub fn dispatch_for<'a>(&'a self, job: &'a GithubJob) -> Option<&'a DispatchRecord> {
+        if job.event != "workflow_dispatch" {
+            return None;
+        }
+        let started_at = job.started_at.timestamp().max(0) as u64;
+        self.dispatch_history.for_repo(&job.repo).find(|record| {
+            record.workflow.name == job.workflow_name
+                && record.git_ref == job.head_branch
+                && record.dispatched_at <= started_at
+                && started_at - record.dispatched_at <= DISPATCH_MATCH_WINDOW.as_secs()
+        })
+    }
+
+    /// The median duration of each step of `job`, by name, over the earlier runs of the same
+    /// job in which the step succeeded. Steps with too few such runs are left out.
+    pub fn step_medians(&self, job: &GithubJob) -> HashMap<String, TimeDelta> {
+        let mut durations: HashMap<&str, Vec<TimeDelta>> = HashMap::new();
+        let earlier_jobs = self.job_details.iter().filter(|earlier| {
+            earlier.id != job.id
+                && earlier.started_at < job.started_at
+                && earlier.name == job.name
+                && earlier.workflow_name == job.workflow_name
+                && earlier.repo == job.repo
+        });
+        for earlier in earlier_jobs {
+            for step in &earlier.steps {
+                if step.conclusion.as_deref() == Some("success")
+                    && step.completed_at.is_some()
+                    && let Some(duration) = step.duration(Utc::now())
+                {
+                    durations.entry(&step.name).or_default().push(duration);
+                }
+            }
+        }
+        durations
+            .into_iter()
+            .filter(|(_, durations)| durations.len() >= MIN_STEP_SAMPLES)
+            .map(|(name, mut durations)| {
+                durations.sort();
+                let middle = durations.len() / 2;
+                let median = if durations.len() % 2 == 0 {
+                    (durations[middle - 1] + durations[middle]) / 2
+                } else {
+                    durations[middle]
+                };
+                (name.to_string(), median)
+            })
+            .collect()
+    }
+
+    /// Fetches the log of the selected job in the background if it failed, to show why in
+    /// the details panel. Each log is fetched once, as a failed job doesn't change anymore.
+    fn fetch_failure_excerpt(&mut self) {
+        let Some(job) = self
+            .job_details
+            .get(self.current_job_index)
+            .filter(|job| job.conclusion.as_deref() == Some("failure"))
+            .cloned()
+        else {
+            return;
+        };
+        if self.app_state.failure_excerpts.contains_key(&job.id) {
+            return;
+        }
+        self.app_state.failure_excerpts.insert(job.id, None);
+        self.events.request(move |gh_cli| {
+            JobDataEvent::JobLogFetched(
+                job.id,
+                gh_cli
+                    .fetch_job_log(&job, Some(LOG_TAIL_BYTES))
+                    .map_err(|e| format!("Error fetching log: {:?}", e)),
+            )
+        });
+    }
+
+    fn toggle_api_inspector(&mut self) {
+        // The inspector is a debugging aid and only available with `--debug`
+        if self.args.debug {
+            self.app_state.show_api_inspector = !self.app_state.show_api_inspector;
+        }
+    }
+
+    fn open_dispatch(&mut self) {
+        let Some(repo_info) = self.gh_cli.repos().get(self.app_state.repo_index).cloned() else {
+            return;
+        };
+        self.app_state.dispatch = Some(DispatchState::default());
+        let refs_repo_info = repo_info.clone();
+        self.events.request(move |gh_cli| {
+            ViewDataEvent::WorkflowsFetched(
+                gh_cli
+                    .fetch_workflows(&repo_info)
+                    .map_err(|e| format!("Error fetching workflows: {:?}", e)),
+            )
+        });
+        self.events.request(move |gh_cli| {
+            ViewDataEvent::RefsFetched(
+                gh_cli
+                    .fetch_refs(&refs_repo_info)
+                    .map_err(|e| format!("Error fetching branches and tags: {:?}", e)),
+            )
+        });
+    }
+
+    /// Opens the popup of the status badges of the active repository's workflows, for the
+    /// branch of the selected job to begin with.
+    fn open_badges(&mut self) {
+        let Some(repo_info) = self.gh_cli.repos().get(self.app_state.repo_index).cloned() else {
+            return;
+        };
+        let branch = self
+            .job_details
+            .get(self.current_job_index)
+            .filter(|job| job.repo == repo_info.full_name())
+            .map_or_else(
+                || self.gh_cli.current_branch().to_string(),
+                |job| job.head_branch.clone(),
+            );
+        self.app_state.badges = Some(BadgePopupState {
+            repo: repo_info.full_name(),
+            workflows: None,
+            selected: 0,
+            branch: TextInput::new(branch),
+        });
+        self.events.request(move |gh_cli| {
+            ViewDataEvent::WorkflowsFetched(
+                gh_cli
+                    .fetch_workflows(&repo_info)
+                    .map_err(|e| format!("Error fetching workflows: {:?}", e)),
+            )
+        });
+    }
+
+    /// Handles key events while the badge popup is open: the arrows pick a workflow, `Enter`
+    /// copies its badge and `Tab` those of every workflow, and other keys edit the branch.
+    fn handle_badges_key_event(&mut self, key_event: KeyEvent) {
+        let Some(badges) = self.app_state.badges.as_mut() else {
+            return;
+        };
+        let workflows = match &badges.workflows {
+            Some(Ok(workflows)) => workflows.as_slice(),
+            _ => &[],
+        };
+        let copied = match key_event.code {
+            KeyCode::Up => {
+                badges.selected = badges.selected.saturating_sub(1);
+                None
+            }
+            KeyCode::Down => {
+                badges.selected = (badges.selected + 1).min(workflows.len().saturating_sub(1));
+                None
+            }
+            KeyCode::Enter => workflows.get(badges.selected).map(|workflow| {
+                (
+                    badges.badge(workflow),
+                    format!("the badge of {}", workflow.name),
+                )
+            }),
+            KeyCode::Tab if !workflows.is_empty() => Some((
+                workflows
+                    .iter()
+                    .map(|workflow| badges.badge(workflow))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                format!("{} badges", workflows.len()),
+            )),
+            KeyCode::Esc => {
+                self.app_state.badges = None;
+                None
+            }
+            _ => {
+                badges.branch.handle_key(key_event);
+                None
+            }
+        };
+        let Some((markdown, description)) = copied else {
+            return;
+        };
+        match links::write_clipboard(&markdown) {
+            Ok(()) => {
+                self.app_state.badges = None;
+                self.show_toast(
+                    format!("Copied {} to the clipboard.", description),
+                    ToastLevel::Success,
+                );
+            }
+            Err(e) => self.show_toast(e.to_string(), ToastLevel::Error),
+        }
+    }
+
+    fn open_job_summary(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index).cloned() else {
+            return;
+        };
+        if job.status != "completed" {
+            self.app_state.loading_status =
+                format!("{} has no summary until it completes.", job.name);
+            return;
+        }
+        self.app_state.job_summary = Some(JobSummaryState {
+            job_id: job.id,
+            job_name: job.name.clone(),
+            markdown: None,
+            error: None,
+            scroll: 0,
+        });
+        self.events.request(move |gh_cli| {
+            JobDataEvent::JobSummaryFetched(
+                job.id,
+                gh_cli
+                    .fetch_job_summary(&job)
+                    .map_err(|e| format!("Error fetching job summary: {:?}", e)),
+            )
+        });
+    }
+
+    /// Handles key events while the job summary popup is open.
+    fn handle_job_summary_key_event(&mut self, key_event: KeyEvent) {
+        let Some(summary) = self.app_state.job_summary.as_mut() else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'S') => self.app_state.job_summary = None,
+            KeyCode::Up => summary.scroll = summary.scroll.saturating_sub(1),
+            KeyCode::Down => summary.scroll = summary.scroll.saturating_add(1),
+            KeyCode::PageUp => summary.scroll = summary.scroll.saturating_sub(10),
+            KeyCode::PageDown => summary.scroll = summary.scroll.saturating_add(10),
+            _ => {}
+        }
+    }
+
+    /// Opens the menu of the commands of the installed `gh` extensions, run for the selected
+    /// job's repository and branch.
+    fn open_extension_menu(&mut self) {
+        let job = self.job_details.get(self.current_job_index);
+        let Some(repo) = job
+            .map(|job| job.repo.clone())
+            .or_else(|| self.active_repo())
+        else {
+            return;
+        };
+        let branch = job.map_or_else(
+            || self.gh_cli.current_branch().to_string(),
+            |job| job.head_branch.clone(),
+        );
+        self.app_state.extension_menu = Some(ExtensionMenuState {
+            actions: None,
+            selected: 0,
+            repo,
+            branch,
+        });
+        self.events.request(|gh_cli| {
+            ViewDataEvent::ExtensionsFetched(
+                gh_cli
+                    .fetch_extensions()
+                    .map_err(|e| format!("Error listing the gh extensions: {:?}", e)),
+            )
+        });
+    }
+
+    /// Handles key events while the menu of extension commands is open.
+    fn handle_extension_menu_key_event(&mut self, key_event: KeyEvent) {
+        let Some(menu) = self.app_state.extension_menu.as_mut() else {
+            return;
+        };
+        let action_count = match &menu.actions {
+            Some(Ok(actions)) => actions.len(),
+            _ => 0,
+        };
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => menu.selected = menu.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                menu.selected = (menu.selected + 1).min(action_count.saturating_sub(1))
+            }
+            KeyCode::Enter => {
+                let Some(menu) = self.app_state.extension_menu.take() else {
+                    return;
+                };
+                let Some(Ok(actions)) = &menu.actions else {
+                    return;
+                };
+                let Some(action) = actions.get(menu.selected) else {
+                    return;
+                };
+                self.run_extension_action(action, &menu.repo, &menu.branch);
+            }
+            KeyCode::Esc | KeyCode::Char('q' | 'X') => self.app_state.extension_menu = None,
+            _ => {}
+        }
+    }
+
+    /// Runs a command of a `gh` extension: in the foreground if it takes over the terminal,
+    /// and otherwise in the background, showing its output once it exits.
+    fn run_extension_action(&mut self, action: &'static ExtensionAction, repo: &str, branch: &str) {
+        let args = action.args(repo, branch);
+        if action.interactive {
+            self.app_state.foreground_command = Some(args);
+            return;
+        }
+        self.app_state.extension_output = Some(ExtensionOutputState {
+            label: action.label,
+            output: None,
+            scroll: 0,
+        });
+        self.events.request(move |gh_cli| {
+            OutcomeEvent::ExtensionRun(
+                action.label.to_string(),
+                gh_cli
+                    .run_extension(&args)
+                    .map_err(|e| format!("Error running `gh {}`: {:?}", args.join(" "), e)),
+            )
+        });
+    }
+
+    /// Handles key events while the output of an extension command is shown.
+    fn handle_extension_output_key_event(&mut self, key_event: KeyEvent) {
+        let Some(popup) = self.app_state.extension_output.as_mut() else {
+            return;
+        };
+        let last_line = match &popup.output {
+            Some(Ok(output)) => output.lines().count().saturating_sub(1) as u16,
+            _ => 0,
+        };
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.app_state.extension_output = None,
+            KeyCode::Up => popup.scroll = popup.scroll.saturating_sub(1),
+            KeyCode::Down => popup.scroll = (popup.scroll + 1).min(last_line),
+            KeyCode::PageUp => popup.scroll = popup.scroll.saturating_sub(10),
+            KeyCode::PageDown => popup.scroll = popup.scroll.saturating_add(10).min(last_line),
+            KeyCode::Home => popup.scroll = 0,
+            KeyCode::End => popup.scroll = last_line,
+            _ => {}
+        }
+    }
+
+    /// Steps aside for `gh` to run in the foreground with `args`, e.g. an extension with a
+    /// UI of its own, and takes the terminal back once it exits.
+    fn run_in_foreground(&mut self, terminal: &mut DefaultTerminal, args: &[String]) {
+        self.events.pause_input(true);
+        ratatui::restore();
+        let status = Command::new("gh").args(args).status();
+        *terminal = ratatui::init();
+        // Whatever the command left on the screen isn't known to the terminal's buffers
+        let _ = terminal.clear();
+        self.events.pause_input(false);
+        match status {
+            Ok(status) if status.success() => {}
+            Ok(status) => self.show_toast(
+                format!("`gh {}` exited with {}", args.join(" "), status),
+                ToastLevel::Error,
+            ),
+            Err(e) => self.show_toast(
+                format!("Could not run `gh {}`: {}", args.join(" "), e),
+                ToastLevel::Error,
+            ),
+        }
+    }
+
+    /// Opens the popup showing the JSON the API returns for the selected job and its run.
+    fn open_json_inspector(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index).cloned() else {
+            return;
+        };
+        self.app_state.json_inspector = Some(JsonInspectorState {
+            job_id: job.id,
+            job_name: job.name.clone(),
+            json: None,
+            scroll: 0,
+        });
+        self.events.request(move |gh_cli| {
+            JobDataEvent::RawJsonFetched(
+                job.id,
+                gh_cli
+                    .fetch_raw_json(&job)
+                    .map_err(|e| format!("Error fetching the JSON of {}: {:?}", job.name, e)),
+            )
+        });
+    }
+
+    /// Handles key events while the JSON inspector is open.
+    fn handle_json_inspector_key_event(&mut self, key_event: KeyEvent) {
+        let Some(inspector) = self.app_state.json_inspector.as_mut() else {
+            return;
+        };
+        let last_line = match &inspector.json {
+            Some(Ok(json)) => json.lines().count().saturating_sub(1) as u16,
+            _ => 0,
+        };
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'J') => self.app_state.json_inspector = None,
+            KeyCode::Up => inspector.scroll = inspector.scroll.saturating_sub(1),
+            KeyCode::Down => inspector.scroll = (inspector.scroll + 1).min(last_line),
+            KeyCode::PageUp => inspector.scroll = inspector.scroll.saturating_sub(10),
+            KeyCode::PageDown => {
+                inspector.scroll = inspector.scroll.saturating_add(10).min(last_line)
+            }
+            KeyCode::Home => inspector.scroll = 0,
+            KeyCode::End => inspector.scroll = last_line,
+            _ => {}
+        }
+    }
+
+    fn open_actions_settings(&mut self) {
+        let Some(repo_info) = self.gh_cli.repos().get(self.app_state.repo_index).cloned() else {
+            return;
+        };
+        let repo = repo_info.full_name();
+        self.app_state.actions_settings = Some(ActionsSettingsState {
+            repo: repo.clone(),
+            settings: None,
+            error: None,
+        });
+        self.events.request(move |gh_cli| {
+            ViewDataEvent::ActionsSettingsFetched(
+                repo,
+                gh_cli
+                    .fetch_actions_settings(&repo_info)
+                    .map_err(|e| format!("Error fetching Actions settings: {:?}", e)),
+            )
+        });
+    }
+
+    /// Switches to the dashboard of a single run, which is fetched on the next tick.
+    fn start_watch(&mut self, repo: String, run_id: u64, exit_on_conclusion: bool) {
+        self.app_state
+            .views
+            .update(ViewMessage::Open(View::Watch(Box::new(WatchState {
+                repo,
+                run_id,
+                details: None,
+                error: None,
+                scroll: 0,
+                next_poll: Some(Instant::now()),
+                exit_on_conclusion,
+            }))));
+    }
+
+    /// Goes to the run, job or pull request checks whose URL is on the clipboard. A job that
+    /// is listed is selected in its column, anything else is followed on the run dashboard.
+    fn open_clipboard_link(&mut self) {
+        let text = match links::read_clipboard() {
+            Ok(text) => text,
+            Err(e) => {
+                self.show_toast(format!("{}", e), ToastLevel::Error);
+                return;
+            }
+        };
+        let Some(link) = ActionsLink::parse(&text) else {
+            self.show_toast(
+                "The clipboard doesn't hold the URL of a run, a job or pull request checks."
+                    .to_string(),
+                ToastLevel::Error,
+            );
+            return;
+        };
+        match link {
+            ActionsLink::Run { repo, run_id } => self.show_run(repo, run_id),
+            ActionsLink::Job {
+                repo,
+                run_id,
+                job_id,
+            } => {
+                if !self.select_job(job_id) {
+                    self.show_run(repo, run_id);
+                }
+            }
+            ActionsLink::PullRequestChecks {
+                repo,
+                number,
+                job_id,
+            } => {
+                if job_id.is_some_and(|job_id| self.select_job(job_id)) {
+                    return;
+                }
+                self.show_toast(
+                    format!("Looking up the run of {}#{}...", repo, number),
+                    ToastLevel::Info,
+                );
+                self.events.request(move |gh_cli| {
+                    let run_id = match job_id {
+                        Some(job_id) => gh_cli.fetch_job_run_id(&repo, job_id),
+                        None => gh_cli.fetch_pull_request_run_id(&repo, number),
+                    };
+                    OutcomeEvent::LinkedRunFound(
+                        run_id
+                            .map(|run_id| (repo.clone(), run_id))
+                            .map_err(|e| format!("Error finding the run of #{}: {:?}", number, e)),
+                    )
+                });
+            }
+        }
+    }
+
+    /// Selects a job in its column and opens its details, switching to its repository if
+    /// needed. Returns whether it is listed, which it isn't when filtered out or not fetched.
+    fn select_job(&mut self, job_id: u64) -> bool {
+        let Some(repo) = self
+            .job_details
+            .iter()
+            .find(|job| job.id == job_id)
+            .map(|job| job.repo.clone())
+        else {
+            return false;
+        };
+        if let Some(repo_index) = self
+            .gh_cli
+            .repos()
+            .iter()
+            .position(|repo_info| repo_info.full_name().eq_ignore_ascii_case(&repo))
+            && repo_index != self.app_state.repo_index
+        {
+            self.change_repo_index(repo_index as isize - self.app_state.repo_index as isize);
+        }
+        if !self.focus_first_job(|job| job.id == job_id) {
+            return false;
+        }
+        self.app_state.views.update(ViewMessage::CloseAll);
+        if !self.app_state.show_details {
+            self.toggle_details_panel();
+        }
+        true
+    }
+
+    /// Selects the first listed job matching `matches`, going through the columns from left
+    /// to right. Returns whether one is listed.
+    fn focus_first_job(&mut self, matches: impl Fn(&GithubJob) -> bool) -> bool {
+        let find = |app: &Self| {
+            (0..3).find_map(|column| {
+                app.app_state
+                    .column_jobs(column)
+                    .values()
+                    .flatten()
+                    .position(|row| {
+                        row.jobs()
+                            .iter()
+                            .any(|&index| app.job_details.get(index).is_some_and(&matches))
+                    })
+                    .map(|row| (column, row))
+            })
+        };
+        let Some((mut column, mut row)) = find(self) else {
+            return false;
+        };
+        // A job of a collapsed matrix gets a row of its own once the matrix is expanded
+        if let Some(ColumnRow::Matrix(matrix)) = self
+            .app_state
+            .column_jobs(column)
+            .values()
+            .flatten()
+            .nth(row)
+        {
+            let key = (matrix.run_id, matrix.name.clone());
+            self.app_state.expanded_matrices.insert(key);
+            self.group_jobs();
+            let Some(found) = find(self) else {
+                return false;
+            };
+            (column, row) = found;
+        }
+        self.app_state.column_index = column;
+        self.app_state.position_mut().row_index = row;
+        self.update_current_job_index_from_state();
+        self.scroll_to_selection();
+        true
+    }
+
+    /// Selects the first job of the new run of the current branch once it is listed, with
+    /// `--follow-new-runs`.
+    fn follow_new_run(&mut self) {
+        let Some(run_id) = self.app_state.followed_run else {
+            return;
+        };
+        if self.focus_first_job(|job| job.run_id == run_id) {
+            self.app_state.followed_run = None;
+            if let Some(job) = self.job_details.get(self.current_job_index) {
+                let message = format!(
+                    "Following the new {} run on {}.",
+                    job.workflow_name, job.head_branch
+                );
+                self.show_toast(message, ToastLevel::Info);
+            }
+        }
+    }
+
+    /// Follows a run on the run dashboard, in place of whichever view is open.
+    fn show_run(&mut self, repo: String, run_id: u64) {
+        self.app_state.views.update(ViewMessage::CloseAll);
+        self.start_watch(repo, run_id, false);
+    }
+
+    /// Scrolls the jobs and steps of the watched run by `delta` lines.
+    fn scroll_watch(&mut self, delta: isize) {
+        if let Some(watch) = self.app_state.views.state_mut::<WatchState>() {
+            let line_count: usize = watch.details.as_ref().map_or(0, |details| {
+                details.jobs.iter().map(|job| job.steps.len() + 1).sum()
+            });
+            watch.scroll = (watch.scroll as isize)
+                .saturating_add(delta)
+                .clamp(0, line_count.saturating_sub(1) as isize)
+                as usize;
+        }
+    }
+
+    fn open_watched_run(&mut self) {
+        let url = self
+            .app_state
+            .views
+            .state::<WatchState>()
+            .and_then(|watch| watch.details.as_ref())
+            .map(|details| details.run.html_url.clone());
+        if let Some(url) = url.filter(|url| !url.is_empty()) {
+            self.open_url(&url);
+        }
+    }
+
+    /// Starts triaging the failed jobs of the failure column, in the order they are listed.
+    fn open_triage(&mut self) {
+        let queue: Vec<GithubJob> = self
+            .app_state
+            .failure_jobs
+            .values()
+            .flatten()
+            .flat_map(ColumnRow::jobs)
+            .filter_map(|&index| self.job_details.get(index))
+            .filter(|job| !self.acked_jobs.contains(&job.id))
+            .cloned()
+            .collect();
+        if queue.is_empty() {
+            self.show_toast(
+                "No failed jobs left to triage.".to_string(),
+                ToastLevel::Info,
+            );
+            return;
+        }
+        self.app_state.triage = Some(TriageState {
+            total: queue.len(),
+            queue,
+            position: 0,
+            summary: None,
+            log_tail: None,
+            error: None,
+        });
+        self.fetch_triage_details();
+    }
+
+    /// Requests the summary and log of the job being triaged.
+    fn fetch_triage_details(&mut self) {
+        let Some(triage) = self.app_state.triage.as_mut() else {
+            return;
+        };
+        triage.summary = None;
+        triage.log_tail = None;
+        triage.error = None;
+        let Some(job) = triage.current().cloned() else {
+            return;
+        };
+        let summary_job = job.clone();
+        self.events.request(move |gh_cli| {
+            JobDataEvent::JobSummaryFetched(
+                summary_job.id,
+                gh_cli
+                    .fetch_job_summary(&summary_job)
+                    .map_err(|e| format!("Error fetching job summary: {:?}", e)),
+            )
+        });
+        self.events.request(move |gh_cli| {
+            JobDataEvent::JobLogFetched(
+                job.id,
+                gh_cli
+                    .fetch_job_log(&job, Some(LOG_TAIL_BYTES))
+                    .map_err(|e| format!("Error fetching log: {:?}", e)),
+            )
+        });
+    }
+
+    /// Moves to another job of the triage queue, wrapping around at either end.
+    fn change_triage_position(&mut self, delta: isize) {
+        let Some(triage) = self.app_state.triage.as_mut() else {
+            return;
+        };
+        if triage.queue.is_empty() {
+            return;
+        }
+        let len = triage.queue.len() as isize;
+        triage.position = (triage.position as isize + delta).rem_euclid(len) as usize;
+        self.fetch_triage_details();
+    }
+
+    /// Takes the current job off the triage queue and moves on to the next one.
+    fn finish_triage_job(&mut self) -> Option<GithubJob> {
+        let triage = self.app_state.triage.as_mut()?;
+        if triage.position >= triage.queue.len() {
+            return None;
+        }
+        let job = triage.queue.remove(triage.position);
+        if triage.position >= triage.queue.len() {
+            triage.position = 0;
+        }
+        self.fetch_triage_details();
+        Some(job)
+    }
+
+    /// Handles key events while the triage view is open.
+    fn handle_triage_key_event(&mut self, key_event: KeyEvent) {
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q' | 't') => self.app_state.triage = None,
+            KeyCode::Char('n') | KeyCode::Right | KeyCode::Down => self.change_triage_position(1),
+            KeyCode::Char('p') | KeyCode::Left | KeyCode::Up => self.change_triage_position(-1),
+            KeyCode::Char('a') => {
+                if let Some(job) = self.finish_triage_job() {
+                    self.show_toast(format!("Acknowledged {}.", job.name), ToastLevel::Success);
+                    self.acked_jobs.insert(job.id);
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(job) = self.finish_triage_job() {
+                    self.show_toast(
+                        format!("Requesting a rerun of {}...", job.name),
+                        ToastLevel::Info,
+                    );
+                    self.events.request(move |gh_cli| {
+                        OutcomeEvent::JobRerun(
+                            gh_cli
+                                .rerun_job(&job)
+                                .map(|_| job.name.clone())
+                                .map_err(|e| format!("Error rerunning {}: {:?}", job.name, e)),
+                        )
+                    });
+                }
+            }
+            KeyCode::Char('o') | KeyCode::Backspace | KeyCode::Enter => {
+                if let Some(url) = self
+                    .app_state
+                    .triage
+                    .as_ref()
+                    .and_then(TriageState::current)
+                    .map(|job| job.html_url.clone())
+                {
+                    self.open_url(&url);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles key events while the filter prompt is capturing typing.
+    /// `Up`/`Down` go through the filters applied before.
+    fn handle_filter_key_event(&mut self, key_event: KeyEvent) {
+        let filter = &mut self.app_state.filter;
+        let changed = match key_event.code {
+            KeyCode::Esc => {
+                filter.editing = false;
+                self.update_filters(FilterMessage::SetQuery(String::new()));
+                return;
+            }
+            KeyCode::Enter => {
+                filter.editing = false;
+                // The search has no validator, so submitting only remembers it
+                let _ = filter.prompt.submit();
+                return;
+            }
+            KeyCode::Up => filter.prompt.previous_in_history(),
+            KeyCode::Down => filter.prompt.next_in_history(),
+            _ => filter.prompt.handle_key(key_event),
+        };
+        if changed {
+            let query = filter.prompt.value().to_string();
+            self.update_filters(FilterMessage::SetQuery(query));
+        }
+    }
+
+    /// The workflows listed in the sidebar: those with jobs in the active repository and those
+    /// hidden, sorted by name.
+    pub fn sidebar_workflows(&self) -> Vec<String> {
+        let active_repo = self.active_repo().filter(|_| self.gh_cli.repos().len() > 1);
+        let workflows: BTreeSet<&String> = self
+            .job_details
+            .iter()
+            .filter(|job| {
+                active_repo
+                    .as_ref()
+                    .is_none_or(|repo| job.repo.eq_ignore_ascii_case(repo))
+            })
+            .map(|job| &job.workflow_name)
+            .chain(&self.app_state.filter.filters().hidden_workflows)
+            .collect();
+        workflows.into_iter().cloned().collect()
+    }
+
+    /// Handles key events while the workflow sidebar is open.
+    fn handle_workflow_sidebar_key_event(&mut self, key_event: KeyEvent) {
+        let workflows = self.sidebar_workflows();
+        let Some(sidebar) = self.app_state.workflow_sidebar.as_mut() else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'W') => self.app_state.workflow_sidebar = None,
+            KeyCode::Up | KeyCode::Char('k') => {
+                sidebar.selected = sidebar.selected.saturating_sub(1)
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                sidebar.selected = (sidebar.selected + 1).min(workflows.len().saturating_sub(1))
+            }
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                if let Some(workflow) = workflows.get(sidebar.selected) {
+                    self.update_filters(FilterMessage::ToggleWorkflow(workflow.clone()));
+                }
+            }
+            KeyCode::Char('a') => {
+                self.update_filters(FilterMessage::ShowAllWorkflows);
+            }
+            _ => {}
+        }
+    }
+
+    /// Opens the branch picker, fetching the branches of the active repository.
+    fn open_branch_picker(&mut self) {
+        let Some(repo_info) = self.gh_cli.repos().get(self.app_state.repo_index).cloned() else {
+            return;
+        };
+        let repo = repo_info.full_name();
+        self.app_state.branch_picker = Some(BranchPickerState {
+            repo: repo.clone(),
+            ..Default::default()
+        });
+        self.events.request(move |gh_cli| {
+            ViewDataEvent::BranchesFetched(
+                repo,
+                gh_cli
+                    .fetch_branches(&repo_info)
+                    .map_err(|e| format!("Error fetching branches: {:?}", e)),
+            )
+        });
+    }
+
+    /// Handles key events while the branch picker is open. Typing narrows down the branches.
+    fn handle_branch_picker_key_event(&mut self, key_event: KeyEvent) {
+        let Some(picker) = self.app_state.branch_picker.as_mut() else {
+            return;
+        };
+        let choice_count = picker.choices().len();
+        match key_event.code {
+            KeyCode::Esc => self.app_state.branch_picker = None,
+            KeyCode::Up => picker.selected = picker.selected.saturating_sub(1),
+            KeyCode::Down => {
+                picker.selected = (picker.selected + 1).min(choice_count.saturating_sub(1))
+            }
+            KeyCode::Enter => {
+                let Some(choice) = picker.choices().get(picker.selected).copied() else {
+                    return;
+                };
+                let branch = choice.map(str::to_string);
+                self.app_state.branch_picker = None;
+                self.set_branch(branch);
+            }
+            _ => {
+                if picker.query.handle_key(key_event) {
+                    picker.selected = 0;
+                }
+            }
+        }
+    }
+
+    /// Handles key events while the command palette is open. Typing narrows down the
+    /// commands, and the one picked goes through the same dispatch as its key would.
+    fn handle_command_palette_key_event(&mut self, key_event: KeyEvent) {
+        let Some(palette) = self.app_state.command_palette.as_mut() else {
+            return;
+        };
+        let choice_count = palette.choices().len();
+        match key_event.code {
+            KeyCode::Esc => self.app_state.command_palette = None,
+            KeyCode::Up => palette.selected = palette.selected.saturating_sub(1),
+            KeyCode::Down => {
+                palette.selected = (palette.selected + 1).min(choice_count.saturating_sub(1))
+            }
+            KeyCode::Enter => {
+                let Some((_, app_event)) = palette.choices().get(palette.selected).copied() else {
+                    return;
+                };
+                self.app_state.command_palette = None;
+                self.events.send(app_event);
+            }
+            _ => {
+                if palette.query.handle_key(key_event) {
+                    palette.selected = 0;
+                }
+            }
+        }
+    }
+
+    /// Fetches the runs of `branch` only, or of every branch, right away rather than at the
+    /// next refresh. The jobs already shown are narrowed down in the meantime.
+    fn set_branch(&mut self, branch: Option<String>) {
+        self.app_state.loading_status = match &branch {
+            Some(branch) => format!("Fetching the runs of {}...", branch),
+            None => "Fetching the runs of every branch...".to_string(),
+        };
+        self.gh_cli.set_branch(branch);
+        self.regroup_jobs();
+        // The fetch of the previous branch, if any, is cancelled rather than racing this one
+        self.events.refresh();
+    }
+
+    /// Changes the filters of the job columns, keeping the selected job selected if it still
+    /// matches. Returns whether they changed.
+    fn update_filters(&mut self, message: FilterMessage) -> bool {
+        let changed = self.app_state.filter.update(message);
+        if changed {
+            self.regroup_jobs();
+        }
+        changed
+    }
+
+    /// Fetches the paths changed by the commits of the jobs that aren't known yet, when the
+    /// filter has a `path:` term.
+    fn fetch_changed_paths(&mut self) {
+        let filter = &self.app_state.filter.filters().query;
+        if !filter
+            .split_whitespace()
+            .any(|term| term.starts_with(PATH_TERM_PREFIX))
+        {
+            return;
+        }
+        let commits: BTreeSet<(String, String)> = self
+            .job_details
+            .iter()
+            .map(|job| (job.repo.to_ascii_lowercase(), job.head_sha.clone()))
+            .filter(|commit| !self.app_state.changed_paths.contains_key(commit))
+            .collect();
+        for commit in commits {
+            self.app_state.changed_paths.insert(commit.clone(), None);
+            self.events.request(move |gh_cli| {
+                let (repo, sha) = &commit;
+                let result = gh_cli
+                    .fetch_changed_paths(repo, sha)
+                    .map_err(|e| format!("Error fetching the files changed by {}: {:?}", sha, e));
+                JobDataEvent::ChangedPathsFetched(commit, result)
+            });
+        }
+    }
+
+    /// Re-filters the job columns after what they leave out changed, keeping the selected job
+    /// selected if it is still shown.
+    fn regroup_jobs(&mut self) {
+        let selected_job_id = self.selected_job_id();
+        self.group_jobs();
+        self.reselect_job(selected_job_id);
+    }
+
+    fn selected_job_id(&self) -> Option<u64> {
+        self.job_details
+            .get(self.current_job_index)
+            .map(|job| job.id)
+    }
+
+    /// Selects a job again after the columns were regrouped, or the row it was in if it
+    /// isn't in the current column anymore.
+    fn reselect_job(&mut self, selected_job_id: Option<u64>) {
+        let row = selected_job_id.and_then(|id| {
+            self.get_jobs_for_current_column()
+                .values()
+                .flatten()
+                .position(|row| {
+                    row.jobs()
+                        .iter()
+                        .any(|&index| self.job_details.get(index).is_some_and(|job| job.id == id))
+                })
+        });
+        match row {
+            Some(row) => self.app_state.position_mut().row_index = row,
+            None => {
+                let row_count = self
+                    .get_jobs_for_current_column()
+                    .values()
+                    .flatten()
+                    .count();
+                let position = self.app_state.position_mut();
+                position.row_index = position.row_index.min(row_count.saturating_sub(1));
+            }
+        }
+        self.update_current_job_index_from_state();
+    }
+
+    /// Marks the selected job for comparison, or compares it with the job marked before.
+    fn compare_logs(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index).cloned() else {
+            return;
+        };
+        match self.app_state.compare_mark.take() {
+            None => {
+                self.app_state.loading_status = format!(
+                    "Marked {} for comparison, pick another job and press `c` again.",
+                    job.name
+                );
+                self.app_state.compare_mark = Some(job);
+            }
+            Some(marked) if marked.id == job.id => {
+                self.show_toast("Log comparison cancelled.".to_string(), ToastLevel::Info);
+            }
+            Some(marked) => {
+                for job in [&marked, &job] {
+                    let job = job.clone();
+                    self.events.request(move |gh_cli| {
+                        JobDataEvent::JobLogFetched(
+                            job.id,
+                            gh_cli
+                                .fetch_job_log(&job, Some(LOG_TAIL_BYTES))
+                                .map_err(|e| format!("Error fetching log: {:?}", e)),
+                        )
+                    });
+                }
+                let workflow_diff = if marked.repo.eq_ignore_ascii_case(&job.repo) {
+                    // "Someone fixed the workflow" often explains why one of them failed
+                    let (earlier, later) = if marked.started_at <= job.started_at {
+                        (marked.clone(), job.clone())
+                    } else {
+                        (job.clone(), marked.clone())
+                    };
+                    self.events.request(move |gh_cli| {
+                        JobDataEvent::WorkflowFileDiffFetched(
+                            (earlier.id, later.id),
+                            gh_cli
+                                .fetch_workflow_file_diff(&earlier, &later)
+                                .map_err(|e| format!("Error comparing the workflow file: {:?}", e)),
+                        )
+                    });
+                    None
+                } else {
+                    Some(Err(
+                        "The jobs are from different repositories, so are their workflow files."
+                            .to_string(),
+                    ))
+                };
+                self.app_state
+                    .views
+                    .update(ViewMessage::Open(View::LogCompare(Box::new(
+                        LogCompareState {
+                            panes: [LogPane::new(marked), LogPane::new(job)],
+                            scroll: 0,
+                            workflow_diff,
+                            search: None,
+                        },
+                    ))));
+            }
+        }
+    }
+
+    /// Opens the comparison of the two jobs' commits on GitHub, when the workflow file changed
+    /// between them.
+    fn open_workflow_diff(&mut self) {
+        if let Some(LogCompareState {
+            workflow_diff: Some(Ok(diff)),
+            ..
+        }) = self.app_state.views.state::<LogCompareState>()
+            && diff.changed
+        {
+            let url = diff.html_url();
+            self.open_url(&url);
+        }
+    }
+
+    /// Starts typing the search through the compared logs, or resumes typing the one shown.
+    fn open_log_search(&mut self) {
+        if let Some(compare) = self.app_state.views.state_mut::<LogCompareState>() {
+            let scroll = compare.scroll;
+            let search = compare.search.get_or_insert_with(|| LogSearch {
+                start_scroll: scroll,
+                ..LogSearch::default()
+            });
+            search.editing = true;
+        }
+    }
+
+    fn handle_log_search_key_event(&mut self, key_event: KeyEvent) {
+        let Some(compare) = self.app_state.views.state_mut::<LogCompareState>() else {
+            return;
+        };
+        let Some(search) = compare.search.as_mut() else {
+            return;
+        };
+        match key_event.code {
+            KeyCode::Esc => {
+                compare.scroll = search.start_scroll;
+                compare.search = None;
+            }
+            KeyCode::Enter if search.query.is_empty() => compare.search = None,
+            KeyCode::Enter => {
+                search.editing = false;
+                // The search has no validator, so submitting only remembers it
+                let _ = search.query.submit();
+            }
+            KeyCode::Up => {
+                if search.query.previous_in_history() {
+                    compare.update_search();
+                }
+            }
+            KeyCode::Down => {
+                if search.query.next_in_history() {
+                    compare.update_search();
+                }
+            }
+            _ => {
+                if search.query.handle_key(key_event) {
+                    compare.update_search();
+                }
+            }
+        }
+    }
+
+    /// Jumps `delta` matches from the current one in the compared logs.
+    fn jump_to_log_match(&mut self, delta: isize) {
+        if let Some(compare) = self.app_state.views.state_mut::<LogCompareState>() {
+            compare.jump_to_match(delta);
+        }
+    }
+
+    /// Scrolls both logs of the comparison by `delta` lines.
+    fn scroll_log_compare(&mut self, delta: isize) {
+        if let Some(compare) = self.app_state.views.state_mut::<LogCompareState>() {
+            let row_count = compare.row_count();
+            compare.scroll = (compare.scroll as isize)
+                .saturating_add(delta)
+                .clamp(0, row_count.saturating_sub(1) as isize)
+                as usize;
+        }
+    }
+
+    /// Lists the runs of the selected job's workflow, to tell a flaky failure from a
+    /// regression.
+    fn open_workflow_history(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index) else {
+            return;
+        };
+        let Some(run) = self.runs.iter().find(|run| run.id == job.run_id) else {
+            let message = format!("The run of {} is no longer listed.", job.name);
+            self.show_toast(message, ToastLevel::Error);
+            return;
+        };
+        let history = WorkflowHistoryState {
+            repo: run.repo.clone(),
+            workflow_name: run.name.clone(),
+            path: run.path.clone(),
+            runs: Vec::new(),
+            total_count: None,
+            pages: 0,
+            loading: false,
+            selected: 0,
+            error: None,
+        };
+        self.app_state.views.update(ViewMessage::CloseAll);
+        self.app_state.show_details = false;
+        self.app_state
+            .views
+            .update(ViewMessage::Open(View::WorkflowHistory(history)));
+        self.fetch_workflow_history_page();
+    }
+
+    /// Fetches the next page of older runs of the workflow whose history is shown.
+    fn fetch_workflow_history_page(&mut self) {
+        let Some(history) = self
+            .app_state
+            .views
+            .state_mut::<WorkflowHistoryState>()
+            .filter(|history| !history.loading)
+        else {
+            return;
+        };
+        history.loading = true;
+        let page = history.pages + 1;
+        let (repo, path) = (history.repo.clone(), history.path.clone());
+        self.events.request(move |gh_cli| {
+            ViewDataEvent::WorkflowHistoryFetched(
+                (repo.clone(), path.clone()),
+                page,
+                gh_cli
+                    .fetch_workflow_history(&repo, &path, page)
+                    .map_err(|e| format!("Error fetching the runs of {}: {:?}", path, e)),
+            )
+        });
+    }
+
+    /// Lists the tests of the selected job's workflow that failed while passing around their
+    /// failures in the test reports of its latest runs, those backfilled if any.
+    fn open_flaky_tests(&mut self) {
+        let Some(job) = self.job_details.get(self.current_job_index) else {
+            return;
+        };
+        let Some(run) = self.runs.iter().find(|run| run.id == job.run_id) else {
+            let message = format!("The run of {} is no longer listed.", job.name);
+            self.show_toast(message, ToastLevel::Error);
+            return;
+        };
+        let (repo, path) = (run.repo.clone(), run.path.clone());
+        let backfilled: Vec<GithubWorkflowRun> = self
+            .run_history
+            .workflow_runs(&repo, &path)
+            .map(|entry| entry.run.clone())
+            .collect();
+        let flaky_tests = FlakyTestsState {
+            repo: repo.clone(),
+            workflow_name: run.name.clone(),
+            path: path.clone(),
+            scan: None,
+            selected: 0,
+        };
+        self.app_state.views.update(ViewMessage::CloseAll);
+        self.app_state.show_details = false;
+        self.app_state
+            .views
+            .update(ViewMessage::Open(View::FlakyTests(flaky_tests)));
+        self.events.request(move |gh_cli| {
+            let runs = if backfilled.is_empty() {
+                gh_cli
+                    .fetch_workflow_history(&repo, &path, 1)
+                    .map(|page| page.runs.into_iter().map(|entry| entry.run).collect())
+            } else {
+                Ok(backfilled)
+            };
+            ViewDataEvent::FlakyTestsScanned(
+                (repo.clone(), path.clone()),
+                runs.and_then(|runs| flaky_tests::scan(gh_cli, &repo, runs))
+                    .map_err(|e| format!("Error scanning the test reports of {}: {:?}", path, e)),
+            )
+        });
+    }
+
+    fn change_flaky_test_selection(&mut self, delta: isize) {
+        let Some(flaky_tests) = self.app_state.views.state_mut::<FlakyTestsState>() else {
+            return;
+        };
+        let count = match &flaky_tests.scan {
+            Some(Ok(scan)) => scan.suspects.len(),
+            _ => 0,
+        };
+        flaky_tests.selected = (flaky_tests.selected as isize)
+            .saturating_add(delta)
+            .clamp(0, count.saturating_sub(1) as isize) as usize;
+    }
+
+    fn open_flaky_test_run(&mut self) {
+        let url = self
+            .app_state
+            .views
+            .state::<FlakyTestsState>()
+            .and_then(|flaky_tests| match &flaky_tests.scan {
+                Some(Ok(scan)) => scan
+                    .suspects
+                    .get(flaky_tests.selected)
+                    .map(|suspect| suspect.last_seen.html_url.clone()),
+                _ => None,
+            });
+        if let Some(url) = url {
+            self.open_url(&url);
+        }
+    }
+
+    /// Writes the suspected flaky tests to a Markdown file in the current directory, as a list
+    /// of candidates to quarantine.
+    fn export_flaky_tests(&mut self) {
+        let Some(flaky_tests) = self.app_state.views.state::<FlakyTestsState>() else {
+            return;
+        };
+        let Some(Ok(scan)) = &flaky_tests.scan else {
+            return;
+        };
+        let path = Path::new(FLAKY_TESTS_EXPORT_PATH);
+        let (message, level) =
+            match flaky_tests::export(path, &flaky_tests.repo, &flaky_tests.workflow_name, scan) {
+                Ok(()) => (
+                    format!(
+                        "Exported {} suspected flaky tests to {}",
+                        scan.suspects.len(),
+                        path.display()
+                    ),
+                    ToastLevel::Success,
+                ),
+                Err(e) => (
+                    format!("Could not export the flaky tests: {:?}", e),
+                    ToastLevel::Error,
+                ),
+            };
+        self.show_toast(message, level);
+    }
+
+    /// Moves the selection of the workflow history by `delta` runs, fetching older runs once
+    /// it reaches the oldest fetched.
+    fn change_history_selection(&mut self, delta: isize) {
+        let Some(history) = self.app_state.views.state_mut::<WorkflowHistoryState>() else {
+            return;
+        };
+        history.selected = (history.selected as isize)
+            .saturating_add(delta)
+            .clamp(0, history.runs.len().saturating_sub(1) as isize)
+            as usize;
+        if history.selected + 1 >= history.runs.len() && history.has_older_runs() {
+            self.fetch_workflow_history_page();
+        }
+    }
+
+    fn open_history_run(&mut self) {
+        let url = self
+            .app_state
+            .views
+            .state::<WorkflowHistoryState>()
+            .and_then(|history| {
+                history
+                    .runs
+                    .get(history.selected)
+                    .map(|run| run.run.html_url.clone())
+            });
+        if let Some(url) = url {
+            self.open_url(&url);
+        }
+    }
+
+    fn toggle_deploy_view(&mut self) {
+        if matches!(self.app_state.views.shown(), Some(View::Deploy(_))) {
+            self.app_state.views.update(ViewMessage::Close);
+        } else {
+            self.app_state
+                .views
+                .update(ViewMessage::Open(View::Deploy(DeployViewState::default())));
+            self.fetch_environments();
+        }
+    }
+
+    /// Shows the runner minutes used by the active repository's runs instead of the job
+    /// columns, along with those its owner was billed for when that is accessible, and back.
+    fn toggle_usage_view(&mut self) {
+        if matches!(self.app_state.views.shown(), Some(View::Usage(_))) {
+            self.app_state.views.update(ViewMessage::Close);
+            return;
+        }
+        let Some(repo_info) = self.gh_cli.repos().get(self.app_state.repo_index).cloned() else {
+            return;
+        };
+        self.app_state.views.update(ViewMessage::CloseAll);
+        self.app_state.show_details = false;
+        let owner = repo_info.owner.login.clone();
+        let billing = self.app_state.billing.get(&owner).cloned();
+        let fetched = billing.is_some();
+        self.app_state
+            .views
+            .update(ViewMessage::Open(View::Usage(UsageViewState {
+                owner: owner.clone(),
+                billing,
+                scroll: 0,
+            })));
+        // The billing is polled in the background, and only fetched here until it first was
+        if fetched {
+            return;
         }
+        self.events.request(move |gh_cli| {
+            let result = gh_cli
+                .fetch_actions_billing(&owner)
+                .map_err(|e| format!("Error fetching the billing of {}: {:?}", owner, e));
+            PolledEvent::BillingFetched(owner, result)
+        });
     }
-}
 
-impl App {
-    /// Constructs a new instance of [`App`].
-    pub fn new() -> Self {
-        Self::default()
+    /// Scrolls the workflows of the usage view by `delta` lines.
+    fn scroll_usage_view(&mut self, delta: isize) {
+        if let Some(usage_view) = self.app_state.views.state_mut::<UsageViewState>() {
+            usage_view.scroll = usage_view.scroll.saturating_add_signed(delta);
+        }
     }
 
-    /// Run the application's main loop.
-    pub fn run(mut self, mut terminal: DefaultTerminal) -> color_eyre::Result<()> {
-        while self.running {
-            terminal.draw(|frame| frame.render_widget(&self, frame.area()))?;
-            self.handle_events()?;
+    /// Switches between the workspace overview and the dashboard of the active repository.
+    fn toggle_overview(&mut self) {
+        if matches!(self.app_state.views.shown(), Some(View::Overview(_))) {
+            self.app_state.views.update(ViewMessage::Close);
+            return;
         }
-        Ok(())
+        if self.gh_cli.repos().len() < 2 {
+            self.show_toast(
+                "The overview needs several repositories, pass --repo more than once.".to_string(),
+                ToastLevel::Info,
+            );
+            return;
+        }
+        self.app_state.views.update(ViewMessage::CloseAll);
+        self.app_state.show_details = false;
+        self.app_state
+            .views
+            .update(ViewMessage::Open(View::Overview(OverviewState {
+                selected: self.app_state.repo_index,
+            })));
     }
 
-    pub fn handle_events(&mut self) -> color_eyre::Result<()> {
-        match self.events.next()? {
-            Event::Action => {
-                // This event now only signals that a data fetch has been *triggered*.
-                // You can update a loading status in the UI here.
-                self.app_state.loading_status = "Fetching data...".to_string();
-            }
-            Event::GitHubDataFetched(result) => {
-                // This is where the actual data (or error) arrives.
-                match result {
-                    Ok(workflow_data) => {
-                        self.update_github_data(workflow_data);
-                        self.app_state.loading_status = "Data updated.".to_string(); // Or clear it
-                    }
-                    Err(e) => {
-                        self.app_state.loading_status = format!("Error: {}", e);
+    /// Summarizes the jobs of each monitored repository for the workspace overview, counted
+    /// like the job columns on the branch shown and without the hidden workflows.
+    pub fn repo_overviews(&self) -> Vec<RepoOverview<'_>> {
+        let branch = self.gh_cli.branch();
+        self.gh_cli
+            .repos()
+            .iter()
+            .map(|repo_info| {
+                let mut overview = RepoOverview {
+                    repo: repo_info.full_name(),
+                    in_progress: 0,
+                    success: 0,
+                    failure: 0,
+                    other: 0,
+                    last_failure: None,
+                };
+                let jobs = self
+                    .job_details
+                    .iter()
+                    .filter(|job| job.repo.eq_ignore_ascii_case(&overview.repo))
+                    .filter(|job| {
+                        branch
+                            .as_ref()
+                            .is_none_or(|branch| job.head_branch == *branch)
+                    })
+                    .filter(|job| {
+                        !self
+                            .app_state
+                            .filter
+                            .filters()
+                            .hides(&job.workflow_name, &job.event)
+                    });
+                for job in jobs {
+                    match (job.status.as_str(), job.conclusion.as_deref()) {
+                        ("completed", Some("success")) => overview.success += 1,
+                        ("completed", Some("failure")) if self.workflow_mutes.is_muted(job) => {}
+                        ("completed", Some("failure")) => {
+                            overview.failure += 1;
+                            if overview
+                                .last_failure
+                                .is_none_or(|last| last.completed_at < job.completed_at)
+                            {
+                                overview.last_failure = Some(job);
+                            }
+                        }
+                        ("completed", Some(_)) => overview.other += 1,
+                        ("in_progress" | "queued" | "waiting", _) => overview.in_progress += 1,
+                        _ => {}
                     }
                 }
-            }
-            Event::Crossterm(event) => match event {
-                crossterm::event::Event::Key(key_event) => self.handle_key_event(key_event)?,
-                _ => {}
-            },
-            Event::App(app_event) => match app_event {
-                AppEvent::Quit => self.quit(),
-                AppEvent::NavigateRight => self.change_column_index(1),
-                AppEvent::NavigateLeft => self.change_column_index(-1),
-                AppEvent::NavigateUp => self.change_row_index(-1),
-                AppEvent::NavigateDown => self.change_row_index(1),
-                AppEvent::ToggleDetails => self.toggle_details_panel(),
-                AppEvent::PageDown => self.change_scroll_offset(25),
-                AppEvent::PageUp => self.change_scroll_offset(-25),
-                AppEvent::OpenGitHub => self.open_github(),
-            },
-        }
-        Ok(())
+                overview
+            })
+            .collect()
     }
-    fn change_column_index(&mut self, delta: isize) {
-        if self.app_state.show_details {
-            return;
+
+    /// Switches between the job columns and the run view.
+    fn toggle_run_view(&mut self) {
+        if !matches!(self.app_state.views.shown(), Some(View::Runs(_))) {
+            // Start at the run of the selected job
+            let run_id = self
+                .job_details
+                .get(self.current_job_index)
+                .map(|job| job.run_id);
+            self.app_state
+                .views
+                .update(ViewMessage::Open(View::Runs(RunViewState {
+                    run_id,
+                    ..RunViewState::default()
+                })));
+            self.sync_run_view();
+        } else {
+            self.app_state.views.update(ViewMessage::Close);
+            self.update_current_job_index_from_state();
         }
-        let num_columns = 3;
-        let new_index = (self.app_state.column_index as isize + delta) as usize;
+    }
 
-        self.app_state.column_index = new_index % num_columns;
+    /// The runs listed by the run view: those of the active repository and branch whose
+    /// workflow isn't hidden and, while a filter is applied, that have jobs matching it.
+    pub fn run_view_runs(&self) -> Vec<&GithubWorkflowRun> {
+        let active_repo = self.active_repo().filter(|_| self.gh_cli.repos().len() > 1);
+        let branch = self.gh_cli.branch();
+        self.runs
+            .iter()
+            .filter(|run| {
+                active_repo
+                    .as_ref()
+                    .is_none_or(|repo| run.repo.eq_ignore_ascii_case(repo))
+            })
+            .filter(|run| {
+                branch
+                    .as_ref()
+                    .is_none_or(|branch| run.head_branch == *branch)
+            })
+            .filter(|run| !self.app_state.filter.filters().hides(&run.name, &run.event))
+            .filter(|run| {
+                self.app_state.filter.filters().query.is_empty()
+                    || !self.run_jobs(run.id).is_empty()
+            })
+            .collect()
+    }
 
-        self.app_state.row_index = 0;
-        self.app_state.scroll_offset = 0;
+    /// The jobs of a run matching the filter, as indices into `job_details`, in the order
+    /// they were created.
+    pub fn run_jobs(&self, run_id: u64) -> Vec<usize> {
+        let mut indices: Vec<usize> = self
+            .job_details
+            .iter()
+            .enumerate()
+            .filter(|(_, job)| job.run_id == run_id)
+            .filter(|(_, job)| {
+                job_matches_filter(
+                    job,
+                    &self.app_state.filter.filters().query,
+                    &self.app_state.changed_paths,
+                )
+            })
+            .map(|(index, _)| index)
+            .collect();
+        indices.sort_by_key(|&index| self.job_details[index].id);
+        indices
+    }
 
-        self.update_current_job_index_from_state();
+    /// The job selected in the run view, as an index into `job_details`.
+    fn selected_run_view_job(&self) -> Option<usize> {
+        let job_id = self.app_state.views.state::<RunViewState>()?.job_id?;
+        self.job_details.iter().position(|job| job.id == job_id)
     }
-    fn open_github(&mut self) {
-        if let Some(job) = self.job_details.get(self.current_job_index) {
-            let url = job.html_url.clone();
-            if let Err(e) = open::that(url) {
-                eprintln!("Error opening URL: {}", e);
+
+    /// Keeps the selection of the run view on a run and job that are still listed, and makes
+    /// its job the one the other actions apply to.
+    fn sync_run_view(&mut self) {
+        let Some(run_view) = self.app_state.views.state::<RunViewState>() else {
+            return;
+        };
+        let runs = self.run_view_runs();
+        let run_id = run_view
+            .run_id
+            .filter(|run_id| runs.iter().any(|run| run.id == *run_id))
+            .or_else(|| runs.first().map(|run| run.id));
+        let jobs = run_id
+            .map(|run_id| self.run_jobs(run_id))
+            .unwrap_or_default();
+        let job_id = run_view
+            .job_id
+            .filter(|job_id| {
+                jobs.iter()
+                    .any(|&index| self.job_details[index].id == *job_id)
+            })
+            .or_else(|| jobs.first().map(|&index| self.job_details[index].id));
+        if let Some(run_view) = self.app_state.views.state_mut::<RunViewState>() {
+            run_view.run_id = run_id;
+            run_view.job_id = job_id;
+            run_view.jobs_focused &= job_id.is_some();
+        }
+        if let Some(index) = self.selected_run_view_job() {
+            self.current_job_index = index;
+        }
+    }
+
+    /// Moves the selection of the focused pane of the run view by `delta` rows.
+    fn change_run_view_selection(&mut self, delta: isize) {
+        let Some(run_view) = self.app_state.views.state::<RunViewState>() else {
+            return;
+        };
+        let step = |ids: Vec<u64>, selected: Option<u64>| {
+            let position = ids.iter().position(|id| Some(*id) == selected).unwrap_or(0);
+            let position = (position as isize)
+                .saturating_add(delta)
+                .clamp(0, ids.len().saturating_sub(1) as isize) as usize;
+            ids.get(position).copied()
+        };
+        if run_view.jobs_focused {
+            let jobs = run_view
+                .run_id
+                .map(|run_id| self.run_jobs(run_id))
+                .unwrap_or_default();
+            let ids = jobs
+                .iter()
+                .map(|&index| self.job_details[index].id)
+                .collect();
+            let job_id = step(ids, run_view.job_id);
+            if let Some(run_view) = self.app_state.views.state_mut::<RunViewState>() {
+                run_view.job_id = job_id;
+            }
+        } else {
+            let ids = self.run_view_runs().iter().map(|run| run.id).collect();
+            let run_id = step(ids, run_view.run_id);
+            if let Some(run_view) = self.app_state.views.state_mut::<RunViewState>() {
+                run_view.run_id = run_id;
+                // The jobs of another run start from the first
+                run_view.job_id = None;
             }
         }
+        self.sync_run_view();
     }
 
-    fn change_row_index(&mut self, delta: isize) {
-        if self.app_state.show_details {
+    /// Fetches every job of the run selected in the run view, when it has more than a refresh
+    /// fetches.
+    fn expand_run(&mut self) {
+        let Some(run_view) = self.app_state.views.state_mut::<RunViewState>() else {
+            return;
+        };
+        let Some(run) = self
+            .runs
+            .iter()
+            .find(|run| Some(run.id) == run_view.run_id && run.total_jobs.is_some())
+            .cloned()
+        else {
+            return;
+        };
+        if run_view.expanding.is_some() {
             return;
         }
-        let current_column_jobs = self.get_jobs_for_current_column();
-        if current_column_jobs.is_empty() {
-            self.app_state.row_index = 0;
-            self.current_job_index = 0;
+        run_view.expanding = Some(run.id);
+        self.events.request(move |gh_cli| {
+            JobDataEvent::RunJobsFetched(
+                run.id,
+                gh_cli
+                    .expand_run(&run)
+                    .map_err(|e| format!("Error fetching the jobs of the run: {:?}", e)),
+            )
+        });
+    }
+
+    /// Moves the focus of the run view to its jobs, or back to its runs.
+    fn focus_run_view_jobs(&mut self, focused: bool) {
+        let has_job = self.selected_run_view_job().is_some();
+        if let Some(run_view) = self.app_state.views.state_mut::<RunViewState>() {
+            run_view.jobs_focused = focused && has_job;
+        }
+    }
+
+    /// Opens the selected job of the run view, or its run while the runs are focused.
+    fn open_run_view_selection(&mut self) {
+        let Some(run_view) = self.app_state.views.state::<RunViewState>() else {
             return;
+        };
+        let url = if run_view.jobs_focused {
+            self.selected_run_view_job()
+                .map(|index| self.job_details[index].html_url.clone())
+        } else {
+            self.runs
+                .iter()
+                .find(|run| Some(run.id) == run_view.run_id)
+                .map(|run| run.html_url.clone())
+        };
+        if let Some(url) = url.filter(|url| !url.is_empty()) {
+            self.open_url(&url);
         }
+    }
 
-        let mut new_row_index = self.app_state.row_index as isize + delta;
+    /// Moves the selection of the timeline by `delta` entries.
+    fn change_timeline_selection(&mut self, delta: isize) {
+        if let Some(timeline_view) = self.app_state.views.state_mut::<TimelineViewState>() {
+            timeline_view.selected = (timeline_view.selected as isize)
+                .saturating_add(delta)
+                .clamp(0, self.timeline.entries.len().saturating_sub(1) as isize)
+                as usize;
+        }
+    }
 
-        // Ensure the row index stays within bounds
-        if new_row_index < 0 {
-            new_row_index = 0;
+    /// Opens the job of the selected timeline entry.
+    fn open_timeline_job(&mut self) {
+        if let Some(url) = self
+            .app_state
+            .views
+            .state::<TimelineViewState>()
+            .and_then(|timeline_view| self.timeline.entries.get(timeline_view.selected))
+            .map(|entry| entry.html_url.clone())
+        {
+            self.open_url(&url);
         }
-        self.app_state.row_index =
-            (new_row_index as usize).min(current_column_jobs.values().flatten().count().saturating_sub(1));
+    }
 
-        // Update current_job_index based on the new row and column
-        self.update_current_job_index_from_state();
+    /// Fetches the deployment state of the active repository's environments in the background.
+    fn fetch_environments(&self) {
+        let Some(repo_info) = self.gh_cli.repos().get(self.app_state.repo_index).cloned() else {
+            return;
+        };
+        self.events.request(move |gh_cli| {
+            ViewDataEvent::EnvironmentsFetched(
+                repo_info.full_name(),
+                gh_cli
+                    .fetch_environment_statuses(&repo_info)
+                    .map_err(|e| format!("Error fetching environments: {:?}", e)),
+            )
+        });
     }
-    fn change_scroll_offset(&mut self, delta: isize) {
-        let new_offset = self.app_state.scroll_offset as isize + delta;
-        if new_offset < 0 {
-            self.app_state.scroll_offset = 0;
-        } else {
-            self.app_state.scroll_offset = new_offset as usize;
+
+    /// Moves the selection of the deploy dashboard by `delta` environments.
+    fn change_deploy_selection(&mut self, delta: isize) {
+        if let Some(deploy_view) = self.app_state.views.state_mut::<DeployViewState>() {
+            let count = deploy_view.environments.as_ref().map_or(0, Vec::len);
+            deploy_view.selected = (deploy_view.selected as isize)
+                .saturating_add(delta)
+                .clamp(0, count.saturating_sub(1) as isize)
+                as usize;
         }
     }
 
-    fn update_current_job_index_from_state(&mut self) {
-        let current_column_jobs_indices = self.get_jobs_for_current_column();
-        let indices: Vec<usize> = current_column_jobs_indices
-            .values()
-            .flatten()
-            .copied()
+    fn change_overview_selection(&mut self, delta: isize) {
+        let count = self.gh_cli.repos().len();
+        if let Some(overview) = self.app_state.views.state_mut::<OverviewState>() {
+            overview.selected = (overview.selected as isize)
+                .saturating_add(delta)
+                .clamp(0, count.saturating_sub(1) as isize)
+                as usize;
+        }
+    }
+
+    /// Leaves the workspace overview for the dashboard of the selected repository.
+    fn open_overview_repo(&mut self) {
+        let Some(selected) = self
+            .app_state
+            .views
+            .state::<OverviewState>()
+            .map(|overview| overview.selected)
+        else {
+            return;
+        };
+        self.app_state.views.update(ViewMessage::Close);
+        self.change_repo_index(selected as isize - self.app_state.repo_index as isize);
+    }
+
+    /// Opens the Actions page of the repository selected in the workspace overview.
+    fn open_overview_actions_page(&mut self) {
+        let repo = self
+            .app_state
+            .views
+            .state::<OverviewState>()
+            .and_then(|overview| self.gh_cli.repos().get(overview.selected));
+        if let Some(repo_info) = repo {
+            let url = format!("https://github.com/{}/actions", repo_info.full_name());
+            self.open_url(&url);
+        }
+    }
+
+    /// Opens the run awaiting approval for the selected environment, or else the run of its
+    /// last deployment.
+    fn open_deploy_run(&mut self) {
+        let url = self
+            .app_state
+            .views
+            .state::<DeployViewState>()
+            .and_then(|deploy_view| {
+                let env = deploy_view
+                    .environments
+                    .as_ref()?
+                    .get(deploy_view.selected)?;
+                env.pending_approvals
+                    .first()
+                    .map(|approval| approval.html_url.clone())
+                    .or_else(|| env.last_deploy.as_ref()?.log_url.clone())
+            });
+        if let Some(url) = url.filter(|url| !url.is_empty()) {
+            self.open_url(&url);
+        }
+    }
+
+    /// Handles key events while the workflow dispatch popup is open.
+    fn handle_dispatch_key_event(&mut self, key_event: KeyEvent) {
+        let repo = self.active_repo().unwrap_or_default();
+        let Some(dispatch) = self.app_state.dispatch.as_mut() else {
+            return;
+        };
+
+        // The form captures typing, so it is handled before the workflow list
+        let suggestions: Vec<String> = dispatch
+            .ref_suggestions()
+            .into_iter()
+            .map(|git_ref| git_ref.name.clone())
             .collect();
-        if let Some(original_index) = indices.get(self.app_state.row_index) {
-            self.current_job_index = *original_index;
-        } else {
-            // No job selected, default to first available or 0
-            self.current_job_index = indices.first().copied().unwrap_or(0);
+        if let Some(form) = dispatch.form.as_mut() {
+            let picking_ref = form.focused_field == DispatchField::Ref && !suggestions.is_empty();
+            let field = match form.focused_field {
+                DispatchField::Ref => &mut form.git_ref,
+                DispatchField::Inputs => &mut form.inputs,
+            };
+            match key_event.code {
+                KeyCode::Esc => dispatch.form = None,
+                // Up and Down move through the suggested refs while the ref is edited
+                KeyCode::Up if picking_ref => {
+                    form.ref_selected = form.ref_selected.and_then(|index| index.checked_sub(1))
+                }
+                KeyCode::Down if picking_ref => {
+                    form.ref_selected = Some(
+                        form.ref_selected
+                            .map_or(0, |index| (index + 1).min(suggestions.len() - 1)),
+                    )
+                }
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                    form.focused_field = match form.focused_field {
+                        DispatchField::Ref => DispatchField::Inputs,
+                        DispatchField::Inputs => DispatchField::Ref,
+                    };
+                    form.ref_selected = None;
+                }
+                KeyCode::Enter => {
+                    match form.ref_selected.and_then(|index| suggestions.get(index)) {
+                        Some(git_ref) => {
+                            form.git_ref.set_value(git_ref.clone());
+                            form.ref_selected = None;
+                        }
+                        None => self.submit_dispatch(),
+                    }
+                }
+                _ => {
+                    if field.handle_key(key_event) {
+                        form.ref_selected = None;
+                    }
+                }
+            }
+            return;
+        }
+
+        if dispatch.show_history {
+            let records: Vec<&DispatchRecord> = self.dispatch_history.for_repo(&repo).collect();
+            let selected = records.get(dispatch.history_selected).copied().cloned();
+            match key_event.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.app_state.dispatch = None,
+                KeyCode::Char('h') => dispatch.show_history = false,
+                KeyCode::Up => {
+                    dispatch.history_selected = dispatch.history_selected.saturating_sub(1)
+                }
+                KeyCode::Down => {
+                    dispatch.history_selected =
+                        (dispatch.history_selected + 1).min(records.len().saturating_sub(1))
+                }
+                // Send the selected dispatch again, unchanged
+                KeyCode::Enter => {
+                    if let Some(record) = selected {
+                        self.app_state.dispatch = None;
+                        self.send_dispatch(record.workflow, record.git_ref, record.inputs);
+                    }
+                }
+                // Open the form pre-filled with the selected dispatch
+                KeyCode::Char('e') => {
+                    if let Some(record) = selected {
+                        dispatch.form = Some(DispatchForm {
+                            inputs: dispatch_inputs_input(record.inputs_string()),
+                            workflow: record.workflow,
+                            git_ref: dispatch_ref_input(record.git_ref),
+                            focused_field: DispatchField::Inputs,
+                            ref_selected: None,
+                            error: None,
+                        });
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        let workflow_count = dispatch.workflows.as_ref().map_or(0, |w| w.len());
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => self.app_state.dispatch = None,
+            KeyCode::Char('h') => {
+                dispatch.show_history = true;
+                dispatch.history_selected = 0;
+            }
+            KeyCode::Up => dispatch.selected = dispatch.selected.saturating_sub(1),
+            KeyCode::Down => {
+                dispatch.selected = (dispatch.selected + 1).min(workflow_count.saturating_sub(1))
+            }
+            KeyCode::Enter => {
+                let workflow = dispatch
+                    .workflows
+                    .as_ref()
+                    .and_then(|workflows| workflows.get(dispatch.selected))
+                    .cloned();
+                if let Some(workflow) = workflow {
+                    let current_branch = self.gh_cli.current_branch();
+                    dispatch.form = Some(DispatchForm {
+                        workflow,
+                        git_ref: dispatch_ref_input(if current_branch.is_empty() {
+                            "main".to_string()
+                        } else {
+                            current_branch.to_string()
+                        }),
+                        inputs: dispatch_inputs_input(String::new()),
+                        focused_field: DispatchField::Ref,
+                        ref_selected: None,
+                        error: None,
+                    });
+                }
+            }
+            _ => {}
         }
     }
 
-    fn get_jobs_for_current_column(&self) -> &BTreeMap<String, Vec<usize>> {
-        match self.app_state.column_index {
-            0 => &self.app_state.in_progress_jobs,
-            1 => &self.app_state.success_jobs,
-            2 => &self.app_state.failure_jobs,
-            _ => unreachable!(), // Should not happen with 0..2
+    /// Sends the workflow dispatch described by the open form.
+    fn submit_dispatch(&mut self) {
+        let Some(form) = self
+            .app_state
+            .dispatch
+            .as_mut()
+            .and_then(|dispatch| dispatch.form.as_mut())
+        else {
+            return;
+        };
+
+        if let Err(e) = form.git_ref.submit().and_then(|_| form.inputs.submit()) {
+            form.error = Some(e);
+            return;
         }
+        let git_ref = form.git_ref.value().trim().to_string();
+        let Ok(inputs) = parse_dispatch_inputs(form.inputs.value()) else {
+            return;
+        };
+
+        let workflow = form.workflow.clone();
+        self.app_state.dispatch = None;
+        self.send_dispatch(workflow, git_ref, inputs);
     }
 
-    fn toggle_details_panel(&mut self) {
-        self.app_state.show_details = !self.app_state.show_details;
+    /// Dispatches a workflow of the active repository in the background.
+    fn send_dispatch(
+        &mut self,
+        workflow: GithubWorkflow,
+        git_ref: String,
+        inputs: BTreeMap<String, String>,
+    ) {
+        let Some(repo_info) = self.gh_cli.repos().get(self.app_state.repo_index).cloned() else {
+            return;
+        };
+        self.app_state.loading_status = format!("Dispatching {} on {}...", workflow.name, git_ref);
+        self.events.request(move |gh_cli| {
+            OutcomeEvent::WorkflowDispatched(
+                gh_cli
+                    .dispatch_workflow(&repo_info, workflow.id, &git_ref, &inputs)
+                    .map(|_| {
+                        DispatchRecord::new(
+                            repo_info.full_name(),
+                            workflow.clone(),
+                            git_ref,
+                            inputs,
+                        )
+                    })
+                    .map_err(|e| format!("Error dispatching {}: {:?}", workflow.name, e)),
+            )
+        });
     }
 
     /// Handles the key events and updates the state of [`App`].
     pub fn handle_key_event(&mut self, key_event: KeyEvent) -> color_eyre::Result<()> {
-        match key_event.code {
-            KeyCode::Esc | KeyCode::Char('q') => self.events.send(AppEvent::Quit),
-            KeyCode::Char('c' | 'C') if key_event.modifiers == KeyModifiers::CONTROL => {
-                self.events.send(AppEvent::Quit)
-            }
-            KeyCode::Right => self.events.send(AppEvent::NavigateRight),
-            KeyCode::Left => self.events.send(AppEvent::NavigateLeft),
-            KeyCode::Up => self.events.send(AppEvent::NavigateUp),
-            KeyCode::Down => self.events.send(AppEvent::NavigateDown),
-            KeyCode::Enter => self.events.send(AppEvent::ToggleDetails),
-            KeyCode::PageDown => self.events.send(AppEvent::PageDown),
-            KeyCode::PageUp => self.events.send(AppEvent::PageUp),
-            KeyCode::Backspace => self.events.send(AppEvent::OpenGitHub),
-            _ => {}
+        if self.app_state.sso_authorization.is_some() {
+            self.handle_sso_authorization_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.rerun_confirmation.is_some() {
+            self.handle_rerun_confirmation_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.cancel_confirmation.is_some() {
+            self.handle_cancel_confirmation_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.approval_confirmation.is_some() {
+            self.handle_approval_confirmation_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.full_log_confirmation.is_some() {
+            self.handle_full_log_confirmation_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.deletion_confirmation.is_some() {
+            self.handle_deletion_confirmation_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.yank_menu.is_some() {
+            self.handle_yank_menu_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.export_prompt.is_some() {
+            self.handle_export_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.dispatch.is_some() {
+            self.handle_dispatch_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.branch_picker.is_some() {
+            self.handle_branch_picker_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.command_palette.is_some() {
+            self.handle_command_palette_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.job_summary.is_some() {
+            self.handle_job_summary_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.json_inspector.is_some() {
+            self.handle_json_inspector_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.badges.is_some() {
+            self.handle_badges_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.extension_menu.is_some() {
+            self.handle_extension_menu_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.extension_output.is_some() {
+            self.handle_extension_output_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.actions_settings.is_some() {
+            // The settings popup is read-only, so any of these keys closes it
+            if matches!(
+                key_event.code,
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q' | 'i')
+            ) {
+                self.app_state.actions_settings = None;
+            }
+            return Ok(());
+        }
+        if self.app_state.triage.is_some() {
+            self.handle_triage_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.filter.editing {
+            self.handle_filter_key_event(key_event);
+            return Ok(());
+        }
+        if self
+            .app_state
+            .views
+            .state::<LogCompareState>()
+            .and_then(|compare| compare.search.as_ref())
+            .is_some_and(|search| search.editing)
+        {
+            self.handle_log_search_key_event(key_event);
+            return Ok(());
+        }
+        if self.app_state.workflow_sidebar.is_some() {
+            self.handle_workflow_sidebar_key_event(key_event);
+            return Ok(());
+        }
+        // The numbers open the links listed in the details, rather than being bound to actions
+        if self.app_state.show_links
+            && self.app_state.show_details
+            && let KeyCode::Char(digit @ '1'..='9') = key_event.code
+        {
+            self.open_job_link(digit as usize - '0' as usize);
+            return Ok(());
+        }
+        if let Some(app_event) = self.keymap.handle(key_event) {
+            self.events.send(app_event);
         }
         Ok(())
     }
@@ -223,7 +5847,29 @@ impl App {
     ///
     /// The tick event is where you can update the state of your application with any logic that
     /// needs to be updated at a fixed frame rate. E.g. polling a server, updating an animation.
-    pub fn tick(&self) {}
+    pub fn tick(&mut self) {
+        self.follow_attachment();
+        let now = Instant::now();
+        self.app_state.toasts.retain(|toast| toast.expires_at > now);
+        // Refresh the watched run when it is due and no refresh is in flight
+        if let Some(watch) = self.app_state.views.state_mut::<WatchState>()
+            && watch
+                .next_poll
+                .is_some_and(|next_poll| next_poll <= Instant::now())
+        {
+            watch.next_poll = None;
+            let (repo, run_id) = (watch.repo.clone(), watch.run_id);
+            self.events.request(move |gh_cli| {
+                ViewDataEvent::RunFetched(
+                    run_id,
+                    gh_cli
+                        .fetch_run(&repo, run_id)
+                        .map(Box::new)
+                        .map_err(|e| format!("Error fetching run {}: {:?}", run_id, e)),
+                )
+            });
+        }
+    }
 
     /// Set running to false to quit the application.
     pub fn quit(&mut self) {
@@ -231,54 +5877,524 @@ impl App {
     }
 
     // Now accepts `WorkflowData` directly
-    pub fn update_github_data(&mut self, workflow_data: crate::gh_cli::WorkflowData) {
-        self.job_details.clear();
-        for job in workflow_data.jobs {
-            if self.job_details.len() >= MAX_DISPLAYED_JOBS {
-                self.job_details.pop_front();
+    pub fn update_github_data(&mut self, workflow_data: WorkflowData) {
+        self.notify_concluded_runs(&workflow_data.runs);
+        let selected_job_id = self.selected_job_id();
+        let fetched_before = self.app_state.updated_at.is_some();
+
+        // Keep the jobs of runs that weren't refetched and replace everything else
+        self.job_details
+            .retain(|job| workflow_data.unchanged_runs.contains(&job.run_id));
+        self.job_details.extend(workflow_data.jobs);
+        self.trim_jobs();
+        match self.workflow_mutes.lift(self.job_details.iter()) {
+            Ok(lifted) => {
+                for mute in lifted {
+                    self.show_toast(
+                        format!(
+                            "A new commit ran on {}, {} is no longer muted.",
+                            mute.branch, mute.workflow
+                        ),
+                        ToastLevel::Info,
+                    );
+                }
+            }
+            Err(e) => self.show_toast(
+                format!("Could not save the muted workflows: {}", e),
+                ToastLevel::Error,
+            ),
+        }
+        match self.pinned_jobs.update(self.job_details.iter()) {
+            Ok(changed) => {
+                for before in changed {
+                    if let Some(job) = self.job_details.iter().find(|job| job.id == before.id) {
+                        let message = format!(
+                            "{} went from {} to {}.",
+                            job.name,
+                            before.conclusion.as_deref().unwrap_or(&before.status),
+                            job.conclusion.as_deref().unwrap_or(&job.status)
+                        );
+                        notifications::notify_pinned_job_changed(job);
+                        self.show_toast(message, ToastLevel::Info);
+                    }
+                }
+            }
+            Err(e) => self.show_toast(
+                format!("Could not save the pinned jobs: {}", e),
+                ToastLevel::Error,
+            ),
+        }
+        // Pinned jobs stay listed once their run is no longer fetched
+        let unlisted: Vec<GithubJob> = self
+            .pinned_jobs
+            .entries
+            .iter()
+            .filter(|pinned| !self.job_details.iter().any(|job| job.id == pinned.id))
+            .cloned()
+            .collect();
+        self.job_details.extend(unlisted);
+        let job_details = &self.job_details;
+        self.app_state
+            .failure_excerpts
+            .retain(|job_id, _| job_details.iter().any(|job| job.id == *job_id));
+        self.app_state
+            .partial_logs
+            .retain(|job_id, _| job_details.iter().any(|job| job.id == *job_id));
+        self.app_state
+            .job_environments
+            .retain(|job_id, _| job_details.iter().any(|job| job.id == *job_id));
+        self.app_state
+            .annotations
+            .retain(|job_id, _| job_details.iter().any(|job| job.id == *job_id));
+        self.app_state
+            .artifacts
+            .retain(|run_id, _| job_details.iter().any(|job| job.run_id == *run_id));
+        self.fetch_retention_days();
+
+        let unseen_before = self.timeline.unseen;
+        self.timeline.observe(&self.job_details);
+        if let Some(timeline_view) = self.app_state.views.state_mut::<TimelineViewState>() {
+            // Keep the same entry selected as new ones come in above it
+            timeline_view.selected += self.timeline.unseen - unseen_before;
+            self.timeline.unseen = 0;
+        }
+
+        self.app_state.stale_since = None;
+        self.app_state.updated_at = Some(Utc::now());
+        self.save_snapshots(&workflow_data.runs);
+        // Runs that weren't refetched keep the count of the jobs left out of them
+        let mut runs = workflow_data.runs;
+        for run in runs
+            .iter_mut()
+            .filter(|run| workflow_data.unchanged_runs.contains(&run.id))
+        {
+            run.total_jobs = self
+                .runs
+                .iter()
+                .find(|previous| previous.id == run.id)
+                .and_then(|previous| previous.total_jobs);
+        }
+        // Runs that started since the previous fetch, rather than before the app did
+        if fetched_before && (self.args.follow_new_runs || self.config.follow_new_runs) {
+            let current_branch = self.gh_cli.current_branch();
+            let active_repo = self.active_repo().unwrap_or_default();
+            if let Some(run) = runs
+                .iter()
+                .filter(|run| {
+                    !current_branch.is_empty()
+                        && run.head_branch == current_branch
+                        && run.repo.eq_ignore_ascii_case(&active_repo)
+                        && !self.runs.iter().any(|previous| previous.id == run.id)
+                })
+                .max_by_key(|run| run.id)
+            {
+                self.app_state.followed_run = Some(run.id);
             }
-            self.job_details.push_back(job);
         }
+        self.runs = runs;
+        let updated_at = self.app_state.updated_at.unwrap_or_else(Utc::now);
+        self.refresh_history
+            .record(updated_at, &self.job_details, &self.runs);
+        // Follow the selected job to its new row, as jobs above it came and went
+        self.group_jobs();
+        self.reselect_job(selected_job_id);
+        self.follow_new_run();
+    }
+
+    /// Steps back or forward through the refreshes kept, the latest being the jobs shown live.
+    fn step_through_refreshes(&mut self, delta: isize) {
+        let count = self.refresh_history.entries.len();
+        let current = match self.app_state.time_travel {
+            Some(fetched_at) => self.refresh_history.position(fetched_at).unwrap_or(0),
+            None => count.saturating_sub(1),
+        };
+        if delta < 0 && (count < 2 || current == 0) {
+            self.show_toast(
+                "No earlier refresh to go back to.".to_string(),
+                ToastLevel::Warning,
+            );
+            return;
+        }
+        let target = current.saturating_add_signed(delta);
+        if target + 1 >= count {
+            self.return_to_present();
+        } else {
+            self.show_past_refresh(target);
+        }
+    }
 
-        // After updating job_details, re-filter them into state vectors
-        self.app_state.in_progress_jobs.clear();
-        self.app_state.success_jobs.clear();
-        self.app_state.failure_jobs.clear();
+    /// Shows the jobs and runs as a refresh kept in the history left them, putting the
+    /// latest ones aside.
+    fn show_past_refresh(&mut self, index: usize) {
+        let Some(past) = self.refresh_history.entries.get(index) else {
+            return;
+        };
+        let selected_job_id = self.selected_job_id();
+        let jobs = std::mem::replace(&mut self.job_details, past.jobs.clone());
+        let runs = std::mem::replace(&mut self.runs, past.runs.clone());
+        self.app_state.time_travel = Some(past.fetched_at);
+        if self.live_data.is_none() {
+            self.live_data = Some((jobs, runs));
+        }
+        self.group_jobs();
+        self.reselect_job(selected_job_id);
+    }
 
-        // Sort by started_at in descending order for better visualization
-        // (most recent jobs at the top of the display lists)
-        let mut sorted_jobs: Vec<(usize, &crate::gh_cli::GithubJob)> =
-            self.job_details.iter().enumerate().collect();
+    /// Shows the latest jobs and runs again after looking at an earlier refresh.
+    fn return_to_present(&mut self) {
+        let Some((jobs, runs)) = self.live_data.take() else {
+            return;
+        };
+        let selected_job_id = self.selected_job_id();
+        self.job_details = jobs;
+        self.runs = runs;
+        self.app_state.time_travel = None;
+        self.group_jobs();
+        self.reselect_job(selected_job_id);
+    }
 
-        sorted_jobs.sort_by(|(_, a), (_, b)| {
-            b.started_at.cmp(&a.started_at) // Sort descending
+    /// Drops the oldest jobs beyond [`MAX_DISPLAYED_JOBS`], except those of expanded runs,
+    /// which were asked for in full.
+    fn trim_jobs(&mut self) {
+        let mut excess = self.job_details.len().saturating_sub(MAX_DISPLAYED_JOBS);
+        let gh_cli = &self.gh_cli;
+        self.job_details.retain(|job| {
+            if excess > 0 && !gh_cli.is_expanded(job.run_id) {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
         });
+    }
+
+    /// Sends a desktop notification for every run that was in progress at the last fetch and
+    /// has since concluded with an outcome the user opted in to.
+    fn notify_concluded_runs(&mut self, runs: &[crate::gh_cli::GithubWorkflowRun]) {
+        let enabled = self.args.notify || self.config.notifications.enabled;
+        let notify_on = if self.args.notify_on.is_empty() {
+            &self.config.notifications.on
+        } else {
+            &self.args.notify_on
+        };
+        for run in runs {
+            let was_running = self
+                .run_statuses
+                .get(&run.id)
+                .is_some_and(|status| status != "completed");
+            let outcome = RunOutcome::from_conclusion(run.conclusion.as_deref());
+            if enabled
+                && was_running
+                && run.status == "completed"
+                && outcome.is_some_and(|outcome| notify_on.contains(&outcome))
+            {
+                notifications::notify_run_concluded(run);
+            }
+        }
+        self.run_statuses = runs
+            .iter()
+            .map(|run| (run.id, run.status.clone()))
+            .collect();
+    }
+
+    /// Re-filters `job_details` into the per-column state maps.
+    fn group_jobs(&mut self) {
+        self.fetch_changed_paths();
+        // After updating job_details, re-filter them into vectors, listed as rows further down
+        let mut in_progress_jobs: BTreeMap<JobGroup, Vec<usize>> = BTreeMap::new();
+        let mut success_jobs: BTreeMap<JobGroup, Vec<usize>> = BTreeMap::new();
+        let mut failure_jobs: BTreeMap<JobGroup, Vec<usize>> = BTreeMap::new();
+        let mut other_jobs: BTreeMap<JobGroup, Vec<usize>> = BTreeMap::new();
+
+        // When monitoring several repositories only the selected one is shown
+        let active_repo = self.active_repo().filter(|_| self.gh_cli.repos().len() > 1);
+        // Jobs of other branches may still come from before a branch was picked
+        let branch = self.gh_cli.branch();
+        let concluded_since = self
+            .app_state
+            .filter
+            .filters()
+            .recent_only
+            .then(|| Utc::now() - TimeDelta::hours(self.config.recent_hours() as i64));
+        let mut sorted_jobs: Vec<(usize, &crate::gh_cli::GithubJob)> = self
+            .job_details
+            .iter()
+            .enumerate()
+            .filter(|(_, job)| {
+                active_repo
+                    .as_ref()
+                    .is_none_or(|repo| job.repo.eq_ignore_ascii_case(repo))
+            })
+            .filter(|(_, job)| {
+                branch
+                    .as_ref()
+                    .is_none_or(|branch| job.head_branch == *branch)
+            })
+            .filter(|(_, job)| {
+                job_matches_filter(
+                    job,
+                    &self.app_state.filter.filters().query,
+                    &self.app_state.changed_paths,
+                )
+            })
+            .filter(|(_, job)| {
+                !self
+                    .app_state
+                    .filter
+                    .filters()
+                    .hides(&job.workflow_name, &job.event)
+            })
+            .collect();
 
+        self.config.sort.sort(&mut sorted_jobs, |(_, job)| job);
 
         for (original_index, job) in sorted_jobs {
-            let tool = self.parse_job_name_for_tool(&job.name);
+            let tool = if self.pinned_jobs.is_pinned(job.id) {
+                JobGroup::Pinned
+            } else {
+                JobGroup::Tool(self.parse_job_name_for_tool(&job.name))
+            };
+            // Pinned jobs are listed however long ago they concluded
+            let is_old = concluded_since.is_some_and(|since| {
+                tool != JobGroup::Pinned && job.completed_at.unwrap_or(job.started_at) < since
+            });
             match job.status.as_str() {
                 "completed" => {
                     if let Some(conclusion) = &job.conclusion {
                         match conclusion.as_str() {
-                            "success" => self.app_state.success_jobs.entry(tool).or_default().push(original_index),
-                            "failure" => self.app_state.failure_jobs.entry(tool).or_default().push(original_index),
-                            _ => { /* Ignore cancelled, skipped, etc. as per request */ }
+                            "success" => success_jobs.entry(tool).or_default().push(original_index),
+                            _ if is_old => {}
+                            // Muted until a newer commit runs, so left out altogether
+                            "failure" if self.workflow_mutes.is_muted(job) => {}
+                            "failure" => failure_jobs.entry(tool).or_default().push(original_index),
+                            // Cancelled, skipped, timed out, waiting on approval, etc.
+                            _ => other_jobs.entry(tool).or_default().push(original_index),
                         }
                     }
                 }
-                "in_progress" | "queued" | "waiting" => {
-                    self.app_state.in_progress_jobs.entry(tool).or_default().push(original_index)
-                }
+                "in_progress" | "queued" | "waiting" => in_progress_jobs
+                    .entry(tool)
+                    .or_default()
+                    .push(original_index),
                 _ => { /* Ignore other statuses if any */ }
             }
         }
 
+        // The failures that matter most come first, e.g. those of the default branch
+        for indices in failure_jobs.values_mut() {
+            indices.sort_by_key(|&index| self.config.branches.kind(&self.job_details[index]));
+        }
+
+        let rows = |groups: BTreeMap<JobGroup, Vec<usize>>| -> BTreeMap<JobGroup, Vec<ColumnRow>> {
+            groups
+                .into_iter()
+                .map(|(tool, indices)| (tool, self.list_matrices(&indices)))
+                .collect()
+        };
+        let grouped = [in_progress_jobs, success_jobs, failure_jobs, other_jobs].map(rows);
+        [
+            self.app_state.in_progress_jobs,
+            self.app_state.success_jobs,
+            self.app_state.failure_jobs,
+            self.app_state.other_jobs,
+        ] = grouped;
+
+        // Keep every column where it was, unless it shrank below its selected row
+        let row_counts = [0, 1, 2].map(|column| {
+            self.app_state
+                .column_jobs(column)
+                .values()
+                .flatten()
+                .count()
+        });
+        for (position, row_count) in self.app_state.columns.iter_mut().zip(row_counts) {
+            position.row_index = position.row_index.min(row_count.saturating_sub(1));
+        }
+
         // Ensure current_job_index is valid after update and re-filtering
         self.update_current_job_index_from_state();
+        self.sync_run_view();
+    }
+    /// Lists jobs as rows, the jobs of a matrix under a single row in place of the first one.
+    fn list_matrices(&self, indices: &[usize]) -> Vec<ColumnRow> {
+        let matrix_of = |index: usize| {
+            let job = &self.job_details[index];
+            matrix_name(&job.name).map(|name| (job.run_id, name.to_string()))
+        };
+        let mut listed = HashSet::new();
+        let mut rows = Vec::new();
+        for &index in indices {
+            let Some(key) = matrix_of(index) else {
+                rows.push(ColumnRow::Job(index));
+                continue;
+            };
+            if !listed.insert(key.clone()) {
+                continue;
+            }
+            let jobs: Vec<usize> = indices
+                .iter()
+                .copied()
+                .filter(|&other| matrix_of(other).as_ref() == Some(&key))
+                .collect();
+            if jobs.len() < 2 {
+                rows.push(ColumnRow::Job(index));
+                continue;
+            }
+            let expanded = self.app_state.expanded_matrices.contains(&key);
+            let (run_id, name) = key;
+            rows.push(ColumnRow::Matrix(MatrixRow {
+                run_id,
+                name,
+                jobs: jobs.clone(),
+                expanded,
+            }));
+            if expanded {
+                rows.extend(jobs.into_iter().map(ColumnRow::Job));
+            }
+        }
+        rows
     }
+
     pub fn parse_job_name_for_tool(&self, job_name: &str) -> String {
         let parts: Vec<&str> = job_name.split(" / ").collect();
-        parts.get(0).unwrap_or(&"Other").to_string()
+        let tool = parts.first().unwrap_or(&"Other");
+        // The jobs of a matrix are grouped together rather than each on their own
+        matrix_name(tool).unwrap_or(tool).to_string()
+    }
+}
+
+/// The name a job of a matrix shares with the other jobs of the matrix, e.g. `test` for
+/// `test (ubuntu, 1.75)`, `None` if the job isn't of a matrix.
+pub fn matrix_name(job_name: &str) -> Option<&str> {
+    let (name, values) = job_name.strip_suffix(')')?.rsplit_once(" (")?;
+    (!name.is_empty() && !values.is_empty()).then_some(name)
+}
+
+/// The URL of a pull request, the conventional one if the pull request couldn't be fetched.
+fn pull_request_url(repo: &str, pull_request: &PullRequest) -> String {
+    if pull_request.html_url.is_empty() {
+        format!("https://github.com/{}/pull/{}", repo, pull_request.number)
+    } else {
+        pull_request.html_url.clone()
+    }
+}
+
+/// What another instance is called in messages, by its process ID if known.
+fn instance_name(pid: Option<u32>) -> String {
+    match pid {
+        Some(pid) => format!("lazyactions (PID {})", pid),
+        None => "another lazyactions".to_string(),
+    }
+}
+
+/// The status of an instance attached to another one that polls GitHub for it.
+fn attached_status(pid: Option<u32>) -> String {
+    format!("Showing what {} fetches.", instance_name(pid))
+}
+
+/// What the runs an instance polls are narrowed down by, e.g. `branch=main actor=* latest=false`,
+/// which another instance must share to show what it fetches.
+fn poll_filters(gh_cli: &dyn GithubClient, latest: bool) -> String {
+    format!(
+        "branch={} actor={} latest={}",
+        gh_cli.branch().as_deref().unwrap_or("*"),
+        gh_cli.actor().unwrap_or("*"),
+        latest
+    )
+}
+
+/// Whether a step that took `duration` is unusually slow for one whose median is `median`.
+pub fn is_slow_step(duration: TimeDelta, median: TimeDelta) -> bool {
+    duration > median * SLOW_STEP_FACTOR && duration - median > SLOW_STEP_MARGIN
+}
+
+/// The paths of the files changed by commits, by repository in lowercase and SHA, `None`
+/// while they are being fetched.
+pub type ChangedPaths = HashMap<(String, String), Option<Result<Vec<String>, String>>>;
+
+/// The prefix of the filter terms matching the paths changed by the commit of a job.
+const PATH_TERM_PREFIX: &str = "path:";
+
+/// Whether something GitHub deletes at `expires_at` is about to be at `now`. Once it is
+/// deleted, there is nothing left to warn about.
+pub fn is_expiring_soon(expires_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    expires_at > now && expires_at - now < EXPIRY_WARNING
+}
+
+/// Whether a job matches a filter. Every whitespace separated term of the filter has to
+/// fuzzily match the job's name, workflow, branch or actor, but for `path:` terms, which have
+/// to match a path changed by the job's commit.
+fn job_matches_filter(job: &GithubJob, filter: &str, changed_paths: &ChangedPaths) -> bool {
+    filter.split_whitespace().all(|term| {
+        if let Some(pattern) = term.strip_prefix(PATH_TERM_PREFIX) {
+            // Jobs are left out until the paths their commit changed are known
+            return changed_paths
+                .get(&(job.repo.to_ascii_lowercase(), job.head_sha.clone()))
+                .and_then(|paths| paths.as_ref()?.as_ref().ok())
+                .is_some_and(|paths| paths.iter().any(|path| path_matches(pattern, path)));
+        }
+        [
+            &job.name,
+            &job.workflow_name,
+            &job.head_branch,
+            &job.actor_login,
+        ]
+        .into_iter()
+        .any(|field| fuzzy_match(term, field))
+    })
+}
+
+/// Whether a changed path matches the pattern of a `path:` term: a pattern with `*`, which
+/// stands for any run of characters, has to match the whole path, and one without matches the
+/// path itself or the files under it.
+fn path_matches(pattern: &str, path: &str) -> bool {
+    if pattern.contains('*') {
+        config::matches_pattern(pattern, path)
+    } else {
+        let directory = pattern.trim_end_matches('/');
+        path == directory
+            || path
+                .strip_prefix(directory)
+                .is_some_and(|rest| rest.starts_with('/'))
     }
 }
+
+/// Whether the characters of `pattern` appear in `text` in order, ignoring case.
+fn fuzzy_match(pattern: &str, text: &str) -> bool {
+    let mut text = text.chars().flat_map(char::to_lowercase);
+    pattern
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|p| text.any(|t| t == p))
+}
+
+/// The input for the ref of a workflow dispatch, which is required.
+fn dispatch_ref_input(git_ref: String) -> TextInput {
+    TextInput::new(git_ref).with_validator(|git_ref| {
+        if git_ref.trim().is_empty() {
+            Err("A ref is required.".to_string())
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// The input for the inputs of a workflow dispatch, which must parse.
+fn dispatch_inputs_input(inputs: String) -> TextInput {
+    TextInput::new(inputs).with_validator(|inputs| parse_dispatch_inputs(inputs).map(|_| ()))
+}
+
+/// Parses workflow dispatch inputs given as comma separated `key=value` pairs.
+fn parse_dispatch_inputs(inputs: &str) -> Result<BTreeMap<String, String>, String> {
+    inputs
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                Ok((key.trim().to_string(), value.trim().to_string()))
+            }
+            _ => Err(format!("Expected `key=value`, got `{}`.", pair)),
+        })
+        .collect()
+}
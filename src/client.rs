@@ -0,0 +1,1110 @@
+use chrono::{DateTime, Utc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+};
+
+use crate::{
+    gh_api::RateLimit,
+    gh_cli::{
+        ActionsBilling, ActionsSetting, Annotation, ApiCall, Artifact, CancelFlag,
+        CheckAnnotations, CommitStatus, EnvironmentStatus, GhCli, GitRef, GithubJob, GithubRunner,
+        GithubWorkflow, GithubWorkflowRun, JobLog, LogProgress, RefKind, RepoInfo, RunDetails,
+        WorkflowData, WorkflowFileDiff, WorkflowHistoryPage,
+    },
+};
+
+/// Everything the app asks of GitHub, implemented by [`GhCli`] and by fixtures in tests, so
+/// that the app can run without a `gh` binary.
+pub trait GithubClient: Debug + Send + Sync {
+    /// The repositories monitored by this client.
+    fn repos(&self) -> &[RepoInfo];
+
+    /// The branch checked out in the current directory, if any.
+    fn current_branch(&self) -> &str;
+
+    /// The user whose runs are fetched, `None` for the runs of every user.
+    fn actor(&self) -> Option<&str>;
+
+    /// The branch whose runs are fetched, `None` for the runs of every branch.
+    fn branch(&self) -> Option<String>;
+
+    /// Changes the branch whose runs are fetched from the next fetch on.
+    fn set_branch(&self, branch: Option<String>);
+
+    /// Leaves the runs of the repositories another instance polls out of the next fetches.
+    fn set_followed_repos(&self, repos: &[String]);
+
+    /// Remembers the completed runs of a fetch whose data is shown, by run id, for the next
+    /// fetches to keep their jobs.
+    fn remember_fetched_runs(&self, runs: HashMap<u64, String>);
+
+    /// What the installed GitHub CLI lacks and what is done instead.
+    fn compatibility_notes(&self) -> &[String];
+
+    /// What couldn't be looked up on creation, e.g. the current Git branch.
+    fn startup_warnings(&self) -> &[String];
+
+    /// The most recent API calls, newest first.
+    fn api_calls(&self) -> Vec<ApiCall>;
+
+    /// The rate limit budget as of the latest response that reported it.
+    fn rate_limit(&self) -> Option<RateLimit>;
+
+    /// How far along the download of the log of a job is, while it is being downloaded.
+    fn log_progress(&self, job_id: u64) -> Option<LogProgress>;
+
+    /// Whether the latest requests have kept failing to reach GitHub for a while.
+    fn is_offline(&self) -> bool;
+
+    /// Checks whether GitHub can be reached again.
+    fn check_connectivity(&self) -> bool;
+
+    /// Checks that the GitHub API can be reached, explaining what is likely in the way when
+    /// it can't.
+    fn diagnose_connectivity(&self) -> Result<(), String>;
+
+    /// Makes every further request fail straight away.
+    fn stop(&self);
+
+    /// Kills the requests that are still running.
+    fn kill_running_commands(&self);
+
+    /// Fetches the runs and jobs shown in the job columns, giving up once `cancel` is set.
+    fn fetch_github_workflow_data(&self, cancel: &CancelFlag) -> color_eyre::Result<WorkflowData>;
+
+    /// Fetches the self-hosted runners registered for the monitored repositories.
+    fn fetch_runners(&self) -> color_eyre::Result<Vec<GithubRunner>>;
+
+    /// Fetches the most recent commits of the current branch with the outcome of their runs.
+    fn fetch_branch_commits(&self) -> color_eyre::Result<Vec<CommitStatus>>;
+
+    /// Fetches every job of a run, and keeps fetching all of them with the following
+    /// refreshes.
+    fn expand_run(&self, run: &GithubWorkflowRun) -> color_eyre::Result<Vec<GithubJob>>;
+
+    /// Whether all the jobs of a run are fetched, however many there are.
+    fn is_expanded(&self, run_id: u64) -> bool;
+
+    /// Fetches a single run of a repository, given as `owner/name`, with all of its jobs.
+    fn fetch_run(&self, repo: &str, run_id: u64) -> color_eyre::Result<RunDetails>;
+
+    /// Fetches the runs of a repository created since `since`, with all of their jobs.
+    fn fetch_runs_since(
+        &self,
+        repo_info: &RepoInfo,
+        since: DateTime<Utc>,
+    ) -> color_eyre::Result<Vec<RunDetails>>;
+
+    /// Fetches the branches and then the tags of a repository.
+    fn fetch_refs(&self, repo_info: &RepoInfo) -> color_eyre::Result<Vec<GitRef>>;
+
+    /// Fetches the names of the branches of a repository.
+    fn fetch_branches(&self, repo_info: &RepoInfo) -> color_eyre::Result<Vec<String>>;
+
+    /// Fetches the workflows defined in a repository.
+    fn fetch_workflows(&self, repo_info: &RepoInfo) -> color_eyre::Result<Vec<GithubWorkflow>>;
+
+    /// Triggers a `workflow_dispatch` event for a workflow on the given ref.
+    fn dispatch_workflow(
+        &self,
+        repo_info: &RepoInfo,
+        workflow_id: u64,
+        git_ref: &str,
+        inputs: &BTreeMap<String, String>,
+    ) -> color_eyre::Result<()>;
+
+    /// Reruns a single job, along with the jobs that depend on it.
+    fn rerun_job(&self, job: &GithubJob) -> color_eyre::Result<()>;
+
+    /// Reruns every job of a run.
+    fn rerun_run(&self, repo: &str, run_id: u64) -> color_eyre::Result<()>;
+
+    /// Reruns the failed jobs of a run, along with the jobs that depend on them.
+    fn rerun_failed_jobs(&self, repo: &str, run_id: u64) -> color_eyre::Result<()>;
+
+    /// Cancels a run that is still queued or in progress.
+    fn cancel_run(&self, repo: &str, run_id: u64) -> color_eyre::Result<()>;
+
+    /// Fetches the Actions minutes the organization or user `owner` used in its current
+    /// billing cycle.
+    fn fetch_actions_billing(&self, owner: &str) -> color_eyre::Result<ActionsBilling>;
+
+    /// Lists the names of the installed `gh` extensions.
+    fn fetch_extensions(&self) -> color_eyre::Result<Vec<String>>;
+
+    /// Runs a command of a `gh` extension to completion and returns its output.
+    fn run_extension(&self, args: &[String]) -> color_eyre::Result<String>;
+
+    /// Deletes a run along with its logs and artifacts.
+    fn delete_run(&self, repo: &str, run_id: u64) -> color_eyre::Result<()>;
+
+    /// Deletes the completed runs of the workflow at `path` in `repo` that were created
+    /// before `before`, returning how many were deleted.
+    fn delete_runs_before(
+        &self,
+        repo: &str,
+        path: &str,
+        before: DateTime<Utc>,
+    ) -> color_eyre::Result<usize>;
+
+    /// Approves the deployments a run is waiting for that the authenticated user may
+    /// approve, returning the names of their environments.
+    fn approve_pending_deployments(
+        &self,
+        repo: &str,
+        run_id: u64,
+    ) -> color_eyre::Result<Vec<String>>;
+
+    /// Fetches the plain text log of a job, or only about `tail` bytes of its end.
+    fn fetch_job_log(&self, job: &GithubJob, tail: Option<u64>) -> color_eyre::Result<JobLog>;
+
+    /// Downloads the whole log of a job as GitHub serves it.
+    fn download_job_log(&self, repo: &str, job_id: u64) -> color_eyre::Result<Vec<u8>>;
+
+    /// Downloads the logs of every job of a run as a zip archive.
+    fn download_run_logs(&self, repo: &str, run_id: u64) -> color_eyre::Result<Vec<u8>>;
+
+    /// Fetches the markdown summary a job published, empty when it didn't publish one.
+    fn fetch_job_summary(&self, job: &GithubJob) -> color_eyre::Result<String>;
+
+    /// Fetches the JSON the API returns for a job and its run, pretty-printed.
+    fn fetch_raw_json(&self, job: &GithubJob) -> color_eyre::Result<String>;
+
+    /// Fetches a page of the runs of the workflow at `path` in `repo`, newest first, counting
+    /// pages from 1.
+    fn fetch_workflow_history(
+        &self,
+        repo: &str,
+        path: &str,
+        page: u64,
+    ) -> color_eyre::Result<WorkflowHistoryPage>;
+
+    /// Downloads the JUnit XML reports a run uploaded as artifacts.
+    fn fetch_test_reports(&self, repo: &str, run_id: u64) -> color_eyre::Result<Vec<String>>;
+
+    /// Fetches a page of the completed runs of every workflow of `repo`, newest first,
+    /// counting pages from 1.
+    fn fetch_repo_history(&self, repo: &str, page: u64) -> color_eyre::Result<WorkflowHistoryPage>;
+
+    /// Fetches the Actions settings of a repository.
+    fn fetch_actions_settings(
+        &self,
+        repo_info: &RepoInfo,
+    ) -> color_eyre::Result<Vec<ActionsSetting>>;
+
+    /// Fetches what is deployed to each environment of a repository.
+    fn fetch_environment_statuses(
+        &self,
+        repo_info: &RepoInfo,
+    ) -> color_eyre::Result<Vec<EnvironmentStatus>>;
+
+    /// Finds the environment a job deployed to, if any.
+    fn fetch_job_environment(&self, job: &GithubJob) -> color_eyre::Result<Option<String>>;
+
+    /// Finds whether the ref of a run, by name, is a tag or a branch.
+    fn fetch_ref_kind(&self, repo: &str, name: &str) -> color_eyre::Result<RefKind>;
+
+    /// Fetches the annotations the checks left on the files of a job's commit.
+    fn fetch_annotations(&self, job: &GithubJob) -> color_eyre::Result<Vec<Annotation>>;
+
+    /// Fetches the annotations of the checks that apps other than GitHub Actions failed on a
+    /// commit.
+    fn fetch_check_annotations(
+        &self,
+        repo: &str,
+        head_sha: &str,
+    ) -> color_eyre::Result<CheckAnnotations>;
+
+    /// Fetches the id of the run a job belongs to.
+    fn fetch_job_run_id(&self, repo: &str, job_id: u64) -> color_eyre::Result<u64>;
+
+    /// Fetches the id of the latest run for the head commit of a pull request.
+    fn fetch_pull_request_run_id(&self, repo: &str, number: u64) -> color_eyre::Result<u64>;
+
+    /// Diffs the workflow file of a later job against the commit of an earlier one.
+    fn fetch_workflow_file_diff(
+        &self,
+        earlier: &GithubJob,
+        later: &GithubJob,
+    ) -> color_eyre::Result<WorkflowFileDiff>;
+
+    /// Lists the paths of the files a commit of `repo` changed.
+    fn fetch_changed_paths(&self, repo: &str, sha: &str) -> color_eyre::Result<Vec<String>>;
+
+    /// Fetches the artifacts a run uploaded, expired ones included.
+    fn fetch_artifacts(&self, repo: &str, run_id: u64) -> color_eyre::Result<Vec<Artifact>>;
+
+    /// Fetches how many days a repository keeps the logs and artifacts of its runs.
+    fn fetch_retention_days(&self, repo: &str) -> color_eyre::Result<u64>;
+}
+
+impl GithubClient for GhCli {
+    fn repos(&self) -> &[RepoInfo] {
+        GhCli::repos(self)
+    }
+
+    fn current_branch(&self) -> &str {
+        GhCli::current_branch(self)
+    }
+
+    fn actor(&self) -> Option<&str> {
+        GhCli::actor(self)
+    }
+
+    fn branch(&self) -> Option<String> {
+        GhCli::branch(self)
+    }
+
+    fn set_branch(&self, branch: Option<String>) {
+        GhCli::set_branch(self, branch)
+    }
+
+    fn set_followed_repos(&self, repos: &[String]) {
+        GhCli::set_followed_repos(self, repos)
+    }
+
+    fn remember_fetched_runs(&self, runs: HashMap<u64, String>) {
+        GhCli::remember_fetched_runs(self, runs)
+    }
+
+    fn compatibility_notes(&self) -> &[String] {
+        GhCli::compatibility_notes(self)
+    }
+
+    fn startup_warnings(&self) -> &[String] {
+        GhCli::startup_warnings(self)
+    }
+
+    fn api_calls(&self) -> Vec<ApiCall> {
+        GhCli::api_calls(self)
+    }
+
+    fn rate_limit(&self) -> Option<RateLimit> {
+        GhCli::rate_limit(self)
+    }
+
+    fn log_progress(&self, job_id: u64) -> Option<LogProgress> {
+        GhCli::log_progress(self, job_id)
+    }
+
+    fn is_offline(&self) -> bool {
+        GhCli::is_offline(self)
+    }
+
+    fn check_connectivity(&self) -> bool {
+        GhCli::check_connectivity(self)
+    }
+
+    fn diagnose_connectivity(&self) -> Result<(), String> {
+        GhCli::diagnose_connectivity(self)
+    }
+
+    fn stop(&self) {
+        GhCli::stop(self)
+    }
+
+    fn kill_running_commands(&self) {
+        GhCli::kill_running_commands(self)
+    }
+
+    fn fetch_github_workflow_data(&self, cancel: &CancelFlag) -> color_eyre::Result<WorkflowData> {
+        GhCli::fetch_github_workflow_data(self, cancel)
+    }
+
+    fn fetch_runners(&self) -> color_eyre::Result<Vec<GithubRunner>> {
+        GhCli::fetch_runners(self)
+    }
+
+    fn fetch_branch_commits(&self) -> color_eyre::Result<Vec<CommitStatus>> {
+        GhCli::fetch_branch_commits(self)
+    }
+
+    fn expand_run(&self, run: &GithubWorkflowRun) -> color_eyre::Result<Vec<GithubJob>> {
+        GhCli::expand_run(self, run)
+    }
+
+    fn is_expanded(&self, run_id: u64) -> bool {
+        GhCli::is_expanded(self, run_id)
+    }
+
+    fn fetch_run(&self, repo: &str, run_id: u64) -> color_eyre::Result<RunDetails> {
+        GhCli::fetch_run(self, repo, run_id)
+    }
+
+    fn fetch_runs_since(
+        &self,
+        repo_info: &RepoInfo,
+        since: DateTime<Utc>,
+    ) -> color_eyre::Result<Vec<RunDetails>> {
+        GhCli::fetch_runs_since(self, repo_info, since)
+    }
+
+    fn fetch_refs(&self, repo_info: &RepoInfo) -> color_eyre::Result<Vec<GitRef>> {
+        GhCli::fetch_refs(self, repo_info)
+    }
+
+    fn fetch_branches(&self, repo_info: &RepoInfo) -> color_eyre::Result<Vec<String>> {
+        GhCli::fetch_branches(self, repo_info)
+    }
+
+    fn fetch_workflows(&self, repo_info: &RepoInfo) -> color_eyre::Result<Vec<GithubWorkflow>> {
+        GhCli::fetch_workflows(self, repo_info)
+    }
+
+    fn dispatch_workflow(
+        &self,
+        repo_info: &RepoInfo,
+        workflow_id: u64,
+        git_ref: &str,
+        inputs: &BTreeMap<String, String>,
+    ) -> color_eyre::Result<()> {
+        GhCli::dispatch_workflow(self, repo_info, workflow_id, git_ref, inputs)
+    }
+
+    fn rerun_job(&self, job: &GithubJob) -> color_eyre::Result<()> {
+        GhCli::rerun_job(self, job)
+    }
+
+    fn rerun_run(&self, repo: &str, run_id: u64) -> color_eyre::Result<()> {
+        GhCli::rerun_run(self, repo, run_id)
+    }
+
+    fn rerun_failed_jobs(&self, repo: &str, run_id: u64) -> color_eyre::Result<()> {
+        GhCli::rerun_failed_jobs(self, repo, run_id)
+    }
+
+    fn cancel_run(&self, repo: &str, run_id: u64) -> color_eyre::Result<()> {
+        GhCli::cancel_run(self, repo, run_id)
+    }
+
+    fn fetch_actions_billing(&self, owner: &str) -> color_eyre::Result<ActionsBilling> {
+        GhCli::fetch_actions_billing(self, owner)
+    }
+
+    fn fetch_extensions(&self) -> color_eyre::Result<Vec<String>> {
+        GhCli::fetch_extensions(self)
+    }
+
+    fn run_extension(&self, args: &[String]) -> color_eyre::Result<String> {
+        GhCli::run_extension(self, args)
+    }
+
+    fn delete_run(&self, repo: &str, run_id: u64) -> color_eyre::Result<()> {
+        GhCli::delete_run(self, repo, run_id)
+    }
+
+    fn delete_runs_before(
+        &self,
+        repo: &str,
+        path: &str,
+        before: DateTime<Utc>,
+    ) -> color_eyre::Result<usize> {
+        GhCli::delete_runs_before(self, repo, path, before)
+    }
+
+    fn approve_pending_deployments(
+        &self,
+        repo: &str,
+        run_id: u64,
+    ) -> color_eyre::Result<Vec<String>> {
+        GhCli::approve_pending_deployments(self, repo, run_id)
+    }
+
+    fn fetch_job_log(&self, job: &GithubJob, tail: Option<u64>) -> color_eyre::Result<JobLog> {
+        GhCli::fetch_job_log(self, job, tail)
+    }
+
+    fn download_job_log(&self, repo: &str, job_id: u64) -> color_eyre::Result<Vec<u8>> {
+        GhCli::download_job_log(self, repo, job_id)
+    }
+
+    fn download_run_logs(&self, repo: &str, run_id: u64) -> color_eyre::Result<Vec<u8>> {
+        GhCli::download_run_logs(self, repo, run_id)
+    }
+
+    fn fetch_job_summary(&self, job: &GithubJob) -> color_eyre::Result<String> {
+        GhCli::fetch_job_summary(self, job)
+    }
+
+    fn fetch_raw_json(&self, job: &GithubJob) -> color_eyre::Result<String> {
+        GhCli::fetch_raw_json(self, job)
+    }
+
+    fn fetch_workflow_history(
+        &self,
+        repo: &str,
+        path: &str,
+        page: u64,
+    ) -> color_eyre::Result<WorkflowHistoryPage> {
+        GhCli::fetch_workflow_history(self, repo, path, page)
+    }
+
+    fn fetch_repo_history(&self, repo: &str, page: u64) -> color_eyre::Result<WorkflowHistoryPage> {
+        GhCli::fetch_repo_history(self, repo, page)
+    }
+
+    fn fetch_test_reports(&self, repo: &str, run_id: u64) -> color_eyre::Result<Vec<String>> {
+        GhCli::fetch_test_reports(self, repo, run_id)
+    }
+
+    fn fetch_actions_settings(
+        &self,
+        repo_info: &RepoInfo,
+    ) -> color_eyre::Result<Vec<ActionsSetting>> {
+        GhCli::fetch_actions_settings(self, repo_info)
+    }
+
+    fn fetch_environment_statuses(
+        &self,
+        repo_info: &RepoInfo,
+    ) -> color_eyre::Result<Vec<EnvironmentStatus>> {
+        GhCli::fetch_environment_statuses(self, repo_info)
+    }
+
+    fn fetch_job_environment(&self, job: &GithubJob) -> color_eyre::Result<Option<String>> {
+        GhCli::fetch_job_environment(self, job)
+    }
+
+    fn fetch_ref_kind(&self, repo: &str, name: &str) -> color_eyre::Result<RefKind> {
+        GhCli::fetch_ref_kind(self, repo, name)
+    }
+
+    fn fetch_annotations(&self, job: &GithubJob) -> color_eyre::Result<Vec<Annotation>> {
+        GhCli::fetch_annotations(self, job)
+    }
+
+    fn fetch_check_annotations(
+        &self,
+        repo: &str,
+        head_sha: &str,
+    ) -> color_eyre::Result<CheckAnnotations> {
+        GhCli::fetch_check_annotations(self, repo, head_sha)
+    }
+
+    fn fetch_job_run_id(&self, repo: &str, job_id: u64) -> color_eyre::Result<u64> {
+        GhCli::fetch_job_run_id(self, repo, job_id)
+    }
+
+    fn fetch_pull_request_run_id(&self, repo: &str, number: u64) -> color_eyre::Result<u64> {
+        GhCli::fetch_pull_request_run_id(self, repo, number)
+    }
+
+    fn fetch_workflow_file_diff(
+        &self,
+        earlier: &GithubJob,
+        later: &GithubJob,
+    ) -> color_eyre::Result<WorkflowFileDiff> {
+        GhCli::fetch_workflow_file_diff(self, earlier, later)
+    }
+
+    fn fetch_changed_paths(&self, repo: &str, sha: &str) -> color_eyre::Result<Vec<String>> {
+        GhCli::fetch_changed_paths(self, repo, sha)
+    }
+
+    fn fetch_artifacts(&self, repo: &str, run_id: u64) -> color_eyre::Result<Vec<Artifact>> {
+        GhCli::fetch_artifacts(self, repo, run_id)
+    }
+
+    fn fetch_retention_days(&self, repo: &str) -> color_eyre::Result<u64> {
+        GhCli::fetch_retention_days(self, repo)
+    }
+}
+
+/// A client serving fixed data, for driving the app in tests.
+#[cfg(test)]
+pub mod fixture {
+    use chrono::{DateTime, Utc};
+    use color_eyre::eyre::eyre;
+    use std::{
+        collections::{BTreeMap, HashMap},
+        sync::Mutex,
+    };
+
+    use super::GithubClient;
+    use crate::{
+        gh_api::RateLimit,
+        gh_cli::{
+            ActionsBilling, ActionsSetting, Annotation, ApiCall, Artifact, CancelFlag,
+            CheckAnnotations, CommitStatus, EnvironmentStatus, GitRef, GithubJob, GithubRunner,
+            GithubStep, GithubWorkflow, GithubWorkflowRun, HistoryRun, JobLog, LogProgress,
+            RefKind, RepoInfo, RunDetails, WorkflowData, WorkflowFileDiff, WorkflowHistoryPage,
+        },
+    };
+
+    /// The repository the fixtures belong to.
+    pub const REPO: &str = "octocat/hello";
+
+    /// Serves `data` as the workflow data of [`REPO`] and fails every other request.
+    #[derive(Debug)]
+    pub struct FixtureClient {
+        repos: Vec<RepoInfo>,
+        data: WorkflowData,
+        branch: Mutex<Option<String>>,
+        /// The logs served, by job id.
+        logs: BTreeMap<u64, String>,
+        /// The annotations served, by job id.
+        annotations: BTreeMap<u64, Vec<Annotation>>,
+        /// The annotations of the checks of other apps served, by SHA.
+        check_annotations: BTreeMap<String, CheckAnnotations>,
+        /// The installed `gh` extensions, by name, with the output of each of their commands.
+        extensions: BTreeMap<String, String>,
+        /// The paths of the files changed by commits, by SHA.
+        changed_paths: BTreeMap<String, Vec<String>>,
+        /// The artifacts served, by run id.
+        artifacts: BTreeMap<u64, Vec<Artifact>>,
+        /// The JUnit reports served, by run id.
+        test_reports: BTreeMap<u64, Vec<String>>,
+    }
+
+    impl FixtureClient {
+        pub fn new(data: WorkflowData) -> Self {
+            Self {
+                repos: vec![REPO.parse().expect("valid repository")],
+                data,
+                branch: Mutex::new(None),
+                logs: BTreeMap::new(),
+                annotations: BTreeMap::new(),
+                check_annotations: BTreeMap::new(),
+                extensions: BTreeMap::new(),
+                changed_paths: BTreeMap::new(),
+                artifacts: BTreeMap::new(),
+                test_reports: BTreeMap::new(),
+            }
+        }
+
+        /// Serves `log` as the log of the job `job_id`.
+        pub fn with_log(mut self, job_id: u64, log: impl Into<String>) -> Self {
+            self.logs.insert(job_id, log.into());
+            self
+        }
+
+        /// Serves `annotations` as the annotations of the job `job_id`.
+        pub fn with_annotations(mut self, job_id: u64, annotations: Vec<Annotation>) -> Self {
+            self.annotations.insert(job_id, annotations);
+            self
+        }
+
+        /// Serves `annotations` as those of the checks of other apps on the commit `sha`.
+        pub fn with_check_annotations(mut self, sha: &str, annotations: Vec<Annotation>) -> Self {
+            self.check_annotations
+                .entry(sha.to_string())
+                .or_default()
+                .annotations
+                .extend(annotations);
+            self
+        }
+
+        /// Fails to serve the annotations of one of the checks of other apps on the commit
+        /// `sha` with `error`, those of the others being served.
+        pub fn with_check_error(mut self, sha: &str, error: &str) -> Self {
+            self.check_annotations
+                .entry(sha.to_string())
+                .or_default()
+                .errors
+                .push(error.to_string());
+            self
+        }
+
+        /// Serves `paths` as the paths of the files the commit `sha` changed.
+        pub fn with_changed_paths(mut self, sha: &str, paths: &[&str]) -> Self {
+            let paths = paths.iter().map(|path| path.to_string()).collect();
+            self.changed_paths.insert(sha.to_string(), paths);
+            self
+        }
+
+        /// Serves `artifacts` as the artifacts of the run `run_id`.
+        pub fn with_artifacts(mut self, run_id: u64, artifacts: Vec<Artifact>) -> Self {
+            self.artifacts.insert(run_id, artifacts);
+            self
+        }
+
+        /// Serves `xml` as a JUnit report of the run `run_id`.
+        pub fn with_test_report(mut self, run_id: u64, xml: impl Into<String>) -> Self {
+            self.test_reports
+                .entry(run_id)
+                .or_default()
+                .push(xml.into());
+            self
+        }
+
+        /// Has the `gh` extension `name` installed, its commands printing `output`.
+        pub fn with_extension(mut self, name: &str, output: impl Into<String>) -> Self {
+            self.extensions.insert(name.to_string(), output.into());
+            self
+        }
+
+        /// Monitors `repos`, given as `owner/name`, instead of [`REPO`] alone.
+        pub fn with_repos(mut self, repos: &[&str]) -> Self {
+            self.repos = repos
+                .iter()
+                .map(|repo| repo.parse().expect("valid repository"))
+                .collect();
+            self
+        }
+    }
+
+    fn unavailable<T>() -> color_eyre::Result<T> {
+        Err(eyre!("Not available in fixtures"))
+    }
+
+    impl GithubClient for FixtureClient {
+        fn repos(&self) -> &[RepoInfo] {
+            &self.repos
+        }
+
+        fn current_branch(&self) -> &str {
+            "main"
+        }
+
+        fn actor(&self) -> Option<&str> {
+            None
+        }
+
+        fn branch(&self) -> Option<String> {
+            self.branch.lock().ok().and_then(|branch| branch.clone())
+        }
+
+        fn set_branch(&self, branch: Option<String>) {
+            if let Ok(mut current) = self.branch.lock() {
+                *current = branch;
+            }
+        }
+
+        fn set_followed_repos(&self, _repos: &[String]) {}
+
+        fn remember_fetched_runs(&self, _runs: HashMap<u64, String>) {}
+
+        fn compatibility_notes(&self) -> &[String] {
+            &[]
+        }
+
+        fn startup_warnings(&self) -> &[String] {
+            &[]
+        }
+
+        fn api_calls(&self) -> Vec<ApiCall> {
+            Vec::new()
+        }
+
+        fn rate_limit(&self) -> Option<RateLimit> {
+            None
+        }
+
+        fn log_progress(&self, _job_id: u64) -> Option<LogProgress> {
+            None
+        }
+
+        fn is_offline(&self) -> bool {
+            false
+        }
+
+        fn check_connectivity(&self) -> bool {
+            true
+        }
+
+        fn diagnose_connectivity(&self) -> Result<(), String> {
+            Ok(())
+        }
+
+        fn stop(&self) {}
+
+        fn kill_running_commands(&self) {}
+
+        fn fetch_github_workflow_data(
+            &self,
+            _cancel: &CancelFlag,
+        ) -> color_eyre::Result<WorkflowData> {
+            let Some(branch) = self.branch() else {
+                return Ok(self.data.clone());
+            };
+            Ok(WorkflowData {
+                runs: self
+                    .data
+                    .runs
+                    .iter()
+                    .filter(|run| run.head_branch == branch)
+                    .cloned()
+                    .collect(),
+                jobs: self
+                    .data
+                    .jobs
+                    .iter()
+                    .filter(|job| job.head_branch == branch)
+                    .cloned()
+                    .collect(),
+                unchanged_runs: Vec::new(),
+                warnings: Vec::new(),
+                fetched_runs: HashMap::new(),
+            })
+        }
+
+        fn fetch_runners(&self) -> color_eyre::Result<Vec<GithubRunner>> {
+            Ok(Vec::new())
+        }
+
+        fn fetch_branch_commits(&self) -> color_eyre::Result<Vec<CommitStatus>> {
+            Ok(Vec::new())
+        }
+
+        fn expand_run(&self, run: &GithubWorkflowRun) -> color_eyre::Result<Vec<GithubJob>> {
+            Ok(self
+                .data
+                .jobs
+                .iter()
+                .filter(|job| job.run_id == run.id)
+                .cloned()
+                .collect())
+        }
+
+        fn is_expanded(&self, _run_id: u64) -> bool {
+            false
+        }
+
+        fn fetch_run(&self, _repo: &str, run_id: u64) -> color_eyre::Result<RunDetails> {
+            let run = self.data.runs.iter().find(|run| run.id == run_id);
+            match run {
+                Some(run) => Ok(RunDetails {
+                    run: run.clone(),
+                    jobs: self.expand_run(run)?,
+                }),
+                None => unavailable(),
+            }
+        }
+
+        fn fetch_runs_since(
+            &self,
+            _repo_info: &RepoInfo,
+            _since: DateTime<Utc>,
+        ) -> color_eyre::Result<Vec<RunDetails>> {
+            unavailable()
+        }
+
+        fn fetch_refs(&self, _repo_info: &RepoInfo) -> color_eyre::Result<Vec<GitRef>> {
+            unavailable()
+        }
+
+        fn fetch_branches(&self, _repo_info: &RepoInfo) -> color_eyre::Result<Vec<String>> {
+            let mut branches: Vec<String> = self
+                .data
+                .runs
+                .iter()
+                .map(|run| run.head_branch.clone())
+                .collect();
+            branches.sort();
+            branches.dedup();
+            Ok(branches)
+        }
+
+        fn fetch_workflows(
+            &self,
+            _repo_info: &RepoInfo,
+        ) -> color_eyre::Result<Vec<GithubWorkflow>> {
+            // The workflows of the runs served, numbered in order of appearance
+            let mut workflows: Vec<GithubWorkflow> = Vec::new();
+            for run in &self.data.runs {
+                if !workflows.iter().any(|workflow| workflow.path == run.path) {
+                    workflows.push(GithubWorkflow {
+                        id: workflows.len() as u64 + 1,
+                        name: run.name.clone(),
+                        path: run.path.clone(),
+                        state: "active".to_string(),
+                    });
+                }
+            }
+            Ok(workflows)
+        }
+
+        fn dispatch_workflow(
+            &self,
+            _repo_info: &RepoInfo,
+            _workflow_id: u64,
+            _git_ref: &str,
+            _inputs: &BTreeMap<String, String>,
+        ) -> color_eyre::Result<()> {
+            unavailable()
+        }
+
+        fn rerun_job(&self, _job: &GithubJob) -> color_eyre::Result<()> {
+            unavailable()
+        }
+
+        fn rerun_run(&self, _repo: &str, _run_id: u64) -> color_eyre::Result<()> {
+            unavailable()
+        }
+
+        fn rerun_failed_jobs(&self, _repo: &str, _run_id: u64) -> color_eyre::Result<()> {
+            unavailable()
+        }
+
+        fn cancel_run(&self, _repo: &str, _run_id: u64) -> color_eyre::Result<()> {
+            Ok(())
+        }
+
+        fn fetch_actions_billing(&self, _owner: &str) -> color_eyre::Result<ActionsBilling> {
+            unavailable()
+        }
+
+        fn fetch_extensions(&self) -> color_eyre::Result<Vec<String>> {
+            Ok(self.extensions.keys().cloned().collect())
+        }
+
+        fn run_extension(&self, args: &[String]) -> color_eyre::Result<String> {
+            match args.first().and_then(|name| self.extensions.get(name)) {
+                Some(output) => Ok(output.clone()),
+                None => unavailable(),
+            }
+        }
+
+        fn delete_run(&self, _repo: &str, _run_id: u64) -> color_eyre::Result<()> {
+            unavailable()
+        }
+
+        fn delete_runs_before(
+            &self,
+            _repo: &str,
+            _path: &str,
+            _before: DateTime<Utc>,
+        ) -> color_eyre::Result<usize> {
+            unavailable()
+        }
+
+        fn approve_pending_deployments(
+            &self,
+            _repo: &str,
+            _run_id: u64,
+        ) -> color_eyre::Result<Vec<String>> {
+            unavailable()
+        }
+
+        fn fetch_job_log(&self, job: &GithubJob, tail: Option<u64>) -> color_eyre::Result<JobLog> {
+            let Some(log) = self.logs.get(&job.id) else {
+                return unavailable();
+            };
+            let total_bytes = log.len() as u64;
+            let start = tail.map_or(0, |tail| total_bytes.saturating_sub(tail) as usize);
+            Ok(JobLog::new(
+                &log.as_bytes()[start..],
+                Some(total_bytes),
+                start > 0,
+            ))
+        }
+
+        fn download_job_log(&self, _repo: &str, job_id: u64) -> color_eyre::Result<Vec<u8>> {
+            match self.logs.get(&job_id) {
+                Some(log) => Ok(log.as_bytes().to_vec()),
+                None => unavailable(),
+            }
+        }
+
+        fn download_run_logs(&self, _repo: &str, _run_id: u64) -> color_eyre::Result<Vec<u8>> {
+            unavailable()
+        }
+
+        fn fetch_job_summary(&self, _job: &GithubJob) -> color_eyre::Result<String> {
+            Ok(String::new())
+        }
+
+        fn fetch_raw_json(&self, job: &GithubJob) -> color_eyre::Result<String> {
+            Ok(format!(
+                "// GET /repos/{}/actions/jobs/{}\n{}",
+                job.repo,
+                job.id,
+                serde_json::to_string_pretty(job)?
+            ))
+        }
+
+        fn fetch_workflow_history(
+            &self,
+            repo: &str,
+            path: &str,
+            page: u64,
+        ) -> color_eyre::Result<WorkflowHistoryPage> {
+            // Every run of the workflow fits on the first page
+            let runs: Vec<HistoryRun> = self
+                .data
+                .runs
+                .iter()
+                .filter(|run| run.repo == repo && run.path == path && page == 1)
+                .map(|run| HistoryRun {
+                    run: run.clone(),
+                    run_number: run.id,
+                    started_at: None,
+                })
+                .collect();
+            Ok(WorkflowHistoryPage {
+                total_count: runs.len() as u64,
+                runs,
+            })
+        }
+
+        fn fetch_repo_history(
+            &self,
+            repo: &str,
+            page: u64,
+        ) -> color_eyre::Result<WorkflowHistoryPage> {
+            // Every completed run fits on the first page
+            let runs: Vec<HistoryRun> = self
+                .data
+                .runs
+                .iter()
+                .filter(|run| run.repo == repo && run.status == "completed" && page == 1)
+                .map(|run| HistoryRun {
+                    run: run.clone(),
+                    run_number: run.id,
+                    started_at: None,
+                })
+                .collect();
+            Ok(WorkflowHistoryPage {
+                total_count: runs.len() as u64,
+                runs,
+            })
+        }
+
+        fn fetch_test_reports(&self, _repo: &str, run_id: u64) -> color_eyre::Result<Vec<String>> {
+            Ok(self.test_reports.get(&run_id).cloned().unwrap_or_default())
+        }
+
+        fn fetch_actions_settings(
+            &self,
+            _repo_info: &RepoInfo,
+        ) -> color_eyre::Result<Vec<ActionsSetting>> {
+            unavailable()
+        }
+
+        fn fetch_environment_statuses(
+            &self,
+            _repo_info: &RepoInfo,
+        ) -> color_eyre::Result<Vec<EnvironmentStatus>> {
+            unavailable()
+        }
+
+        fn fetch_job_environment(&self, _job: &GithubJob) -> color_eyre::Result<Option<String>> {
+            Ok(None)
+        }
+
+        fn fetch_ref_kind(&self, _repo: &str, _name: &str) -> color_eyre::Result<RefKind> {
+            Ok(RefKind::Branch)
+        }
+
+        fn fetch_annotations(&self, job: &GithubJob) -> color_eyre::Result<Vec<Annotation>> {
+            Ok(self.annotations.get(&job.id).cloned().unwrap_or_default())
+        }
+
+        fn fetch_check_annotations(
+            &self,
+            _repo: &str,
+            head_sha: &str,
+        ) -> color_eyre::Result<CheckAnnotations> {
+            Ok(self
+                .check_annotations
+                .get(head_sha)
+                .cloned()
+                .unwrap_or_default())
+        }
+
+        fn fetch_job_run_id(&self, _repo: &str, _job_id: u64) -> color_eyre::Result<u64> {
+            unavailable()
+        }
+
+        fn fetch_pull_request_run_id(&self, _repo: &str, _number: u64) -> color_eyre::Result<u64> {
+            unavailable()
+        }
+
+        fn fetch_workflow_file_diff(
+            &self,
+            _earlier: &GithubJob,
+            _later: &GithubJob,
+        ) -> color_eyre::Result<WorkflowFileDiff> {
+            unavailable()
+        }
+
+        fn fetch_changed_paths(&self, _repo: &str, sha: &str) -> color_eyre::Result<Vec<String>> {
+            self.changed_paths
+                .get(sha)
+                .cloned()
+                .ok_or_else(|| eyre!("No changed paths for commit {}", sha))
+        }
+
+        fn fetch_artifacts(&self, _repo: &str, run_id: u64) -> color_eyre::Result<Vec<Artifact>> {
+            Ok(self.artifacts.get(&run_id).cloned().unwrap_or_default())
+        }
+
+        fn fetch_retention_days(&self, _repo: &str) -> color_eyre::Result<u64> {
+            unavailable()
+        }
+    }
+
+    /// A time on the fixed day the fixtures happen on.
+    pub fn at(time: &str) -> DateTime<Utc> {
+        format!("2026-01-05T{}Z", time).parse().expect("valid time")
+    }
+
+    /// A run of the `CI` workflow of [`REPO`] on `main`.
+    pub fn run(id: u64, status: &str, conclusion: Option<&str>) -> GithubWorkflowRun {
+        GithubWorkflowRun {
+            id,
+            name: "CI".to_string(),
+            actor_login: "octocat".to_string(),
+            head_branch: "main".to_string(),
+            event: "push".to_string(),
+            repo: REPO.to_string(),
+            status: status.to_string(),
+            conclusion: conclusion.map(str::to_string),
+            updated_at: "2026-01-05T10:10:00Z".to_string(),
+            html_url: format!("https://github.com/{}/actions/runs/{}", REPO, id),
+            pull_request: None,
+            path: ".github/workflows/ci.yml".to_string(),
+            head_sha: format!("{:0<40}", format!("c0ffee{}", id)),
+            commit_title: format!("Change number {}", id),
+            commit_message: format!("Change number {}\n\nWith a longer description.", id),
+            commit_author: "Mona Lisa".to_string(),
+            total_jobs: None,
+            calls_workflows: false,
+        }
+    }
+
+    /// A job of `run` started at `started_at` that ran for `minutes` if it completed, with a
+    /// checkout and a test step.
+    pub fn job(
+        id: u64,
+        name: &str,
+        run: &GithubWorkflowRun,
+        started_at: &str,
+        minutes: Option<i64>,
+    ) -> GithubJob {
+        let started_at = at(started_at);
+        let completed_at = minutes.map(|minutes| started_at + chrono::TimeDelta::minutes(minutes));
+        let step = |number: u64, name: &str| GithubStep {
+            number,
+            name: name.to_string(),
+            status: run.status.clone(),
+            conclusion: run.conclusion.clone(),
+            started_at: Some(started_at),
+            completed_at,
+        };
+        GithubJob {
+            id,
+            name: name.to_string(),
+            workflow_name: run.name.clone(),
+            run_id: run.id,
+            repo: run.repo.clone(),
+            run_url: run.html_url.clone(),
+            actor_login: run.actor_login.clone(),
+            head_branch: run.head_branch.clone(),
+            head_sha: run.head_sha.clone(),
+            commit_message: run.commit_message.clone(),
+            commit_author: run.commit_author.clone(),
+            event: run.event.clone(),
+            status: run.status.clone(),
+            conclusion: run.conclusion.clone(),
+            started_at,
+            completed_at,
+            html_url: format!("{}/job/{}", run.html_url, id),
+            steps: vec![step(1, "Checkout"), step(2, "Test")],
+            labels: vec!["ubuntu-latest".to_string()],
+            pull_request: None,
+            called_workflow: None,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use crate::event::AppEvent;
+
+/// The commands of the command palette opened with `:`, each running the action it names as
+/// if its key was pressed, in the order they are listed before anything is typed.
+pub const COMMANDS: &[(&str, AppEvent)] = &[
+    ("Open on GitHub", AppEvent::OpenGitHub),
+    ("Show the details of the job", AppEvent::ToggleDetails),
+    ("Rerun the run", AppEvent::RerunRun),
+    (
+        "Rerun the failed jobs of the run",
+        AppEvent::RerunFailedJobs,
+    ),
+    ("Cancel the run", AppEvent::CancelRun),
+    ("Watch the run", AppEvent::WatchRun),
+    ("Refresh", AppEvent::Refresh),
+    ("Filter the jobs", AppEvent::OpenFilter),
+    ("Switch branch", AppEvent::OpenBranchPicker),
+    ("Switch to the next repository", AppEvent::NextRepo),
+    ("Switch to the previous repository", AppEvent::PrevRepo),
+    ("Change the theme", AppEvent::CycleTheme),
+    ("Change the order of the jobs", AppEvent::CycleSort),
+    ("Dispatch a workflow", AppEvent::OpenDispatch),
+    (
+        "Approve the deployments of the run",
+        AppEvent::ApproveDeployment,
+    ),
+    ("Delete the run", AppEvent::DeleteRun),
+    ("Open the pull request", AppEvent::OpenPullRequest),
+    ("Open the called workflow", AppEvent::OpenCalledWorkflow),
+    (
+        "Open the link on the clipboard",
+        AppEvent::OpenClipboardLink,
+    ),
+    ("Open the file of an annotation", AppEvent::OpenAnnotation),
+    ("Show the links of the job", AppEvent::ToggleLinks),
+    ("Show the summary of the job", AppEvent::OpenJobSummary),
+    ("Inspect the JSON of the job", AppEvent::InspectJson),
+    ("Load the full log", AppEvent::LoadFullLog),
+    ("Save the log of the job", AppEvent::SaveLog),
+    ("Save the logs of the run", AppEvent::SaveRunLogs),
+    ("Compare logs", AppEvent::CompareLogs),
+    ("Triage the failures", AppEvent::OpenTriage),
+    ("Copy the commit SHA", AppEvent::CopyCommitSha),
+    ("Copy...", AppEvent::OpenYankMenu),
+    ("Pin the job", AppEvent::TogglePin),
+    ("Mute the workflow", AppEvent::ToggleWorkflowMute),
+    (
+        "Show the history of the workflow",
+        AppEvent::ToggleWorkflowHistory,
+    ),
+    ("Show the suspected flaky tests", AppEvent::ToggleFlakyTests),
+    ("Backfill the run history", AppEvent::ToggleBackfill),
+    ("Show the run view", AppEvent::ToggleRunView),
+    (
+        "Show the overview of the repositories",
+        AppEvent::ToggleOverview,
+    ),
+    ("Show the deployments", AppEvent::ToggleDeployView),
+    ("Show the timeline", AppEvent::ToggleTimeline),
+    ("Show the runner usage", AppEvent::ToggleUsageView),
+    ("Pick the workflows shown", AppEvent::ToggleWorkflowSidebar),
+    (
+        "Show the jobs that concluded otherwise",
+        AppEvent::ToggleOtherJobs,
+    ),
+    ("Show only the recent failures", AppEvent::ToggleRecentOnly),
+    ("Hide the scheduled runs", AppEvent::ToggleScheduled),
+    ("Clear all filters", AppEvent::ClearFilters),
+    ("Restore the previous filters", AppEvent::RestoreFilters),
+    ("Show the previous refresh", AppEvent::PreviousRefresh),
+    ("Show the next refresh", AppEvent::NextRefresh),
+    (
+        "Switch between relative and absolute timestamps",
+        AppEvent::ToggleTimestamps,
+    ),
+    ("Export the jobs", AppEvent::OpenExport),
+    ("Show the Actions settings", AppEvent::ShowActionsSettings),
+    ("Show the status badges", AppEvent::OpenBadges),
+    ("Run a gh extension", AppEvent::OpenExtensions),
+    ("Show the API inspector", AppEvent::ToggleApiInspector),
+    ("Quit", AppEvent::Quit),
+];
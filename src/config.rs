@@ -0,0 +1,239 @@
+use clap::ValueEnum;
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{fs, io, path::PathBuf};
+
+use crate::{
+    gh_cli::{GithubJob, GithubWorkflowRun},
+    job_sort::JobSort,
+    keymap::KeymapConfig,
+    theme::ThemeConfig,
+};
+
+/// Settings read from `config.json` in the lazyactions config directory, e.g.
+/// `~/.config/lazyactions/config.json`. Every setting is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Whether the first job of each new run of the current branch is selected, as with
+    /// `--follow-new-runs`.
+    pub follow_new_runs: bool,
+    /// Seconds between two refreshes of the runs, as with `--interval`.
+    pub interval: Option<u64>,
+    /// How many hours back concluded failures are listed while older ones are hidden with
+    /// `z`, [`DEFAULT_RECENT_HOURS`] unless set.
+    pub recent_hours: Option<u64>,
+    /// Whether a crash report is written when lazyactions panics or stops on an error.
+    pub crash_reports: bool,
+    /// The order the jobs of each group of the columns are listed in, cycled with `s`.
+    pub sort: JobSort,
+    /// Whether the details panel tells when a job started and finished as local ISO 8601
+    /// timestamps rather than how long ago, toggled with `g t`.
+    pub absolute_timestamps: bool,
+    pub notifications: NotificationConfig,
+    pub open_on_failure: OpenOnFailureConfig,
+    pub branches: BranchesConfig,
+    pub keymap: KeymapConfig,
+    pub theme: ThemeConfig,
+    /// Where the config file is, `None` when there is no config directory or the config
+    /// isn't read from a file, e.g. in tests.
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+/// How many hours back concluded failures are listed while older ones are hidden, unless the
+/// config file says otherwise.
+pub const DEFAULT_RECENT_HOURS: u64 = 24;
+
+impl Config {
+    /// How many hours back concluded failures are listed while older ones are hidden.
+    pub fn recent_hours(&self) -> u64 {
+        self.recent_hours.unwrap_or(DEFAULT_RECENT_HOURS)
+    }
+}
+
+/// Settings for desktop notifications.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationConfig {
+    /// Whether notifications are sent, as with `--notify`.
+    pub enabled: bool,
+    /// The outcomes of a run that are notified.
+    pub on: Vec<RunOutcome>,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            on: vec![RunOutcome::Success, RunOutcome::Failure],
+        }
+    }
+}
+
+/// Settings for opening the browser when a watched run fails.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OpenOnFailureConfig {
+    /// Whether the browser is opened when a run followed with `w` or `--watch` fails.
+    pub enabled: bool,
+    /// The page to open instead of the run, with `{repo}`, `{run_id}`, `{branch}`, `{sha}`
+    /// and `{url}` replaced by those of the run.
+    pub url: Option<String>,
+}
+
+impl OpenOnFailureConfig {
+    /// The page to open for a run that failed.
+    pub fn url_for(&self, run: &GithubWorkflowRun) -> String {
+        match &self.url {
+            Some(template) => template
+                .replace("{repo}", &run.repo)
+                .replace("{run_id}", &run.id.to_string())
+                .replace("{branch}", &run.head_branch)
+                .replace("{sha}", &run.head_sha)
+                .replace("{url}", &run.html_url),
+            None => run.html_url.clone(),
+        }
+    }
+}
+
+/// The branches whose failures matter most, which are listed first in the failure column.
+/// Patterns match whole branch names, with `*` standing for any run of characters.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BranchesConfig {
+    /// Patterns of the default branch.
+    pub default: Vec<String>,
+    /// Patterns of the release branches.
+    pub release: Vec<String>,
+}
+
+impl Default for BranchesConfig {
+    fn default() -> Self {
+        Self {
+            default: vec!["main".to_string(), "master".to_string()],
+            release: vec!["release/*".to_string(), "release-*".to_string()],
+        }
+    }
+}
+
+impl BranchesConfig {
+    /// The kind of branch a job ran on.
+    pub fn kind(&self, job: &GithubJob) -> BranchKind {
+        let matches = |patterns: &[String]| {
+            patterns
+                .iter()
+                .any(|pattern| matches_pattern(pattern, &job.head_branch))
+        };
+        if matches(&self.default) {
+            BranchKind::Default
+        } else if matches(&self.release) {
+            BranchKind::Release
+        } else if job.pull_request.is_some() || job.event.starts_with("pull_request") {
+            BranchKind::PullRequest
+        } else {
+            BranchKind::Other
+        }
+    }
+}
+
+/// The kinds of branches, in the order their failures are listed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BranchKind {
+    Default,
+    Release,
+    PullRequest,
+    Other,
+}
+
+/// Whether `text` matches `pattern` as a whole, with `*` in the pattern matching any run of
+/// characters.
+pub fn matches_pattern(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            // Try every way of letting the `*` take a part of the text
+            text.char_indices()
+                .map(|(index, _)| index)
+                .chain([text.len()])
+                .any(|index| matches_pattern(rest, &text[index..]))
+        }
+    }
+}
+
+/// How a workflow run concluded, matching the Success and Failure columns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum RunOutcome {
+    Success,
+    Failure,
+}
+
+impl RunOutcome {
+    /// The outcome of a run with the given conclusion, if it is one that is notified.
+    pub fn from_conclusion(conclusion: Option<&str>) -> Option<Self> {
+        match conclusion? {
+            "success" => Some(RunOutcome::Success),
+            "failure" | "timed_out" | "startup_failure" => Some(RunOutcome::Failure),
+            _ => None,
+        }
+    }
+}
+
+impl Config {
+    /// Loads the config file, using the defaults when there is none.
+    pub fn load() -> color_eyre::Result<Self> {
+        match config_path() {
+            Some(path) => Self::load_from(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Loads the config file at `path`, using the defaults when there is none.
+    pub fn load_from(path: PathBuf) -> color_eyre::Result<Self> {
+        let config: Self = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .wrap_err(format!("Failed to parse config file {}", path.display()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                return Err(e).wrap_err(format!("Failed to read config file {}", path.display()));
+            }
+        };
+        Ok(Self {
+            path: Some(path),
+            ..config
+        })
+    }
+
+    /// Sets `key` to `value` in the config file, keeping the other settings as they are. A
+    /// config not read from a file isn't saved.
+    pub fn save_setting(&self, key: &str, value: impl Serialize) -> color_eyre::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut settings: Map<String, Value> = match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .wrap_err(format!("Failed to parse config file {}", path.display()))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Map::new(),
+            Err(e) => {
+                return Err(e).wrap_err(format!("Failed to read config file {}", path.display()));
+            }
+        };
+        settings.insert(key.to_string(), serde_json::to_value(value)?);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err(format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(&settings)?;
+        fs::write(path, json + "\n").wrap_err(format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Where the config file is read from.
+pub fn config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("lazyactions").join("config.json"))
+}
@@ -0,0 +1,247 @@
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{WrapErr, eyre};
+use ratatui::crossterm::{event::Event as CrosstermEvent, terminal};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    backtrace::Backtrace,
+    collections::VecDeque,
+    env,
+    fmt::{self, Write as _},
+    fs,
+    panic::{self, PanicHookInfo},
+    path::PathBuf,
+    sync::{Mutex, OnceLock},
+};
+
+use crate::{config, event::Event};
+
+/// How many of the last events handled a crash report lists.
+const RECENT_EVENTS: usize = 50;
+/// The keys of the config file whose values are left out of crash reports, matched within
+/// the lowercased key.
+const REDACTED_KEYS: &[&str] = &["token", "secret", "password", "auth", "url"];
+const REDACTED: &str = "<redacted>";
+
+/// What a crash report says about a panic or a fatal error, saved as JSON so that a bug
+/// report can be made from it without reproducing the crash.
+#[derive(Debug, Serialize)]
+pub struct CrashReport {
+    pub version: &'static str,
+    pub time: DateTime<Utc>,
+    pub os: &'static str,
+    pub arch: &'static str,
+    pub terminal: TerminalInfo,
+    /// The panic message or the chain of errors.
+    pub error: String,
+    /// Where the code panicked, `None` for an error.
+    pub location: Option<String>,
+    pub backtrace: String,
+    /// The last events handled before the crash, oldest first.
+    pub recent_events: Vec<String>,
+    /// The config file with the values of secret-looking keys and URLs left out, `None` when
+    /// there is none.
+    pub config: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TerminalInfo {
+    pub term: Option<String>,
+    pub term_program: Option<String>,
+    pub colorterm: Option<String>,
+    /// The size in columns and rows.
+    pub size: Option<(u16, u16)>,
+}
+
+/// The events recorded for the next crash report, `None` until crash reports are enabled.
+static RECORDER: OnceLock<Mutex<VecDeque<String>>> = OnceLock::new();
+
+/// Enables crash reports: the events handled are recorded from now on, and a panic writes a
+/// report before the usual message, followed by where the report is.
+pub fn enable() {
+    if RECORDER.set(Mutex::new(VecDeque::new())).is_err() {
+        return;
+    }
+    let previous_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let report = CrashReport::of_panic(info);
+        previous_hook(info);
+        match report.save() {
+            Ok(path) => eprintln!("A crash report was written to {}", path.display()),
+            Err(e) => eprintln!("Could not write a crash report: {:?}", e),
+        }
+    }));
+}
+
+/// Whether crash reports are enabled.
+pub fn is_enabled() -> bool {
+    RECORDER.get().is_some()
+}
+
+/// Records an event for the next crash report, if they are enabled. Only the kinds of the
+/// events are kept, along with the keys pressed and the actions they triggered.
+pub fn record(event: &Event) {
+    let Some(recorder) = RECORDER.get() else {
+        return;
+    };
+    let description = match event {
+        Event::App(app_event) => format!("App({:?})", app_event),
+        Event::Crossterm(CrosstermEvent::Key(key)) => {
+            format!("Key({}, {:?})", key.code, key.modifiers)
+        }
+        Event::Crossterm(CrosstermEvent::Resize(columns, rows)) => {
+            format!("Resize({}, {})", columns, rows)
+        }
+        Event::Polled(event) => variant_name(event),
+        Event::Outcome(event) => variant_name(event),
+        Event::JobData(event) => variant_name(event),
+        Event::ViewData(event) => variant_name(event),
+        event => variant_name(event),
+    };
+    let Ok(mut events) = recorder.lock() else {
+        return;
+    };
+    if events.len() == RECENT_EVENTS {
+        events.pop_front();
+    }
+    events.push_back(description);
+}
+
+/// The name of the variant of an event, without the data it carries.
+fn variant_name(event: &impl fmt::Debug) -> String {
+    /// Stops formatting at the data of the variant, which can be large, e.g. every job
+    /// fetched.
+    struct VariantName(String);
+    impl fmt::Write for VariantName {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            match s.find(['(', ' ', '{']) {
+                Some(end) => {
+                    self.0.push_str(&s[..end]);
+                    Err(fmt::Error)
+                }
+                None => {
+                    self.0.push_str(s);
+                    Ok(())
+                }
+            }
+        }
+    }
+    let mut name = VariantName(String::new());
+    let _ = write!(name, "{:?}", event);
+    name.0
+}
+
+impl CrashReport {
+    pub fn of_panic(info: &PanicHookInfo) -> Self {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_string());
+        let mut report = Self::new(format!("panicked: {}", message));
+        report.location = info.location().map(ToString::to_string);
+        report
+    }
+
+    pub fn of_error(error: &color_eyre::Report) -> Self {
+        let chain: Vec<String> = error.chain().map(ToString::to_string).collect();
+        Self::new(chain.join(": "))
+    }
+
+    fn new(error: String) -> Self {
+        let var = |name| env::var(name).ok();
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            time: Utc::now(),
+            os: env::consts::OS,
+            arch: env::consts::ARCH,
+            terminal: TerminalInfo {
+                term: var("TERM"),
+                term_program: var("TERM_PROGRAM"),
+                colorterm: var("COLORTERM"),
+                size: terminal::size().ok(),
+            },
+            error,
+            location: None,
+            backtrace: Backtrace::force_capture().to_string(),
+            recent_events: RECORDER
+                .get()
+                .and_then(|recorder| recorder.lock().ok())
+                .map(|events| events.iter().cloned().collect())
+                .unwrap_or_default(),
+            config: config::config_path()
+                .and_then(|path| fs::read_to_string(path).ok())
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .map(|mut config| {
+                    redact(&mut config);
+                    config
+                }),
+        }
+    }
+
+    /// Writes the report to the crash reports directory, returning where.
+    pub fn save(&self) -> color_eyre::Result<PathBuf> {
+        let dir = reports_dir().ok_or_else(|| eyre!("Could not determine the data directory"))?;
+        fs::create_dir_all(&dir).wrap_err(format!("Failed to create {}", dir.display()))?;
+        let path = dir.join(format!("crash-{}.json", self.time.format("%Y%m%d-%H%M%S")));
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(&path, json).wrap_err(format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
+
+/// Leaves out the values of secret-looking keys, and any URL, which may carry a token.
+fn redact(value: &mut Value) {
+    match value {
+        Value::String(s) if s.contains("://") => *s = REDACTED.to_string(),
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        Value::Object(entries) => {
+            for (key, value) in entries {
+                let key = key.to_ascii_lowercase();
+                if REDACTED_KEYS.iter().any(|redacted| key.contains(redacted)) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Where crash reports are written, e.g. `~/.local/share/lazyactions/crashes`, `None` if there
+/// is no data directory to write them in.
+fn reports_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("lazyactions").join("crashes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::PolledEvent;
+
+    #[test]
+    fn reports_leave_out_secrets_and_the_data_of_events() {
+        let mut config = serde_json::json!({
+            "interval": 10,
+            "open_on_failure": { "enabled": true, "url": "https://ci.example.com/{run_id}" },
+            "keymap": { "bindings": { "x": "quit" } },
+            "webhook": { "api_token": "hunter2", "targets": ["https://hooks.example.com/a"] },
+        });
+        redact(&mut config);
+        assert_eq!(
+            config,
+            serde_json::json!({
+                "interval": 10,
+                "open_on_failure": { "enabled": true, "url": REDACTED },
+                "keymap": { "bindings": { "x": "quit" } },
+                "webhook": { "api_token": REDACTED, "targets": [REDACTED] },
+            })
+        );
+
+        let fetched = PolledEvent::GitHubDataFetched(Err("Error fetching GitHub data".to_string()));
+        assert_eq!(variant_name(&fetched), "GitHubDataFetched");
+        assert_eq!(variant_name(&Event::Tick), "Tick");
+    }
+}
@@ -0,0 +1,99 @@
+use color_eyre::eyre::{WrapErr, eyre};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::gh_cli::GithubWorkflow;
+
+/// Maximum number of dispatches kept in the history file.
+const MAX_HISTORY_ENTRIES: usize = 50;
+
+/// A workflow dispatch that was sent successfully.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DispatchRecord {
+    pub repo: String,
+    pub workflow: GithubWorkflow,
+    pub git_ref: String,
+    pub inputs: BTreeMap<String, String>,
+    /// Seconds since the Unix epoch.
+    pub dispatched_at: u64,
+}
+
+impl DispatchRecord {
+    /// Creates a record of a dispatch sent now.
+    pub fn new(
+        repo: String,
+        workflow: GithubWorkflow,
+        git_ref: String,
+        inputs: BTreeMap<String, String>,
+    ) -> Self {
+        Self {
+            repo,
+            workflow,
+            git_ref,
+            inputs,
+            dispatched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+
+    /// The inputs in the `key=value, key=value` form used by the dispatch form.
+    pub fn inputs_string(&self) -> String {
+        self.inputs
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// The local history of workflow dispatches, newest first, persisted as JSON.
+#[derive(Debug, Default)]
+pub struct DispatchHistory {
+    pub entries: Vec<DispatchRecord>,
+}
+
+impl DispatchHistory {
+    /// Loads the history from disk, starting empty if there is none yet or it can't be read.
+    pub fn load() -> Self {
+        let entries = history_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self { entries }
+    }
+
+    /// The dispatches sent to a repository, newest first.
+    pub fn for_repo<'a>(&'a self, repo: &'a str) -> impl Iterator<Item = &'a DispatchRecord> {
+        self.entries
+            .iter()
+            .filter(move |record| record.repo.eq_ignore_ascii_case(repo))
+    }
+
+    /// Adds a dispatch to the front of the history and writes it to disk.
+    pub fn push(&mut self, record: DispatchRecord) -> color_eyre::Result<()> {
+        self.entries.insert(0, record);
+        self.entries.truncate(MAX_HISTORY_ENTRIES);
+        self.save()
+    }
+
+    fn save(&self) -> color_eyre::Result<()> {
+        let path = history_path().ok_or_else(|| eyre!("Could not determine the data directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err(format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(&self.entries)?;
+        fs::write(&path, json).wrap_err(format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Where the dispatch history is stored, e.g. `~/.local/share/lazyactions/dispatch_history.json`.
+fn history_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("lazyactions").join("dispatch_history.json"))
+}
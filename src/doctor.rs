@@ -0,0 +1,454 @@
+use serde::Deserialize;
+use std::{
+    io::IsTerminal,
+    path::PathBuf,
+    process::{Command, ExitCode},
+};
+
+use crate::{
+    config::{self, Config},
+    gh_api::GhApi,
+    gh_cli::{self, Backend, GhCli, RepoInfo},
+    gh_version::{GhFeature, GhVersion},
+    links,
+};
+
+/// The token scope needed to read the runs of private repositories and to rerun, dispatch and
+/// approve them.
+const REQUIRED_SCOPE: &str = "repo";
+
+/// How a check went.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    /// Works, but not as well as it could.
+    Warn,
+    Fail,
+}
+
+/// The result of one check: what was found and, unless it passed, how to fix it.
+#[derive(Debug)]
+struct Check {
+    name: &'static str,
+    outcome: Outcome,
+    found: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, found: impl Into<String>) -> Self {
+        Self {
+            name,
+            outcome: Outcome::Pass,
+            found: found.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: &'static str, found: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            outcome: Outcome::Warn,
+            found: found.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: &'static str, found: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            outcome: Outcome::Fail,
+            found: found.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn print(&self) {
+        let symbol = match self.outcome {
+            Outcome::Pass => "✓",
+            Outcome::Warn => "!",
+            Outcome::Fail => "✗",
+        };
+        println!("{} {:<16} {}", symbol, self.name, self.found);
+        if let Some(fix) = &self.fix {
+            println!("  {:<16} Fix: {}", "", fix);
+        }
+    }
+}
+
+/// The parts of `gh api user` read here.
+#[derive(Debug, Deserialize)]
+struct ApiUser {
+    login: String,
+}
+
+/// Checks everything `lazyactions` relies on, from the GitHub CLI to the terminal, and prints
+/// what passed and how to fix what didn't, failing if anything is broken. The first thing to
+/// run when something doesn't work, and to paste into a bug report.
+pub fn run(repos: Vec<RepoInfo>, backend: Backend) -> ExitCode {
+    println!("lazyactions {}\n", env!("CARGO_PKG_VERSION"));
+    let mut checks = vec![check_config(config::config_path())];
+    let gh_version = match Command::new("gh").arg("--version").output() {
+        Ok(_) => {
+            let version = GhVersion::detect();
+            checks.push(check_gh_version(version));
+            Some(version)
+        }
+        Err(e) => {
+            checks.push(Check::fail(
+                "GitHub CLI",
+                format!("`gh` can't be run ({})", e),
+                "Install it from https://cli.github.com and make sure it is on your PATH.",
+            ));
+            None
+        }
+    };
+    if let Some(version) = gh_version {
+        let authenticated = Command::new("gh")
+            .args(["auth", "status"])
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if authenticated {
+            checks.push(check_token());
+            let (check, repos) = check_repos(repos, version);
+            checks.push(check);
+            checks.push(check_api(repos, backend));
+        } else {
+            checks.push(Check::fail(
+                "Authentication",
+                "`gh auth status` reports no valid login",
+                "Run `gh auth login`, or set GH_TOKEN.",
+            ));
+        }
+    }
+    checks.push(check_terminal());
+    checks.push(check_colors());
+    checks.push(check_clipboard());
+
+    for check in &checks {
+        check.print();
+    }
+    let failed = checks
+        .iter()
+        .filter(|check| check.outcome == Outcome::Fail)
+        .count();
+    match failed {
+        0 => {
+            println!("\nEverything lazyactions needs is in place.");
+            ExitCode::SUCCESS
+        }
+        1 => {
+            println!("\n1 check failed.");
+            ExitCode::FAILURE
+        }
+        failed => {
+            println!("\n{} checks failed.", failed);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Checks that the config file, if any, can be read, as lazyactions won't start otherwise.
+fn check_config(path: Option<PathBuf>) -> Check {
+    let Some(path) = path else {
+        return Check::pass("Config", "no config directory, using the defaults");
+    };
+    if !path.exists() {
+        return Check::pass(
+            "Config",
+            format!("no {}, using the defaults", path.display()),
+        );
+    }
+    match Config::load_from(path.clone()) {
+        Ok(_) => Check::pass("Config", path.display().to_string()),
+        Err(e) => Check::fail(
+            "Config",
+            format!("{:#}", e),
+            format!(
+                "Correct the JSON of {}, or move it away to start with the defaults.",
+                path.display()
+            ),
+        ),
+    }
+}
+
+/// Checks that the GitHub CLI has every feature that is used, `None` being a development
+/// build, which is assumed to.
+fn check_gh_version(version: Option<GhVersion>) -> Check {
+    let Some(version) = version else {
+        return Check::pass("GitHub CLI", "gh of an unknown version, assumed recent");
+    };
+    let missing: Vec<String> = GhFeature::ALL
+        .into_iter()
+        .filter(|&feature| !version.supports(feature))
+        .map(|feature| format!("{} (added in {})", feature.name(), feature.added_in()))
+        .collect();
+    if missing.is_empty() {
+        Check::pass("GitHub CLI", format!("gh {}", version))
+    } else {
+        Check::warn(
+            "GitHub CLI",
+            format!("gh {} lacks {}", version, missing.join(", ")),
+            "Upgrade the GitHub CLI, see https://cli.github.com.",
+        )
+    }
+}
+
+/// Checks who the token belongs to and that it has the scope needed.
+fn check_token() -> Check {
+    let output = match Command::new("gh")
+        .args(["api", "--include", "user"])
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Check::fail(
+                "Token",
+                match first_line(&stderr) {
+                    "" => format!("`gh api user` exited with {}", output.status),
+                    error => format!("`gh api user` failed: {}", error),
+                },
+                "Run `gh auth login` again.",
+            );
+        }
+        Err(e) => {
+            return Check::fail(
+                "Token",
+                format!("`gh api user` can't be run ({})", e),
+                "Check that `gh` works.",
+            );
+        }
+    };
+    let response = String::from_utf8_lossy(&output.stdout);
+    let (headers, body) = split_response(&response);
+    let login = serde_json::from_str::<ApiUser>(body)
+        .map(|user| user.login)
+        .unwrap_or_else(|_| "an unknown user".to_string());
+    match token_scopes(headers) {
+        Some(scopes) if scopes.contains(&REQUIRED_SCOPE) => Check::pass(
+            "Token",
+            format!("logged in as {} with scopes {}", login, scopes.join(", ")),
+        ),
+        Some(scopes) => Check::fail(
+            "Token",
+            format!(
+                "logged in as {} without the `{}` scope (has {})",
+                login,
+                REQUIRED_SCOPE,
+                if scopes.is_empty() {
+                    "none".to_string()
+                } else {
+                    scopes.join(", ")
+                }
+            ),
+            format!("Run `gh auth refresh --scopes {}`.", REQUIRED_SCOPE),
+        ),
+        // Fine-grained tokens and GitHub App tokens have permissions instead of scopes
+        None => Check::warn(
+            "Token",
+            format!("logged in as {} with a token that lists no scopes", login),
+            "Make sure it can read Actions, and write them to rerun, dispatch and approve runs.",
+        ),
+    }
+}
+
+/// The first line of an error, which is all that fits on the line of a check.
+fn first_line(error: &str) -> &str {
+    error.trim().lines().next().unwrap_or_default()
+}
+
+/// Splits the output of `gh api --include` into its headers and its body.
+fn split_response(response: &str) -> (&str, &str) {
+    response
+        .split_once("\r\n\r\n")
+        .or_else(|| response.split_once("\n\n"))
+        .unwrap_or((response, ""))
+}
+
+/// The scopes listed in the `X-OAuth-Scopes` header, `None` if there is no such header.
+fn token_scopes(headers: &str) -> Option<Vec<&str>> {
+    headers.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("x-oauth-scopes").then(|| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|scope| !scope.is_empty())
+                .collect()
+        })
+    })
+}
+
+/// Checks which repositories would be monitored, returning them for the checks that need one.
+fn check_repos(repos: Vec<RepoInfo>, version: Option<GhVersion>) -> (Check, Vec<RepoInfo>) {
+    if !repos.is_empty() {
+        let names: Vec<String> = repos.iter().map(RepoInfo::full_name).collect();
+        return (
+            Check::pass("Repository", format!("{} (from --repo)", names.join(", "))),
+            repos,
+        );
+    }
+    let repo_info = if version.is_none_or(|version| version.supports(GhFeature::RepoViewJson)) {
+        gh_cli::fetch_repo_info()
+    } else {
+        gh_cli::fetch_repo_info_without_json()
+    };
+    match repo_info {
+        Ok(repo_info) => (
+            Check::pass(
+                "Repository",
+                format!("{} (from the current directory)", repo_info.full_name()),
+            ),
+            vec![repo_info],
+        ),
+        Err(_) => (
+            Check::fail(
+                "Repository",
+                "the current directory isn't a clone of a GitHub repository",
+                "Run lazyactions in a clone, or pass --repo OWNER/NAME.",
+            ),
+            Vec::new(),
+        ),
+    }
+}
+
+/// Checks that the GitHub API can be reached the way the app will reach it.
+fn check_api(repos: Vec<RepoInfo>, backend: Backend) -> Check {
+    if backend == Backend::Api
+        && let Err(e) = GhApi::new()
+    {
+        return Check::warn(
+            "GitHub API",
+            format!(
+                "no token for the native client ({}), using `gh api`",
+                first_line(&e.root_cause().to_string())
+            ),
+            "Check that `gh auth token` prints a token, or pass --backend cli.",
+        );
+    }
+    // Without a repository the one of the current directory would be looked up again
+    let repos = if repos.is_empty() {
+        vec![RepoInfo::default()]
+    } else {
+        repos
+    };
+    let gh_cli = GhCli::new(false, false, false, repos, backend);
+    match gh_cli.diagnose_connectivity() {
+        Ok(()) => Check::pass(
+            "GitHub API",
+            match backend {
+                Backend::Api => "reachable with the native client",
+                Backend::Cli => "reachable through `gh api`",
+            },
+        ),
+        Err(problem) => Check::fail("GitHub API", "unreachable", problem),
+    }
+}
+
+/// Checks that the TUI has a terminal to draw in.
+fn check_terminal() -> Check {
+    // The output of the doctor itself may well be piped into a bug report
+    if !std::io::stdin().is_terminal() {
+        return Check::warn(
+            "Terminal",
+            "the input isn't a terminal, so the TUI couldn't read keys",
+            "Run lazyactions in a terminal; only --once, --export and report work without one.",
+        );
+    }
+    match std::env::var("TERM") {
+        Ok(term) if term == "dumb" => Check::fail(
+            "Terminal",
+            "TERM=dumb can't draw the TUI",
+            "Run lazyactions in a full terminal emulator.",
+        ),
+        Ok(term) => Check::pass("Terminal", format!("TERM={}", term)),
+        // Windows terminals don't set TERM
+        Err(_) => Check::pass("Terminal", "a terminal without TERM"),
+    }
+}
+
+/// Checks whether the terminal announces 24-bit color, which the light and solarized themes
+/// use. The dark theme only uses the 16 basic colors.
+fn check_colors() -> Check {
+    let truecolor = std::env::var("COLORTERM")
+        .is_ok_and(|colorterm| matches!(colorterm.as_str(), "truecolor" | "24bit"))
+        || std::env::var_os("WT_SESSION").is_some();
+    if truecolor {
+        Check::pass("Colors", "24-bit color")
+    } else {
+        Check::warn(
+            "Colors",
+            "COLORTERM doesn't announce 24-bit color, the light and solarized themes may look off",
+            "Use the dark theme, or set COLORTERM=truecolor if the terminal supports it.",
+        )
+    }
+}
+
+/// Checks how copying to the clipboard works.
+fn check_clipboard() -> Check {
+    // There is no telling whether the terminal honors OSC 52, only what it takes
+    let tmux = if std::env::var_os("TMUX").is_some() {
+        ", and `set -g set-clipboard on` in tmux"
+    } else {
+        ""
+    };
+    if links::copies_over_osc52() {
+        return Check::warn(
+            "Clipboard",
+            "over SSH, copying asks the local terminal with OSC 52",
+            format!("Make sure the terminal allows OSC 52{}.", tmux),
+        );
+    }
+    match links::clipboard_tool() {
+        Some(program) => Check::pass("Clipboard", format!("copies with {}", program)),
+        None => Check::warn(
+            "Clipboard",
+            "no clipboard tool found, copying asks the terminal with OSC 52",
+            format!(
+                "Install wl-clipboard, xclip or xsel, or make sure the terminal allows OSC 52{}.",
+                tmux
+            ),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_scopes_of_the_token() {
+        let response = "HTTP/2.0 200 OK\r\nContent-Type: application/json\r\n\
+                        X-Oauth-Scopes: gist, read:org, repo, workflow\r\n\r\n{\"login\":\"octocat\"}";
+        let (headers, body) = split_response(response);
+        assert_eq!(body, "{\"login\":\"octocat\"}");
+        assert_eq!(
+            token_scopes(headers),
+            Some(vec!["gist", "read:org", "repo", "workflow"])
+        );
+        assert_eq!(
+            token_scopes("HTTP/2.0 200 OK\r\nX-Oauth-Scopes: "),
+            Some(vec![])
+        );
+        assert_eq!(token_scopes("HTTP/2.0 200 OK"), None);
+    }
+
+    #[test]
+    fn a_malformed_config_fails_with_where_it_broke() {
+        let dir = std::env::temp_dir().join(format!("lazyactions-doctor-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temporary directory");
+        let path = dir.join("config.json");
+        assert_eq!(check_config(Some(path.clone())).outcome, Outcome::Pass);
+
+        std::fs::write(&path, "{\"interval\": 30,\n}").expect("config file");
+        let check = check_config(Some(path.clone()));
+        assert_eq!(check.outcome, Outcome::Fail);
+        assert!(check.found.contains(&path.display().to_string()));
+        assert!(check.found.contains("line 2"), "{}", check.found);
+
+        std::fs::write(&path, "{\"interval\": 30}").expect("config file");
+        assert_eq!(check_config(Some(path)).outcome, Outcome::Pass);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -1,125 +1,828 @@
-use color_eyre::eyre::WrapErr; // `eyre` might not be strictly needed here anymore, but keeping for safety.
+use color_eyre::eyre::{WrapErr, eyre};
 use ratatui::crossterm::event::{self, Event as CrosstermEvent};
+use serde::Deserialize;
 use std::{
-    sync::mpsc,
+    collections::{BTreeSet, HashMap, HashSet},
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
     thread,
     time::{Duration, Instant},
 };
+use tokio::{
+    runtime::{self, Runtime},
+    sync::{Notify, mpsc},
+    task,
+};
 
 // Import the necessary components from the new gh_cli module
-use crate::gh_cli::{GhCli, WorkflowData};
+use crate::{
+    client::GithubClient,
+    dispatch_history::DispatchRecord,
+    flaky_tests::FlakyTestScan,
+    gh_api::RateLimit,
+    gh_cli::{
+        ActionsBilling, ActionsSetting, Annotation, Artifact, CancelFlag, CheckAnnotations,
+        CommitStatus, EnvironmentStatus, GitRef, GithubJob, GithubRunner, GithubWorkflow, JobLog,
+        RefKind, RunDetails, WorkflowData, WorkflowFileDiff, WorkflowHistoryPage,
+    },
+};
+
+/// How often the workflow runs and their jobs are refreshed unless `--interval` or the config
+/// file says otherwise.
+pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(7);
+
+/// How often the screen is redrawn when nothing else happens, keeping elapsed times current.
+const REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often reading the terminal checks whether it was left to a command in the meantime.
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often self-hosted runner status is polled.
+const RUNNER_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often the commit strip of the current branch is refreshed.
+const COMMIT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often the Actions minutes billed to the owners of the repositories are refreshed, as
+/// GitHub only updates them a few times an hour.
+const BILLING_POLL_INTERVAL: Duration = Duration::from_secs(15 * 60);
 
-/// The frequency at which tick events are emitted.
-const TICK_FPS: f64 = 0.15;
+/// How many key presses, ticks and app events can be queued before input waits for the app.
+const UI_CHANNEL_CAPACITY: usize = 256;
+
+/// How many fetch results can be queued before fetches wait for the app to catch up.
+const FETCH_CHANNEL_CAPACITY: usize = 16;
+
+/// How often GitHub is checked for being reachable again while it is offline.
+const OFFLINE_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long quitting waits for fetches in flight to give up before killing their `gh`
+/// processes.
+const QUIT_GRACE_PERIOD: Duration = Duration::from_millis(300);
+
+/// Below this percentage of the rate limit budget left, polling slows down to stretch the rest
+/// until the budget is refilled.
+const RATE_LIMIT_LOW_PERCENT: u64 = 10;
+
+/// Below this percentage of the rate limit budget left, polling pauses until the budget is
+/// refilled, keeping the rest for requests made on demand.
+const RATE_LIMIT_RESERVE_PERCENT: u64 = 1;
+
+/// How many times longer the poll intervals get while the rate limit budget is low.
+const RATE_LIMIT_SLOWDOWN: u32 = 4;
+
+/// How polling adapts to the rate limit budget left.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Throttle {
+    /// Every data source is polled at its usual interval.
+    None,
+    /// The budget is running low, so data sources are polled less often.
+    Slowed,
+    /// The budget is nearly exhausted, so polling waits for it to be refilled.
+    Paused { resets_in: Duration },
+}
+
+impl Throttle {
+    /// How polling should adapt to a rate limit budget, if one is known.
+    pub fn for_rate_limit(rate_limit: Option<&RateLimit>) -> Self {
+        let Some(rate_limit) = rate_limit else {
+            return Throttle::None;
+        };
+        let resets_in = rate_limit.resets_in();
+        // A budget that has already been refilled is not low anymore
+        if resets_in.is_zero() {
+            return Throttle::None;
+        }
+        let percent_left = rate_limit.remaining * 100 / rate_limit.limit.max(1);
+        if percent_left < RATE_LIMIT_RESERVE_PERCENT {
+            Throttle::Paused { resets_in }
+        } else if percent_left < RATE_LIMIT_LOW_PERCENT {
+            Throttle::Slowed
+        } else {
+            Throttle::None
+        }
+    }
+
+    /// How long to wait before a request made in the background every `interval`, stretched
+    /// while the budget is low and held off until it is refilled once nearly exhausted.
+    pub fn delay(self, interval: Duration) -> Duration {
+        match self {
+            Throttle::None => interval,
+            Throttle::Slowed => interval * RATE_LIMIT_SLOWDOWN,
+            Throttle::Paused { resets_in } => resets_in.max(interval),
+        }
+    }
+}
+
+/// The kinds of data fetched in the background, each polled on its own cadence. Data only
+/// needed on demand, e.g. the annotations of the job whose details are open, is requested
+/// through [`EventHandler::request`] instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DataSource {
+    /// Workflow runs and their jobs.
+    Workflows,
+    /// Self-hosted runner status.
+    Runners,
+    /// The recent commits of the current branch and their combined run outcome.
+    Commits,
+    /// The Actions minutes billed to the owners of the repositories, shown by the usage view.
+    Billing,
+}
+
+impl DataSource {
+    /// The interval between two fetches of this data source, given the interval between two
+    /// refreshes of the workflow runs.
+    fn interval(self, refresh_interval: Duration) -> Duration {
+        match self {
+            DataSource::Workflows => refresh_interval,
+            DataSource::Runners => RUNNER_POLL_INTERVAL,
+            DataSource::Commits => COMMIT_POLL_INTERVAL,
+            DataSource::Billing => BILLING_POLL_INTERVAL,
+        }
+    }
+}
+
+/// Keeps track of when each [`DataSource`] is next due to be fetched.
+#[derive(Debug)]
+struct Scheduler {
+    next_due: Vec<(DataSource, Instant)>,
+    refresh_interval: Duration,
+}
+
+impl Scheduler {
+    /// Creates a scheduler where every data source is due immediately.
+    fn new(sources: &[DataSource], refresh_interval: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            next_due: sources.iter().map(|&source| (source, now)).collect(),
+            refresh_interval,
+        }
+    }
+
+    /// Returns the data sources that are due and schedules their next fetch, `slowdown` times
+    /// their usual interval from now.
+    fn take_due(&mut self, slowdown: u32) -> Vec<DataSource> {
+        let now = Instant::now();
+        let mut due = Vec::new();
+        for (source, next) in self.next_due.iter_mut() {
+            if *next <= now {
+                due.push(*source);
+                *next = now + source.interval(self.refresh_interval) * slowdown;
+            }
+        }
+        due
+    }
+
+    /// Makes every data source due immediately.
+    fn make_all_due(&mut self) {
+        let now = Instant::now();
+        for (_, next) in self.next_due.iter_mut() {
+            *next = now;
+        }
+    }
+
+    /// Holds back every data source until at least `until`.
+    fn postpone(&mut self, until: Instant) {
+        for (_, next) in self.next_due.iter_mut() {
+            *next = (*next).max(until);
+        }
+    }
+
+    /// The time left until the next data source becomes due.
+    fn until_next_due(&self) -> Duration {
+        let now = Instant::now();
+        self.next_due
+            .iter()
+            .map(|(_, next)| next.saturating_duration_since(now))
+            .min()
+            .unwrap_or(Duration::ZERO)
+    }
+}
 
 /// Representation of all possible events.
 #[derive(Clone, Debug)]
 pub enum Event {
-    /// An event that is emitted on a regular schedule.
-    Action, // This will now trigger a *background* fetch, not carry data directly
-    /// Event carrying the result of the background GitHub data fetch.
-    GitHubDataFetched(Result<WorkflowData, String>), // Carries result or error
+    /// An event that is emitted every second to redraw the screen.
+    Tick,
+    /// A refresh of a data source that is polled, or its result.
+    Polled(PolledEvent),
+    /// The outcome of something the user asked GitHub to do.
+    Outcome(OutcomeEvent),
+    /// Data about a job or its run.
+    JobData(JobDataEvent),
+    /// The data of a popup or a view.
+    ViewData(ViewDataEvent),
     /// Crossterm events.
     Crossterm(CrosstermEvent),
     /// Application events.
     App(AppEvent),
 }
 
-/// Application events.
+/// Events of the data sources refreshed on a schedule.
 #[derive(Clone, Debug)]
+pub enum PolledEvent {
+    /// An event that is emitted on a regular schedule.
+    Action, // This will now trigger a *background* fetch, not carry data directly
+    /// Event carrying the result of the background GitHub data fetch.
+    GitHubDataFetched(Result<WorkflowData, String>), // Carries result or error
+    /// Event carrying the outcome of the connectivity check made on launch, with what is
+    /// likely in the way if GitHub can't be reached.
+    ConnectivityChecked(Result<(), String>),
+    /// Event carrying the result of the background runner status fetch.
+    RunnersFetched(Result<Vec<GithubRunner>, String>),
+    /// Event carrying the recent commits of the current branch, newest first.
+    CommitsFetched(Result<Vec<CommitStatus>, String>),
+    /// Event carrying the Actions minutes an account used in its billing cycle, by owner.
+    BillingFetched(String, Result<ActionsBilling, String>),
+    /// Event carrying a page of the completed runs of a repository being backfilled, by
+    /// repository, and the number of the page.
+    HistoryBackfilled(String, u64, Result<WorkflowHistoryPage, String>),
+}
+
+impl From<PolledEvent> for Event {
+    fn from(event: PolledEvent) -> Self {
+        Event::Polled(event)
+    }
+}
+
+/// Events carrying the outcome of something the user asked GitHub to do.
+#[derive(Clone, Debug)]
+pub enum OutcomeEvent {
+    /// Event carrying the outcome of a workflow dispatch.
+    WorkflowDispatched(Result<DispatchRecord, String>),
+    /// Event carrying the outcome of a job rerun, with the name of the job.
+    JobRerun(Result<String, String>),
+    /// Event carrying the outcome of a rerun of a whole run or its failed jobs, with a
+    /// description of what is rerun.
+    RunRerun(Result<String, String>),
+    /// Event carrying the outcome of cancelling a run, with a description of the run.
+    RunCancelled(Result<String, String>),
+    /// Event carrying the output of a command of a `gh` extension, with its label.
+    ExtensionRun(String, Result<String, String>),
+    /// Event carrying the outcome of deleting a run, or the older runs of a workflow, with a
+    /// description of what was deleted.
+    RunsDeleted(Result<String, String>),
+    /// Event carrying the outcome of approving the deployments a run waits for, with the
+    /// description of the run and the names of the approved environments.
+    DeploymentsApproved(Result<(String, Vec<String>), String>),
+    /// Event carrying the repository and id of the run a link from the clipboard leads to.
+    LinkedRunFound(Result<(String, u64), String>),
+    /// Event carrying what was saved, e.g. `the log of build`, and where.
+    LogsSaved(Result<(String, PathBuf), String>),
+}
+
+impl From<OutcomeEvent> for Event {
+    fn from(event: OutcomeEvent) -> Self {
+        Event::Outcome(event)
+    }
+}
+
+/// Events carrying data about a job or its run, fetched when it is selected or opened.
+#[derive(Clone, Debug)]
+pub enum JobDataEvent {
+    /// Event carrying the annotations of a job, by job id.
+    AnnotationsFetched(u64, Result<Vec<Annotation>, String>),
+    /// Event carrying the annotations of the checks of other apps on a commit, by repository in
+    /// lowercase and SHA.
+    CheckAnnotationsFetched((String, String), Result<CheckAnnotations, String>),
+    /// Event carrying the artifacts of a run, by run id.
+    ArtifactsFetched(u64, Result<Vec<Artifact>, String>),
+    /// Event carrying how many days a repository keeps the logs and artifacts of its runs, by
+    /// lowercased repository.
+    RetentionFetched(String, Result<u64, String>),
+    /// Event carrying the paths of the files a commit changed, by repository and SHA.
+    ChangedPathsFetched((String, String), Result<Vec<String>, String>),
+    /// Event carrying the environment a job deployed to, if any, by job id.
+    JobEnvironmentFetched(u64, Result<Option<String>, String>),
+    /// Event carrying whether the ref a run is on is a tag or a branch, by repository in
+    /// lowercase and ref name.
+    RefKindFetched((String, String), Result<RefKind, String>),
+    /// Event carrying how the workflow file changed between the two jobs of a log comparison,
+    /// by the ids of the earlier and the later job.
+    WorkflowFileDiffFetched((u64, u64), Result<WorkflowFileDiff, String>),
+    /// Event carrying every job of a run that had too many to fetch with a refresh, by run id.
+    RunJobsFetched(u64, Result<Vec<GithubJob>, String>),
+    /// Event carrying the log of a job, or the end of it, by job id.
+    JobLogFetched(u64, Result<JobLog, String>),
+    /// Event carrying the markdown summary of a job, by job id.
+    JobSummaryFetched(u64, Result<String, String>),
+    /// The raw JSON of a job and its run, by job id.
+    RawJsonFetched(u64, Result<String, String>),
+}
+
+impl From<JobDataEvent> for Event {
+    fn from(event: JobDataEvent) -> Self {
+        Event::JobData(event)
+    }
+}
+
+/// Events carrying the data of a popup or a view, fetched when it is opened.
+#[derive(Clone, Debug)]
+pub enum ViewDataEvent {
+    /// Event carrying the workflows of a repository, requested when opening the dispatch popup
+    /// or the badge popup.
+    WorkflowsFetched(Result<Vec<GithubWorkflow>, String>),
+    /// Event carrying the branches and tags of a repository, requested with the workflows.
+    RefsFetched(Result<Vec<GitRef>, String>),
+    /// Event carrying the branches of a repository, by `owner/name`, requested when opening
+    /// the branch picker.
+    BranchesFetched(String, Result<Vec<String>, String>),
+    /// Event carrying a watched run and its jobs, by run id.
+    RunFetched(u64, Result<Box<RunDetails>, String>),
+    /// Event carrying the names of the installed `gh` extensions.
+    ExtensionsFetched(Result<Vec<String>, String>),
+    /// Event carrying a page of the runs of a workflow, by repository and workflow path, and
+    /// the number of the page.
+    WorkflowHistoryFetched((String, String), u64, Result<WorkflowHistoryPage, String>),
+    /// Event carrying the tests suspected of being flaky in the test reports of a workflow, by
+    /// repository and workflow path.
+    FlakyTestsScanned((String, String), Result<FlakyTestScan, String>),
+    /// Event carrying the deployment state of a repository's environments, by `owner/name`.
+    EnvironmentsFetched(String, Result<Vec<EnvironmentStatus>, String>),
+    /// Event carrying the Actions settings of a repository, by `owner/name`.
+    ActionsSettingsFetched(String, Result<Vec<ActionsSetting>, String>),
+}
+
+impl From<ViewDataEvent> for Event {
+    fn from(event: ViewDataEvent) -> Self {
+        Event::ViewData(event)
+    }
+}
+
+/// Application events, named in snake case when bound to keys in the config file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AppEvent {
     NavigateLeft,
     NavigateRight,
     NavigateUp,
     NavigateDown,
+    /// Moves to the first row.
+    Top,
+    /// Moves to the last row.
+    Bottom,
     Quit,
+    /// Closes the current view or clears the filter, quitting if there is neither.
+    Back,
     ToggleDetails,
     PageUp,
     PageDown,
+    #[serde(rename = "open_github")]
     OpenGitHub,
+    NextRepo,
+    PrevRepo,
+    ToggleApiInspector,
+    OpenDispatch,
+    ToggleDeployView,
+    OpenJobSummary,
+    OpenFilter,
+    CompareLogs,
+    OpenTriage,
+    WatchRun,
+    ShowActionsSettings,
+    OpenPullRequest,
+    OpenCalledWorkflow,
+    /// Reruns every job of the selected job's run, once confirmed.
+    RerunRun,
+    /// Reruns the failed jobs of the selected job's run, once confirmed.
+    RerunFailedJobs,
+    /// Cancels the selected job's run while it is still going, once confirmed.
+    CancelRun,
+    /// Opens the sidebar picking the workflows whose jobs are shown.
+    ToggleWorkflowSidebar,
+    /// Shows the timeline of job status changes instead of the job columns.
+    ToggleTimeline,
+    /// Switches the failure column to the jobs that concluded otherwise, and back.
+    ToggleOtherJobs,
+    /// Prompts for a file to export the jobs in the columns to.
+    OpenExport,
+    /// Goes to the run, job or pull request checks whose URL is on the clipboard.
+    OpenClipboardLink,
+    /// Switches to the next built-in color theme.
+    CycleTheme,
+    /// Lists the runs with the jobs of the selected one instead of the job columns, and back.
+    ToggleRunView,
+    /// Picks the branch whose runs are fetched.
+    OpenBranchPicker,
+    /// Summarizes every monitored repository instead of the job columns, and back.
+    ToggleOverview,
+    /// Copies the SHA of the selected job's commit to the clipboard.
+    CopyCommitSha,
+    /// Approves the deployments the selected run waits for, once confirmed.
+    ApproveDeployment,
+    /// Opens a menu of what to copy about the selected job: its URL, run ID or `gh` command.
+    OpenYankMenu,
+    /// Shows the JSON the API returns for the selected job and its run.
+    InspectJson,
+    /// Mutes the failures of the selected job's workflow on its branch until a newer commit
+    /// runs, or unmutes them.
+    ToggleWorkflowMute,
+    /// Loads the whole log of the selected job, once confirmed, when only its end was fetched
+    /// as it is large.
+    LoadFullLog,
+    /// Lists the runs of the selected job's workflow, newest first, and back.
+    ToggleWorkflowHistory,
+    /// Refreshes the runs, runners and commits right away.
+    Refresh,
+    /// Opens the file an annotation of the selected job is on, at the annotated lines.
+    OpenAnnotation,
+    /// Pins the selected job to the top of its column, or unpins it.
+    TogglePin,
+    /// Shows the jobs as the refresh before the one shown left them.
+    PreviousRefresh,
+    /// Shows the jobs as the refresh after the one shown left them, the latest being live.
+    NextRefresh,
+    /// Deletes the selected job's run, or every older run of its workflow, once confirmed.
+    DeleteRun,
+    /// Opens the menu of the commands of the installed `gh` extensions.
+    OpenExtensions,
+    /// Opens the popup of the markdown status badges of the workflows, to copy to a README.
+    OpenBadges,
+    /// Hides the jobs of the third column that concluded longer ago than the configured
+    /// number of hours, or shows them again.
+    ToggleRecentOnly,
+    /// Shows the runner minutes used by each workflow instead of the job columns, and back.
+    ToggleUsageView,
+    /// Lists the links of the selected job numbered in its details, each opened with its
+    /// number, and back.
+    ToggleLinks,
+    /// Lists the jobs of each group of the columns in the next order: newest first, longest
+    /// first, by workflow or by branch.
+    CycleSort,
+    /// Jumps to the next match of the search in the compared logs.
+    NextMatch,
+    /// Jumps to the previous match of the search in the compared logs.
+    PreviousMatch,
+    /// Saves the whole log of the selected job to a file in the current directory.
+    SaveLog,
+    /// Saves the logs of every job of the selected job's run to a zip archive in the current
+    /// directory.
+    SaveRunLogs,
+    /// Starts paging older runs of the repository shown into the run history, or pauses it.
+    ToggleBackfill,
+    /// Switches the details panel between how long ago a job started and finished and when.
+    ToggleTimestamps,
+    /// Lists the tests of the selected job's workflow suspected of being flaky, or closes the
+    /// list.
+    ToggleFlakyTests,
+    /// Opens the command palette, listing every action to run by name.
+    OpenCommandPalette,
+    /// Hides the jobs of the runs triggered on a schedule, or shows them again.
+    ToggleScheduled,
+    /// Clears every filter of the job columns: the search, the hidden workflows, the recent
+    /// jobs only and the scheduled runs hidden.
+    ClearFilters,
+    /// Brings back the filters as they were before they last changed.
+    RestoreFilters,
 }
 
 /// Terminal event handler.
+///
+/// Events arrive on two bounded channels: one for key presses, ticks and app events, and one
+/// for the results of fetches. The first always takes precedence, so the UI stays responsive
+/// however many fetch results are waiting.
 #[derive(Debug)]
 pub struct EventHandler {
-    sender: mpsc::Sender<Event>,
-    receiver: mpsc::Receiver<Event>,
+    /// Taken when the handler is dropped, to shut down without waiting long for in-flight
+    /// fetches.
+    runtime: Option<Runtime>,
+    ui_sender: mpsc::Sender<Event>,
+    ui_receiver: mpsc::Receiver<Event>,
+    fetch_sender: mpsc::Sender<Event>,
+    fetch_receiver: mpsc::Receiver<Event>,
+    gh_cli: Arc<dyn GithubClient>,
+    /// Wakes the fetch task up to refresh everything right away.
+    refresh: Arc<Notify>,
+    /// Whether the terminal is left to a command running in the foreground, which reads
+    /// the keys pressed meanwhile.
+    input_paused: Arc<AtomicBool>,
 }
 
 impl EventHandler {
-    /// Constructs a new instance of [`EventHandler`] and spawns a new thread to handle events.
-    pub fn new(gh_cli: GhCli) -> Self {
-        let (sender, receiver) = mpsc::channel();
-        let actor = EventThread::new(sender.clone(), gh_cli);
-        thread::spawn(|| actor.run());
-        Self { sender, receiver }
+    /// Constructs a new instance of [`EventHandler`], starting the fetch task, refreshing the
+    /// workflow runs every `refresh_interval`, the tick task and a thread reading terminal
+    /// input.
+    pub fn new(gh_cli: Arc<dyn GithubClient>, refresh_interval: Duration) -> Self {
+        let handler = Self::without_polling(gh_cli);
+        handler.start_polling(refresh_interval);
+        handler
+    }
+
+    /// Constructs an [`EventHandler`] that ticks and reads terminal input but leaves the
+    /// workflow runs to be fetched by another instance, until [`Self::start_polling`].
+    pub fn without_polling(gh_cli: Arc<dyn GithubClient>) -> Self {
+        let handler = Self::idle(gh_cli);
+        if let Some(runtime) = &handler.runtime {
+            runtime.spawn(tick(handler.ui_sender.clone()));
+        }
+        // Reading the terminal blocks, so it gets a thread of its own
+        let input_sender = handler.ui_sender.clone();
+        let input_paused = handler.input_paused.clone();
+        thread::spawn(move || read_input(input_sender, input_paused));
+        handler
     }
 
-    /// Receives an event from the sender.
-    pub fn next(&self) -> color_eyre::Result<Event> {
-        Ok(self.receiver.recv()?)
+    /// Starts the fetch task, refreshing the workflow runs every `refresh_interval`.
+    pub fn start_polling(&self, refresh_interval: Duration) {
+        if let Some(runtime) = &self.runtime {
+            let fetch_task = FetchTask::new(
+                self.fetch_sender.clone(),
+                self.gh_cli.clone(),
+                self.refresh.clone(),
+            );
+            runtime.spawn(fetch_task.run(refresh_interval));
+        }
+    }
+
+    /// Constructs an [`EventHandler`] that only produces the events that are sent to it and
+    /// the results of requests, e.g. to drive the app from tests.
+    pub fn idle(gh_cli: Arc<dyn GithubClient>) -> Self {
+        let runtime = runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_time()
+            .build()
+            .expect("failed to start the async runtime");
+        let (ui_sender, ui_receiver) = mpsc::channel(UI_CHANNEL_CAPACITY);
+        let (fetch_sender, fetch_receiver) = mpsc::channel(FETCH_CHANNEL_CAPACITY);
+        Self {
+            runtime: Some(runtime),
+            ui_sender,
+            ui_receiver,
+            fetch_sender,
+            fetch_receiver,
+            gh_cli,
+            refresh: Arc::new(Notify::new()),
+            input_paused: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Receives the next event, preferring input over fetch results.
+    pub fn next_event(&mut self) -> color_eyre::Result<Event> {
+        let Some(runtime) = &self.runtime else {
+            return Err(eyre!("the event handler has shut down"));
+        };
+        runtime.block_on(async {
+            tokio::select! {
+                biased;
+                Some(event) = self.ui_receiver.recv() => Ok(event),
+                Some(event) = self.fetch_receiver.recv() => Ok(event),
+                else => Err(eyre!("every event source has stopped")),
+            }
+        })
     }
 
     /// Queue an app event to be sent to the event receiver.
     pub fn send(&mut self, app_event: AppEvent) {
-        let _ = self.sender.send(Event::App(app_event));
+        // Only the app receives, so waiting for room here would never end
+        let _ = self.ui_sender.try_send(Event::App(app_event));
+    }
+
+    /// Stops reading the terminal while a command runs in the foreground, or starts again.
+    pub fn pause_input(&self, paused: bool) {
+        self.input_paused.store(paused, Ordering::SeqCst);
+    }
+
+    /// Refreshes every data source right away rather than when it is next due.
+    pub fn refresh(&self) {
+        self.refresh.notify_one();
+    }
+
+    /// Runs an on-demand GitHub request in the background and queues the event it produces.
+    pub fn request<F, E>(&self, request: F)
+    where
+        F: FnOnce(&dyn GithubClient) -> E + Send + 'static,
+        E: Into<Event> + Send + 'static,
+    {
+        let Some(runtime) = &self.runtime else {
+            return;
+        };
+        let sender_clone = self.fetch_sender.clone();
+        let gh_cli_clone = self.gh_cli.clone();
+        runtime.spawn(async move {
+            if let Ok(event) = task::spawn_blocking(move || request(gh_cli_clone.as_ref())).await {
+                let _ = sender_clone.send(event.into()).await;
+            }
+        });
+    }
+}
+
+impl Drop for EventHandler {
+    fn drop(&mut self) {
+        // Ask the fetches in flight to stop and give them a moment to, then kill the `gh`
+        // processes of those that didn't, so neither quitting waits behind a slow request nor
+        // requests carry on once the app is gone
+        self.gh_cli.stop();
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_timeout(QUIT_GRACE_PERIOD);
+        }
+        self.gh_cli.kill_running_commands();
+    }
+}
+
+/// Sends a tick every [`REDRAW_INTERVAL`] until the app stops listening.
+async fn tick(sender: mpsc::Sender<Event>) {
+    let mut interval = tokio::time::interval(REDRAW_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        if sender.send(Event::Tick).await.is_err() {
+            return;
+        }
     }
 }
 
-/// A thread that handles reading crossterm events and emitting tick events on a regular schedule.
-struct EventThread {
+/// Forwards crossterm events, except while input is paused, until the app stops listening or
+/// the terminal can't be read.
+fn read_input(sender: mpsc::Sender<Event>, paused: Arc<AtomicBool>) -> color_eyre::Result<()> {
+    loop {
+        // Polling rather than blocking on a read lets the keys go to a command run in the
+        // foreground as soon as input is paused
+        if paused.load(Ordering::SeqCst) {
+            thread::sleep(INPUT_POLL_INTERVAL);
+            continue;
+        }
+        if !event::poll(INPUT_POLL_INTERVAL).wrap_err("failed to poll crossterm events")?
+            || paused.load(Ordering::SeqCst)
+        {
+            continue;
+        }
+        let event = event::read().wrap_err("failed to read crossterm event")?;
+        if sender.blocking_send(Event::Crossterm(event)).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+/// The task that fetches each [`DataSource`] when it is due, one fetch per source at a time.
+struct FetchTask {
     sender: mpsc::Sender<Event>,
-    gh_cli: GhCli, // Use the new GhCli struct
+    gh_cli: Arc<dyn GithubClient>,
+    /// Notified to refresh everything without waiting for it to be due.
+    refresh: Arc<Notify>,
+    /// The fetch currently running for each data source. Running on the blocking pool, it
+    /// can't be aborted but gives up between two requests once cancelled, its result being
+    /// dropped.
+    in_flight: HashMap<DataSource, CancelFlag>,
+    /// The data sources to fetch as soon as their cancelled fetch ends.
+    pending: HashSet<DataSource>,
+    /// Told by each fetch of its data source once it ended.
+    finished_sender: mpsc::UnboundedSender<DataSource>,
+    finished: mpsc::UnboundedReceiver<DataSource>,
 }
 
-impl EventThread {
-    /// Constructs a new instance of [`EventThread`].
-    fn new(sender: mpsc::Sender<Event>, gh_cli: GhCli) -> Self {
-        Self { sender, gh_cli }
+impl FetchTask {
+    /// Constructs a new instance of [`FetchTask`].
+    fn new(
+        sender: mpsc::Sender<Event>,
+        gh_cli: Arc<dyn GithubClient>,
+        refresh: Arc<Notify>,
+    ) -> Self {
+        let (finished_sender, finished) = mpsc::unbounded_channel();
+        Self {
+            sender,
+            gh_cli,
+            refresh,
+            in_flight: HashMap::new(),
+            pending: HashSet::new(),
+            finished_sender,
+            finished,
+        }
     }
 
-    /// Runs the event thread.
-    fn run(self) -> color_eyre::Result<()> {
-        let tick_interval = Duration::from_secs_f64(1.0 / TICK_FPS);
-        let mut last_tick = Instant::now();
-        let mut first = true; // Flag to ensure an immediate first fetch
+    /// Runs the fetch task, refreshing the workflow runs every `refresh_interval`.
+    async fn run(mut self, refresh_interval: Duration) {
+        let mut scheduler = Scheduler::new(
+            &[
+                DataSource::Workflows,
+                DataSource::Runners,
+                DataSource::Commits,
+                DataSource::Billing,
+            ],
+            refresh_interval,
+        );
 
         loop {
-            let timeout = tick_interval.saturating_sub(last_tick.elapsed());
-
-            // If it's time for a tick or it's the very first run, trigger an action
-            if timeout == Duration::ZERO || first {
-                last_tick = Instant::now();
-                first = false; // Reset first run flag after the initial tick
-
-                // Send an `Action` event to trigger the fetch
-                self.send(Event::Action);
-
-                // Spawn a new thread for the potentially blocking network call
-                let sender_clone = self.sender.clone();
-                let gh_cli_clone = self.gh_cli.clone(); // Clone GhCli for the new thread
-                thread::spawn(move || {
-                    match gh_cli_clone.fetch_github_workflow_data() {
-                        // Call method on GhCli instance
-                        Ok(data) => sender_clone.send(Event::GitHubDataFetched(Ok(data))),
-                        Err(e) => sender_clone.send(Event::GitHubDataFetched(Err(format!(
-                            "Error fetching GitHub data via gh CLI: {:?}",
-                            e
-                        )))),
+            // Only check for the network to come back rather than failing every fetch
+            if self.gh_cli.is_offline() {
+                let gh_cli = self.gh_cli.clone();
+                let back_online = task::spawn_blocking(move || gh_cli.check_connectivity())
+                    .await
+                    .unwrap_or(false);
+                if !back_online {
+                    tokio::time::sleep(OFFLINE_PROBE_INTERVAL).await;
+                    continue;
+                }
+                // Catch up on everything that was missed
+                scheduler.make_all_due();
+            }
+
+            // Back off as the rate limit budget runs out
+            let slowdown = match Throttle::for_rate_limit(self.gh_cli.rate_limit().as_ref()) {
+                Throttle::None => 1,
+                Throttle::Slowed => RATE_LIMIT_SLOWDOWN,
+                Throttle::Paused { resets_in } => {
+                    scheduler.postpone(Instant::now() + resets_in);
+                    1
+                }
+            };
+
+            let mut due = scheduler.take_due(slowdown);
+            for source in &self.pending {
+                if !due.contains(source) {
+                    due.push(*source);
+                }
+            }
+            for source in due {
+                // A source still being fetched is fetched once more only if its fetch was
+                // cancelled, the running one being recent enough otherwise
+                if let Some(cancel) = self.in_flight.get(&source) {
+                    if cancel.is_cancelled() {
+                        self.pending.insert(source);
+                    }
+                    continue;
+                }
+                self.pending.remove(&source);
+                if source == DataSource::Workflows {
+                    // Signal that the fetch was triggered
+                    if self.sender.send(PolledEvent::Action.into()).await.is_err() {
+                        return;
                     }
-                });
+                }
+                self.start_fetch(source);
             }
 
-            // Poll for crossterm events
-            if event::poll(timeout).wrap_err("failed to poll for crossterm events")? {
-                let event = event::read().wrap_err("failed to read crossterm event")?;
-                self.send(Event::Crossterm(event));
+            tokio::select! {
+                _ = tokio::time::sleep(scheduler.until_next_due()) => {}
+                _ = self.refresh.notified() => {
+                    // What is being fetched may be outdated, e.g. the runs of another branch
+                    for cancel in self.in_flight.values() {
+                        cancel.cancel();
+                    }
+                    scheduler.make_all_due();
+                }
+                Some(source) = self.finished.recv() => {
+                    self.in_flight.remove(&source);
+                }
             }
         }
     }
 
-    /// Sends an event to the receiver.
-    fn send(&self, event: Event) {
-        let _ = self.sender.send(event);
+    /// Starts fetching a data source, once its previous fetch ended so that an older result can
+    /// never arrive after a newer one.
+    fn start_fetch(&mut self, source: DataSource) {
+        let cancel = CancelFlag::default();
+        let sender_clone = self.sender.clone();
+        let gh_cli_clone = self.gh_cli.clone();
+        let cancel_clone = cancel.clone();
+        // The GitHub calls block, so they run on the blocking pool while this task waits
+        let fetch = task::spawn_blocking(move || match source {
+            DataSource::Workflows => vec![PolledEvent::GitHubDataFetched(
+                gh_cli_clone
+                    .fetch_github_workflow_data(&cancel_clone)
+                    .map_err(|e| format!("Error fetching GitHub data: {:?}", e)),
+            )],
+            DataSource::Runners => vec![PolledEvent::RunnersFetched(
+                gh_cli_clone
+                    .fetch_runners()
+                    .map_err(|e| format!("Error fetching runners: {:?}", e)),
+            )],
+            DataSource::Commits => vec![PolledEvent::CommitsFetched(
+                gh_cli_clone
+                    .fetch_branch_commits()
+                    .map_err(|e| format!("Error fetching commits: {:?}", e)),
+            )],
+            DataSource::Billing => {
+                let owners: BTreeSet<String> = gh_cli_clone
+                    .repos()
+                    .iter()
+                    .map(|repo_info| repo_info.owner.login.clone())
+                    .collect();
+                owners
+                    .into_iter()
+                    .map(|owner| {
+                        let result = gh_cli_clone.fetch_actions_billing(&owner).map_err(|e| {
+                            format!("Error fetching the billing of {}: {:?}", owner, e)
+                        });
+                        PolledEvent::BillingFetched(owner, result)
+                    })
+                    .collect()
+            }
+        });
+        let finished = self.finished_sender.clone();
+        let cancelled = cancel.clone();
+        tokio::spawn(async move {
+            if let Ok(events) = fetch.await
+                && !cancelled.is_cancelled()
+            {
+                for event in events {
+                    let _ = sender_clone.send(event.into()).await;
+                }
+            }
+            let _ = finished.send(source);
+        });
+        self.in_flight.insert(source, cancel);
     }
 }
@@ -0,0 +1,214 @@
+use chrono::{TimeDelta, Utc};
+use clap::ValueEnum;
+use color_eyre::eyre::{WrapErr, eyre};
+use serde::Serialize;
+use std::{fs, path::Path};
+
+use crate::{gh_cli::GithubJob, ui::format_elapsed};
+
+/// The file formats jobs can be exported to, picked by the extension of the file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl ExportFormat {
+    /// The format of a file, from its extension.
+    pub fn from_path(path: &Path) -> color_eyre::Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("json") => Ok(ExportFormat::Json),
+            Some("csv") => Ok(ExportFormat::Csv),
+            Some("md" | "markdown") => Ok(ExportFormat::Markdown),
+            _ => Err(eyre!(
+                "Can't tell the format of {}, end it with .json, .csv or .md",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// How `--once` prints the jobs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns, for people
+    #[default]
+    Table,
+    /// The fields of `--export` as a JSON array, for scripts
+    Json,
+}
+
+/// A job as it is exported.
+#[derive(Debug, Serialize)]
+struct ExportedJob<'a> {
+    repo: &'a str,
+    workflow: &'a str,
+    name: &'a str,
+    status: &'a str,
+    conclusion: Option<&'a str>,
+    started_at: String,
+    completed_at: Option<String>,
+    /// How long the job took, or has been running for, in seconds.
+    duration_seconds: i64,
+    branch: &'a str,
+    actor: &'a str,
+    url: &'a str,
+}
+
+/// The columns of the CSV and Markdown exports, in the order of [`ExportedJob::fields`].
+const COLUMNS: [&str; 11] = [
+    "repo",
+    "workflow",
+    "name",
+    "status",
+    "conclusion",
+    "started_at",
+    "completed_at",
+    "duration",
+    "branch",
+    "actor",
+    "url",
+];
+
+impl ExportedJob<'_> {
+    /// The fields of the job as text, with the duration written out for Markdown.
+    fn fields(&self, format: ExportFormat) -> [String; 11] {
+        [
+            self.repo.to_string(),
+            self.workflow.to_string(),
+            self.name.to_string(),
+            self.status.to_string(),
+            self.conclusion.unwrap_or_default().to_string(),
+            self.started_at.clone(),
+            self.completed_at.clone().unwrap_or_default(),
+            match format {
+                ExportFormat::Markdown => format_elapsed(TimeDelta::seconds(self.duration_seconds)),
+                _ => self.duration_seconds.to_string(),
+            },
+            self.branch.to_string(),
+            self.actor.to_string(),
+            self.url.to_string(),
+        ]
+    }
+}
+
+/// Writes jobs to a file as JSON, CSV or a Markdown table depending on its extension, and
+/// returns how many were written.
+pub fn export_jobs<'a>(
+    path: &Path,
+    jobs: impl IntoIterator<Item = &'a GithubJob>,
+) -> color_eyre::Result<usize> {
+    let format = ExportFormat::from_path(path)?;
+    let jobs = exported_jobs(jobs);
+    let contents = match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&jobs)? + "\n",
+        ExportFormat::Csv => {
+            let mut csv = COLUMNS.join(",") + "\n";
+            for job in &jobs {
+                let fields = job.fields(format).map(|field| csv_field(&field));
+                csv += &(fields.join(",") + "\n");
+            }
+            csv
+        }
+        ExportFormat::Markdown => {
+            let mut table = format!("| {} |\n", COLUMNS.join(" | "));
+            table += &format!("|{}\n", " --- |".repeat(COLUMNS.len()));
+            for job in &jobs {
+                let fields = job
+                    .fields(format)
+                    .map(|field| field.replace('|', "\\|").replace('\n', " "));
+                table += &format!("| {} |\n", fields.join(" | "));
+            }
+            table
+        }
+    };
+    fs::write(path, contents).wrap_err(format!("Failed to write {}", path.display()))?;
+    Ok(jobs.len())
+}
+
+/// The jobs as they are exported, with their durations up to now.
+fn exported_jobs<'a>(jobs: impl IntoIterator<Item = &'a GithubJob>) -> Vec<ExportedJob<'a>> {
+    let now = Utc::now();
+    jobs.into_iter()
+        .map(|job| ExportedJob {
+            repo: &job.repo,
+            workflow: &job.workflow_name,
+            name: &job.name,
+            status: &job.status,
+            conclusion: job.conclusion.as_deref(),
+            started_at: job.started_at.to_rfc3339(),
+            completed_at: job
+                .completed_at
+                .map(|completed_at| completed_at.to_rfc3339()),
+            duration_seconds: job.duration(now).num_seconds(),
+            branch: &job.head_branch,
+            actor: &job.actor_login,
+            url: &job.html_url,
+        })
+        .collect()
+}
+
+/// Prints jobs to stdout for `--once`, and returns whether any of them failed.
+pub fn print_jobs(jobs: &[GithubJob], format: OutputFormat) -> color_eyre::Result<bool> {
+    let exported = exported_jobs(jobs);
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&exported)?),
+        OutputFormat::Table => print!("{}", table(&exported)),
+    }
+    Ok(jobs
+        .iter()
+        .any(|job| job.conclusion.as_deref() == Some("failure")))
+}
+
+/// Lays out jobs in aligned columns, the status being the conclusion once there is one.
+fn table(jobs: &[ExportedJob]) -> String {
+    let rows: Vec<[String; 7]> = std::iter::once(
+        [
+            "REPO", "WORKFLOW", "JOB", "STATUS", "DURATION", "BRANCH", "URL",
+        ]
+        .map(str::to_string),
+    )
+    .chain(jobs.iter().map(|job| {
+        [
+            job.repo.to_string(),
+            job.workflow.to_string(),
+            job.name.to_string(),
+            job.conclusion.unwrap_or(job.status).to_string(),
+            format_elapsed(TimeDelta::seconds(job.duration_seconds)),
+            job.branch.to_string(),
+            job.url.to_string(),
+        ]
+    }))
+    .collect();
+    let mut widths = [0; 7];
+    for row in &rows {
+        for (width, field) in widths.iter_mut().zip(row) {
+            *width = (*width).max(field.chars().count());
+        }
+    }
+    let mut table = String::new();
+    for row in &rows {
+        let fields: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(field, width)| format!("{:<width$}", field, width = width))
+            .collect();
+        table += fields.join("  ").trim_end();
+        table.push('\n');
+    }
+    table
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
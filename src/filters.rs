@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+
+use crate::text_input::TextInput;
+
+/// How many earlier filters are kept to restore.
+const MAX_PREVIOUS_FILTERS: usize = 50;
+
+/// What narrows down the jobs listed in the columns.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Filters {
+    /// The search matched fuzzily against each job.
+    pub query: String,
+    /// The workflows whose jobs are left out, by name.
+    pub hidden_workflows: HashSet<String>,
+    /// Whether the jobs that concluded other than successfully longer ago than the configured
+    /// number of hours are left out, e.g. old failures fetched with the history of a branch.
+    pub recent_only: bool,
+    /// Whether the runs triggered on a schedule are left out, e.g. nightly builds drowning out
+    /// those of pull requests. Only the runs already fetched are filtered, as GitHub can't list
+    /// runs by the events that didn't trigger them.
+    pub hide_scheduled: bool,
+}
+
+impl Filters {
+    /// Whether every job is listed.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether the runs of a workflow triggered by `event` are left out, whatever the search.
+    pub fn hides(&self, workflow_name: &str, event: &str) -> bool {
+        self.hidden_workflows.contains(workflow_name)
+            || (self.hide_scheduled && event == "schedule")
+    }
+}
+
+/// A change to the filters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilterMessage {
+    /// Replaces the search, as typed into the prompt.
+    SetQuery(String),
+    /// Hides the jobs of a workflow, or shows them again.
+    ToggleWorkflow(String),
+    ShowAllWorkflows,
+    ToggleRecentOnly,
+    ToggleScheduled,
+    /// Clears every filter at once.
+    ClearAll,
+}
+
+/// The filters after a change, with no side effects: refreshing what is listed is left to the
+/// caller.
+pub fn reduce(mut filters: Filters, message: FilterMessage) -> Filters {
+    match message {
+        FilterMessage::SetQuery(query) => filters.query = query,
+        FilterMessage::ToggleWorkflow(workflow) => {
+            if !filters.hidden_workflows.remove(&workflow) {
+                filters.hidden_workflows.insert(workflow);
+            }
+        }
+        FilterMessage::ShowAllWorkflows => filters.hidden_workflows.clear(),
+        FilterMessage::ToggleRecentOnly => filters.recent_only = !filters.recent_only,
+        FilterMessage::ToggleScheduled => filters.hide_scheduled = !filters.hide_scheduled,
+        FilterMessage::ClearAll => filters = Filters::default(),
+    }
+    filters
+}
+
+/// The filters of the job columns, along with those applied before them so that any change,
+/// e.g. clearing them all, can be taken back.
+#[derive(Debug, Default)]
+pub struct FilterState {
+    /// The prompt the search is typed into.
+    pub prompt: TextInput,
+    /// Whether the prompt is capturing typing.
+    pub editing: bool,
+    filters: Filters,
+    /// The filters applied before, the latest last.
+    previous: Vec<Filters>,
+    /// Whether the latest change was typed into the search, for the keys typed in a row to be
+    /// taken back at once.
+    typing: bool,
+}
+
+impl FilterState {
+    pub fn filters(&self) -> &Filters {
+        &self.filters
+    }
+
+    /// Whether there are earlier filters to restore.
+    pub fn can_restore(&self) -> bool {
+        !self.previous.is_empty()
+    }
+
+    /// Applies a change, remembering the filters it replaced. Returns whether the filters
+    /// changed.
+    pub fn update(&mut self, message: FilterMessage) -> bool {
+        let typing = matches!(message, FilterMessage::SetQuery(_));
+        let filters = reduce(self.filters.clone(), message);
+        if filters == self.filters {
+            return false;
+        }
+        if !(typing && self.typing) {
+            if self.previous.len() == MAX_PREVIOUS_FILTERS {
+                self.previous.remove(0);
+            }
+            self.previous.push(self.filters.clone());
+        }
+        self.typing = typing;
+        self.apply(filters);
+        true
+    }
+
+    /// Brings back the filters as they were before the latest change. Returns whether there
+    /// were any to restore.
+    pub fn restore(&mut self) -> bool {
+        let Some(filters) = self.previous.pop() else {
+            return false;
+        };
+        self.typing = false;
+        self.apply(filters);
+        true
+    }
+
+    fn apply(&mut self, filters: Filters) {
+        // Typing into the prompt already changed it, cursor and all
+        if self.prompt.value() != filters.query {
+            self.prompt.set_value(filters.query.clone());
+        }
+        self.filters = filters;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cleared_filters_can_be_restored_one_change_at_a_time() {
+        let mut state = FilterState::default();
+        for query in ["t", "te", "test"] {
+            assert!(state.update(FilterMessage::SetQuery(query.to_string())));
+        }
+        assert!(state.update(FilterMessage::ToggleWorkflow("CI".to_string())));
+        assert!(state.update(FilterMessage::ClearAll));
+        assert!(state.filters().is_empty());
+        assert!(!state.update(FilterMessage::ClearAll));
+
+        assert!(state.restore());
+        assert_eq!(state.filters().query, "test");
+        assert_eq!(state.prompt.value(), "test");
+        assert!(state.filters().hidden_workflows.contains("CI"));
+        assert!(state.restore());
+        assert!(state.filters().hidden_workflows.is_empty());
+        // The search typed in a row goes at once
+        assert!(state.restore());
+        assert_eq!(state.filters(), &Filters::default());
+        assert!(!state.restore());
+    }
+}
@@ -0,0 +1,307 @@
+use color_eyre::eyre::WrapErr;
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::{client::GithubClient, gh_cli::GithubWorkflowRun};
+
+/// How many of the latest completed runs of a workflow have their test reports scanned, each
+/// taking a download of its artifacts.
+pub const SCANNED_RUNS: usize = 20;
+
+/// A test case of a JUnit report, named `classname::name`, and whether it failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestCase {
+    pub name: String,
+    pub failed: bool,
+}
+
+/// A test suspected of being flaky.
+#[derive(Clone, Debug)]
+pub struct FlakyTest {
+    pub name: String,
+    /// How many runs it failed in while passing around them.
+    pub occurrences: usize,
+    /// The latest of those runs.
+    pub last_seen: GithubWorkflowRun,
+}
+
+/// What scanning the test reports of the latest runs of a workflow found.
+#[derive(Clone, Debug, Default)]
+pub struct FlakyTestScan {
+    /// How many of the runs scanned uploaded test reports.
+    pub runs_with_reports: usize,
+    /// The tests suspected of being flaky, those failing most often first.
+    pub suspects: Vec<FlakyTest>,
+}
+
+/// Whether an artifact is named like it holds JUnit reports, e.g. `junit-results` or
+/// `test-reports-ubuntu`.
+pub fn is_test_report(artifact_name: &str) -> bool {
+    let name = artifact_name.to_lowercase().replace('_', "-");
+    ["junit", "test-result", "test-report"]
+        .iter()
+        .any(|needle| name.contains(needle))
+}
+
+/// Reads the test cases of a JUnit XML report, leaving out those skipped.
+pub fn parse_junit(xml: &str) -> Vec<TestCase> {
+    let mut cases = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find("<testcase") {
+        rest = &rest[start + "<testcase".len()..];
+        // Not `<testcases>` or the like
+        if !rest.starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+            continue;
+        }
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let attributes = &rest[..tag_end];
+        let body = if attributes.ends_with('/') {
+            ""
+        } else {
+            &rest[tag_end..rest.find("</testcase>").unwrap_or(rest.len())]
+        };
+        let Some(name) = attribute(attributes, "name") else {
+            continue;
+        };
+        if body.contains("<skipped") {
+            continue;
+        }
+        cases.push(TestCase {
+            name: match attribute(attributes, "classname").filter(|class| !class.is_empty()) {
+                Some(class) => format!("{}::{}", class, name),
+                None => name,
+            },
+            failed: body.contains("<failure") || body.contains("<error"),
+        });
+    }
+    cases
+}
+
+/// The value of the attribute `key` among the attributes of a tag, unescaped.
+fn attribute(attributes: &str, key: &str) -> Option<String> {
+    let pattern = format!("{}=\"", key);
+    let mut searched = 0;
+    while let Some(found) = attributes[searched..].find(&pattern) {
+        let start = searched + found;
+        searched = start + pattern.len();
+        // `name` is also the end of `classname`
+        if !attributes[..start].ends_with(char::is_whitespace) {
+            continue;
+        }
+        let value = &attributes[searched..];
+        let value = &value[..value.find('"')?];
+        return Some(
+            value
+                .replace("&lt;", "<")
+                .replace("&gt;", ">")
+                .replace("&quot;", "\"")
+                .replace("&apos;", "'")
+                .replace("&amp;", "&"),
+        );
+    }
+    None
+}
+
+/// Finds the tests that failed in a run while passing on the same commit in another run, or
+/// in the runs before and after it, which points at the test rather than the code. `reports`
+/// are the test cases of each run, newest first.
+pub fn suspects(reports: &[(GithubWorkflowRun, Vec<TestCase>)]) -> Vec<FlakyTest> {
+    // The outcome of each test in each run that ran it, newest first
+    let mut outcomes: BTreeMap<&str, Vec<(&GithubWorkflowRun, bool)>> = BTreeMap::new();
+    for (run, cases) in reports {
+        for case in cases {
+            let runs = outcomes.entry(&case.name).or_default();
+            // A run with several reports, e.g. one per matrix job, fails a test in any of them
+            match runs.last_mut() {
+                Some((last_run, failed)) if last_run.id == run.id => *failed |= case.failed,
+                _ => runs.push((run, case.failed)),
+            }
+        }
+    }
+    let mut suspects: Vec<FlakyTest> = outcomes
+        .into_iter()
+        .filter_map(|(name, runs)| {
+            let passed = |index: Option<usize>| {
+                index
+                    .and_then(|index| runs.get(index))
+                    .is_some_and(|(_, failed)| !failed)
+            };
+            let flaky_failures: Vec<&GithubWorkflowRun> = runs
+                .iter()
+                .enumerate()
+                .filter(|(index, (run, failed))| {
+                    *failed
+                        && (runs
+                            .iter()
+                            .any(|(other, failed)| !failed && other.head_sha == run.head_sha)
+                            || (passed(index.checked_sub(1)) && passed(Some(index + 1))))
+                })
+                .map(|(_, (run, _))| *run)
+                .collect();
+            Some(FlakyTest {
+                name: name.to_string(),
+                occurrences: flaky_failures.len(),
+                last_seen: (*flaky_failures.first()?).clone(),
+            })
+        })
+        .collect();
+    suspects.sort_by(|a, b| {
+        b.occurrences
+            .cmp(&a.occurrences)
+            .then(b.last_seen.id.cmp(&a.last_seen.id))
+    });
+    suspects
+}
+
+/// Downloads the test reports of the latest completed runs among `runs`, newest first, and
+/// finds the tests suspected of being flaky among them.
+pub fn scan(
+    gh_cli: &dyn GithubClient,
+    repo: &str,
+    runs: Vec<GithubWorkflowRun>,
+) -> color_eyre::Result<FlakyTestScan> {
+    let mut reports = Vec::new();
+    let runs = runs
+        .into_iter()
+        .filter(|run| run.status == "completed")
+        .take(SCANNED_RUNS);
+    for run in runs {
+        let cases: Vec<TestCase> = gh_cli
+            .fetch_test_reports(repo, run.id)?
+            .iter()
+            .flat_map(|xml| parse_junit(xml))
+            .collect();
+        if !cases.is_empty() {
+            reports.push((run, cases));
+        }
+    }
+    Ok(FlakyTestScan {
+        runs_with_reports: reports.len(),
+        suspects: suspects(&reports),
+    })
+}
+
+/// Lays out the suspected flaky tests of a workflow as a Markdown table, a list of candidates
+/// to quarantine for the owners of the tests.
+pub fn markdown(repo: &str, workflow_name: &str, scan: &FlakyTestScan) -> String {
+    let mut markdown = format!(
+        "# Suspected flaky tests of {}\n\nIn the test reports of the latest {} runs of {} in \
+         {} that uploaded any.\n\n",
+        workflow_name, scan.runs_with_reports, workflow_name, repo
+    );
+    markdown += "| Test | Occurrences | Last seen |\n| --- | --- | --- |\n";
+    for suspect in &scan.suspects {
+        markdown += &format!(
+            "| `{}` | {} | [{}]({}) |\n",
+            suspect.name.replace('|', "\\|"),
+            suspect.occurrences,
+            suspect
+                .last_seen
+                .updated_at
+                .get(..10)
+                .unwrap_or(&suspect.last_seen.updated_at),
+            suspect.last_seen.html_url
+        );
+    }
+    markdown
+}
+
+/// Writes the suspected flaky tests of a workflow to `path` as Markdown.
+pub fn export(
+    path: &Path,
+    repo: &str,
+    workflow_name: &str,
+    scan: &FlakyTestScan,
+) -> color_eyre::Result<()> {
+    fs::write(path, markdown(repo, workflow_name, scan))
+        .wrap_err(format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::fixture;
+
+    #[test]
+    fn tests_passing_around_their_failures_are_suspected_flaky() {
+        let report = |failing: &str| {
+            let case = |name: &str| {
+                if name == failing {
+                    format!(
+                        r#"<testcase classname="api::auth" name="{}"><failure message="timed out"/></testcase>"#,
+                        name
+                    )
+                } else {
+                    format!(
+                        r#"<testcase classname="api::auth" name="{}" time="0.1"/>"#,
+                        name
+                    )
+                }
+            };
+            format!(
+                r#"<?xml version="1.0"?>
+                <testsuites><testsuite name="api">
+                  {}
+                  {}
+                  <testcase classname="api::auth" name="refreshes &amp; retries"></testcase>
+                  <testcase classname="api::auth" name="expires"><skipped/></testcase>
+                </testsuite></testsuites>"#,
+                case("logs_in"),
+                case("logs_out")
+            )
+        };
+        assert_eq!(
+            parse_junit(&report("logs_out")),
+            [
+                TestCase {
+                    name: "api::auth::logs_in".to_string(),
+                    failed: false,
+                },
+                TestCase {
+                    name: "api::auth::logs_out".to_string(),
+                    failed: true,
+                },
+                TestCase {
+                    name: "api::auth::refreshes & retries".to_string(),
+                    failed: false,
+                },
+            ]
+        );
+
+        let runs: Vec<_> = (1..=4)
+            .map(|id| fixture::run(id, "completed", Some("failure")))
+            .collect();
+        // `logs_out` fails in run 3 between two passes and in the oldest run, `logs_in` in the
+        // newest run only
+        let reports = vec![
+            (runs[3].clone(), parse_junit(&report("logs_in"))),
+            (runs[2].clone(), parse_junit(&report("logs_out"))),
+            (runs[1].clone(), parse_junit(&report("none"))),
+            (runs[0].clone(), parse_junit(&report("logs_out"))),
+        ];
+        let suspects = suspects(&reports);
+        assert_eq!(
+            suspects
+                .iter()
+                .map(|suspect| (
+                    suspect.name.as_str(),
+                    suspect.occurrences,
+                    suspect.last_seen.id
+                ))
+                .collect::<Vec<_>>(),
+            [("api::auth::logs_out", 1, 3)]
+        );
+
+        let scan = FlakyTestScan {
+            runs_with_reports: 4,
+            suspects,
+        };
+        assert_eq!(
+            markdown("octocat/hello", "CI", &scan).lines().last(),
+            Some(
+                "| `api::auth::logs_out` | 1 | [2026-01-05](https://github.com/octocat/hello/actions/runs/3) |"
+            )
+        );
+    }
+}
@@ -0,0 +1,414 @@
+use color_eyre::eyre::{WrapErr, eyre};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fmt,
+    io::Read,
+    process::Command,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use ureq::{Agent, Proxy, SendBody, http};
+
+/// The REST API root used unless `GH_HOST` points at a GitHub Enterprise Server.
+const DEFAULT_API_ROOT: &str = "https://api.github.com";
+
+/// Requests that take longer than this are aborted.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The cache of conditional responses is emptied once it holds this many URLs.
+const MAX_CACHED_RESPONSES: usize = 500;
+
+/// How much of a download is read at a time.
+const DOWNLOAD_CHUNK_BYTES: usize = 64 * 1024;
+
+/// A response from the GitHub REST API.
+#[derive(Debug)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: String,
+    /// The URL of the next page of results, taken from the `Link` header.
+    pub next_page: Option<String>,
+    /// The rate limit budget left after this request, from the `X-RateLimit-*` headers.
+    pub rate_limit: Option<RateLimit>,
+    /// The page authorizing the token for the SAML single sign-on of the organization owning
+    /// what was asked for, from the `X-GitHub-SSO` header of a `403`.
+    pub sso_url: Option<String>,
+}
+
+/// A large plain text body, such as a job log, read a chunk at a time.
+#[derive(Debug)]
+pub struct Download {
+    pub status: u16,
+    pub bytes: Vec<u8>,
+    /// The size of the whole body, when the server tells it.
+    pub total_bytes: Option<u64>,
+    pub rate_limit: Option<RateLimit>,
+}
+
+/// The REST API rate limit budget of the authenticated user.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct RateLimit {
+    pub limit: u64,
+    pub remaining: u64,
+    /// When the budget is refilled, in seconds since the Unix epoch.
+    pub reset: u64,
+}
+
+impl RateLimit {
+    /// The time left until the budget is refilled.
+    pub fn resets_in(&self) -> Duration {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Duration::from_secs(self.reset.saturating_sub(now))
+    }
+}
+
+/// A response kept so that it can be revalidated with `If-None-Match`.
+#[derive(Debug)]
+struct CachedResponse {
+    etag: String,
+    body: String,
+    next_page: Option<String>,
+}
+
+/// A native client for the GitHub REST API, authenticated with the GitHub CLI's token.
+///
+/// JSON GET responses carrying an `ETag` are cached and revalidated on the next request, so
+/// unchanged resources come back as `304 Not Modified`, which doesn't count against the
+/// rate limit.
+#[derive(Clone)]
+pub struct GhApi {
+    agent: Agent,
+    api_root: String,
+    /// The proxy requests go through, from `HTTPS_PROXY` and the like, unless the API host is
+    /// listed in `NO_PROXY`.
+    proxy: Option<Proxy>,
+    token: String,
+    cache: Arc<Mutex<HashMap<String, CachedResponse>>>,
+}
+
+impl fmt::Debug for GhApi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the token
+        f.debug_struct("GhApi")
+            .field("api_root", &self.api_root)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GhApi {
+    /// Creates a new `GhApi` using the token returned by `gh auth token`.
+    pub fn new() -> color_eyre::Result<Self> {
+        let output = Command::new("gh")
+            .arg("auth")
+            .arg("token")
+            .output()
+            .wrap_err("Failed to execute `gh auth token` command")?;
+        if !output.status.success() {
+            return Err(eyre!(
+                "`gh auth token` exited with status {}:\n{}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if token.is_empty() {
+            return Err(eyre!("`gh auth token` did not return a token"));
+        }
+
+        // Follow the GitHub CLI in talking to an Enterprise Server when GH_HOST is set
+        let api_root = match std::env::var("GH_HOST") {
+            Ok(host) if !host.is_empty() && host != "github.com" => {
+                format!("https://{}/api/v3", host)
+            }
+            _ => DEFAULT_API_ROOT.to_string(),
+        };
+
+        // Corporate networks often only reach GitHub through the proxy set in the environment
+        let proxy = Proxy::try_from_env().filter(|proxy| {
+            api_root
+                .parse::<http::Uri>()
+                .is_ok_and(|uri| !proxy.is_no_proxy(&uri))
+        });
+        let agent: Agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(REQUEST_TIMEOUT))
+            .proxy(proxy.clone())
+            .build()
+            .into();
+
+        Ok(Self {
+            agent,
+            api_root,
+            proxy,
+            token,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// Checks that the API can be reached, explaining what is likely in the way when it can't,
+    /// such as a proxy refusing the connection or a firewall with no proxy set.
+    pub fn check_reachability(&self) -> Result<(), String> {
+        let host = self
+            .api_root
+            .trim_start_matches("https://")
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        // Never print the credentials a proxy URL may carry
+        let proxy = self
+            .proxy
+            .as_ref()
+            .map(|proxy| format!("{}:{}", proxy.host(), proxy.port()));
+        match (self.request("GET", "/rate_limit", None), proxy) {
+            (Ok(response), Some(proxy)) if response.status == 407 => Err(format!(
+                "The proxy {} asks for credentials, add them to HTTPS_PROXY, e.g. \
+                 http://user:password@{}.",
+                proxy, proxy
+            )),
+            (Ok(_), _) => Ok(()),
+            (Err(e), Some(proxy)) => Err(format!(
+                "Couldn't reach {} through the proxy {} set in the environment ({}). Check that \
+                 the proxy is up and lets {} through, or list {} in NO_PROXY to connect directly.",
+                host,
+                proxy,
+                e.root_cause(),
+                host,
+                host
+            )),
+            (Err(e), None) => Err(format!(
+                "Couldn't reach {} ({}). If your network only reaches it through a proxy, set \
+                 HTTPS_PROXY, e.g. HTTPS_PROXY=http://proxy.example.com:8080.",
+                host,
+                e.root_cause()
+            )),
+        }
+    }
+
+    /// Sends a request to the API. `path` is either relative to the API root or an absolute
+    /// URL, as found in `Link` headers. A `304` response carries the cached body.
+    pub fn request(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&str>,
+    ) -> color_eyre::Result<ApiResponse> {
+        let url = self.url(path);
+        let mut request = http::Request::builder()
+            .method(method)
+            .uri(&url)
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header(
+                "User-Agent",
+                concat!("lazyactions/", env!("CARGO_PKG_VERSION")),
+            );
+        let cacheable = method == "GET";
+        if cacheable
+            && let Some(cached) = self
+                .cache
+                .lock()
+                .ok()
+                .and_then(|cache| cache.get(&url).map(|cached| cached.etag.clone()))
+        {
+            request = request.header("If-None-Match", cached);
+        }
+        let result = match body {
+            Some(body) => self.agent.run(request.body(body.to_string())?),
+            None => self.agent.run(request.body(SendBody::none())?),
+        };
+        let mut response = result.wrap_err(format!("Failed to send {} {}", method, url))?;
+
+        let status = response.status().as_u16();
+        let rate_limit = rate_limit(response.headers());
+        let next_page = response
+            .headers()
+            .get("link")
+            .and_then(|link| link.to_str().ok())
+            .and_then(next_page_url);
+        // e.g. `required; url=https://github.com/orgs/octocat/sso?authorization_request=...`
+        let sso_url = response
+            .headers()
+            .get("x-github-sso")
+            .and_then(|sso| sso.to_str().ok())
+            .and_then(|sso| sso.split_once("url="))
+            .map(|(_, url)| url.trim().to_string());
+        // Logs and other large plain text bodies are not worth keeping around
+        let is_json = response
+            .headers()
+            .get("content-type")
+            .and_then(|content_type| content_type.to_str().ok())
+            .is_some_and(|content_type| content_type.contains("json"));
+        let etag = response
+            .headers()
+            .get("etag")
+            .and_then(|etag| etag.to_str().ok())
+            .filter(|_| is_json)
+            .map(str::to_string);
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .wrap_err(format!("Failed to read response of {} {}", method, url))?;
+
+        if cacheable && let Ok(mut cache) = self.cache.lock() {
+            if status == 304 {
+                if let Some(cached) = cache.get(&url) {
+                    return Ok(ApiResponse {
+                        status,
+                        body: cached.body.clone(),
+                        next_page: cached.next_page.clone(),
+                        rate_limit,
+                        sso_url,
+                    });
+                }
+            } else if let Some(etag) = etag.filter(|_| status == 200) {
+                if cache.len() >= MAX_CACHED_RESPONSES {
+                    cache.clear();
+                }
+                cache.insert(
+                    url.clone(),
+                    CachedResponse {
+                        etag,
+                        body: body.clone(),
+                        next_page: next_page.clone(),
+                    },
+                );
+            }
+        }
+
+        Ok(ApiResponse {
+            status,
+            body,
+            next_page,
+            rate_limit,
+            sso_url,
+        })
+    }
+
+    /// Downloads a large plain text body, such as a job log, a chunk at a time, calling
+    /// `progress` with the bytes read so far and the bytes expected. Unlike other requests it
+    /// may take as long as it needs. With `tail`, only that many bytes from the end are asked
+    /// for, and kept should the server send the whole body anyway.
+    pub fn download(
+        &self,
+        path: &str,
+        tail: Option<u64>,
+        progress: &dyn Fn(u64, Option<u64>),
+    ) -> color_eyre::Result<Download> {
+        let url = self.url(path);
+        let mut request = http::Request::builder()
+            .method("GET")
+            .uri(&url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header(
+                "User-Agent",
+                concat!("lazyactions/", env!("CARGO_PKG_VERSION")),
+            );
+        if let Some(tail) = tail {
+            request = request.header("Range", format!("bytes=-{}", tail));
+        }
+        let request = self
+            .agent
+            .configure_request(request.body(SendBody::none())?)
+            .timeout_global(None)
+            .timeout_recv_response(Some(REQUEST_TIMEOUT))
+            .build();
+        let mut response = self
+            .agent
+            .run(request)
+            .wrap_err(format!("Failed to send GET {}", url))?;
+
+        let status = response.status().as_u16();
+        let rate_limit = rate_limit(response.headers());
+        // A partial response tells the size of the whole body in `Content-Range: bytes a-b/size`
+        let expected_bytes = response.body().content_length();
+        let total_bytes = response
+            .headers()
+            .get("content-range")
+            .and_then(|range| range.to_str().ok())
+            .and_then(|range| range.rsplit_once('/'))
+            .and_then(|(_, size)| size.parse().ok())
+            .or(expected_bytes);
+
+        let keep = tail.map_or(usize::MAX, |tail| tail as usize);
+        let mut reader = response.body_mut().as_reader();
+        let mut bytes = Vec::new();
+        let mut chunk = vec![0; DOWNLOAD_CHUNK_BYTES];
+        let mut read = 0;
+        loop {
+            let count = reader
+                .read(&mut chunk)
+                .wrap_err(format!("Failed to read response of GET {}", url))?;
+            if count == 0 {
+                break;
+            }
+            bytes.extend_from_slice(&chunk[..count]);
+            read += count as u64;
+            // Drop what is ahead of the tail now and then rather than on every chunk
+            if bytes.len() > keep.saturating_mul(2) {
+                bytes.drain(..bytes.len() - keep);
+            }
+            progress(read, expected_bytes);
+        }
+        if bytes.len() > keep {
+            bytes.drain(..bytes.len() - keep);
+        }
+
+        Ok(Download {
+            status,
+            bytes,
+            total_bytes,
+            rate_limit,
+        })
+    }
+
+    /// The URL of `path`, which is either relative to the API root or already absolute, as
+    /// found in `Link` headers.
+    fn url(&self, path: &str) -> String {
+        if path.starts_with("https://") || path.starts_with("http://") {
+            path.to_string()
+        } else {
+            format!("{}{}", self.api_root, path)
+        }
+    }
+}
+
+/// The rate limit budget from the `X-RateLimit-*` headers of a response, if it has them.
+fn rate_limit(headers: &http::HeaderMap) -> Option<RateLimit> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+    };
+    match (
+        header("x-ratelimit-limit"),
+        header("x-ratelimit-remaining"),
+        header("x-ratelimit-reset"),
+    ) {
+        (Some(limit), Some(remaining), Some(reset)) => Some(RateLimit {
+            limit,
+            remaining,
+            reset,
+        }),
+        _ => None,
+    }
+}
+
+/// Extracts the `rel="next"` URL from a `Link` header.
+fn next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params.contains("rel=\"next\"").then(|| {
+            url.trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .to_string()
+        })
+    })
+}
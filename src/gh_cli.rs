@@ -1,13 +1,210 @@
+use base64::Engine;
+use chrono::{DateTime, TimeDelta, Utc};
+use clap::ValueEnum;
 use color_eyre::eyre::{WrapErr, eyre};
-use serde::{Deserialize, Serialize};
-use std::process::Command;
+use serde::{
+    Deserialize, Serialize,
+    de::{DeserializeOwned, IgnoredAny},
+};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    env, fmt, fs,
+    io::{self, Read, Write},
+    path::Path,
+    process::{self, Child, Command, Output, Stdio},
+    str::FromStr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    flaky_tests,
+    gh_api::{ApiResponse, GhApi, RateLimit},
+    gh_extensions,
+    gh_version::{GhFeature, GhVersion},
+};
+
+/// Maximum number of API calls kept for the request inspector.
+const MAX_RECORDED_API_CALLS: usize = 100;
+
+/// The media type requested from the GitHub REST API.
+const ACCEPT_HEADER: &str = "Accept: application/vnd.github+json";
+
+/// How many requests in a row have to fail to reach GitHub before it is considered offline.
+const OFFLINE_AFTER_FAILURES: u32 = 3;
+
+/// How long requests have to keep failing to reach GitHub before it is considered offline, so
+/// that a burst of concurrent failures doesn't count as an outage on its own.
+const OFFLINE_AFTER: Duration = Duration::from_secs(10);
+
+/// How many jobs of a run are fetched with each refresh, a single page. The rest of a run with
+/// more is only fetched once the run is expanded, so huge matrices don't slow refreshes down.
+pub const MAX_JOBS_PER_RUN: u64 = 100;
+
+/// How much of the end of a job log is fetched at first. Larger logs are only fetched whole
+/// once asked to, as they can run into hundreds of megabytes.
+pub const LOG_TAIL_BYTES: u64 = 2 * 1024 * 1024;
+
+/// How many runs of a workflow each page of its history holds.
+pub const WORKFLOW_HISTORY_PAGE_SIZE: u64 = 25;
+
+/// How many completed runs of a repository each page of a history backfill holds, the most the
+/// API lists on a page.
+pub const BACKFILL_PAGE_SIZE: u64 = 100;
+
+/// What GitHub answers with when the token isn't authorized for the SAML single sign-on of the
+/// organization owning what was asked for.
+const SAML_ENFORCEMENT_ERROR: &str = "protected by organization SAML enforcement";
+
+/// What an [`SsoAuthorization`] error says, for it to be found again in a message.
+const SSO_AUTHORIZATION_ERROR: &str =
+    "enforces SAML single sign-on, authorize the token of the GitHub CLI for it at";
+
+/// What `gh` prints when it can't reach GitHub at all.
+const GH_NETWORK_ERRORS: [&str; 2] = ["error connecting to", "dial tcp"];
+
+/// The statuses of responses to retry, as GitHub answers with them when it is briefly
+/// overloaded or being deployed.
+const TRANSIENT_STATUSES: [u16; 4] = [500, 502, 503, 504];
+
+/// How many times a read failing for a reason that may pass, such as a dropped connection, is
+/// retried.
+const MAX_RETRIES: u32 = 3;
+
+/// How long to wait before retrying a read the first time, doubled before each further retry.
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// How requests to the GitHub API are made.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Backend {
+    /// Native HTTP client authenticated with the GitHub CLI's token
+    #[default]
+    Api,
+    /// `gh api` subprocesses
+    Cli,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GithubWorkflowRun {
     pub id: u64,
+    pub name: String,
     pub actor_login: String,
     pub head_branch: String,
+    /// The event that triggered the run, e.g. `push` or `pull_request`.
+    pub event: String,
+    pub repo: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub updated_at: String,
+    pub html_url: String,
+    pub pull_request: Option<PullRequest>,
+    /// The path of the run's workflow file, e.g. `.github/workflows/ci.yml`.
+    pub path: String,
+    pub head_sha: String,
+    /// The first line of the message of the run's commit.
+    #[serde(default)]
+    pub commit_title: String,
+    /// The whole message of the run's commit.
+    #[serde(default)]
+    pub commit_message: String,
+    /// The name of the author of the run's commit.
+    #[serde(default)]
+    pub commit_author: String,
+    /// How many jobs the run has, when it has more than [`MAX_JOBS_PER_RUN`] and only those
+    /// were fetched.
+    #[serde(default)]
+    pub total_jobs: Option<u64>,
+    /// Whether the run's workflow calls reusable workflows.
+    pub calls_workflows: bool,
+}
+
+/// A run in the history of a workflow.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HistoryRun {
+    pub run: GithubWorkflowRun,
+    /// The number of the run among the runs of its workflow.
+    pub run_number: u64,
+    /// When the latest attempt of the run started.
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+impl HistoryRun {
+    /// How long the run took, or has been running for at `now` if it hasn't completed yet.
+    pub fn duration(&self, now: DateTime<Utc>) -> Option<TimeDelta> {
+        let ended_at = if self.run.status == "completed" {
+            self.run.updated_at.parse::<DateTime<Utc>>().ok()?
+        } else {
+            now
+        };
+        Some(ended_at - self.started_at?)
+    }
+}
+
+/// A page of the runs of a workflow, newest first.
+#[derive(Clone, Debug)]
+pub struct WorkflowHistoryPage {
+    pub runs: Vec<HistoryRun>,
+    /// How many runs the workflow has across every page.
+    pub total_count: u64,
+}
+
+/// A reusable workflow called by a job of another workflow.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CalledWorkflow {
+    /// The repository of the called workflow, as `owner/name`.
     pub repo: String,
+    /// The path of the called workflow file in its repository.
+    pub path: String,
+    /// The ref the workflow is called at.
+    pub git_ref: String,
+    pub html_url: String,
+}
+
+impl CalledWorkflow {
+    /// Resolves the `uses` of a job in a workflow of `run`, either `./path` within the same
+    /// repository or `owner/name/path@ref`.
+    fn from_uses(uses: &str, run: &GithubWorkflowRun) -> Option<Self> {
+        let (repo, path, git_ref) = match uses.strip_prefix("./") {
+            Some(path) => (run.repo.clone(), path.to_string(), run.head_sha.clone()),
+            None => {
+                let (location, git_ref) = uses.split_once('@')?;
+                let mut parts = location.splitn(3, '/');
+                let (owner, name, path) = (parts.next()?, parts.next()?, parts.next()?);
+                (
+                    format!("{}/{}", owner, name),
+                    path.to_string(),
+                    git_ref.to_string(),
+                )
+            }
+        };
+        // The run's URL tells the web host, which differs on GitHub Enterprise Server
+        let host = run
+            .html_url
+            .splitn(4, '/')
+            .take(3)
+            .collect::<Vec<_>>()
+            .join("/");
+        let html_url = format!("{}/{}/blob/{}/{}", host, repo, git_ref, path);
+        Some(Self {
+            repo,
+            path,
+            git_ref,
+            html_url,
+        })
+    }
+}
+
+/// The pull request a run was triggered for.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    /// The title of the pull request, empty if it couldn't be fetched.
+    pub title: String,
+    pub html_url: String,
 }
 
 #[derive(Debug, Default, Deserialize, Clone)]
@@ -19,259 +216,3074 @@ pub struct RepoInfo {
 pub struct Owner {
     pub login: String,
 }
+
+impl RepoInfo {
+    /// The `owner/name` form of the repository.
+    pub fn full_name(&self) -> String {
+        format!("{}/{}", self.owner.login, self.name)
+    }
+}
+
+impl FromStr for RepoInfo {
+    type Err = String;
+
+    /// Parses a repository given as `owner/name`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('/') {
+            Some((owner, name)) if !owner.is_empty() && !name.is_empty() && !name.contains('/') => {
+                Ok(RepoInfo {
+                    name: name.to_string(),
+                    owner: Owner {
+                        login: owner.to_string(),
+                    },
+                })
+            }
+            _ => Err(format!(
+                "expected a repository as `owner/name`, got `{}`",
+                s
+            )),
+        }
+    }
+}
+
+/// An organization enforcing SAML single sign-on that the token of the GitHub CLI isn't
+/// authorized for, so that every request for its repositories is refused with a `403`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SsoAuthorization {
+    pub org: String,
+    /// The page authorizing the token for the organization.
+    pub url: String,
+}
+
+impl SsoAuthorization {
+    /// The authorization `org` asks for, at the page GitHub pointed to if it did.
+    fn new(org: &str, url: Option<String>) -> Self {
+        Self {
+            org: org.to_string(),
+            url: url.unwrap_or_else(|| format!("https://github.com/orgs/{}/sso", org)),
+        }
+    }
+
+    /// Finds the authorization an error message says is missing, once it has been turned into
+    /// a string to be sent along with an event.
+    pub fn from_error(error: &str) -> Option<Self> {
+        let (before, after) = error.split_once(SSO_AUTHORIZATION_ERROR)?;
+        Some(Self {
+            org: before.split_whitespace().last()?.to_string(),
+            url: after.split_whitespace().next()?.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for SsoAuthorization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.org, SSO_AUTHORIZATION_ERROR, self.url)
+    }
+}
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GithubJob {
     pub id: u64,
     pub name: String,
+    pub workflow_name: String,
     pub run_id: u64,
     pub repo: String,
     pub run_url: String,
     pub actor_login: String,
     pub head_branch: String,
+    /// The commit the job's run is for, empty in jobs saved by older versions.
+    #[serde(default)]
+    pub head_sha: String,
+    /// The message of the commit the job's run is for.
+    #[serde(default)]
+    pub commit_message: String,
+    /// The name of the author of the commit the job's run is for.
+    #[serde(default)]
+    pub commit_author: String,
+    /// The event that triggered the job's run, e.g. `push` or `pull_request`.
+    pub event: String,
     pub status: String,
     pub conclusion: Option<String>,
-    pub started_at: String,
-    pub completed_at: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
     pub html_url: String,
+    pub steps: Vec<GithubStep>,
+    /// The `runs-on` labels a runner needs to pick the job up.
+    pub labels: Vec<String>,
+    /// The pull request the job's run was triggered for.
+    pub pull_request: Option<PullRequest>,
+    /// The reusable workflow the job comes from, if any.
+    pub called_workflow: Option<CalledWorkflow>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct WorkflowData {
-    pub runs: Vec<GithubWorkflowRun>,
-    pub jobs: Vec<GithubJob>,
-}
+impl GithubJob {
+    /// The first line of the message of the job's commit.
+    pub fn commit_title(&self) -> &str {
+        self.commit_message.lines().next().unwrap_or_default()
+    }
 
-/// Fetches repository information using the `gh repo view` command.
-pub fn fetch_repo_info() -> color_eyre::Result<RepoInfo> {
-    let output = Command::new("gh")
-        .arg("repo")
-        .arg("view")
-        .arg("--json")
-        .arg("owner,name")
-        .output()
-        .wrap_err("Failed to execute `gh repo view` command")?;
+    /// How long the job took, or has been running for at `now` if it hasn't completed yet.
+    pub fn duration(&self, now: DateTime<Utc>) -> TimeDelta {
+        self.completed_at.unwrap_or(now) - self.started_at
+    }
 
-    if output.status.success() {
-        let json_str = String::from_utf8(output.stdout)
-            .wrap_err("`gh repo view` output is not valid UTF-8")?;
-        let repo_info: RepoInfo = serde_json::from_str(&json_str)
-            .wrap_err(format!("Failed to parse `gh repo view` JSON: {}", json_str))?;
-        Ok(repo_info)
-    } else {
-        let error_msg = String::from_utf8_lossy(&output.stderr);
-        Err(eyre!(
-            "Failed to fetch repo info. `gh repo view` exited with status {}:\n{}",
-            output.status,
-            error_msg
-        ))
+    /// Whether the job is queued for a self-hosted runner that no online runner of its
+    /// repository has all the labels of, so it will never be picked up unless one is added.
+    /// Runners of other repositories and organizations are not known, so a repository
+    /// without runners of its own is never flagged.
+    pub fn lacks_runner(&self, runners: &[GithubRunner]) -> bool {
+        let self_hosted = self
+            .labels
+            .iter()
+            .any(|label| label.eq_ignore_ascii_case("self-hosted"));
+        let mut repo_runners = runners
+            .iter()
+            .filter(|runner| runner.repo.eq_ignore_ascii_case(&self.repo))
+            .peekable();
+        if self.status != "queued" || !self_hosted || repo_runners.peek().is_none() {
+            return false;
+        }
+        !repo_runners.any(|runner| {
+            runner.status == "online"
+                && self.labels.iter().all(|label| {
+                    runner
+                        .labels
+                        .iter()
+                        .any(|runner_label| runner_label.eq_ignore_ascii_case(label))
+                })
+        })
+    }
+
+    /// The subject claim of the OIDC tokens the job can request, following GitHub's default
+    /// template: the `environment` the job references comes first, then a pull request, then
+    /// the ref the run is on, whose `kind` GitHub doesn't report along with its name.
+    pub fn oidc_subject(&self, environment: Option<&str>, kind: RefKind) -> String {
+        if let Some(environment) = environment {
+            return format!("repo:{}:environment:{}", self.repo, environment);
+        }
+        match (self.event.as_str(), kind) {
+            ("pull_request", _) => format!("repo:{}:pull_request", self.repo),
+            (_, RefKind::Tag) => format!("repo:{}:ref:refs/tags/{}", self.repo, self.head_branch),
+            (_, RefKind::Branch) => {
+                format!("repo:{}:ref:refs/heads/{}", self.repo, self.head_branch)
+            }
+        }
+    }
+
+    /// What the ref of the job's run is when its event tells, e.g. a tag for a release, or
+    /// `None` when it has to be looked up.
+    pub fn known_ref_kind(&self) -> Option<RefKind> {
+        match self.event.as_str() {
+            "release" => Some(RefKind::Tag),
+            // The subject of a pull request doesn't name its ref
+            "pull_request" => Some(RefKind::Branch),
+            _ => None,
+        }
     }
 }
-// Helper function to run a command and return its stdout
-fn run_command(command_name: &str, args: &[&str], error_msg: &str) -> color_eyre::Result<String> {
-    let output = Command::new(command_name)
-        .args(args)
-        .output()
-        .wrap_err(format!("Failed to execute `{}` command", command_name))?;
 
-    if !output.status.success() {
-        return Err(eyre!(
-            "{}. Command `{}` failed with exit code {}:\nStdout: {}\nStderr: {}",
-            error_msg,
-            command_name,
-            output.status,
-            String::from_utf8_lossy(&output.stdout),
-            String::from_utf8_lossy(&output.stderr)
-        ));
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GithubStep {
+    pub number: u64,
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl GithubStep {
+    /// How long the step took, or has been running for at `now` if it hasn't completed yet.
+    /// `None` until the step starts.
+    pub fn duration(&self, now: DateTime<Utc>) -> Option<TimeDelta> {
+        self.started_at
+            .map(|started_at| self.completed_at.unwrap_or(now) - started_at)
     }
+}
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GithubRunner {
+    pub id: u64,
+    pub name: String,
+    /// The repository the runner is registered to, as `owner/name`.
+    pub repo: String,
+    pub os: String,
+    pub status: String,
+    pub busy: bool,
+    pub labels: Vec<String>,
 }
-/// A client for interacting with the GitHub CLI.
-#[derive(Debug, Clone)]
-pub struct GhCli {
-    repo_info: RepoInfo,
-    branch: bool,
-    user: bool,
-    latest: bool,
-    current_user: String,
-    current_branch: String,
+
+/// A single API request, as shown in the API request inspector.
+#[derive(Clone, Debug)]
+pub struct ApiCall {
+    pub started: Instant,
+    pub endpoint: String,
+    pub duration: Duration,
+    pub status: String,
+    pub success: bool,
+    pub payload_size: usize,
 }
 
-impl GhCli {
-    /// Creates a new `GhCli` instance.
-    /// It requires `RepoInfo` to construct API endpoints specific to the repository.
-    pub fn new(branch: bool, user: bool, latest: bool) -> Self {
-        let repo_info = match fetch_repo_info() {
-            Ok(info) => info,
-            Err(e) => {
-                eprintln!("Error fetching repository info: {:?}", e);
-                RepoInfo::default() // Provide a default or handle the error appropriately
-            }
-        };
-        // Fetch current user using `gh auth status`
-        let current_user = match Self::fetch_current_gh_user() {
-            Ok(user) => user,
-            Err(e) => {
-                eprintln!("Warning: Could not determine current GitHub user: {:?}", e);
-                String::new() // Default to empty string if not found
-            }
-        };
+/// The log of a job, or the end of it.
+#[derive(Clone, Debug)]
+pub struct JobLog {
+    pub text: String,
+    /// The size of the whole log in bytes, when GitHub tells it.
+    pub total_bytes: Option<u64>,
+    /// Whether `text` is only the end of the log.
+    pub partial: bool,
+}
 
-        // Fetch current branch using `git rev-parse --abbrev-ref HEAD`
-        let current_branch = match Self::fetch_current_git_branch() {
-            Ok(branch) => branch,
-            Err(e) => {
-                eprintln!("Warning: Could not determine current Git branch: {:?}", e);
-                String::new() // Default to empty string if not found
-            }
+impl JobLog {
+    /// A log downloaded as `bytes`, which are the end of it when `partial`, starting with
+    /// the first whole line.
+    pub fn new(bytes: &[u8], total_bytes: Option<u64>, partial: bool) -> Self {
+        let text = String::from_utf8_lossy(bytes);
+        let text = match text.split_once('\n') {
+            Some((_, rest)) if partial => rest.to_string(),
+            _ => text.into_owned(),
         };
-        if branch {}
         Self {
-            repo_info,
-            branch,
-            user,
-            latest,
-            current_branch,
-            current_user,
+            text,
+            total_bytes,
+            partial,
         }
     }
+}
 
-    /// Fetches the current authenticated GitHub user's login.
-    fn fetch_current_gh_user() -> color_eyre::Result<String> {
-        // We parse the output of `gh auth status` to find the user.
-        // A typical output might look like:
-        // gh.github.com
-        //   ✓ Logged in to github.com as octocat (~/.config/gh/hosts.yml)
-        let output = run_command(
-            "gh",
-            &["auth", "status"],
-            "Failed to fetch GitHub user status",
-        )?;
+/// How far along the download of a log is.
+#[derive(Clone, Copy, Debug)]
+pub struct LogProgress {
+    pub loaded_bytes: u64,
+    /// The size of the download, when GitHub tells it.
+    pub expected_bytes: Option<u64>,
+}
 
-        // Look for the line containing "Logged in to ... as <username>"
-        output
-            .lines()
-            .find(|line| line.contains("Logged in to") && line.contains(" account "))
-            .and_then(|line| {
-                line.split(" account ")
-                    .nth(1) // Get the part after " as "
-                    .and_then(|s| s.split_whitespace().next()) // Get the first word (the username)
-                    .map(|s| s.trim_matches(|c| c == '(' || c == ')').to_string())
-            })
-            .ok_or_else(|| {
-                eyre!("Could not parse current GitHub user from `gh auth status` output")
-            })
+/// The requests that failed to reach GitHub in a row.
+#[derive(Debug, Default)]
+struct NetworkFailures {
+    count: u32,
+    /// When the first of them failed.
+    since: Option<Instant>,
+}
+
+/// Set to abandon a fetch running in the background between two of its requests, e.g. once a
+/// refresh makes its result outdated.
+#[derive(Clone, Debug, Default)]
+pub struct CancelFlag(Arc<AtomicBool>);
+
+impl CancelFlag {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
     }
 
-    /// Fetches the current Git branch name.
-    fn fetch_current_git_branch() -> color_eyre::Result<String> {
-        run_command(
-            "git",
-            &["rev-parse", "--abbrev-ref", "HEAD"],
-            "Failed to fetch current Git branch",
-        )
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
     }
-    /// Executes a `gh` CLI command and returns its stdout as a string.
-    fn run_gh_command(&self, args: &[&str]) -> color_eyre::Result<String> {
-        let output = Command::new("gh")
-            .args(args)
-            .output()
-            .wrap_err(format!("Failed to execute `gh {}` command", args.join(" ")))?;
 
-        if !output.status.success() {
-            return Err(eyre!(
-                "Command `gh {}` failed with exit code {}:\nStdout: {}\nStderr: {}",
-                args.join(" "),
-                output.status,
-                String::from_utf8_lossy(&output.stdout),
-                String::from_utf8_lossy(&output.stderr)
-            ));
+    /// Fails once cancelled, for a fetch to stop before its next request.
+    fn check(&self) -> color_eyre::Result<()> {
+        if self.is_cancelled() {
+            return Err(eyre!("The fetch was cancelled"));
         }
-
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        Ok(())
     }
+}
 
-    /// Fetches workflow runs and jobs using the GitHub CLI.
-    pub fn fetch_github_workflow_data(&self) -> color_eyre::Result<WorkflowData> {
-        let mut workflow_runs: Vec<GithubWorkflowRun> = Vec::new();
-        let mut all_jobs: Vec<GithubJob> = Vec::new();
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkflowData {
+    pub runs: Vec<GithubWorkflowRun>,
+    pub jobs: Vec<GithubJob>,
+    /// Runs that haven't changed since the previous fetch. Their jobs are not refetched and
+    /// are left out of `jobs`, so the ones fetched before should be kept.
+    pub unchanged_runs: Vec<u64>,
+    /// What couldn't be fetched, e.g. the jobs of a run or the runs of a repository, while the
+    /// rest was.
+    #[serde(default, skip_serializing)]
+    pub warnings: Vec<String>,
+    /// The `updated_at` of the completed runs whose jobs were all fetched, by run id, for
+    /// [`GhCli::remember_fetched_runs`] once the data is shown.
+    #[serde(default, skip_serializing)]
+    pub fetched_runs: HashMap<u64, String>,
+}
 
-        let mut gh_args = vec!["api", "-H", "Accept: application/vnd.github+json"];
-        let api_path = format!(
-            "/repos/{}/{}/actions/runs",
-            self.repo_info.owner.login, self.repo_info.name
-        );
-        gh_args.push(&api_path);
-        let mut jq_filters = Vec::new();
-        jq_filters.push(format!(
-            ".workflow_runs[0:{}]",
-            self.latest.then_some(1).unwrap_or(3)
-        ));
-        jq_filters.push(".[]".to_string());
-        if self.user {
-            jq_filters.push(format!("select(.actor.login == \"{}\")", self.current_user));
-        }
-        if self.branch {
-            jq_filters.push(format!(
-                "select(.head_branch == \"{}\")",
-                self.current_branch
-            ));
-        }
-        jq_filters.push("{id: .id, actor_login: .actor.login, head_branch: .head_branch, repo: .repository.full_name}".to_string());
-        let jq_query = jq_filters.join(" | ");
-        gh_args.push("--jq");
-        gh_args.push(&jq_query);
-        let runs_json_str = self.run_gh_command(&gh_args)?;
+/// A single workflow run with all of its jobs, whatever their status.
+#[derive(Clone, Debug)]
+pub struct RunDetails {
+    pub run: GithubWorkflowRun,
+    pub jobs: Vec<GithubJob>,
+}
 
-        let mut gh_runs: Vec<GithubWorkflowRun> = Vec::new();
-        for line in runs_json_str.lines() {
-            if line.trim().is_empty() {
-                continue;
-            }
-            let run: GithubWorkflowRun = serde_json::from_str(line)
-                .wrap_err(format!("Failed to parse workflow run JSON line: {}", line))?;
-            gh_runs.push(run);
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GithubWorkflow {
+    pub id: u64,
+    pub name: String,
+    pub path: String,
+    pub state: String,
+}
+
+/// The combined outcome of the workflow runs triggered by a commit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommitState {
+    /// Every run concluded and at least one succeeded, none failed.
+    Passed,
+    /// At least one run failed.
+    Failed,
+    /// At least one run has not concluded yet.
+    Running,
+    /// All runs were cancelled or skipped.
+    Neutral,
+}
+
+impl CommitState {
+    /// The symbol shown for this state in the commit strip.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            CommitState::Passed => "✓",
+            CommitState::Failed => "✗",
+            CommitState::Running => "⟳",
+            CommitState::Neutral => "·",
         }
+    }
+}
 
-        for run in gh_runs {
-            let current_run_id = run.id;
-            let current_actor_login = run.actor_login.clone();
-            let current_head_branch = run.head_branch.clone();
-            let repo_name = run.repo.clone();
-            workflow_runs.push(run);
+/// A recent commit of the tracked branch and how its workflow runs went.
+#[derive(Clone, Debug)]
+pub struct CommitStatus {
+    pub repo: String,
+    pub sha: String,
+    /// The first line of the commit message.
+    pub title: String,
+    pub state: CommitState,
+}
 
-            // 2. Fetch jobs for each run using `gh api` and jq
-            let jobs_json_str = self.run_gh_command(&[
-                "api",
-                "--paginate",
-                "-H",
-                "Accept: application/vnd.github+json",
-                &format!(
-                    "/repos/{}/{}/actions/runs/{}/jobs",
-                    self.repo_info.owner.login, self.repo_info.name, current_run_id
-                ),
-                "--jq",
-                &format!(
-                    ".\"jobs\"[] | select(.status == \"in_progress\" or (.conclusion == \"success\" or .conclusion == \"failure\")) | {{id: .id, name: .name, run_id: {}, run_url: .run_url, actor_login: \"{}\", head_branch: \"{}\", status: .status, conclusion: .conclusion, started_at: .started_at, completed_at: .completed_at, html_url: .html_url, repo: \"{}\"}}",
-                    current_run_id, current_actor_login, current_head_branch, repo_name
-                ),
-            ])?;
+/// The latest deployment to an environment and the state it ended up in.
+#[derive(Clone, Debug)]
+pub struct DeploymentSummary {
+    pub git_ref: String,
+    pub sha: String,
+    pub creator: String,
+    pub created_at: String,
+    /// The state of the deployment's latest status, e.g. `success`, `failure` or `in_progress`.
+    pub state: String,
+    /// A link to the run that performed the deployment, if it reported one.
+    pub log_url: Option<String>,
+}
 
-            for line in jobs_json_str.lines() {
-                if line.trim().is_empty() {
-                    continue;
-                }
-                let job: GithubJob = serde_json::from_str(line).wrap_err(format!(
-                    "Failed to parse job JSON line for run {}: {}",
-                    current_run_id, line
-                ))?;
-                all_jobs.push(job);
-            }
-        }
+/// A workflow run waiting for a reviewer to approve a deployment to an environment.
+#[derive(Clone, Debug)]
+pub struct PendingApproval {
+    pub run_id: u64,
+    pub run_name: String,
+    pub head_branch: String,
+    pub html_url: String,
+}
 
-        Ok(WorkflowData {
-            runs: workflow_runs,
-            jobs: all_jobs,
-        })
+/// The deployment state of a single environment, as shown in the deploy dashboard.
+#[derive(Clone, Debug)]
+pub struct EnvironmentStatus {
+    pub name: String,
+    /// The ref of the most recent successful deployment.
+    pub deployed_ref: Option<String>,
+    pub last_deploy: Option<DeploymentSummary>,
+    pub pending_approvals: Vec<PendingApproval>,
+}
+
+/// Whether a [`GitRef`] is a branch or a tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RefKind {
+    Branch,
+    Tag,
+}
+
+/// A branch or tag of a repository, which a workflow can be dispatched on.
+#[derive(Clone, Debug)]
+pub struct GitRef {
+    pub name: String,
+    pub kind: RefKind,
+}
+
+/// A single Actions setting of a repository, as shown in the settings popup.
+#[derive(Clone, Debug)]
+pub struct ActionsSetting {
+    pub name: &'static str,
+    /// The setting in words, `None` when it couldn't be read, usually for lack of admin access.
+    pub value: Option<String>,
+}
+
+/// The Actions minutes an account used in its current billing cycle, which the billing API
+/// only tells its admins.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ActionsBilling {
+    pub total_minutes_used: f64,
+    pub total_paid_minutes_used: f64,
+    pub included_minutes: f64,
+    /// The minutes used on each kind of runner, e.g. `UBUNTU` or `MACOS_12_CORE`.
+    #[serde(default)]
+    pub minutes_used_breakdown: BTreeMap<String, f64>,
+}
+
+/// How a workflow file changed between the commits of two runs.
+#[derive(Clone, Debug)]
+pub struct WorkflowFileDiff {
+    pub repo: String,
+    /// The path of the workflow file of the later run.
+    pub path: String,
+    pub base_sha: String,
+    pub head_sha: String,
+    pub changed: bool,
+    /// The lines of the unified diff of the file, empty when it is unchanged or when GitHub
+    /// leaves the diff out for being too large.
+    pub patch: Vec<String>,
+}
+
+impl WorkflowFileDiff {
+    /// The page comparing the two commits on GitHub.
+    pub fn html_url(&self) -> String {
+        format!(
+            "https://github.com/{}/compare/{}...{}",
+            self.repo, self.base_sha, self.head_sha
+        )
+    }
+}
+
+/// A message a check left on a line of a file, e.g. a compiler error matched in the log of a
+/// job.
+#[derive(Clone, Debug)]
+pub struct Annotation {
+    pub repo: String,
+    /// The commit the job ran on, which the line is of.
+    pub head_sha: String,
+    pub path: String,
+    pub start_line: u64,
+    pub end_line: u64,
+    /// `failure`, `warning` or `notice`.
+    pub level: String,
+    pub title: Option<String>,
+    pub message: String,
+    /// The app whose check left it when it isn't GitHub Actions, e.g. `SonarCloud`.
+    pub source: Option<String>,
+}
+
+impl Annotation {
+    /// The annotated lines of the file on GitHub.
+    pub fn html_url(&self) -> String {
+        let lines = if self.end_line > self.start_line {
+            format!("L{}-L{}", self.start_line, self.end_line)
+        } else {
+            format!("L{}", self.start_line)
+        };
+        format!(
+            "https://github.com/{}/blob/{}/{}#{}",
+            self.repo, self.head_sha, self.path, lines
+        )
+    }
+}
+
+/// The annotations of the checks that apps other than GitHub Actions failed on a commit.
+#[derive(Clone, Debug, Default)]
+pub struct CheckAnnotations {
+    pub annotations: Vec<Annotation>,
+    /// Why the annotations of some of the checks couldn't be fetched, the others' being kept.
+    pub errors: Vec<String>,
+}
+
+/// A file a run uploaded, which GitHub deletes once it expires.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Artifact {
+    pub name: String,
+    pub size_in_bytes: u64,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub expired: bool,
+}
+
+// Shapes of the REST API responses, shared by both backends.
+
+#[derive(Debug, Deserialize)]
+struct ApiWorkflowsPage {
+    workflows: Vec<GithubWorkflow>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiWorkflowRunsPage {
+    workflow_runs: Vec<ApiWorkflowRun>,
+    /// How many runs there are across every page.
+    #[serde(default)]
+    total_count: u64,
+}
+
+impl From<ApiWorkflowRunsPage> for WorkflowHistoryPage {
+    fn from(runs_page: ApiWorkflowRunsPage) -> Self {
+        Self {
+            runs: runs_page
+                .workflow_runs
+                .into_iter()
+                .map(|run| HistoryRun {
+                    run_number: run.run_number.unwrap_or_default(),
+                    started_at: run.run_started_at,
+                    run: GithubWorkflowRun::from(run),
+                })
+                .collect(),
+            total_count: runs_page.total_count,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiWorkflowRun {
+    id: u64,
+    run_number: Option<u64>,
+    run_started_at: Option<DateTime<Utc>>,
+    name: Option<String>,
+    actor: Option<ApiActor>,
+    head_branch: Option<String>,
+    event: Option<String>,
+    head_sha: Option<String>,
+    head_commit: Option<ApiCommit>,
+    status: Option<String>,
+    conclusion: Option<String>,
+    html_url: Option<String>,
+    updated_at: Option<String>,
+    path: Option<String>,
+    repository: ApiRepository,
+    /// The reusable workflows called by the run's workflow.
+    #[serde(default)]
+    referenced_workflows: Vec<IgnoredAny>,
+    /// Pull requests of the same repository whose head is the run's commit. Always empty for
+    /// pull requests from forks.
+    #[serde(default)]
+    pull_requests: Vec<ApiRunPullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiRunPullRequest {
+    number: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiPullRequest {
+    title: String,
+    html_url: String,
+    head: Option<ApiPullRequestHead>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiPullRequestHead {
+    sha: String,
+}
+
+/// The part of a single job response needed to find its run.
+#[derive(Debug, Deserialize)]
+struct ApiJobRun {
+    run_id: u64,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiCommit {
+    message: String,
+    author: Option<ApiCommitAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiCommitAuthor {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiActor {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiRepository {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiJobsPage {
+    #[serde(default)]
+    total_count: u64,
+    jobs: Vec<ApiJob>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiJob {
+    id: u64,
+    name: String,
+    workflow_name: Option<String>,
+    run_url: String,
+    status: String,
+    conclusion: Option<String>,
+    started_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+    html_url: Option<String>,
+    #[serde(default)]
+    steps: Vec<GithubStep>,
+    #[serde(default)]
+    labels: Vec<String>,
+}
+
+impl From<ApiWorkflowRun> for GithubWorkflowRun {
+    fn from(run: ApiWorkflowRun) -> Self {
+        Self {
+            id: run.id,
+            name: run.name.unwrap_or_default(),
+            actor_login: run.actor.map(|actor| actor.login).unwrap_or_default(),
+            head_branch: run.head_branch.unwrap_or_default(),
+            event: run.event.unwrap_or_default(),
+            repo: run.repository.full_name,
+            status: run.status.unwrap_or_default(),
+            conclusion: run.conclusion,
+            updated_at: run.updated_at.unwrap_or_default(),
+            html_url: run.html_url.unwrap_or_default(),
+            // Titles are filled in by `GhCli::resolve_pull_request`
+            pull_request: run.pull_requests.first().map(|pull_request| PullRequest {
+                number: pull_request.number,
+                title: String::new(),
+                html_url: String::new(),
+            }),
+            path: run.path.unwrap_or_default(),
+            head_sha: run.head_sha.unwrap_or_default(),
+            commit_title: run
+                .head_commit
+                .as_ref()
+                .and_then(|commit| commit.message.lines().next())
+                .unwrap_or_default()
+                .to_string(),
+            commit_message: run
+                .head_commit
+                .as_ref()
+                .map(|commit| commit.message.clone())
+                .unwrap_or_default(),
+            commit_author: run
+                .head_commit
+                .and_then(|commit| commit.author)
+                .map(|author| author.name)
+                .unwrap_or_default(),
+            calls_workflows: !run.referenced_workflows.is_empty(),
+            // Filled in when the run's jobs are fetched
+            total_jobs: None,
+        }
+    }
+}
+
+impl ApiJob {
+    /// Converts a job of `run` into the shape used by the UI.
+    fn into_job(self, run: &GithubWorkflowRun) -> GithubJob {
+        GithubJob {
+            id: self.id,
+            name: self.name,
+            workflow_name: self.workflow_name.unwrap_or_default(),
+            run_id: run.id,
+            repo: run.repo.clone(),
+            run_url: self.run_url,
+            actor_login: run.actor_login.clone(),
+            head_branch: run.head_branch.clone(),
+            head_sha: run.head_sha.clone(),
+            commit_message: run.commit_message.clone(),
+            commit_author: run.commit_author.clone(),
+            event: run.event.clone(),
+            status: self.status,
+            conclusion: self.conclusion,
+            started_at: self.started_at,
+            completed_at: self.completed_at,
+            html_url: self.html_url.unwrap_or_default(),
+            steps: self.steps,
+            labels: self.labels,
+            pull_request: run.pull_request.clone(),
+            // Filled in by `GhCli::resolve_called_workflows`
+            called_workflow: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiContent {
+    content: String,
+}
+
+/// The `uses` of the jobs of a workflow that call reusable workflows, by the id and by the
+/// name of each calling job.
+type WorkflowCalls = HashMap<String, String>;
+
+/// The parts of a workflow file needed to trace reusable workflow calls.
+#[derive(Debug, Deserialize)]
+struct WorkflowFile {
+    #[serde(default)]
+    jobs: HashMap<String, WorkflowFileJob>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkflowFileJob {
+    name: Option<String>,
+    uses: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiComparison {
+    #[serde(default)]
+    files: Vec<ApiComparedFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiComparedFile {
+    filename: String,
+    previous_filename: Option<String>,
+    patch: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiAnnotation {
+    path: String,
+    start_line: u64,
+    end_line: u64,
+    annotation_level: String,
+    title: Option<String>,
+    message: String,
+}
+
+impl ApiAnnotation {
+    /// The annotation, on the files of `head_sha` in `repo`, left by the check of `source`.
+    fn into_annotation(self, repo: &str, head_sha: &str, source: Option<String>) -> Annotation {
+        Annotation {
+            repo: repo.to_string(),
+            head_sha: head_sha.to_string(),
+            path: self.path,
+            start_line: self.start_line,
+            end_line: self.end_line,
+            level: self.annotation_level,
+            title: self.title.filter(|title| !title.is_empty()),
+            message: self.message,
+            source,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiCommitCheckRuns {
+    check_runs: Vec<ApiCommitCheckRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiCommitCheckRun {
+    id: u64,
+    conclusion: Option<String>,
+    app: Option<ApiCheckApp>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiCheckApp {
+    slug: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiArtifacts {
+    artifacts: Vec<Artifact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiRetention {
+    days: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiNamedRef {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiGitRef {
+    #[serde(rename = "ref")]
+    git_ref: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiActionsPermissions {
+    enabled: bool,
+    allowed_actions: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiSelectedActions {
+    github_owned_allowed: Option<bool>,
+    verified_allowed: Option<bool>,
+    #[serde(default)]
+    patterns_allowed: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiWorkflowPermissions {
+    default_workflow_permissions: String,
+    can_approve_pull_request_reviews: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiForkPrApproval {
+    approval_policy: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiPrivateForkPrWorkflows {
+    run_workflows_from_fork_pull_requests: bool,
+    send_write_tokens_to_workflows: bool,
+    send_secrets_and_variables: bool,
+    require_approval_for_fork_pr_workflows: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiRateLimitResponse {
+    rate: RateLimit,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiRunnersPage {
+    runners: Vec<ApiRunner>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiRunner {
+    id: u64,
+    name: String,
+    os: String,
+    status: String,
+    busy: bool,
+    labels: Vec<ApiLabel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiLabel {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiCheckRun {
+    output: Option<ApiCheckRunOutput>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiCheckRunOutput {
+    title: Option<String>,
+    summary: Option<String>,
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiEnvironmentsPage {
+    environments: Vec<ApiEnvironment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiEnvironment {
+    #[serde(default)]
+    id: u64,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiDeployment {
+    id: u64,
+    #[serde(rename = "ref")]
+    git_ref: String,
+    sha: String,
+    environment: String,
+    creator: Option<ApiActor>,
+    created_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiDeploymentStatus {
+    state: String,
+    log_url: Option<String>,
+    target_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiPendingDeployment {
+    environment: ApiEnvironment,
+    /// Whether the authenticated user is one of the environment's required reviewers.
+    #[serde(default)]
+    current_user_can_approve: bool,
+}
+
+/// How many commits of the current branch are shown in the commit strip.
+const MAX_TRACKED_COMMITS: usize = 10;
+
+/// How many past deployments of an environment are searched for the one currently deployed.
+const MAX_DEPLOYMENTS_SEARCHED: usize = 10;
+
+/// How many annotations of a job are fetched, a single page, as the first ones are the ones
+/// worth reading.
+const MAX_ANNOTATIONS: usize = 50;
+
+/// Fetches repository information using the `gh repo view` command.
+pub fn fetch_repo_info() -> color_eyre::Result<RepoInfo> {
+    let output = Command::new("gh")
+        .arg("repo")
+        .arg("view")
+        .arg("--json")
+        .arg("owner,name")
+        .output()
+        .wrap_err("Failed to execute `gh repo view` command")?;
+
+    if output.status.success() {
+        let json_str = String::from_utf8(output.stdout)
+            .wrap_err("`gh repo view` output is not valid UTF-8")?;
+        let repo_info: RepoInfo = serde_json::from_str(&json_str)
+            .wrap_err(format!("Failed to parse `gh repo view` JSON: {}", json_str))?;
+        Ok(repo_info)
+    } else {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        Err(eyre!(
+            "Failed to fetch repo info. `gh repo view` exited with status {}:\n{}",
+            output.status,
+            error_msg
+        ))
+    }
+}
+
+/// Fetches repository information from the plain output of `gh repo view`, for versions of
+/// the GitHub CLI without `--json`, which start it with `name:\towner/name` when piped.
+pub fn fetch_repo_info_without_json() -> color_eyre::Result<RepoInfo> {
+    let output = run_command("gh", &["repo", "view"], "Failed to fetch repo info")?;
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("name:"))
+        .ok_or_else(|| eyre!("Could not find the repository in `gh repo view` output"))?
+        .trim()
+        .parse()
+        .map_err(|e: String| eyre!(e))
+}
+// Helper function to run a command and return its stdout
+fn run_command(command_name: &str, args: &[&str], error_msg: &str) -> color_eyre::Result<String> {
+    let output = Command::new(command_name)
+        .args(args)
+        .output()
+        .wrap_err(format!("Failed to execute `{}` command", command_name))?;
+
+    if !output.status.success() {
+        return Err(eyre!(
+            "{}. Command `{}` failed with exit code {}:\nStdout: {}\nStderr: {}",
+            error_msg,
+            command_name,
+            output.status,
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+/// A client for interacting with the GitHub CLI.
+#[derive(Debug, Clone)]
+pub struct GhCli {
+    repos: Vec<RepoInfo>,
+    /// The branch whose runs are fetched, `None` for the runs of every branch.
+    branch: Arc<Mutex<Option<String>>>,
+    /// The repositories another instance polls, in lowercase, whose runs aren't fetched.
+    followed_repos: Arc<Mutex<HashSet<String>>>,
+    user: bool,
+    latest: bool,
+    current_user: String,
+    current_branch: String,
+    /// The native API client, or `None` when `gh api` subprocesses are used.
+    api: Option<GhApi>,
+    /// What the installed GitHub CLI lacks and what is done instead.
+    compatibility_notes: Vec<String>,
+    /// What couldn't be looked up on creation and what is done without it.
+    startup_warnings: Vec<String>,
+    api_calls: Arc<Mutex<VecDeque<ApiCall>>>,
+    /// The `updated_at` of the completed runs whose jobs were last fetched, by run id.
+    fetched_runs: Arc<Mutex<HashMap<u64, String>>>,
+    /// The runs whose jobs are all fetched, however many there are.
+    expanded_runs: Arc<Mutex<HashSet<u64>>>,
+    /// Pull requests already fetched, by repository and number.
+    pull_requests: Arc<Mutex<HashMap<(String, u64), PullRequest>>>,
+    /// The reusable workflow calls of each workflow file already read, by `owner/name/path@sha`.
+    workflow_calls: Arc<Mutex<HashMap<String, WorkflowCalls>>>,
+    /// The rate limit budget as of the latest response that reported it.
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    /// The requests that failed to reach GitHub since the last one that did.
+    network_failures: Arc<Mutex<NetworkFailures>>,
+    /// Set when the app quits, after which no new request is made.
+    stopping: Arc<AtomicBool>,
+    /// The `gh` processes currently running, by process id, to kill those still running when
+    /// the app quits.
+    running_commands: Arc<Mutex<HashMap<u32, Child>>>,
+    /// The logs being downloaded, by job id.
+    log_downloads: Arc<Mutex<HashMap<u64, LogProgress>>>,
+}
+
+impl GhCli {
+    /// Creates a new `GhCli` instance.
+    /// It requires `RepoInfo` to construct API endpoints specific to each repository.
+    /// When no repositories are given, the repository of the current directory is used.
+    pub fn new(
+        branch: bool,
+        user: bool,
+        latest: bool,
+        repos: Vec<RepoInfo>,
+        backend: Backend,
+    ) -> Self {
+        // Older versions of the GitHub CLI lack some of what is used, so fall back on what
+        // they have and say so rather than failing at every refresh
+        let gh_version = GhVersion::detect();
+        let mut compatibility_notes = Vec::new();
+        // Printing them would draw over the TUI, so they are left to the caller to show
+        let mut startup_warnings = Vec::new();
+        let mut supports = |feature: GhFeature, fallback: &str| match gh_version {
+            Some(version) if !version.supports(feature) => {
+                compatibility_notes.push(format!(
+                    "gh {} lacks {} (added in {}), {}.",
+                    version,
+                    feature.name(),
+                    feature.added_in(),
+                    fallback
+                ));
+                false
+            }
+            _ => true,
+        };
+
+        let repos = if repos.is_empty() {
+            let repo_info = if supports(GhFeature::RepoViewJson, "reading `gh repo view` instead") {
+                fetch_repo_info()
+            } else {
+                fetch_repo_info_without_json()
+            };
+            match repo_info {
+                Ok(info) => vec![info],
+                Err(e) => {
+                    startup_warnings.push(format!("Error fetching repository info: {:#}", e));
+                    vec![RepoInfo::default()] // Provide a default or handle the error appropriately
+                }
+            }
+        } else {
+            repos
+        };
+        // Fetch current user using `gh auth status`, or the API when it words it differently
+        let current_user = if supports(
+            GhFeature::AuthStatusAccount,
+            "asking the API for the current user",
+        ) {
+            Self::fetch_current_gh_user()
+        } else {
+            Self::fetch_current_api_user()
+        };
+        let current_user = match current_user {
+            Ok(user) => user,
+            Err(e) => {
+                startup_warnings.push(format!("Could not determine current GitHub user: {:#}", e));
+                String::new() // Default to empty string if not found
+            }
+        };
+
+        // Fetch current branch using `git rev-parse --abbrev-ref HEAD`
+        let current_branch = match Self::fetch_current_git_branch() {
+            Ok(branch) => branch,
+            Err(e) => {
+                startup_warnings.push(format!("Could not determine current Git branch: {:#}", e));
+                String::new() // Default to empty string if not found
+            }
+        };
+        // Only the runs of the current branch with `--branch`, until another one is picked
+        let branch = Some(current_branch.clone()).filter(|current| branch && !current.is_empty());
+        // Set up the native API client, falling back to `gh api` if no token is available
+        let api = match backend {
+            Backend::Api
+                if !supports(
+                    GhFeature::AuthToken,
+                    "so requests go through `gh api` as with `--backend cli`",
+                ) =>
+            {
+                None
+            }
+            Backend::Api => match GhApi::new() {
+                Ok(api) => Some(api),
+                Err(e) => {
+                    startup_warnings.push(format!(
+                        "Could not set up the native API client, using `gh api` instead: {:#}",
+                        e
+                    ));
+                    None
+                }
+            },
+            Backend::Cli => None,
+        };
+        Self {
+            repos,
+            branch: Arc::new(Mutex::new(branch)),
+            followed_repos: Arc::new(Mutex::new(HashSet::new())),
+            user,
+            latest,
+            current_branch,
+            current_user,
+            api,
+            compatibility_notes,
+            startup_warnings,
+            api_calls: Arc::new(Mutex::new(VecDeque::new())),
+            fetched_runs: Arc::new(Mutex::new(HashMap::new())),
+            expanded_runs: Arc::new(Mutex::new(HashSet::new())),
+            pull_requests: Arc::new(Mutex::new(HashMap::new())),
+            workflow_calls: Arc::new(Mutex::new(HashMap::new())),
+            rate_limit: Arc::new(Mutex::new(None)),
+            network_failures: Arc::new(Mutex::new(NetworkFailures::default())),
+            stopping: Arc::new(AtomicBool::new(false)),
+            running_commands: Arc::new(Mutex::new(HashMap::new())),
+            log_downloads: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Makes every further request fail straight away, so that fetches in progress give up
+    /// at their next request rather than going through the remaining pages.
+    pub fn stop(&self) {
+        self.stopping.store(true, Ordering::Relaxed);
+    }
+
+    /// Kills the `gh` processes that are still running, failing the requests waiting on them.
+    pub fn kill_running_commands(&self) {
+        if let Ok(mut running_commands) = self.running_commands.lock() {
+            for child in running_commands.values_mut() {
+                let _ = child.kill();
+            }
+        }
+    }
+
+    /// Fails if [`GhCli::stop`] was called.
+    fn ensure_running(&self) -> color_eyre::Result<()> {
+        if self.stopping.load(Ordering::Relaxed) {
+            return Err(eyre!("Not sending requests as lazyactions is quitting"));
+        }
+        Ok(())
+    }
+
+    /// Waits before retrying a read that failed for a reason that may pass, longer with each
+    /// of the `retries` made so far, and returns whether to retry. Reads aren't retried once
+    /// they have been too many times, nor while GitHub is offline or lazyactions is quitting.
+    fn back_off(&self, retries: &mut u32) -> bool {
+        if *retries >= MAX_RETRIES || self.is_offline() || self.ensure_running().is_err() {
+            return false;
+        }
+        thread::sleep(RETRY_BACKOFF * 2u32.pow(*retries));
+        *retries += 1;
+        self.ensure_running().is_ok()
+    }
+
+    /// The repositories monitored by this client.
+    pub fn repos(&self) -> &[RepoInfo] {
+        &self.repos
+    }
+
+    /// The most recent API calls, newest first.
+    pub fn api_calls(&self) -> Vec<ApiCall> {
+        self.api_calls
+            .lock()
+            .map(|calls| calls.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// How far along the download of the log of a job is, while it is being downloaded.
+    pub fn log_progress(&self, job_id: u64) -> Option<LogProgress> {
+        self.log_downloads
+            .lock()
+            .ok()
+            .and_then(|downloads| downloads.get(&job_id).copied())
+    }
+
+    fn set_log_progress(&self, job_id: u64, progress: Option<LogProgress>) {
+        if let Ok(mut downloads) = self.log_downloads.lock() {
+            match progress {
+                Some(progress) => downloads.insert(job_id, progress),
+                None => downloads.remove(&job_id),
+            };
+        }
+    }
+
+    /// The rate limit budget as of the latest response that reported it.
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limit
+            .lock()
+            .ok()
+            .and_then(|rate_limit| *rate_limit)
+    }
+
+    fn set_rate_limit(&self, rate_limit: RateLimit) {
+        if let Ok(mut current) = self.rate_limit.lock() {
+            *current = Some(rate_limit);
+        }
+    }
+
+    /// Whether the latest requests have kept failing to reach GitHub for a while, e.g. because
+    /// the network is down.
+    pub fn is_offline(&self) -> bool {
+        self.network_failures.lock().is_ok_and(|failures| {
+            failures.count >= OFFLINE_AFTER_FAILURES
+                && failures
+                    .since
+                    .is_some_and(|since| since.elapsed() >= OFFLINE_AFTER)
+        })
+    }
+
+    /// Checks whether GitHub can be reached again with the `rate_limit` endpoint, which is
+    /// cheap and doesn't count against the budget.
+    pub fn check_connectivity(&self) -> bool {
+        self.refresh_rate_limit().is_ok()
+    }
+
+    /// Checks that the GitHub API can be reached, explaining what is likely in the way when
+    /// it can't, e.g. a proxy or a firewall.
+    pub fn diagnose_connectivity(&self) -> Result<(), String> {
+        if let Some(api) = &self.api {
+            return api.check_reachability();
+        }
+        // `gh` honors HTTPS_PROXY on its own, so only its network errors say anything here
+        match self.refresh_rate_limit() {
+            Err(e)
+                if GH_NETWORK_ERRORS
+                    .iter()
+                    .any(|network_error| e.to_string().contains(network_error)) =>
+            {
+                let reason = e
+                    .to_string()
+                    .lines()
+                    .find(|line| {
+                        GH_NETWORK_ERRORS
+                            .iter()
+                            .any(|network_error| line.contains(network_error))
+                    })
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string();
+                Err(format!(
+                    "`gh` couldn't reach GitHub ({}). If your network only reaches it through a \
+                     proxy, set HTTPS_PROXY, e.g. HTTPS_PROXY=http://proxy.example.com:8080.",
+                    reason.trim_start_matches("Stderr: ")
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records whether a request reached GitHub, whatever it responded.
+    fn record_reachability(&self, reached: bool) {
+        if let Ok(mut failures) = self.network_failures.lock() {
+            if reached {
+                *failures = NetworkFailures::default();
+            } else {
+                failures.count += 1;
+                failures.since.get_or_insert_with(Instant::now);
+            }
+        }
+    }
+
+    /// Refreshes the rate limit budget from the `rate_limit` endpoint, which doesn't count
+    /// against it. Only needed with `gh api`, whose output doesn't include the headers.
+    fn refresh_rate_limit(&self) -> color_eyre::Result<()> {
+        let response: ApiRateLimitResponse = self.api_get("/rate_limit")?;
+        self.set_rate_limit(response.rate);
+        Ok(())
+    }
+
+    /// Records an API call for the request inspector, dropping the oldest if the log is full.
+    fn record_api_call(&self, call: ApiCall) {
+        if let Ok(mut calls) = self.api_calls.lock() {
+            if calls.len() >= MAX_RECORDED_API_CALLS {
+                calls.pop_front();
+            }
+            calls.push_back(call);
+        }
+    }
+
+    /// Fetches the current authenticated GitHub user's login.
+    fn fetch_current_gh_user() -> color_eyre::Result<String> {
+        // We parse the output of `gh auth status` to find the user.
+        // A typical output might look like:
+        // gh.github.com
+        //   ✓ Logged in to github.com as octocat (~/.config/gh/hosts.yml)
+        let output = run_command(
+            "gh",
+            &["auth", "status"],
+            "Failed to fetch GitHub user status",
+        )?;
+
+        // Look for the line containing "Logged in to ... as <username>"
+        output
+            .lines()
+            .find(|line| line.contains("Logged in to") && line.contains(" account "))
+            .and_then(|line| {
+                line.split(" account ")
+                    .nth(1) // Get the part after " as "
+                    .and_then(|s| s.split_whitespace().next()) // Get the first word (the username)
+                    .map(|s| s.trim_matches(|c| c == '(' || c == ')').to_string())
+            })
+            .ok_or_else(|| {
+                eyre!("Could not parse current GitHub user from `gh auth status` output")
+            })
+    }
+
+    /// Fetches the current authenticated GitHub user's login from the API, for versions of
+    /// the GitHub CLI whose `gh auth status` doesn't word it as expected.
+    fn fetch_current_api_user() -> color_eyre::Result<String> {
+        let output = run_command("gh", &["api", "user"], "Failed to fetch GitHub user")?;
+        let user: ApiActor = serde_json::from_str(&output)
+            .wrap_err(format!("Failed to parse `gh api user` JSON: {}", output))?;
+        Ok(user.login)
+    }
+
+    /// Fetches the current Git branch name.
+    fn fetch_current_git_branch() -> color_eyre::Result<String> {
+        run_command(
+            "git",
+            &["rev-parse", "--abbrev-ref", "HEAD"],
+            "Failed to fetch current Git branch",
+        )
+    }
+    /// Executes a `gh` CLI command and returns its stdout as a string.
+    fn run_gh_command(&self, args: &[&str]) -> color_eyre::Result<String> {
+        self.run_gh_command_with_input(args, None)
+    }
+
+    /// Runs `gh` to completion, registered in `running_commands` for as long as it runs so
+    /// that quitting can kill it.
+    fn run_gh_process(&self, args: &[&str], input: Option<&str>) -> io::Result<Output> {
+        let mut child = Command::new("gh")
+            .args(args)
+            .stdin(if input.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take();
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        let pid = child.id();
+        if let Ok(mut running_commands) = self.running_commands.lock() {
+            running_commands.insert(pid, child);
+        }
+
+        // Read both pipes at once so that `gh` never blocks on a full one
+        let output = thread::scope(|scope| {
+            let stderr_reader = scope.spawn(move || {
+                let mut buffer = Vec::new();
+                stderr.read_to_end(&mut buffer).map(|_| buffer)
+            });
+            if let (Some(mut stdin), Some(input)) = (stdin, input) {
+                stdin.write_all(input.as_bytes())?;
+            }
+            let mut stdout_buffer = Vec::new();
+            stdout.read_to_end(&mut stdout_buffer)?;
+            let stderr_buffer = stderr_reader.join().unwrap_or_else(|_| Ok(Vec::new()))?;
+            Ok::<_, io::Error>((stdout_buffer, stderr_buffer))
+        });
+
+        let child = self
+            .running_commands
+            .lock()
+            .ok()
+            .and_then(|mut running_commands| running_commands.remove(&pid));
+        let status = match child {
+            Some(mut child) => child.wait()?,
+            None => return Err(io::Error::other("lost track of the `gh` process")),
+        };
+        let (stdout, stderr) = output?;
+        Ok(Output {
+            status,
+            stdout,
+            stderr,
+        })
+    }
+
+    /// Runs `gh` once, recording the call for the request inspector and whether it reached
+    /// GitHub.
+    fn run_gh_command_once(&self, args: &[&str], input: Option<&str>) -> io::Result<Output> {
+        let started = Instant::now();
+        let result = self.run_gh_process(args, input);
+        self.record_api_call(ApiCall {
+            started,
+            // Show the API path rather than the whole argument list where there is one
+            endpoint: args
+                .iter()
+                .find(|arg| arg.starts_with('/'))
+                .map_or_else(|| args.join(" "), |path| path.to_string()),
+            duration: started.elapsed(),
+            status: match &result {
+                Ok(output) if output.status.success() => "ok".to_string(),
+                Ok(output) => output
+                    .status
+                    .code()
+                    .map_or("killed".to_string(), |code| format!("exit {}", code)),
+                Err(_) => "spawn error".to_string(),
+            },
+            success: result.as_ref().is_ok_and(|output| output.status.success()),
+            payload_size: result.as_ref().map_or(0, |output| output.stdout.len()),
+        });
+        if let Ok(output) = &result {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            self.record_reachability(
+                output.status.success()
+                    || !GH_NETWORK_ERRORS
+                        .iter()
+                        .any(|network_error| stderr.contains(network_error)),
+            );
+        }
+        result
+    }
+
+    /// Executes a `gh` CLI command, writing `input` to its stdin, and returns its stdout.
+    fn run_gh_command_with_input(
+        &self,
+        args: &[&str],
+        input: Option<&str>,
+    ) -> color_eyre::Result<String> {
+        let stdout = self.run_gh_command_for_bytes(args, input)?;
+        Ok(String::from_utf8_lossy(&stdout).to_string())
+    }
+
+    /// Executes a `gh` CLI command, writing `input` to its stdin, and returns its stdout as
+    /// is, e.g. for a binary download.
+    fn run_gh_command_for_bytes(
+        &self,
+        args: &[&str],
+        input: Option<&str>,
+    ) -> color_eyre::Result<Vec<u8>> {
+        self.ensure_running()?;
+        let mut retries = 0;
+        let result = loop {
+            let result = self.run_gh_command_once(args, input);
+            // Only `gh api` reads are retried, as a change may have gone through before failing
+            let transient = args.first() == Some(&"api")
+                && !args.contains(&"-X")
+                && result.as_ref().is_ok_and(|output| {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    !output.status.success()
+                        && (GH_NETWORK_ERRORS
+                            .iter()
+                            .any(|network_error| stderr.contains(network_error))
+                            || TRANSIENT_STATUSES
+                                .iter()
+                                .any(|status| stderr.contains(&format!("HTTP {}", status))))
+                });
+            if !transient || !self.back_off(&mut retries) {
+                break result;
+            }
+        };
+        let output =
+            result.wrap_err(format!("Failed to execute `gh {}` command", args.join(" ")))?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        // Say what to do about a flag or command the installed GitHub CLI doesn't know
+        if !output.status.success()
+            && let Some(unknown) = stderr.lines().find(|line| {
+                line.starts_with("unknown flag") || line.starts_with("unknown command")
+            })
+        {
+            return Err(eyre!(
+                "The installed GitHub CLI can't run `gh {}` ({}), please upgrade it",
+                args.join(" "),
+                unknown.trim()
+            ));
+        }
+        if !output.status.success()
+            && stderr.contains(SAML_ENFORCEMENT_ERROR)
+            && let Some(org) = owner_named_in(args)
+        {
+            // Recent versions of `gh` tell the page to visit
+            let url = stderr
+                .split_whitespace()
+                .find(|word| word.starts_with("https://") && word.contains("/sso"))
+                .map(str::to_string);
+            return Err(eyre!(SsoAuthorization::new(org, url)));
+        }
+        if !output.status.success() {
+            return Err(eyre!(
+                "Command `gh {}` failed with exit code {}:\nStdout: {}\nStderr: {}",
+                args.join(" "),
+                output.status,
+                String::from_utf8_lossy(&output.stdout),
+                stderr
+            ));
+        }
+
+        Ok(output.stdout)
+    }
+
+    /// Sends a request with the native API client, recording it for the request inspector.
+    fn native_request(
+        &self,
+        api: &GhApi,
+        method: &str,
+        path: &str,
+        body: Option<&str>,
+    ) -> color_eyre::Result<ApiResponse> {
+        self.ensure_running()?;
+        let mut retries = 0;
+        let (result, success) = loop {
+            let started = Instant::now();
+            let result = api.request(method, path, body);
+            // A 304 means a cached response is still current
+            let success = matches!(
+                &result,
+                Ok(response) if (200..300).contains(&response.status) || response.status == 304
+            );
+            self.record_api_call(ApiCall {
+                started,
+                endpoint: path.to_string(),
+                duration: started.elapsed(),
+                status: match &result {
+                    Ok(response) => response.status.to_string(),
+                    Err(_) => "network error".to_string(),
+                },
+                success,
+                payload_size: result.as_ref().map_or(0, |response| response.body.len()),
+            });
+            self.record_reachability(result.is_ok());
+            // Only reads are retried, as a change may have gone through before failing
+            let transient = method == "GET"
+                && result
+                    .as_ref()
+                    .ok()
+                    .is_none_or(|response| TRANSIENT_STATUSES.contains(&response.status));
+            if !transient || !self.back_off(&mut retries) {
+                break (result, success);
+            }
+        };
+        let response = result?;
+        if let Some(rate_limit) = response.rate_limit {
+            self.set_rate_limit(rate_limit);
+        }
+
+        if response.status == 403
+            && response.body.contains(SAML_ENFORCEMENT_ERROR)
+            && let Some(org) = owner_named_in(&[path])
+        {
+            return Err(eyre!(SsoAuthorization::new(org, response.sso_url)));
+        }
+        if !success {
+            return Err(eyre!(
+                "`{} {}` failed with status {}:\n{}",
+                method,
+                path,
+                response.status,
+                response.body
+            ));
+        }
+        Ok(response)
+    }
+
+    /// Fetches a single page of an API endpoint and deserializes it.
+    fn api_get<T: DeserializeOwned>(&self, path: &str) -> color_eyre::Result<T> {
+        let body = match &self.api {
+            Some(api) => self.native_request(api, "GET", path, None)?.body,
+            None => self.run_gh_command(&["api", "-H", ACCEPT_HEADER, path])?,
+        };
+        serde_json::from_str(&body).wrap_err(format!("Failed to parse API response of {}", path))
+    }
+
+    /// Fetches every page of an API endpoint and deserializes each page.
+    fn api_get_pages<T: DeserializeOwned>(&self, path: &str) -> color_eyre::Result<Vec<T>> {
+        match &self.api {
+            Some(api) => {
+                let mut pages = Vec::new();
+                let mut next_page = Some(path.to_string());
+                while let Some(url) = next_page {
+                    let response = self.native_request(api, "GET", &url, None)?;
+                    pages.push(
+                        serde_json::from_str(&response.body)
+                            .wrap_err(format!("Failed to parse API response of {}", url))?,
+                    );
+                    next_page = response.next_page;
+                }
+                Ok(pages)
+            }
+            None => {
+                // `gh api --paginate` prints the JSON document of each page back to back
+                let output =
+                    self.run_gh_command(&["api", "--paginate", "-H", ACCEPT_HEADER, path])?;
+                serde_json::Deserializer::from_str(&output)
+                    .into_iter()
+                    .collect::<Result<Vec<T>, _>>()
+                    .wrap_err(format!("Failed to parse API response of {}", path))
+            }
+        }
+    }
+
+    /// Sends a request that changes state, such as a POST with a JSON body, and returns the
+    /// body of the response.
+    fn api_send(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> color_eyre::Result<String> {
+        let body = body.map(|body| body.to_string());
+        match &self.api {
+            Some(api) => Ok(self
+                .native_request(api, method, path, body.as_deref())?
+                .body),
+            None => {
+                let mut args = vec!["api", "-X", method, "-H", ACCEPT_HEADER, path];
+                if body.is_some() {
+                    args.extend(["--input", "-"]);
+                }
+                self.run_gh_command_with_input(&args, body.as_deref())
+            }
+        }
+    }
+
+    /// Fetches workflow runs and jobs for all monitored repositories concurrently.
+    ///
+    /// The jobs of completed runs that haven't been updated since the previous successful
+    /// fetch are not fetched again; those runs are listed in `unchanged_runs` instead.
+    pub fn fetch_github_workflow_data(
+        &self,
+        cancel: &CancelFlag,
+    ) -> color_eyre::Result<WorkflowData> {
+        let followed_repos = self
+            .followed_repos
+            .lock()
+            .map(|followed_repos| followed_repos.clone())
+            .unwrap_or_default();
+        let repos: Vec<&RepoInfo> = self
+            .repos
+            .iter()
+            .filter(|repo_info| {
+                !followed_repos.contains(&repo_info.full_name().to_ascii_lowercase())
+            })
+            .collect();
+        let results: Vec<color_eyre::Result<(WorkflowData, Vec<u64>)>> = thread::scope(|scope| {
+            let handles: Vec<_> = repos
+                .iter()
+                .map(|&repo_info| {
+                    scope.spawn(move || self.fetch_repo_workflow_data(repo_info, cancel))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| Err(eyre!("Workflow fetch thread panicked")))
+                })
+                .collect()
+        });
+        // The result of a cancelled fetch is thrown away
+        cancel.check()?;
+
+        // `gh api` doesn't show the rate limit headers, so the budget is checked separately
+        if self.api.is_none() {
+            let _ = self.refresh_rate_limit();
+        }
+
+        let mut workflow_data = WorkflowData {
+            runs: Vec::new(),
+            jobs: Vec::new(),
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        };
+        let mut unfetched_jobs = Vec::new();
+        let mut first_error = None;
+        for (repo_info, result) in repos.iter().zip(results) {
+            match result {
+                Ok((data, unfetched)) => {
+                    workflow_data.runs.extend(data.runs);
+                    workflow_data.jobs.extend(data.jobs);
+                    workflow_data.unchanged_runs.extend(data.unchanged_runs);
+                    workflow_data.warnings.extend(data.warnings);
+                    unfetched_jobs.extend(unfetched);
+                }
+                // The other repositories are still worth showing
+                Err(e) => {
+                    workflow_data.warnings.push(format!(
+                        "Failed to fetch the runs of {}: {}",
+                        repo_info.full_name(),
+                        first_line(&e)
+                    ));
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        if let Some(e) = first_error.filter(|_| workflow_data.runs.is_empty()) {
+            return Err(e);
+        }
+
+        // The runs whose jobs are still to be fetched are fetched again next time
+        workflow_data.fetched_runs = workflow_data
+            .runs
+            .iter()
+            .filter(|run| run.status == "completed" && !unfetched_jobs.contains(&run.id))
+            .map(|run| (run.id, run.updated_at.clone()))
+            .collect();
+        Ok(workflow_data)
+    }
+
+    /// Fetches the most recent workflow runs of a single repository and their jobs, along with
+    /// the runs whose jobs couldn't be fetched. Those runs are listed as unchanged so that the
+    /// jobs fetched before are kept, with a warning. Stops before the next request once
+    /// `cancel` is set.
+    fn fetch_repo_workflow_data(
+        &self,
+        repo_info: &RepoInfo,
+        cancel: &CancelFlag,
+    ) -> color_eyre::Result<(WorkflowData, Vec<u64>)> {
+        cancel.check()?;
+        let mut runs_path = format!(
+            "/repos/{}/{}/actions/runs?per_page={}",
+            repo_info.owner.login,
+            repo_info.name,
+            if self.latest { 1 } else { 3 }
+        );
+        if self.user {
+            runs_path.push_str(&format!(
+                "&actor={}",
+                encode_query_value(&self.current_user)
+            ));
+        }
+        if let Some(branch) = self.branch() {
+            runs_path.push_str(&format!("&branch={}", encode_query_value(&branch)));
+        }
+        let runs_page: ApiWorkflowRunsPage = self.api_get(&runs_path)?;
+        let fetched_runs = self
+            .fetched_runs
+            .lock()
+            .map(|fetched_runs| fetched_runs.clone())
+            .unwrap_or_default();
+
+        let mut workflow_runs: Vec<GithubWorkflowRun> = Vec::new();
+        let mut all_jobs: Vec<GithubJob> = Vec::new();
+        let mut unchanged_runs: Vec<u64> = Vec::new();
+        let mut warnings = Vec::new();
+        let mut unfetched_jobs = Vec::new();
+        for run in runs_page.workflow_runs {
+            let mut run = GithubWorkflowRun::from(run);
+
+            // The jobs of a completed run only change when the run is updated, e.g. re-run
+            if run.status == "completed" && fetched_runs.get(&run.id) == Some(&run.updated_at) {
+                unchanged_runs.push(run.id);
+                workflow_runs.push(run);
+                continue;
+            }
+            cancel.check()?;
+            self.resolve_pull_request(&mut run);
+
+            let (jobs, total_jobs) = match self.fetch_listed_jobs(&run, self.is_expanded(run.id)) {
+                Ok(listed) => listed,
+                Err(e) => {
+                    warnings.push(format!(
+                        "Failed to fetch the jobs of {} run {}: {}",
+                        run.name,
+                        run.id,
+                        first_line(&e)
+                    ));
+                    unfetched_jobs.push(run.id);
+                    unchanged_runs.push(run.id);
+                    workflow_runs.push(run);
+                    continue;
+                }
+            };
+            run.total_jobs = Some(total_jobs).filter(|&total| total > MAX_JOBS_PER_RUN);
+            all_jobs.extend(jobs);
+            self.resolve_called_workflows(&run, &mut all_jobs);
+            workflow_runs.push(run);
+        }
+
+        Ok((
+            WorkflowData {
+                runs: workflow_runs,
+                jobs: all_jobs,
+                unchanged_runs,
+                warnings,
+                fetched_runs: HashMap::new(),
+            },
+            unfetched_jobs,
+        ))
+    }
+
+    /// Fetches the running and concluded jobs of a run, all of them or only the first
+    /// [`MAX_JOBS_PER_RUN`], along with how many jobs the run has in total.
+    fn fetch_listed_jobs(
+        &self,
+        run: &GithubWorkflowRun,
+        all: bool,
+    ) -> color_eyre::Result<(Vec<GithubJob>, u64)> {
+        let path = format!(
+            "/repos/{}/actions/runs/{}/jobs?per_page={}",
+            run.repo, run.id, MAX_JOBS_PER_RUN
+        );
+        let jobs_pages: Vec<ApiJobsPage> = if all {
+            self.api_get_pages(&path)?
+        } else {
+            vec![self.api_get(&path)?]
+        };
+        let total_jobs = jobs_pages.first().map_or(0, |page| page.total_count);
+        let jobs = jobs_pages
+            .into_iter()
+            .flat_map(|page| page.jobs)
+            // Queued jobs are kept to spot those no runner can pick up
+            .filter(|job| {
+                matches!(
+                    job.status.as_str(),
+                    "in_progress" | "queued" | "waiting" | "completed"
+                )
+            })
+            .map(|job| job.into_job(run))
+            .collect();
+        Ok((jobs, total_jobs))
+    }
+
+    /// Fetches every running and concluded job of a run that has too many for a refresh, and
+    /// keeps fetching all of them with the following refreshes.
+    pub fn expand_run(&self, run: &GithubWorkflowRun) -> color_eyre::Result<Vec<GithubJob>> {
+        let (mut jobs, _) = self.fetch_listed_jobs(run, true)?;
+        self.resolve_called_workflows(run, &mut jobs);
+        if let Ok(mut expanded_runs) = self.expanded_runs.lock() {
+            expanded_runs.insert(run.id);
+        }
+        Ok(jobs)
+    }
+
+    /// Whether all the jobs of a run are fetched, however many there are.
+    pub fn is_expanded(&self, run_id: u64) -> bool {
+        self.expanded_runs
+            .lock()
+            .is_ok_and(|expanded_runs| expanded_runs.contains(&run_id))
+    }
+
+    /// Fetches a single run of a repository, given as `owner/name`, with all of its jobs.
+    pub fn fetch_run(&self, repo: &str, run_id: u64) -> color_eyre::Result<RunDetails> {
+        let run: ApiWorkflowRun =
+            self.api_get(&format!("/repos/{}/actions/runs/{}", repo, run_id))?;
+        let mut run = GithubWorkflowRun::from(run);
+        self.resolve_pull_request(&mut run);
+        self.fetch_run_details(run)
+    }
+
+    /// Fetches the runs of a repository created since `since`, with all of their jobs.
+    pub fn fetch_runs_since(
+        &self,
+        repo_info: &RepoInfo,
+        since: DateTime<Utc>,
+    ) -> color_eyre::Result<Vec<RunDetails>> {
+        let runs_pages: Vec<ApiWorkflowRunsPage> = self.api_get_pages(&format!(
+            "/repos/{}/{}/actions/runs?per_page=100&created={}",
+            repo_info.owner.login,
+            repo_info.name,
+            encode_query_value(&format!(">={}", since.format("%Y-%m-%dT%H:%M:%SZ")))
+        ))?;
+        runs_pages
+            .into_iter()
+            .flat_map(|page| page.workflow_runs)
+            .map(|run| self.fetch_run_details(GithubWorkflowRun::from(run)))
+            .collect()
+    }
+
+    /// Fetches every job of a run.
+    fn fetch_run_details(&self, run: GithubWorkflowRun) -> color_eyre::Result<RunDetails> {
+        let jobs_pages: Vec<ApiJobsPage> = self.api_get_pages(&format!(
+            "/repos/{}/actions/runs/{}/jobs?per_page=100",
+            run.repo, run.id
+        ))?;
+        let mut jobs: Vec<GithubJob> = jobs_pages
+            .into_iter()
+            .flat_map(|page| page.jobs)
+            .map(|job| job.into_job(&run))
+            .collect();
+        self.resolve_called_workflows(&run, &mut jobs);
+        Ok(RunDetails { run, jobs })
+    }
+
+    /// Fills in the title and link of the pull request a run was triggered for, fetching
+    /// each pull request only once. A pull request that can't be fetched keeps its number
+    /// only and is tried again with the next run.
+    fn resolve_pull_request(&self, run: &mut GithubWorkflowRun) {
+        let Some(pull_request) = run.pull_request.as_mut() else {
+            return;
+        };
+        let key = (run.repo.clone(), pull_request.number);
+        if let Some(cached) = self
+            .pull_requests
+            .lock()
+            .ok()
+            .and_then(|pull_requests| pull_requests.get(&key).cloned())
+        {
+            *pull_request = cached;
+            return;
+        }
+        if let Ok(fetched) = self.api_get::<ApiPullRequest>(&format!(
+            "/repos/{}/pulls/{}",
+            run.repo, pull_request.number
+        )) {
+            pull_request.title = fetched.title;
+            pull_request.html_url = fetched.html_url;
+            if let Ok(mut pull_requests) = self.pull_requests.lock() {
+                pull_requests.insert(key, pull_request.clone());
+            }
+        }
+    }
+
+    /// Fills in the reusable workflow each job of `run` comes from, reading the run's workflow
+    /// file to find which jobs call which workflow. Jobs of other runs are left untouched.
+    fn resolve_called_workflows(&self, run: &GithubWorkflowRun, jobs: &mut [GithubJob]) {
+        if !run.calls_workflows {
+            return;
+        }
+        let key = format!("{}/{}@{}", run.repo, run.path, run.head_sha);
+        let cached = self
+            .workflow_calls
+            .lock()
+            .ok()
+            .and_then(|workflow_calls| workflow_calls.get(&key).cloned());
+        let calls = match cached {
+            Some(calls) => calls,
+            None => {
+                let Ok(calls) = self.fetch_workflow_calls(&run.repo, &run.path, &run.head_sha)
+                else {
+                    return;
+                };
+                if let Ok(mut workflow_calls) = self.workflow_calls.lock() {
+                    workflow_calls.insert(key, calls.clone());
+                }
+                calls
+            }
+        };
+
+        for job in jobs.iter_mut().filter(|job| job.run_id == run.id) {
+            // Jobs of a reusable workflow are named `<calling job> / <called job>`, with the
+            // matrix values of the calling job in parentheses
+            let Some((caller, _)) = job.name.split_once(" / ") else {
+                continue;
+            };
+            let uses = calls.get(caller).or_else(|| {
+                caller
+                    .rsplit_once(" (")
+                    .and_then(|(caller, _)| calls.get(caller))
+            });
+            job.called_workflow = uses.and_then(|uses| CalledWorkflow::from_uses(uses, run));
+        }
+    }
+
+    /// Reads a workflow file at a commit and returns the `uses` of its jobs that call reusable
+    /// workflows, by both the id and the name of each calling job.
+    fn fetch_workflow_calls(
+        &self,
+        repo: &str,
+        path: &str,
+        sha: &str,
+    ) -> color_eyre::Result<WorkflowCalls> {
+        let content: ApiContent =
+            self.api_get(&format!("/repos/{}/contents/{}?ref={}", repo, path, sha))?;
+        // The content is base64 encoded, wrapped over several lines
+        let encoded: String = content.content.split_whitespace().collect();
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .wrap_err("Failed to decode the workflow file")?;
+        let workflow: WorkflowFile =
+            serde_yaml::from_slice(&decoded).wrap_err("Failed to parse the workflow file")?;
+
+        let mut calls = HashMap::new();
+        for (id, job) in workflow.jobs {
+            let Some(uses) = job.uses else {
+                continue;
+            };
+            if let Some(name) = job.name {
+                calls.insert(name, uses.clone());
+            }
+            calls.insert(id, uses);
+        }
+        Ok(calls)
+    }
+
+    /// Fetches the self-hosted runners registered for the monitored repositories.
+    pub fn fetch_runners(&self) -> color_eyre::Result<Vec<GithubRunner>> {
+        let mut runners = Vec::new();
+        for repo_info in &self.repos {
+            let runners_pages: Vec<ApiRunnersPage> = self.api_get_pages(&format!(
+                "/repos/{}/{}/actions/runners?per_page=100",
+                repo_info.owner.login, repo_info.name
+            ))?;
+            runners.extend(
+                runners_pages
+                    .into_iter()
+                    .flat_map(|page| page.runners)
+                    .map(|runner| GithubRunner {
+                        id: runner.id,
+                        name: runner.name,
+                        repo: repo_info.full_name(),
+                        os: runner.os,
+                        status: runner.status,
+                        busy: runner.busy,
+                        labels: runner.labels.into_iter().map(|label| label.name).collect(),
+                    }),
+            );
+        }
+        Ok(runners)
+    }
+
+    /// Fetches the branches and then the tags of a repository.
+    pub fn fetch_refs(&self, repo_info: &RepoInfo) -> color_eyre::Result<Vec<GitRef>> {
+        let mut refs = Vec::new();
+        for (kind, endpoint) in [(RefKind::Branch, "branches"), (RefKind::Tag, "tags")] {
+            let pages: Vec<Vec<ApiNamedRef>> = self.api_get_pages(&format!(
+                "/repos/{}/{}/{}?per_page=100",
+                repo_info.owner.login, repo_info.name, endpoint
+            ))?;
+            refs.extend(pages.into_iter().flatten().map(|named| GitRef {
+                name: named.name,
+                kind,
+            }));
+        }
+        Ok(refs)
+    }
+
+    /// Finds whether `name`, the ref of a run in `repo` as GitHub reports it, is a tag rather
+    /// than a branch, such as the version a push of a tag ran for.
+    pub fn fetch_ref_kind(&self, repo: &str, name: &str) -> color_eyre::Result<RefKind> {
+        let encoded: Vec<String> = name.split('/').map(encode_query_value).collect();
+        // Unlike looking up the ref itself, listing those it prefixes doesn't fail on none
+        let tags: Vec<ApiGitRef> = self.api_get(&format!(
+            "/repos/{}/git/matching-refs/tags/{}",
+            repo,
+            encoded.join("/")
+        ))?;
+        let tag = format!("refs/tags/{}", name);
+        Ok(if tags.iter().any(|found| found.git_ref == tag) {
+            RefKind::Tag
+        } else {
+            RefKind::Branch
+        })
+    }
+
+    /// Fetches the names of the branches of a repository.
+    pub fn fetch_branches(&self, repo_info: &RepoInfo) -> color_eyre::Result<Vec<String>> {
+        let pages: Vec<Vec<ApiNamedRef>> = self.api_get_pages(&format!(
+            "/repos/{}/{}/branches?per_page=100",
+            repo_info.owner.login, repo_info.name
+        ))?;
+        Ok(pages
+            .into_iter()
+            .flatten()
+            .map(|named| named.name)
+            .collect())
+    }
+
+    /// Fetches the workflows defined in a repository.
+    pub fn fetch_workflows(&self, repo_info: &RepoInfo) -> color_eyre::Result<Vec<GithubWorkflow>> {
+        let workflows_pages: Vec<ApiWorkflowsPage> = self.api_get_pages(&format!(
+            "/repos/{}/{}/actions/workflows?per_page=100",
+            repo_info.owner.login, repo_info.name
+        ))?;
+        Ok(workflows_pages
+            .into_iter()
+            .flat_map(|page| page.workflows)
+            .collect())
+    }
+
+    /// Triggers a `workflow_dispatch` event for a workflow on the given ref.
+    pub fn dispatch_workflow(
+        &self,
+        repo_info: &RepoInfo,
+        workflow_id: u64,
+        git_ref: &str,
+        inputs: &BTreeMap<String, String>,
+    ) -> color_eyre::Result<()> {
+        self.api_send(
+            "POST",
+            &format!(
+                "/repos/{}/{}/actions/workflows/{}/dispatches",
+                repo_info.owner.login, repo_info.name, workflow_id
+            ),
+            Some(&serde_json::json!({ "ref": git_ref, "inputs": inputs })),
+        )?;
+        Ok(())
+    }
+
+    /// Fetches the most recent commits of the current branch in every monitored repository
+    /// that has it, newest first, with the combined outcome of their workflow runs.
+    pub fn fetch_branch_commits(&self) -> color_eyre::Result<Vec<CommitStatus>> {
+        if self.current_branch.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut commits = Vec::new();
+        for repo_info in &self.repos {
+            let runs_page: ApiWorkflowRunsPage = self.api_get(&format!(
+                "/repos/{}/{}/actions/runs?branch={}&per_page=100",
+                repo_info.owner.login,
+                repo_info.name,
+                encode_query_value(&self.current_branch)
+            ))?;
+
+            // Runs are listed newest first, so commits are collected in the same order
+            let mut repo_commits: Vec<(CommitStatus, Vec<ApiWorkflowRun>)> = Vec::new();
+            for run in runs_page.workflow_runs {
+                let Some(sha) = run.head_sha.clone() else {
+                    continue;
+                };
+                match repo_commits
+                    .iter()
+                    .position(|(commit, _)| commit.sha == sha)
+                {
+                    Some(index) => repo_commits[index].1.push(run),
+                    None if repo_commits.len() < MAX_TRACKED_COMMITS => {
+                        let commit = CommitStatus {
+                            repo: run.repository.full_name.clone(),
+                            sha,
+                            title: run
+                                .head_commit
+                                .as_ref()
+                                .and_then(|commit| commit.message.lines().next())
+                                .unwrap_or_default()
+                                .to_string(),
+                            state: CommitState::Neutral,
+                        };
+                        repo_commits.push((commit, vec![run]));
+                    }
+                    None => {}
+                }
+            }
+            commits.extend(repo_commits.into_iter().map(|(mut commit, runs)| {
+                commit.state = combined_state(&runs);
+                commit
+            }));
+        }
+        Ok(commits)
+    }
+
+    /// Fetches a page of the runs of the workflow at `path` in `repo`, newest first, counting
+    /// pages from 1.
+    pub fn fetch_workflow_history(
+        &self,
+        repo: &str,
+        path: &str,
+        page: u64,
+    ) -> color_eyre::Result<WorkflowHistoryPage> {
+        // Workflows can be referred to by the name of their file
+        let file = path.rsplit('/').next().unwrap_or(path);
+        let runs_page: ApiWorkflowRunsPage = self.api_get(&format!(
+            "/repos/{}/actions/workflows/{}/runs?per_page={}&page={}",
+            repo,
+            encode_query_value(file),
+            WORKFLOW_HISTORY_PAGE_SIZE,
+            page
+        ))?;
+        Ok(WorkflowHistoryPage::from(runs_page))
+    }
+
+    /// Fetches a page of the completed runs of every workflow of `repo`, newest first, counting
+    /// pages from 1.
+    pub fn fetch_repo_history(
+        &self,
+        repo: &str,
+        page: u64,
+    ) -> color_eyre::Result<WorkflowHistoryPage> {
+        let runs_page: ApiWorkflowRunsPage = self.api_get(&format!(
+            "/repos/{}/actions/runs?status=completed&per_page={}&page={}",
+            repo, BACKFILL_PAGE_SIZE, page
+        ))?;
+        Ok(WorkflowHistoryPage::from(runs_page))
+    }
+
+    /// Reruns a single job, along with the jobs that depend on it.
+    pub fn rerun_job(&self, job: &GithubJob) -> color_eyre::Result<()> {
+        self.api_send(
+            "POST",
+            &format!("/repos/{}/actions/jobs/{}/rerun", job.repo, job.id),
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Reruns every job of a run.
+    pub fn rerun_run(&self, repo: &str, run_id: u64) -> color_eyre::Result<()> {
+        self.api_send(
+            "POST",
+            &format!("/repos/{}/actions/runs/{}/rerun", repo, run_id),
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Reruns the failed jobs of a run, along with the jobs that depend on them.
+    pub fn rerun_failed_jobs(&self, repo: &str, run_id: u64) -> color_eyre::Result<()> {
+        self.api_send(
+            "POST",
+            &format!("/repos/{}/actions/runs/{}/rerun-failed-jobs", repo, run_id),
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Cancels a run that is still queued or in progress, along with all of its jobs.
+    pub fn cancel_run(&self, repo: &str, run_id: u64) -> color_eyre::Result<()> {
+        self.api_send(
+            "POST",
+            &format!("/repos/{}/actions/runs/{}/cancel", repo, run_id),
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Fetches the Actions minutes the organization or user `owner` used in its current
+    /// billing cycle.
+    pub fn fetch_actions_billing(&self, owner: &str) -> color_eyre::Result<ActionsBilling> {
+        // Only one of the endpoints applies, depending on what kind of account the owner is
+        self.api_get(&format!("/orgs/{}/settings/billing/actions", owner))
+            .or_else(|_| self.api_get(&format!("/users/{}/settings/billing/actions", owner)))
+    }
+
+    /// Lists the names of the installed `gh` extensions.
+    pub fn fetch_extensions(&self) -> color_eyre::Result<Vec<String>> {
+        let output = self.run_gh_command(&["extension", "list"])?;
+        Ok(gh_extensions::parse_installed(&output))
+    }
+
+    /// Runs a command of a `gh` extension to completion and returns its output.
+    pub fn run_extension(&self, args: &[String]) -> color_eyre::Result<String> {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        self.run_gh_command(&args)
+    }
+
+    /// Deletes a run along with its logs and artifacts.
+    pub fn delete_run(&self, repo: &str, run_id: u64) -> color_eyre::Result<()> {
+        self.api_send(
+            "DELETE",
+            &format!("/repos/{}/actions/runs/{}", repo, run_id),
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// Deletes the completed runs of the workflow at `path` in `repo` that were created
+    /// before `before`, returning how many were deleted.
+    pub fn delete_runs_before(
+        &self,
+        repo: &str,
+        path: &str,
+        before: DateTime<Utc>,
+    ) -> color_eyre::Result<usize> {
+        let file = path.rsplit('/').next().unwrap_or(path);
+        // Every page is listed before deleting, as deleting would shift the later pages
+        let runs_pages: Vec<ApiWorkflowRunsPage> = self.api_get_pages(&format!(
+            "/repos/{}/actions/workflows/{}/runs?per_page=100&status=completed&created={}",
+            repo,
+            encode_query_value(file),
+            encode_query_value(&format!("<{}", before.format("%Y-%m-%dT%H:%M:%SZ")))
+        ))?;
+        let mut deleted = 0;
+        for run in runs_pages.into_iter().flat_map(|page| page.workflow_runs) {
+            self.delete_run(repo, run.id)
+                .wrap_err(format!("Deleted {} runs, then failed", deleted))?;
+            deleted += 1;
+        }
+        Ok(deleted)
+    }
+
+    /// Approves the deployments a run is waiting for whose environments the authenticated
+    /// user may approve, returning the names of those environments.
+    pub fn approve_pending_deployments(
+        &self,
+        repo: &str,
+        run_id: u64,
+    ) -> color_eyre::Result<Vec<String>> {
+        let path = format!(
+            "/repos/{}/actions/runs/{}/pending_deployments",
+            repo, run_id
+        );
+        let pending: Vec<ApiPendingDeployment> = self.api_get(&path)?;
+        if pending.is_empty() {
+            return Err(eyre!(
+                "the run isn't waiting for a deployment to be approved"
+            ));
+        }
+        let (approvable, others): (Vec<_>, Vec<_>) = pending
+            .into_iter()
+            .partition(|deployment| deployment.current_user_can_approve);
+        if approvable.is_empty() {
+            return Err(eyre!(
+                "you aren't a required reviewer of {}",
+                others
+                    .iter()
+                    .map(|deployment| deployment.environment.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        let body = serde_json::json!({
+            "environment_ids": approvable
+                .iter()
+                .map(|deployment| deployment.environment.id)
+                .collect::<Vec<_>>(),
+            "state": "approved",
+            "comment": "Approved from lazyactions",
+        });
+        self.api_send("POST", &path, Some(&body))?;
+        Ok(approvable
+            .into_iter()
+            .map(|deployment| deployment.environment.name)
+            .collect())
+    }
+
+    /// Fetches the plain text log of a job, or only about `tail` bytes of its end. The native
+    /// client downloads it a chunk at a time, keeping track of how far along it is for
+    /// [`GhCli::log_progress`].
+    pub fn fetch_job_log(&self, job: &GithubJob, tail: Option<u64>) -> color_eyre::Result<JobLog> {
+        let path = format!("/repos/{}/actions/jobs/{}/logs", job.repo, job.id);
+        let range = tail.map(|tail| format!("Range: bytes=-{}", tail));
+        let Some(api) = &self.api else {
+            // `gh api` hands the log over at once, without the size of the whole of it
+            let mut args = vec!["api", path.as_str()];
+            if let Some(range) = &range {
+                args.extend(["-H", range.as_str()]);
+            }
+            let text = self.run_gh_command(&args)?;
+            let partial = tail.is_some_and(|tail| text.len() as u64 >= tail);
+            return Ok(JobLog::new(text.as_bytes(), None, partial));
+        };
+
+        self.ensure_running()?;
+        let started = Instant::now();
+        let progress = |loaded_bytes, expected_bytes| {
+            self.set_log_progress(
+                job.id,
+                Some(LogProgress {
+                    loaded_bytes,
+                    expected_bytes,
+                }),
+            );
+        };
+        progress(0, None);
+        let result = api.download(&path, tail, &progress);
+        self.set_log_progress(job.id, None);
+        let success = matches!(&result, Ok(download) if (200..300).contains(&download.status));
+        self.record_api_call(ApiCall {
+            started,
+            endpoint: path.clone(),
+            duration: started.elapsed(),
+            status: match &result {
+                Ok(download) => download.status.to_string(),
+                Err(_) => "network error".to_string(),
+            },
+            success,
+            payload_size: result.as_ref().map_or(0, |download| download.bytes.len()),
+        });
+        self.record_reachability(result.is_ok());
+        let download = result?;
+        if let Some(rate_limit) = download.rate_limit {
+            self.set_rate_limit(rate_limit);
+        }
+        if !success {
+            return Err(eyre!(
+                "`GET {}` failed with status {}:\n{}",
+                path,
+                download.status,
+                String::from_utf8_lossy(&download.bytes)
+            ));
+        }
+        let partial = match download.total_bytes {
+            Some(total_bytes) => total_bytes > download.bytes.len() as u64,
+            None => tail.is_some_and(|tail| download.bytes.len() as u64 >= tail),
+        };
+        Ok(JobLog::new(&download.bytes, download.total_bytes, partial))
+    }
+
+    /// Fetches the JSON the API returns for a job and for its run, pretty-printed below the
+    /// endpoint of each, to see what the summarized fields leave out.
+    pub fn fetch_raw_json(&self, job: &GithubJob) -> color_eyre::Result<String> {
+        let mut documents = Vec::new();
+        for path in [
+            format!("/repos/{}/actions/jobs/{}", job.repo, job.id),
+            format!("/repos/{}/actions/runs/{}", job.repo, job.run_id),
+        ] {
+            let document: serde_json::Value = self.api_get(&path)?;
+            documents.push(format!(
+                "// GET {}\n{}",
+                path,
+                serde_json::to_string_pretty(&document)?
+            ));
+        }
+        Ok(documents.join("\n\n"))
+    }
+
+    /// Fetches the markdown summary a job published to its check run, which is empty when the
+    /// job didn't publish one. An Actions job shares its id with its check run.
+    pub fn fetch_job_summary(&self, job: &GithubJob) -> color_eyre::Result<String> {
+        let check_run: ApiCheckRun =
+            self.api_get(&format!("/repos/{}/check-runs/{}", job.repo, job.id))?;
+        let Some(output) = check_run.output else {
+            return Ok(String::new());
+        };
+        let mut sections = Vec::new();
+        if let Some(title) = output.title.filter(|title| !title.trim().is_empty()) {
+            sections.push(format!("# {}", title.trim()));
+        }
+        sections.extend(
+            [output.summary, output.text]
+                .into_iter()
+                .flatten()
+                .filter(|section| !section.trim().is_empty()),
+        );
+        Ok(sections.join("\n\n"))
+    }
+
+    /// Fetches the Actions settings of a repository: which actions may run, what the
+    /// `GITHUB_TOKEN` can do and how workflows from forks are treated.
+    ///
+    /// Most settings need admin access to read, so only failing to read whether Actions is
+    /// enabled at all is an error; other settings that can't be read have no value.
+    pub fn fetch_actions_settings(
+        &self,
+        repo_info: &RepoInfo,
+    ) -> color_eyre::Result<Vec<ActionsSetting>> {
+        let permissions_path = format!(
+            "/repos/{}/{}/actions/permissions",
+            repo_info.owner.login, repo_info.name
+        );
+        let permissions: ApiActionsPermissions = self.api_get(&permissions_path)?;
+        let yes_no = |value: bool| if value { "yes" } else { "no" }.to_string();
+
+        let allowed_actions = match permissions.allowed_actions.as_deref() {
+            Some("all") => Some("all actions and reusable workflows".to_string()),
+            Some("local_only") => Some(format!(
+                "only actions and reusable workflows in {}",
+                repo_info.owner.login
+            )),
+            Some("selected") => self
+                .api_get::<ApiSelectedActions>(&format!("{}/selected-actions", permissions_path))
+                .ok()
+                .map(|selected| {
+                    let mut allowed = vec![format!("actions in {}", repo_info.owner.login)];
+                    if selected.github_owned_allowed == Some(true) {
+                        allowed.push("GitHub-owned actions".to_string());
+                    }
+                    if selected.verified_allowed == Some(true) {
+                        allowed.push("actions by verified creators".to_string());
+                    }
+                    allowed.extend(selected.patterns_allowed);
+                    format!("selected: {}", allowed.join(", "))
+                }),
+            Some(other) => Some(other.to_string()),
+            None => None,
+        };
+        let workflow_permissions: Option<ApiWorkflowPermissions> =
+            self.api_get(&format!("{}/workflow", permissions_path)).ok();
+        let fork_pr_approval: Option<ApiForkPrApproval> = self
+            .api_get(&format!(
+                "{}/fork-pr-contributor-approval",
+                permissions_path
+            ))
+            .ok();
+
+        let mut settings = vec![
+            ActionsSetting {
+                name: "Actions",
+                value: Some(
+                    if permissions.enabled {
+                        "enabled"
+                    } else {
+                        "disabled"
+                    }
+                    .to_string(),
+                ),
+            },
+            ActionsSetting {
+                name: "Allowed actions",
+                value: allowed_actions,
+            },
+            ActionsSetting {
+                name: "Default GITHUB_TOKEN permissions",
+                value: workflow_permissions.as_ref().map(|permissions| {
+                    match permissions.default_workflow_permissions.as_str() {
+                        "read" => "read repository contents and packages".to_string(),
+                        "write" => "read and write".to_string(),
+                        other => other.to_string(),
+                    }
+                }),
+            },
+            ActionsSetting {
+                name: "Workflows can approve pull requests",
+                value: workflow_permissions
+                    .map(|permissions| yes_no(permissions.can_approve_pull_request_reviews)),
+            },
+            ActionsSetting {
+                name: "Approval for fork pull requests",
+                value: fork_pr_approval.map(|approval| match approval.approval_policy.as_str() {
+                    "first_time_contributors_new_to_github" => {
+                        "required for first-time contributors new to GitHub".to_string()
+                    }
+                    "first_time_contributors" => "required for first-time contributors".to_string(),
+                    "all_external_contributors" => {
+                        "required for all outside collaborators".to_string()
+                    }
+                    other => other.to_string(),
+                }),
+            },
+        ];
+
+        // Only private repositories have settings for workflows from forks
+        if let Ok(fork_workflows) = self.api_get::<ApiPrivateForkPrWorkflows>(&format!(
+            "{}/fork-pr-workflows-private-repos",
+            permissions_path
+        )) {
+            settings.extend([
+                ActionsSetting {
+                    name: "Run workflows from fork pull requests",
+                    value: Some(yes_no(fork_workflows.run_workflows_from_fork_pull_requests)),
+                },
+                ActionsSetting {
+                    name: "Send write tokens to fork workflows",
+                    value: Some(yes_no(fork_workflows.send_write_tokens_to_workflows)),
+                },
+                ActionsSetting {
+                    name: "Send secrets to fork workflows",
+                    value: Some(yes_no(fork_workflows.send_secrets_and_variables)),
+                },
+                ActionsSetting {
+                    name: "Require approval for fork workflows",
+                    value: Some(yes_no(
+                        fork_workflows.require_approval_for_fork_pr_workflows,
+                    )),
+                },
+            ]);
+        }
+        Ok(settings)
+    }
+
+    /// Fetches what is deployed to each environment of a repository, along with the runs
+    /// waiting for approval to deploy to it.
+    pub fn fetch_environment_statuses(
+        &self,
+        repo_info: &RepoInfo,
+    ) -> color_eyre::Result<Vec<EnvironmentStatus>> {
+        let repo_path = format!("/repos/{}/{}", repo_info.owner.login, repo_info.name);
+        let environments_pages: Vec<ApiEnvironmentsPage> =
+            self.api_get_pages(&format!("{}/environments?per_page=100", repo_path))?;
+
+        // Runs blocked on a protection rule report which environments they are waiting for
+        let waiting_runs: ApiWorkflowRunsPage = self.api_get(&format!(
+            "{}/actions/runs?status=waiting&per_page=100",
+            repo_path
+        ))?;
+        let mut pending: BTreeMap<String, Vec<PendingApproval>> = BTreeMap::new();
+        for run in waiting_runs.workflow_runs {
+            let pending_deployments: Vec<ApiPendingDeployment> = self.api_get(&format!(
+                "{}/actions/runs/{}/pending_deployments",
+                repo_path, run.id
+            ))?;
+            for deployment in pending_deployments {
+                pending
+                    .entry(deployment.environment.name)
+                    .or_default()
+                    .push(PendingApproval {
+                        run_id: run.id,
+                        run_name: run.name.clone().unwrap_or_default(),
+                        head_branch: run.head_branch.clone().unwrap_or_default(),
+                        html_url: run.html_url.clone().unwrap_or_default(),
+                    });
+            }
+        }
+
+        let mut statuses = Vec::new();
+        for environment in environments_pages
+            .into_iter()
+            .flat_map(|page| page.environments)
+        {
+            let deployments: Vec<ApiDeployment> = self.api_get(&format!(
+                "{}/deployments?environment={}&per_page={}",
+                repo_path,
+                encode_query_value(&environment.name),
+                MAX_DEPLOYMENTS_SEARCHED
+            ))?;
+
+            // Walk back from the latest deployment until one that succeeded is found
+            let mut last_deploy = None;
+            let mut deployed_ref = None;
+            for deployment in deployments {
+                let latest_status: Vec<ApiDeploymentStatus> = self.api_get(&format!(
+                    "{}/deployments/{}/statuses?per_page=1",
+                    repo_path, deployment.id
+                ))?;
+                let latest_status = latest_status.into_iter().next();
+                let state = latest_status
+                    .as_ref()
+                    .map_or("pending".to_string(), |status| status.state.clone());
+                if state == "success" {
+                    deployed_ref = Some(deployment.git_ref.clone());
+                }
+                if last_deploy.is_none() {
+                    last_deploy = Some(DeploymentSummary {
+                        git_ref: deployment.git_ref,
+                        sha: deployment.sha,
+                        creator: deployment
+                            .creator
+                            .map(|creator| creator.login)
+                            .unwrap_or_default(),
+                        created_at: deployment.created_at,
+                        state,
+                        log_url: latest_status.and_then(|status| status.log_url),
+                    });
+                }
+                if deployed_ref.is_some() {
+                    break;
+                }
+            }
+
+            statuses.push(EnvironmentStatus {
+                pending_approvals: pending.remove(&environment.name).unwrap_or_default(),
+                name: environment.name,
+                deployed_ref,
+                last_deploy,
+            });
+        }
+        Ok(statuses)
+    }
+
+    /// Finds the environment a job deployed to, from the deployments of its commit whose
+    /// status links to the job. Jobs without an `environment` create no deployment.
+    pub fn fetch_job_environment(&self, job: &GithubJob) -> color_eyre::Result<Option<String>> {
+        if job.head_sha.is_empty() {
+            return Ok(None);
+        }
+        let deployments: Vec<ApiDeployment> = self.api_get(&format!(
+            "/repos/{}/deployments?sha={}&per_page={}",
+            job.repo, job.head_sha, MAX_DEPLOYMENTS_SEARCHED
+        ))?;
+        let job_path = format!("/job/{}", job.id);
+        // A job's deployment is created once the job starts
+        for deployment in deployments.into_iter().filter(|deployment| {
+            DateTime::parse_from_rfc3339(&deployment.created_at)
+                .is_ok_and(|created_at| created_at >= job.started_at - TimeDelta::minutes(1))
+        }) {
+            let statuses: Vec<ApiDeploymentStatus> = self.api_get(&format!(
+                "/repos/{}/deployments/{}/statuses?per_page=1",
+                job.repo, deployment.id
+            ))?;
+            let links_to_job = statuses.iter().any(|status| {
+                [&status.log_url, &status.target_url]
+                    .into_iter()
+                    .flatten()
+                    .any(|url| url.ends_with(&job_path))
+            });
+            if links_to_job {
+                return Ok(Some(deployment.environment));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Fetches the annotations of a job, whose check run shares its id.
+    pub fn fetch_annotations(&self, job: &GithubJob) -> color_eyre::Result<Vec<Annotation>> {
+        let annotations: Vec<ApiAnnotation> = self.api_get(&format!(
+            "/repos/{}/check-runs/{}/annotations?per_page={}",
+            job.repo, job.id, MAX_ANNOTATIONS
+        ))?;
+        Ok(annotations
+            .into_iter()
+            .map(|annotation| annotation.into_annotation(&job.repo, &job.head_sha, None))
+            .collect())
+    }
+
+    /// Fetches the annotations of the checks that apps other than GitHub Actions, e.g.
+    /// SonarCloud or Codecov, failed on a commit, as a pull request needs every check to pass
+    /// and not only its jobs.
+    pub fn fetch_check_annotations(
+        &self,
+        repo: &str,
+        head_sha: &str,
+    ) -> color_eyre::Result<CheckAnnotations> {
+        let checks: ApiCommitCheckRuns = self.api_get(&format!(
+            "/repos/{}/commits/{}/check-runs?filter=latest&per_page=100",
+            repo, head_sha
+        ))?;
+        let mut check_annotations = CheckAnnotations::default();
+        for check_run in checks.check_runs.into_iter().filter(|check_run| {
+            matches!(
+                check_run.conclusion.as_deref(),
+                Some("failure" | "timed_out" | "action_required")
+            )
+        }) {
+            let Some(app) = check_run.app.filter(|app| app.slug != "github-actions") else {
+                continue;
+            };
+            let fetched: color_eyre::Result<Vec<ApiAnnotation>> = self.api_get(&format!(
+                "/repos/{}/check-runs/{}/annotations?per_page={}",
+                repo, check_run.id, MAX_ANNOTATIONS
+            ));
+            match fetched {
+                Ok(fetched) => check_annotations
+                    .annotations
+                    .extend(fetched.into_iter().map(|annotation| {
+                        annotation.into_annotation(repo, head_sha, Some(app.name.clone()))
+                    })),
+                Err(e) => check_annotations.errors.push(format!(
+                    "Error fetching the annotations of {}: {}",
+                    app.name, e
+                )),
+            }
+        }
+        Ok(check_annotations)
+    }
+
+    /// Fetches the artifacts a run uploaded, expired ones included.
+    pub fn fetch_artifacts(&self, repo: &str, run_id: u64) -> color_eyre::Result<Vec<Artifact>> {
+        let page: ApiArtifacts = self.api_get(&format!(
+            "/repos/{}/actions/runs/{}/artifacts?per_page=100",
+            repo, run_id
+        ))?;
+        Ok(page.artifacts)
+    }
+
+    /// Downloads the artifacts of a run named like test reports, e.g. `junit-results`, and
+    /// returns the XML files in them, none when it uploaded none.
+    pub fn fetch_test_reports(&self, repo: &str, run_id: u64) -> color_eyre::Result<Vec<String>> {
+        let names: Vec<String> = self
+            .fetch_artifacts(repo, run_id)?
+            .into_iter()
+            .filter(|artifact| !artifact.expired && flaky_tests::is_test_report(&artifact.name))
+            .map(|artifact| artifact.name)
+            .collect();
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+        // `gh` unzips the artifacts, which the API only serves zipped
+        let dir = env::temp_dir().join(format!(
+            "lazyactions-test-reports-{}-{}",
+            process::id(),
+            run_id
+        ));
+        let run_id = run_id.to_string();
+        let dir_arg = dir.to_string_lossy().into_owned();
+        let mut args = vec!["run", "download", &run_id, "-R", repo, "-D", &dir_arg];
+        for name in &names {
+            args.extend(["-n", name]);
+        }
+        let reports = self.run_gh_command(&args).map(|_| {
+            let mut reports = Vec::new();
+            read_xml_files(&dir, &mut reports);
+            reports
+        });
+        let _ = fs::remove_dir_all(&dir);
+        reports
+    }
+
+    /// Fetches how many days a repository keeps the logs and artifacts of its runs, which
+    /// takes admin access to read.
+    pub fn fetch_retention_days(&self, repo: &str) -> color_eyre::Result<u64> {
+        let retention: ApiRetention = self.api_get(&format!(
+            "/repos/{}/actions/permissions/artifact-and-log-retention",
+            repo
+        ))?;
+        Ok(retention.days)
+    }
+
+    /// Fetches the id of the run a job belongs to.
+    pub fn fetch_job_run_id(&self, repo: &str, job_id: u64) -> color_eyre::Result<u64> {
+        Ok(self.fetch_job_run(repo, job_id)?.0)
+    }
+
+    /// Fetches the id of the run a job belongs to, along with the name of the job.
+    pub fn fetch_job_run(&self, repo: &str, job_id: u64) -> color_eyre::Result<(u64, String)> {
+        let job: ApiJobRun = self.api_get(&format!("/repos/{}/actions/jobs/{}", repo, job_id))?;
+        Ok((job.run_id, job.name))
+    }
+
+    /// Downloads the whole log of a job as GitHub serves it.
+    pub fn download_job_log(&self, repo: &str, job_id: u64) -> color_eyre::Result<Vec<u8>> {
+        self.download_raw(&format!("/repos/{}/actions/jobs/{}/logs", repo, job_id))
+    }
+
+    /// Downloads the logs of every job of a run as the zip archive GitHub serves, holding a
+    /// file per job and per step.
+    pub fn download_run_logs(&self, repo: &str, run_id: u64) -> color_eyre::Result<Vec<u8>> {
+        self.download_raw(&format!("/repos/{}/actions/runs/{}/logs", repo, run_id))
+    }
+
+    /// Downloads the body of an API path in full, as is.
+    fn download_raw(&self, path: &str) -> color_eyre::Result<Vec<u8>> {
+        let Some(api) = &self.api else {
+            return self.run_gh_command_for_bytes(&["api", path], None);
+        };
+        self.ensure_running()?;
+        let started = Instant::now();
+        let result = api.download(path, None, &|_, _| {});
+        let success = matches!(&result, Ok(download) if (200..300).contains(&download.status));
+        self.record_api_call(ApiCall {
+            started,
+            endpoint: path.to_string(),
+            duration: started.elapsed(),
+            status: match &result {
+                Ok(download) => download.status.to_string(),
+                Err(_) => "network error".to_string(),
+            },
+            success,
+            payload_size: result.as_ref().map_or(0, |download| download.bytes.len()),
+        });
+        self.record_reachability(result.is_ok());
+        let download = result?;
+        if let Some(rate_limit) = download.rate_limit {
+            self.set_rate_limit(rate_limit);
+        }
+        if !success {
+            return Err(eyre!(
+                "`GET {}` failed with status {}:\n{}",
+                path,
+                download.status,
+                String::from_utf8_lossy(&download.bytes)
+            ));
+        }
+        Ok(download.bytes)
+    }
+
+    /// Fetches the id of the latest run for the head commit of a pull request.
+    pub fn fetch_pull_request_run_id(&self, repo: &str, number: u64) -> color_eyre::Result<u64> {
+        let pull_request: ApiPullRequest =
+            self.api_get(&format!("/repos/{}/pulls/{}", repo, number))?;
+        let head = pull_request
+            .head
+            .ok_or_else(|| eyre!("Pull request #{} has no head commit", number))?;
+        let runs: ApiWorkflowRunsPage = self.api_get(&format!(
+            "/repos/{}/actions/runs?head_sha={}&per_page=1",
+            repo, head.sha
+        ))?;
+        runs.workflow_runs
+            .first()
+            .map(|run| run.id)
+            .ok_or_else(|| eyre!("No run found for pull request #{}", number))
+    }
+
+    /// Diffs the workflow file of a later job against the commit of an earlier one, of the
+    /// same repository.
+    pub fn fetch_workflow_file_diff(
+        &self,
+        earlier: &GithubJob,
+        later: &GithubJob,
+    ) -> color_eyre::Result<WorkflowFileDiff> {
+        let run = |job: &GithubJob| -> color_eyre::Result<(String, String)> {
+            let run: ApiWorkflowRun =
+                self.api_get(&format!("/repos/{}/actions/runs/{}", job.repo, job.run_id))?;
+            Ok((
+                run.path.unwrap_or_default(),
+                run.head_sha
+                    .ok_or_else(|| eyre!("Run {} has no head commit", job.run_id))?,
+            ))
+        };
+        let (_, base_sha) = run(earlier)?;
+        let (path, head_sha) = run(later)?;
+        let file = if base_sha == head_sha {
+            None
+        } else {
+            // Compared from the commits' merge base, the same as the last commit of the
+            // earlier job's branch when the later job ran on top of it
+            let comparison: ApiComparison = self.api_get(&format!(
+                "/repos/{}/compare/{}...{}",
+                later.repo, base_sha, head_sha
+            ))?;
+            comparison.files.into_iter().find(|file| {
+                file.filename == path || file.previous_filename.as_deref() == Some(path.as_str())
+            })
+        };
+        Ok(WorkflowFileDiff {
+            repo: later.repo.clone(),
+            path,
+            base_sha,
+            head_sha,
+            changed: file.is_some(),
+            patch: file
+                .and_then(|file| file.patch)
+                .map(|patch| patch.lines().map(str::to_string).collect())
+                .unwrap_or_default(),
+        })
+    }
+
+    /// The paths of the files a commit of `repo` changed, of up to the first 300 of them.
+    pub fn fetch_changed_paths(&self, repo: &str, sha: &str) -> color_eyre::Result<Vec<String>> {
+        // A commit lists its files the same way a comparison does
+        let commit: ApiComparison = self.api_get(&format!("/repos/{}/commits/{}", repo, sha))?;
+        Ok(commit.files.into_iter().map(|file| file.filename).collect())
+    }
+
+    /// The branch checked out in the current directory, if any.
+    pub fn current_branch(&self) -> &str {
+        &self.current_branch
+    }
+
+    /// What the installed GitHub CLI lacks and what is done instead, empty when it is recent
+    /// enough.
+    pub fn compatibility_notes(&self) -> &[String] {
+        &self.compatibility_notes
+    }
+
+    /// What couldn't be looked up on creation, e.g. the current Git branch, and what is done
+    /// without it.
+    pub fn startup_warnings(&self) -> &[String] {
+        &self.startup_warnings
+    }
+
+    /// The user whose runs are fetched, `None` for the runs of every user.
+    pub fn actor(&self) -> Option<&str> {
+        Some(self.current_user.as_str()).filter(|user| self.user && !user.is_empty())
+    }
+
+    /// The branch whose runs are fetched, `None` for the runs of every branch.
+    pub fn branch(&self) -> Option<String> {
+        self.branch.lock().ok().and_then(|branch| branch.clone())
+    }
+
+    /// Changes the branch whose runs are fetched from the next fetch on.
+    pub fn set_branch(&self, branch: Option<String>) {
+        if let Ok(mut current) = self.branch.lock() {
+            *current = branch;
+        }
+    }
+
+    /// Leaves the runs of the repositories another instance polls, given as `owner/name`, out
+    /// of the next fetches.
+    pub fn set_followed_repos(&self, repos: &[String]) {
+        if let Ok(mut followed_repos) = self.followed_repos.lock() {
+            *followed_repos = repos.iter().map(|repo| repo.to_ascii_lowercase()).collect();
+        }
+    }
+
+    /// Remembers the completed runs of a fetch whose data is shown, for the next fetches to
+    /// keep their jobs rather than fetch them again. Only called once the data is applied, as
+    /// the jobs of a fetch that was thrown away, e.g. cancelled, are nowhere to be kept.
+    pub fn remember_fetched_runs(&self, runs: HashMap<u64, String>) {
+        if let Ok(mut fetched_runs) = self.fetched_runs.lock() {
+            *fetched_runs = runs;
+        }
+    }
+}
+
+/// Combines the outcomes of the workflow runs of a single commit.
+fn combined_state(runs: &[ApiWorkflowRun]) -> CommitState {
+    if runs
+        .iter()
+        .any(|run| run.status.as_deref() != Some("completed"))
+    {
+        return CommitState::Running;
+    }
+    let conclusions = || runs.iter().filter_map(|run| run.conclusion.as_deref());
+    if conclusions().any(|conclusion| {
+        matches!(
+            conclusion,
+            "failure" | "timed_out" | "startup_failure" | "action_required"
+        )
+    }) {
+        CommitState::Failed
+    } else if conclusions().any(|conclusion| conclusion == "success") {
+        CommitState::Passed
+    } else {
+        CommitState::Neutral
+    }
+}
+
+/// The owner of the repository or organization that the arguments of `gh` or an API path are
+/// about, e.g. `octocat` for `-R octocat/hello` or `/repos/octocat/hello/actions/runs`.
+fn owner_named_in<'a>(args: &[&'a str]) -> Option<&'a str> {
+    args.iter()
+        .enumerate()
+        .find_map(|(index, arg)| {
+            if index > 0 && matches!(args[index - 1], "-R" | "--repo") {
+                return arg.split('/').next();
+            }
+            let mut segments = arg.split(['/', '?']);
+            segments.find(|segment| matches!(*segment, "repos" | "orgs"))?;
+            segments.next()
+        })
+        .filter(|owner| !owner.is_empty())
+}
+
+/// The first line of an error, leaving out e.g. the body of the response or the output of `gh`.
+fn first_line(error: &color_eyre::Report) -> String {
+    error
+        .to_string()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Reads the `.xml` files under `dir` into `contents`, those that can't be read being left out.
+fn read_xml_files(dir: &Path, contents: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            read_xml_files(&path, contents);
+        } else if path.extension().is_some_and(|extension| extension == "xml")
+            && let Ok(xml) = fs::read_to_string(&path)
+        {
+            contents.push(xml);
+        }
+    }
+}
+
+/// Percent-encodes a value for use in a URL query string.
+pub fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::fixture;
+
+    fn job_of(event: &str, head_branch: &str) -> GithubJob {
+        let mut run = fixture::run(1, "completed", Some("success"));
+        run.event = event.to_string();
+        run.head_branch = head_branch.to_string();
+        fixture::job(11, "deploy", &run, "10:00:00", Some(3))
+    }
+
+    #[test]
+    fn the_oidc_subject_of_a_branch_names_it() {
+        let job = job_of("push", "main");
+        assert_eq!(job.known_ref_kind(), None);
+        assert_eq!(
+            job.oidc_subject(None, RefKind::Branch),
+            "repo:octocat/hello:ref:refs/heads/main"
+        );
+    }
+
+    #[test]
+    fn the_oidc_subject_of_a_tag_names_it() {
+        assert_eq!(
+            job_of("push", "v1.2.0").oidc_subject(None, RefKind::Tag),
+            "repo:octocat/hello:ref:refs/tags/v1.2.0"
+        );
+        let release = job_of("release", "v1.2.0");
+        assert_eq!(release.known_ref_kind(), Some(RefKind::Tag));
+    }
+
+    #[test]
+    fn the_oidc_subject_of_a_pull_request_names_no_ref() {
+        let job = job_of("pull_request", "feature/x");
+        assert_eq!(
+            job.oidc_subject(None, job.known_ref_kind().expect("known")),
+            "repo:octocat/hello:pull_request"
+        );
+    }
+
+    #[test]
+    fn the_oidc_subject_of_a_deployment_names_its_environment() {
+        for job in [
+            job_of("push", "v1.2.0"),
+            job_of("pull_request", "feature/x"),
+        ] {
+            assert_eq!(
+                job.oidc_subject(Some("production"), RefKind::Tag),
+                "repo:octocat/hello:environment:production"
+            );
+        }
     }
 }
@@ -0,0 +1,142 @@
+/// The `gh` extensions that lazyactions knows commands of, by name, with the repository they
+/// are installed from.
+pub const KNOWN_EXTENSIONS: &[(&str, &str)] = &[
+    ("dash", "dlvhdr/gh-dash"),
+    ("actions-cache", "actions/gh-actions-cache"),
+    ("act", "nektos/gh-act"),
+];
+
+/// A command of an installed `gh` extension offered in the extensions menu, so that what an
+/// extension already does well needn't be done again here.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExtensionAction {
+    /// The name of the extension, as in `gh <name>`.
+    pub extension: &'static str,
+    pub label: &'static str,
+    /// The arguments of `gh`, in which `{repo}` and `{branch}` are replaced by those of the
+    /// selected job.
+    pub args: &'static [&'static str],
+    /// Whether the command takes over the terminal, in which case it runs in the foreground
+    /// until it exits rather than having its output shown.
+    pub interactive: bool,
+}
+
+const ACTIONS: &[ExtensionAction] = &[
+    ExtensionAction {
+        extension: "dash",
+        label: "Open the dashboard of pull requests and issues",
+        args: &["dash"],
+        interactive: true,
+    },
+    ExtensionAction {
+        extension: "actions-cache",
+        label: "List the Actions caches of the repository",
+        args: &["actions-cache", "list", "-R", "{repo}"],
+        interactive: false,
+    },
+    ExtensionAction {
+        extension: "actions-cache",
+        label: "List the Actions caches of the branch",
+        args: &["actions-cache", "list", "-R", "{repo}", "-B", "{branch}"],
+        interactive: false,
+    },
+    ExtensionAction {
+        extension: "act",
+        label: "List the jobs act can run locally",
+        args: &["act", "--list"],
+        interactive: false,
+    },
+    ExtensionAction {
+        extension: "act",
+        label: "Run the workflows locally with act",
+        args: &["act"],
+        interactive: true,
+    },
+];
+
+impl ExtensionAction {
+    /// The arguments of `gh` to run the command for a job of `repo` on `branch`.
+    pub fn args(&self, repo: &str, branch: &str) -> Vec<String> {
+        self.args
+            .iter()
+            .map(|arg| arg.replace("{repo}", repo).replace("{branch}", branch))
+            .collect()
+    }
+}
+
+/// The names of the extensions listed by `gh extension list`, whose lines start with e.g.
+/// `gh dash` followed by the repository and version of the extension.
+pub fn parse_installed(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("gh ")?.split_whitespace().next())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The commands offered by the `installed` extensions.
+pub fn available_actions(installed: &[String]) -> Vec<&'static ExtensionAction> {
+    ACTIONS
+        .iter()
+        .filter(|action| installed.iter().any(|name| name == action.extension))
+        .collect()
+}
+
+/// Lines up the columns of tables that `gh` commands print separated by tabs when their output
+/// is piped.
+pub fn align_columns(output: &str) -> Vec<String> {
+    let rows: Vec<Vec<&str>> = output
+        .lines()
+        .map(|line| line.split('\t').collect())
+        .collect();
+    let mut widths: Vec<usize> = Vec::new();
+    for row in &rows {
+        for (column, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+            match widths.get_mut(column) {
+                Some(max) => *max = (*max).max(width),
+                None => widths.push(width),
+            }
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            let last = row.len().saturating_sub(1);
+            row.iter()
+                .enumerate()
+                .map(|(column, cell)| {
+                    if column == last {
+                        cell.to_string()
+                    } else {
+                        format!("{:<1$}  ", cell, widths[column])
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_of_installed_extensions_are_offered() {
+        let installed = parse_installed(
+            "gh actions-cache\tactions/gh-actions-cache\tv1.0.4\n\
+             gh screensaver\tvilmibm/gh-screensaver\t1.0.0\n",
+        );
+        assert_eq!(installed, ["actions-cache", "screensaver"]);
+        let actions = available_actions(&installed);
+        assert_eq!(
+            actions
+                .iter()
+                .map(|action| action.args("octocat/hello", "main"))
+                .collect::<Vec<_>>(),
+            [
+                vec!["actions-cache", "list", "-R", "octocat/hello"],
+                vec!["actions-cache", "list", "-R", "octocat/hello", "-B", "main"],
+            ]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::{fmt, process::Command};
+
+/// A version of the GitHub CLI, as printed by `gh --version`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GhVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl GhVersion {
+    /// Runs `gh --version`, `None` if it fails or prints something unexpected, e.g. for a
+    /// development build, in which case every feature is assumed to be there.
+    pub fn detect() -> Option<Self> {
+        let output = Command::new("gh").arg("--version").output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Self::parse(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Parses the first line of `gh --version`, e.g. `gh version 2.45.0 (2024-03-04)`.
+    fn parse(output: &str) -> Option<Self> {
+        let version = output
+            .lines()
+            .next()?
+            .strip_prefix("gh version ")?
+            .split_whitespace()
+            .next()?;
+        let mut numbers = version.split('.').map(|number| number.parse::<u32>().ok());
+        Some(Self {
+            major: numbers.next()??,
+            minor: numbers.next()??,
+            patch: numbers.next().flatten().unwrap_or(0),
+        })
+    }
+
+    /// Whether this version has a feature.
+    pub fn supports(self, feature: GhFeature) -> bool {
+        self >= feature.added_in()
+    }
+}
+
+impl fmt::Display for GhVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// The features of the GitHub CLI that lazyactions uses and older versions lack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GhFeature {
+    /// `gh auth token`, which the native API client gets its token from.
+    AuthToken,
+    /// `gh repo view --json`, which finds the repository of the current directory.
+    RepoViewJson,
+    /// The `Logged in to <host> account <login>` wording of `gh auth status`, which the
+    /// current user is read from.
+    AuthStatusAccount,
+}
+
+impl GhFeature {
+    /// Every feature, e.g. to list those an installed version lacks.
+    pub const ALL: [GhFeature; 3] = [
+        GhFeature::AuthToken,
+        GhFeature::RepoViewJson,
+        GhFeature::AuthStatusAccount,
+    ];
+
+    /// The first version of the GitHub CLI that has the feature.
+    pub fn added_in(self) -> GhVersion {
+        let (major, minor) = match self {
+            GhFeature::AuthToken => (2, 17),
+            GhFeature::RepoViewJson => (2, 0),
+            GhFeature::AuthStatusAccount => (2, 40),
+        };
+        GhVersion {
+            major,
+            minor,
+            patch: 0,
+        }
+    }
+
+    /// How the feature is called in messages about it missing.
+    pub fn name(self) -> &'static str {
+        match self {
+            GhFeature::AuthToken => "`gh auth token`",
+            GhFeature::RepoViewJson => "`gh repo view --json`",
+            GhFeature::AuthStatusAccount => "the account shown by `gh auth status`",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_release_and_partial_versions() {
+        assert_eq!(
+            GhVersion::parse("gh version 2.45.0 (2024-03-04)\nhttps://github.com/cli/cli"),
+            Some(GhVersion {
+                major: 2,
+                minor: 45,
+                patch: 0
+            })
+        );
+        assert_eq!(
+            GhVersion::parse("gh version 1.14 (2021-08-04)"),
+            Some(GhVersion {
+                major: 1,
+                minor: 14,
+                patch: 0
+            })
+        );
+        assert_eq!(GhVersion::parse("gh version DEV"), None);
+    }
+
+    #[test]
+    fn features_need_the_version_that_added_them() {
+        let version = GhVersion::parse("gh version 2.17.0").expect("valid version");
+        assert!(version.supports(GhFeature::AuthToken));
+        assert!(version.supports(GhFeature::RepoViewJson));
+        assert!(!version.supports(GhFeature::AuthStatusAccount));
+    }
+}
@@ -0,0 +1,146 @@
+use color_eyre::eyre::{WrapErr, eyre};
+use std::{
+    fs::{self, File, OpenOptions, TryLockError},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// The claim of the lazyactions instance polling a set of repositories, held as locks on a
+/// file per repository for as long as the instance runs. The operating system releases them
+/// when the instance exits, however it does, so a claim never outlives its instance.
+#[derive(Debug, Default)]
+pub struct InstanceLock {
+    /// The repositories claimed, as `owner/name`, with the locked file of each.
+    files: Vec<(String, File)>,
+}
+
+/// Who polls the repositories an instance was started for.
+#[derive(Debug)]
+pub struct Claims {
+    /// The repositories this instance polls, which it holds the lock of until it exits.
+    pub lock: InstanceLock,
+    /// The repositories another instance already polls.
+    pub held: Vec<Holder>,
+}
+
+/// Another instance polling a repository.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Holder {
+    /// The repository, as `owner/name`.
+    pub repo: String,
+    /// The process ID of the instance, if it could be read.
+    pub pid: Option<u32>,
+    /// Whether it polls the repository with the same filters, e.g. the same branch, so that
+    /// what it fetches can be shown instead of polling again.
+    pub same_filters: bool,
+}
+
+impl InstanceLock {
+    /// Claims the repositories, given as `owner/name`, for this instance unless another one
+    /// already polls them. `filters` tell what the runs polled are narrowed down by, for an
+    /// instance to only follow another one that fetches the same runs.
+    pub fn acquire(repos: &[String], filters: &str) -> color_eyre::Result<Claims> {
+        let dir = dirs::cache_dir()
+            .ok_or_else(|| eyre!("Could not determine the cache directory"))?
+            .join("lazyactions");
+        acquire_in(&dir, repos, filters)
+    }
+
+    /// Whether this instance polls a repository, given as `owner/name`.
+    pub fn holds(&self, repo: &str) -> bool {
+        self.files
+            .iter()
+            .any(|(held, _)| held.eq_ignore_ascii_case(repo))
+    }
+
+    /// Adds the repositories claimed later, e.g. once the instance polling them exited.
+    pub fn extend(&mut self, other: InstanceLock) {
+        self.files.extend(other.files);
+    }
+}
+
+fn acquire_in(dir: &Path, repos: &[String], filters: &str) -> color_eyre::Result<Claims> {
+    let mut claims = Claims {
+        lock: InstanceLock::default(),
+        held: Vec::new(),
+    };
+    for repo in repos {
+        let Some(path) = lock_path(dir, repo) else {
+            continue;
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err(format!("Failed to create {}", parent.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .wrap_err(format!("Failed to open {}", path.display()))?;
+        match file.try_lock() {
+            Ok(()) => {
+                // Tell the instances started later who they are attaching to, and what for
+                file.set_len(0)?;
+                write!(file, "{}\n{}", std::process::id(), filters)?;
+                claims.lock.files.push((repo.clone(), file));
+            }
+            Err(TryLockError::WouldBlock) => {
+                let contents = fs::read_to_string(&path).unwrap_or_default();
+                let (pid, held_filters) = contents.split_once('\n').unwrap_or((&contents, ""));
+                claims.held.push(Holder {
+                    repo: repo.clone(),
+                    pid: pid.trim().parse().ok(),
+                    same_filters: held_filters == filters,
+                });
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(e).wrap_err(format!("Failed to lock {}", path.display()));
+            }
+        }
+    }
+    Ok(claims)
+}
+
+/// Where the lock of a repository is, e.g. `~/.cache/lazyactions/octocat/hello.lock`, next
+/// to its snapshot.
+fn lock_path(dir: &Path, repo: &str) -> Option<PathBuf> {
+    let repo = repo.to_ascii_lowercase();
+    let (owner, name) = repo.split_once('/')?;
+    Some(dir.join(owner).join(format!("{}.lock", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_second_instance_finds_the_repositories_claimed_until_the_first_exits() {
+        let dir = std::env::temp_dir().join(format!("lazyactions-lock-{}", std::process::id()));
+        let repos = ["octocat/hello".to_string(), "octocat/world".to_string()];
+
+        let first = acquire_in(&dir, &repos[..1], "branch=main").expect("first claim");
+        assert!(first.held.is_empty());
+        assert!(first.lock.holds("octocat/hello"));
+        // Only the repository claimed is left to the first instance, with its filters
+        let second = acquire_in(&dir, &repos, "branch=main").expect("second claim");
+        assert_eq!(
+            second.held,
+            [Holder {
+                repo: "octocat/hello".to_string(),
+                pid: Some(std::process::id()),
+                same_filters: true,
+            }]
+        );
+        assert!(second.lock.holds("octocat/world"));
+        let third = acquire_in(&dir, &repos[..1], "branch=dev").expect("third claim");
+        assert!(!third.held[0].same_filters);
+
+        drop(first);
+        let fourth = acquire_in(&dir, &repos[..1], "branch=dev").expect("fourth claim");
+        assert!(fourth.held.is_empty());
+        drop((second, fourth));
+        let _ = fs::remove_dir_all(dir);
+    }
+}
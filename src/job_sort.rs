@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+
+use crate::gh_cli::GithubJob;
+
+/// The orders the jobs of each group of the columns can be listed in, cycled with `s` and
+/// kept in the config file as `sort`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobSort {
+    /// Those that started last first.
+    #[default]
+    Started,
+    /// Those that took, or have been running for, the longest first.
+    Duration,
+    /// By workflow name, then those that started last first.
+    Workflow,
+    /// By branch name, then those that started last first.
+    Branch,
+}
+
+impl JobSort {
+    pub fn next(self) -> Self {
+        match self {
+            JobSort::Started => JobSort::Duration,
+            JobSort::Duration => JobSort::Workflow,
+            JobSort::Workflow => JobSort::Branch,
+            JobSort::Branch => JobSort::Started,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            JobSort::Started => "newest first",
+            JobSort::Duration => "longest first",
+            JobSort::Workflow => "by workflow",
+            JobSort::Branch => "by branch",
+        }
+    }
+
+    /// Compares two jobs, the one listed first being the lesser, with how long the jobs still
+    /// running took counted up to `now`.
+    pub fn compare(self, a: &GithubJob, b: &GithubJob, now: DateTime<Utc>) -> Ordering {
+        let newest_first = Reverse(a.started_at).cmp(&Reverse(b.started_at));
+        match self {
+            JobSort::Started => newest_first,
+            JobSort::Duration => Reverse(a.duration(now))
+                .cmp(&Reverse(b.duration(now)))
+                .then(newest_first),
+            JobSort::Workflow => a.workflow_name.cmp(&b.workflow_name).then(newest_first),
+            JobSort::Branch => a.head_branch.cmp(&b.head_branch).then(newest_first),
+        }
+    }
+
+    /// Sorts `jobs` in this order.
+    pub fn sort<T>(self, jobs: &mut [T], job: impl Fn(&T) -> &GithubJob) {
+        let now = Utc::now();
+        jobs.sort_by(|a, b| self.compare(job(a), job(b), now));
+    }
+}
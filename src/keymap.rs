@@ -0,0 +1,309 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::event::AppEvent;
+
+/// The built-in sets of key bindings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Preset {
+    /// Arrow keys and the bindings shown in the status bar.
+    #[default]
+    Default,
+    /// The default bindings plus `h`/`j`/`k`/`l`, `gg`/`G` and `Ctrl-d`/`Ctrl-u`.
+    Vim,
+}
+
+/// The `keymap` section of the config file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeymapConfig {
+    pub preset: Preset,
+    /// Extra bindings, overriding those of the preset, e.g. `{ "ctrl-d": "page_down" }`.
+    pub bindings: HashMap<KeySequence, AppEvent>,
+}
+
+/// A single key press, written in the config file as e.g. `q`, `G`, `ctrl-d`, `pagedown`
+/// or `f12`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyPress {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl From<KeyEvent> for KeyPress {
+    fn from(key_event: KeyEvent) -> Self {
+        // Shift is already part of an uppercase character and of BackTab
+        let modifiers = match key_event.code {
+            KeyCode::Char(_) | KeyCode::BackTab => key_event.modifiers - KeyModifiers::SHIFT,
+            _ => key_event.modifiers,
+        };
+        Self {
+            code: key_event.code,
+            modifiers,
+        }
+    }
+}
+
+impl FromStr for KeyPress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = s;
+        // A lone `-` is the minus key rather than a separator
+        while let Some((modifier, rest)) = key.split_once('-').filter(|(_, rest)| !rest.is_empty())
+        {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier `{}` in `{}`", modifier, s)),
+            };
+            key = rest;
+        }
+
+        let code = match key.to_ascii_lowercase().as_str() {
+            "esc" => KeyCode::Esc,
+            "enter" => KeyCode::Enter,
+            "backspace" => KeyCode::Backspace,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            lower => {
+                let function_key = lower
+                    .strip_prefix('f')
+                    .and_then(|number| number.parse::<u8>().ok());
+                match (key.chars().next(), key.chars().count(), function_key) {
+                    (Some(c), 1, _) => KeyCode::Char(c),
+                    (_, _, Some(number)) if (1..=24).contains(&number) => KeyCode::F(number),
+                    _ => return Err(format!("unknown key `{}`", s)),
+                }
+            }
+        };
+        Ok(KeyPress::from(KeyEvent::new(code, modifiers)))
+    }
+}
+
+impl fmt::Display for KeyPress {
+    /// Writes the key as shown in the status bar, e.g. `q`, `Ctrl-d`, `PgDn` or `↑`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl-"),
+            (KeyModifiers::ALT, "Alt-"),
+            (KeyModifiers::SHIFT, "Shift-"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Esc => f.write_str("Esc"),
+            KeyCode::Enter => f.write_str("Enter"),
+            KeyCode::Backspace => f.write_str("Backspace"),
+            KeyCode::Tab => f.write_str("Tab"),
+            KeyCode::BackTab => f.write_str("Shift-Tab"),
+            KeyCode::Up => f.write_str("↑"),
+            KeyCode::Down => f.write_str("↓"),
+            KeyCode::Left => f.write_str("←"),
+            KeyCode::Right => f.write_str("→"),
+            KeyCode::PageUp => f.write_str("PgUp"),
+            KeyCode::PageDown => f.write_str("PgDn"),
+            KeyCode::Home => f.write_str("Home"),
+            KeyCode::End => f.write_str("End"),
+            KeyCode::F(number) => write!(f, "F{}", number),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// One or more key presses bound together, written space separated, e.g. `g g`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct KeySequence(Vec<KeyPress>);
+
+impl TryFrom<String> for KeySequence {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let keys = s
+            .split_whitespace()
+            .map(KeyPress::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        if keys.is_empty() {
+            return Err("a key binding can't be empty".to_string());
+        }
+        Ok(Self(keys))
+    }
+}
+
+/// Maps key presses to [`AppEvent`]s, including multi-key sequences such as `gg`.
+#[derive(Debug)]
+pub struct Keymap {
+    bindings: Vec<(Vec<KeyPress>, AppEvent)>,
+    /// The keys pressed so far of a sequence that is not complete yet.
+    pending: Vec<KeyPress>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&KeymapConfig::default())
+    }
+}
+
+impl Keymap {
+    /// Creates the keymap of a preset with the configured bindings applied on top.
+    pub fn new(config: &KeymapConfig) -> Self {
+        let mut bindings: Vec<(Vec<KeyPress>, AppEvent)> = preset_bindings(config.preset)
+            .into_iter()
+            .map(|(keys, event)| {
+                let keys = keys
+                    .split_whitespace()
+                    .filter_map(|key| key.parse().ok())
+                    .collect();
+                (keys, event)
+            })
+            .collect();
+        for (KeySequence(keys), event) in &config.bindings {
+            bindings.retain(|(bound, _)| bound != keys);
+            bindings.push((keys.clone(), *event));
+        }
+        Self {
+            bindings,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the event bound to a key press, or `None` if the key is unbound or starts a
+    /// sequence that needs more keys.
+    pub fn handle(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let key = KeyPress::from(key_event);
+        self.pending.push(key);
+        if !self.starts_binding(&self.pending) {
+            // Not part of any sequence, so start over with just this key
+            self.pending = vec![key];
+        }
+        if let Some((_, event)) = self.bindings.iter().find(|(keys, _)| *keys == self.pending) {
+            self.pending.clear();
+            return Some(*event);
+        }
+        if !self.starts_binding(&self.pending) {
+            self.pending.clear();
+        }
+        None
+    }
+
+    /// The keys first bound to an event, as shown in the status bar, e.g. `gg`, or `None`
+    /// if the event is unbound.
+    pub fn keys_for(&self, event: AppEvent) -> Option<String> {
+        self.bindings
+            .iter()
+            .find(|(_, bound)| *bound == event)
+            .map(|(keys, _)| keys.iter().map(ToString::to_string).collect())
+    }
+
+    /// Whether some binding starts with the given keys.
+    fn starts_binding(&self, keys: &[KeyPress]) -> bool {
+        self.bindings
+            .iter()
+            .any(|(bound, _)| bound.starts_with(keys))
+    }
+}
+
+/// The bindings of a preset, as written in the config file.
+fn preset_bindings(preset: Preset) -> Vec<(&'static str, AppEvent)> {
+    let mut bindings = vec![
+        ("esc", AppEvent::Back),
+        ("q", AppEvent::Quit),
+        ("ctrl-c", AppEvent::Quit),
+        ("right", AppEvent::NavigateRight),
+        ("left", AppEvent::NavigateLeft),
+        ("up", AppEvent::NavigateUp),
+        ("down", AppEvent::NavigateDown),
+        ("enter", AppEvent::ToggleDetails),
+        ("pagedown", AppEvent::PageDown),
+        ("pageup", AppEvent::PageUp),
+        ("backspace", AppEvent::OpenGitHub),
+        ("tab", AppEvent::NextRepo),
+        ("backtab", AppEvent::PrevRepo),
+        ("f12", AppEvent::ToggleApiInspector),
+        ("d", AppEvent::OpenDispatch),
+        ("E", AppEvent::ToggleDeployView),
+        ("S", AppEvent::OpenJobSummary),
+        ("/", AppEvent::OpenFilter),
+        ("c", AppEvent::CompareLogs),
+        ("t", AppEvent::OpenTriage),
+        ("w", AppEvent::WatchRun),
+        ("i", AppEvent::ShowActionsSettings),
+        ("P", AppEvent::OpenPullRequest),
+        ("u", AppEvent::OpenCalledWorkflow),
+        ("R", AppEvent::RerunRun),
+        ("F", AppEvent::RerunFailedJobs),
+        ("K", AppEvent::CancelRun),
+        ("W", AppEvent::ToggleWorkflowSidebar),
+        ("L", AppEvent::ToggleTimeline),
+        ("o", AppEvent::ToggleOtherJobs),
+        ("e", AppEvent::OpenExport),
+        ("V", AppEvent::OpenClipboardLink),
+        ("T", AppEvent::CycleTheme),
+        ("v", AppEvent::ToggleRunView),
+        ("b", AppEvent::OpenBranchPicker),
+        ("O", AppEvent::ToggleOverview),
+        ("Y", AppEvent::CopyCommitSha),
+        ("a", AppEvent::ApproveDeployment),
+        ("y", AppEvent::OpenYankMenu),
+        ("J", AppEvent::InspectJson),
+        ("M", AppEvent::ToggleWorkflowMute),
+        ("f", AppEvent::LoadFullLog),
+        ("H", AppEvent::ToggleWorkflowHistory),
+        ("f5", AppEvent::Refresh),
+        ("g r", AppEvent::Refresh),
+        ("A", AppEvent::OpenAnnotation),
+        ("p", AppEvent::TogglePin),
+        ("[", AppEvent::PreviousRefresh),
+        ("]", AppEvent::NextRefresh),
+        ("D", AppEvent::DeleteRun),
+        ("X", AppEvent::OpenExtensions),
+        ("B", AppEvent::OpenBadges),
+        ("z", AppEvent::ToggleRecentOnly),
+        ("U", AppEvent::ToggleUsageView),
+        ("#", AppEvent::ToggleLinks),
+        ("s", AppEvent::CycleSort),
+        ("n", AppEvent::NextMatch),
+        ("N", AppEvent::PreviousMatch),
+        ("x", AppEvent::SaveLog),
+        ("g x", AppEvent::SaveRunLogs),
+        ("g h", AppEvent::ToggleBackfill),
+        ("g t", AppEvent::ToggleTimestamps),
+        ("Q", AppEvent::ToggleFlakyTests),
+        (":", AppEvent::OpenCommandPalette),
+        ("g s", AppEvent::ToggleScheduled),
+        ("C", AppEvent::ClearFilters),
+        ("Z", AppEvent::RestoreFilters),
+    ];
+    if preset == Preset::Vim {
+        bindings.extend([
+            ("h", AppEvent::NavigateLeft),
+            ("j", AppEvent::NavigateDown),
+            ("k", AppEvent::NavigateUp),
+            ("l", AppEvent::NavigateRight),
+            ("g g", AppEvent::Top),
+            ("G", AppEvent::Bottom),
+            ("ctrl-d", AppEvent::PageDown),
+            ("ctrl-u", AppEvent::PageUp),
+        ]);
+    }
+    bindings
+}
@@ -0,0 +1,248 @@
+use base64::Engine;
+use color_eyre::eyre::eyre;
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+use crate::gh_cli::encode_query_value;
+
+/// A GitHub Actions page, as linked to from a URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ActionsLink {
+    /// `https://github.com/owner/name/actions/runs/123`
+    Run { repo: String, run_id: u64 },
+    /// `https://github.com/owner/name/actions/runs/123/job/456`
+    Job {
+        repo: String,
+        run_id: u64,
+        job_id: u64,
+    },
+    /// `https://github.com/owner/name/pull/7/checks`, with the `check_run_id` of a job when
+    /// one of its checks is selected.
+    PullRequestChecks {
+        repo: String,
+        number: u64,
+        job_id: Option<u64>,
+    },
+}
+
+impl ActionsLink {
+    /// Parses the URL of a run, a job or the checks of a pull request.
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let path = url
+            .strip_prefix("https://github.com/")
+            .or_else(|| url.strip_prefix("http://github.com/"))
+            .or_else(|| url.strip_prefix("github.com/"))?;
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let path = path.split('#').next().unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let repo = format!("{}/{}", segments.first()?, segments.get(1)?);
+        let number = |index: usize| segments.get(index).and_then(|s| s.parse::<u64>().ok());
+        match segments.get(2..) {
+            Some(["actions", "runs", _, "job", _, ..]) => Some(ActionsLink::Job {
+                repo,
+                run_id: number(4)?,
+                job_id: number(6)?,
+            }),
+            Some(["actions", "runs", ..]) => Some(ActionsLink::Run {
+                repo,
+                run_id: number(4)?,
+            }),
+            Some(["pull", _, "checks", ..]) => Some(ActionsLink::PullRequestChecks {
+                repo,
+                number: number(3)?,
+                job_id: query
+                    .split('&')
+                    .find_map(|pair| pair.strip_prefix("check_run_id="))
+                    .and_then(|id| id.parse().ok()),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The runs tab of a repository's Actions page, narrowed down with the same filters as a
+/// view of the job columns, so the view can be shared with someone who doesn't use
+/// `lazyactions`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ActionsView {
+    pub repo: String,
+    /// The file name of the only workflow shown, e.g. `ci.yml`.
+    pub workflow_file: Option<String>,
+    pub branch: Option<String>,
+    pub actor: Option<String>,
+    /// The status the runs are filtered by, e.g. `failure` or `in_progress`.
+    pub status: Option<&'static str>,
+}
+
+impl ActionsView {
+    /// The URL of the page, e.g.
+    /// `https://github.com/owner/name/actions/workflows/ci.yml?query=branch%3Amain+is%3Afailure`.
+    pub fn url(&self) -> String {
+        let mut url = format!("https://github.com/{}/actions", self.repo);
+        if let Some(file) = &self.workflow_file {
+            url.push_str(&format!("/workflows/{}", encode_query_value(file)));
+        }
+        let qualifiers: Vec<String> = [
+            ("branch", self.branch.as_deref()),
+            ("actor", self.actor.as_deref()),
+            ("is", self.status),
+        ]
+        .into_iter()
+        .filter_map(|(qualifier, value)| {
+            value.map(|value| encode_query_value(&format!("{}:{}", qualifier, value)))
+        })
+        .collect();
+        if !qualifiers.is_empty() {
+            url.push_str(&format!("?query={}", qualifiers.join("+")));
+        }
+        url
+    }
+}
+
+/// The markdown of a workflow's status badge for a README, linking to the runs of the
+/// workflow, e.g.
+/// `[![CI](https://github.com/owner/name/actions/workflows/ci.yml/badge.svg?branch=main)](https://github.com/owner/name/actions/workflows/ci.yml?query=branch%3Amain)`.
+/// The badge shows the status of the latest run on `branch`, or on any branch without one.
+pub fn workflow_badge(repo: &str, workflow_name: &str, path: &str, branch: Option<&str>) -> String {
+    let file = path.rsplit('/').next().unwrap_or(path);
+    let mut image = format!(
+        "https://github.com/{}/actions/workflows/{}/badge.svg",
+        repo,
+        encode_query_value(file)
+    );
+    if let Some(branch) = branch {
+        image.push_str(&format!("?branch={}", encode_query_value(branch)));
+    }
+    let runs = ActionsView {
+        repo: repo.to_string(),
+        workflow_file: Some(file.to_string()),
+        branch: branch.map(str::to_string),
+        ..ActionsView::default()
+    };
+    let alt = workflow_name.replace('[', "\\[").replace(']', "\\]");
+    format!("[![{}]({})]({})", alt, image, runs.url())
+}
+
+/// Opens a page in the default browser. On Windows the URL is handed to the URL protocol
+/// handler itself rather than to `cmd /c start`, which expands the `%NAME%` of environment
+/// variables in it, as percent-encoded queries may happen to spell.
+pub fn open_in_browser(url: &str) -> io::Result<()> {
+    if cfg!(windows) {
+        Command::new("rundll32")
+            .args(["url.dll,FileProtocolHandler", url])
+            .spawn()
+            .map(|_| ())
+    } else {
+        open::that(url)
+    }
+}
+
+/// Reads text from the system clipboard with the platform's command line tool.
+pub fn read_clipboard() -> color_eyre::Result<String> {
+    let commands: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbpaste", &[])]
+    } else if cfg!(windows) {
+        &[("powershell", &["-NoProfile", "-Command", "Get-Clipboard"])]
+    } else {
+        &[
+            ("wl-paste", &["--no-newline"]),
+            ("xclip", &["-selection", "clipboard", "-o"]),
+            ("xsel", &["--clipboard", "--output"]),
+        ]
+    };
+    for (program, args) in commands {
+        // Try the next tool when this one isn't installed or has no display to talk to
+        if let Ok(output) = Command::new(program).args(*args).output()
+            && output.status.success()
+        {
+            return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string());
+        }
+    }
+    Err(eyre!(
+        "Couldn't read the clipboard with {}",
+        commands
+            .iter()
+            .map(|(program, _)| *program)
+            .collect::<Vec<_>>()
+            .join(", ")
+    ))
+}
+
+/// Writes text to the system clipboard with the platform's command line tool, or else asks
+/// the terminal to with an OSC 52 escape sequence.
+pub fn write_clipboard(text: &str) -> color_eyre::Result<()> {
+    if copies_over_osc52() {
+        return write_osc52(text);
+    }
+    for (program, args) in copy_commands() {
+        // Try the next tool when this one isn't installed or has no display to talk to
+        let Ok(mut child) = Command::new(program)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        let written = child
+            .stdin
+            .take()
+            .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        if child.wait().is_ok_and(|status| status.success()) && written {
+            return Ok(());
+        }
+    }
+    write_osc52(text)
+}
+
+/// Whether copying always asks the terminal, as over SSH the tools would copy to the
+/// clipboard of the remote machine.
+pub fn copies_over_osc52() -> bool {
+    std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some()
+}
+
+/// The first of the platform's clipboard tools that is on the `PATH`, which copying uses
+/// unless it has no display to talk to.
+pub fn clipboard_tool() -> Option<&'static str> {
+    let paths = std::env::var_os("PATH")?;
+    let executable = |program: &str| {
+        std::env::split_paths(&paths).any(|dir| {
+            dir.join(program).is_file()
+                || (cfg!(windows) && dir.join(format!("{}.exe", program)).is_file())
+        })
+    };
+    copy_commands()
+        .iter()
+        .map(|(program, _)| *program)
+        .find(|program| executable(program))
+}
+
+/// The command line tools that copy to the clipboard on this platform, in the order they are
+/// tried.
+fn copy_commands() -> &'static [(&'static str, &'static [&'static str])] {
+    if cfg!(target_os = "macos") {
+        &[("pbcopy", &[])]
+    } else if cfg!(windows) {
+        &[("clip", &[])]
+    } else {
+        &[
+            ("wl-copy", &[]),
+            ("xclip", &["-selection", "clipboard"]),
+            ("xsel", &["--clipboard", "--input"]),
+        ]
+    }
+}
+
+/// Asks the terminal to put text on the clipboard. Most terminals support it, some only once
+/// enabled, and there is no telling whether it worked.
+fn write_osc52(text: &str) -> color_eyre::Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()?;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use color_eyre::eyre::WrapErr;
+use std::{
+    fs,
+    path::{self, Path, PathBuf},
+};
+
+/// The file the log of a job is saved to, e.g. `octocat-hello-1234-build-and-test.log`,
+/// named after its repository, its run and itself so that the logs saved from different runs
+/// don't overwrite each other.
+pub fn job_log_file(repo: &str, run_id: u64, job_name: &str) -> PathBuf {
+    PathBuf::from(format!("{}-{}-{}.log", slug(repo), run_id, slug(job_name)))
+}
+
+/// The file the logs of every job of a run are saved to, e.g. `octocat-hello-1234-logs.zip`.
+pub fn run_logs_file(repo: &str, run_id: u64) -> PathBuf {
+    PathBuf::from(format!("{}-{}-logs.zip", slug(repo), run_id))
+}
+
+/// Writes `bytes` to `path`, returning the absolute path written so that it can be found
+/// from anywhere.
+pub fn save(path: &Path, bytes: &[u8]) -> color_eyre::Result<PathBuf> {
+    fs::write(path, bytes).wrap_err(format!("Failed to write {}", path.display()))?;
+    Ok(path::absolute(path).unwrap_or_else(|_| path.to_path_buf()))
+}
+
+/// Lowercases a name and joins its words with dashes, e.g. `Build / test (ubuntu)` into
+/// `build-test-ubuntu`, which every file system takes.
+fn slug(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_logs_are_named_after_their_repository_run_and_job() {
+        assert_eq!(
+            job_log_file("Octocat/hello", 1234, "CI / test (ubuntu-latest, 1.85)"),
+            PathBuf::from("octocat-hello-1234-ci-test-ubuntu-latest-1-85.log")
+        );
+        assert_eq!(
+            run_logs_file("octocat/hello.rs", 1234),
+            PathBuf::from("octocat-hello-rs-1234-logs.zip")
+        );
+    }
+}
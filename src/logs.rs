@@ -0,0 +1,300 @@
+use ratatui::style::{Color, Modifier, Style};
+use std::ops::Range;
+
+/// Removes the timestamp GitHub prefixes every log line with, e.g.
+/// `2024-05-01T12:34:56.1234567Z `.
+pub fn strip_timestamp(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    let has_timestamp = bytes.len() > 20
+        && bytes[4] == b'-'
+        && bytes[7] == b'-'
+        && bytes[10] == b'T'
+        && bytes[..4].iter().all(u8::is_ascii_digit);
+    match line.split_once(' ') {
+        Some((timestamp, rest)) if has_timestamp && timestamp.ends_with('Z') => rest,
+        _ => line,
+    }
+}
+
+/// The marker GitHub puts in front of the lines reporting an error.
+const ERROR_MARKER: &str = "##[error]";
+
+/// The lines leading up to the first error of a log, plus a couple following it, at most
+/// `max_lines` of them. A log without an error marker gives its last lines instead.
+pub fn failure_excerpt(lines: &[String], max_lines: usize) -> Vec<String> {
+    let end = match lines.iter().position(|line| line.contains(ERROR_MARKER)) {
+        // Whatever follows the error often explains it, e.g. the exit code
+        Some(error) => (error + 3).min(lines.len()),
+        None => lines.len(),
+    };
+    let start = end.saturating_sub(max_lines);
+    lines[start..end]
+        .iter()
+        .map(|line| strip_ansi_codes(line))
+        .collect()
+}
+
+/// Removes the ANSI escape sequences tools color their output with, e.g. `\x1b[32m`.
+fn strip_ansi_codes(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip up to and including the letter ending the sequence
+            if chars.next() == Some('[') {
+                chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            }
+            continue;
+        }
+        stripped.push(c);
+    }
+    stripped
+}
+
+/// Splits a job log into lines without their timestamps.
+pub fn log_lines(log: &str) -> Vec<String> {
+    log.lines()
+        .map(|line| strip_timestamp(line).to_string())
+        .collect()
+}
+
+/// Where `query` occurs in `line`, ignoring ASCII case, as byte ranges that don't overlap.
+pub fn find_matches(line: &str, query: &str) -> Vec<Range<usize>> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    // ASCII lowercasing keeps the byte offsets of the original line
+    let (line, query) = (line.to_ascii_lowercase(), query.to_ascii_lowercase());
+    line.match_indices(&query)
+        .map(|(start, found)| start..start + found.len())
+        .collect()
+}
+
+/// What the workflow command a log line starts with, e.g. `##[error]`, makes of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineKind {
+    Plain,
+    /// `##[group]`, the header of a section of the log.
+    GroupStart,
+    /// `##[endgroup]`, closing the section opened last.
+    GroupEnd,
+    Error,
+    Warning,
+    Notice,
+    Debug,
+    /// `##[command]`, a command the runner ran, e.g. `/usr/bin/git version`.
+    Command,
+}
+
+const COMMANDS: &[(&str, LineKind)] = &[
+    ("##[group]", LineKind::GroupStart),
+    ("##[endgroup]", LineKind::GroupEnd),
+    (ERROR_MARKER, LineKind::Error),
+    ("##[warning]", LineKind::Warning),
+    ("##[notice]", LineKind::Notice),
+    ("##[debug]", LineKind::Debug),
+    ("##[command]", LineKind::Command),
+];
+
+/// A log line with its workflow command and ANSI escape sequences parsed out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogLine {
+    pub kind: LineKind,
+    /// The text shown, without the command nor the escape sequences.
+    pub text: String,
+    /// The styles the escape sequences set, by byte range of the text. The unstyled parts
+    /// are left out.
+    pub styles: Vec<(Range<usize>, Style)>,
+}
+
+impl LogLine {
+    /// Parses a log line without its timestamp.
+    pub fn parse(line: &str) -> Self {
+        let (kind, line) = COMMANDS
+            .iter()
+            .find_map(|(marker, kind)| Some((*kind, line.strip_prefix(marker)?)))
+            .unwrap_or((LineKind::Plain, line));
+        let mut text = String::with_capacity(line.len());
+        let mut styles: Vec<(Range<usize>, Style)> = Vec::new();
+        let mut style = Style::default();
+        let mut start = 0;
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '\x1b' {
+                text.push(c);
+                continue;
+            }
+            if chars.peek() != Some(&'[') {
+                continue;
+            }
+            chars.next();
+            let mut parameters = String::new();
+            let Some(end) = chars.by_ref().find(|c| {
+                let end = c.is_ascii_alphabetic();
+                if !end {
+                    parameters.push(*c);
+                }
+                end
+            }) else {
+                break;
+            };
+            // Only the sequences setting colors and text attributes matter, the others
+            // move the cursor of a terminal or clear it
+            if end != 'm' {
+                continue;
+            }
+            if start < text.len() && style != Style::default() {
+                styles.push((start..text.len(), style));
+            }
+            start = text.len();
+            style = apply_sgr(style, &parameters);
+        }
+        if start < text.len() && style != Style::default() {
+            styles.push((start..text.len(), style));
+        }
+        Self { kind, text, styles }
+    }
+}
+
+/// Applies the parameters of a Select Graphic Rendition sequence, e.g. `1;31` of `\x1b[1;31m`,
+/// to `style`.
+fn apply_sgr(mut style: Style, parameters: &str) -> Style {
+    let mut codes = parameters
+        .split(';')
+        .map(|code| code.parse::<u8>().unwrap_or(0));
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            30..=37 => style.fg(Color::Indexed(code - 30)),
+            90..=97 => style.fg(Color::Indexed(code - 90 + 8)),
+            40..=47 => style.bg(Color::Indexed(code - 40)),
+            100..=107 => style.bg(Color::Indexed(code - 100 + 8)),
+            38 | 48 => {
+                // 256 colors as `38;5;n`, or true colors as `38;2;r;g;b`
+                let color = match codes.next() {
+                    Some(5) => codes.next().map(Color::Indexed),
+                    Some(2) => match (codes.next(), codes.next(), codes.next()) {
+                        (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r, g, b)),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                match color {
+                    Some(color) if code == 38 => style.fg(color),
+                    Some(color) => style.bg(color),
+                    None => style,
+                }
+            }
+            39 => Style { fg: None, ..style },
+            49 => Style { bg: None, ..style },
+            _ => style,
+        };
+    }
+    style
+}
+
+/// A `##[group]` section of a log, which GitHub shows folded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogGroup {
+    /// The line of its header.
+    pub header: usize,
+    /// The line after its last one, its `##[endgroup]` when it has one.
+    pub end: usize,
+    /// Whether one of its lines reports an error.
+    pub has_error: bool,
+}
+
+/// The sections of a log. They don't nest, a group opened within another closes it.
+pub fn groups(lines: &[LogLine]) -> Vec<LogGroup> {
+    let mut groups: Vec<LogGroup> = Vec::new();
+    let mut open: Option<LogGroup> = None;
+    for (index, line) in lines.iter().enumerate() {
+        match line.kind {
+            LineKind::GroupStart => {
+                if let Some(mut group) = open.take() {
+                    group.end = index;
+                    groups.push(group);
+                }
+                open = Some(LogGroup {
+                    header: index,
+                    end: lines.len(),
+                    has_error: false,
+                });
+            }
+            LineKind::GroupEnd => {
+                if let Some(mut group) = open.take() {
+                    group.end = index;
+                    groups.push(group);
+                }
+            }
+            LineKind::Error => {
+                if let Some(group) = open.as_mut() {
+                    group.has_error = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    groups.extend(open);
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_are_parsed_into_commands_and_styled_text() {
+        let line =
+            LogLine::parse("##[error]\x1b[1;31merror\x1b[0m: 2 tests \x1b[38;5;208mfailed\x1b[K");
+        assert_eq!(line.kind, LineKind::Error);
+        assert_eq!(line.text, "error: 2 tests failed");
+        assert_eq!(
+            line.styles,
+            [
+                (
+                    0..5,
+                    Style::default()
+                        .fg(Color::Indexed(1))
+                        .add_modifier(Modifier::BOLD)
+                ),
+                (15..21, Style::default().fg(Color::Indexed(208))),
+            ]
+        );
+
+        let lines: Vec<LogLine> = [
+            "##[group]Run actions/checkout@v4",
+            "with: ...",
+            "##[endgroup]",
+            "##[group]Run cargo test",
+            "##[error]Process completed with exit code 101.",
+        ]
+        .into_iter()
+        .map(LogLine::parse)
+        .collect();
+        assert_eq!(
+            groups(&lines),
+            [
+                LogGroup {
+                    header: 0,
+                    end: 2,
+                    has_error: false
+                },
+                LogGroup {
+                    header: 3,
+                    end: 5,
+                    has_error: true
+                },
+            ]
+        );
+    }
+}
@@ -1,14 +1,55 @@
-use std::process::Command;
+use std::{
+    cmp::Reverse,
+    path::PathBuf,
+    process::{Command, ExitCode},
+};
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use color_eyre::eyre::eyre;
 
-use crate::app::App;
+use crate::{
+    app::App,
+    config::{Config, RunOutcome},
+    export::OutputFormat,
+    gh_cli::{Backend, CancelFlag, GhCli, RepoInfo},
+    theme::ThemePreset,
+};
 
 pub mod app;
+pub mod client;
+pub mod commands;
+pub mod config;
+pub mod crash_report;
+pub mod dispatch_history;
+pub mod doctor;
 pub mod event;
+pub mod export;
+pub mod filters;
+pub mod flaky_tests;
+pub mod gh_api;
 pub mod gh_cli;
+pub mod gh_extensions;
+pub mod gh_version;
+pub mod instance_lock;
+pub mod job_sort;
+pub mod keymap;
+pub mod links;
+pub mod log_dump;
+pub mod logs;
+pub mod markdown;
+pub mod notifications;
+pub mod pinned_jobs;
+pub mod refresh_history;
+pub mod report;
+pub mod run_history;
+pub mod snapshot;
+pub mod text_input;
+pub mod theme;
+pub mod timeline;
 pub mod ui;
+pub mod usage;
+pub mod views;
+pub mod workflow_mutes;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -24,14 +65,114 @@ pub struct Args {
     /// Lastest Run Only
     #[arg(short, long, default_value_t = false)]
     latest: bool,
+
+    /// Repository to monitor as owner/name, can be repeated (defaults to the current repo)
+    #[arg(short, long = "repo", value_name = "OWNER/NAME")]
+    repos: Vec<RepoInfo>,
+
+    /// How to talk to the GitHub API
+    #[arg(long, value_enum, default_value_t = Backend::Api)]
+    backend: Backend,
+
+    /// Enable the API request inspector, toggled with F12
+    #[arg(long, default_value_t = false)]
+    debug: bool,
+
+    /// Send a desktop notification when a run concludes
+    #[arg(long, default_value_t = false)]
+    notify: bool,
+
+    /// Outcomes to notify, comma separated (defaults to the config file, or both)
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "OUTCOME")]
+    notify_on: Vec<RunOutcome>,
+
+    /// Select the first job of each new run of the current branch, e.g. right after a push
+    #[arg(long, default_value_t = false)]
+    follow_new_runs: bool,
+
+    /// Seconds between two refreshes of the runs (defaults to the config file, or 7)
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    interval: Option<u64>,
+
+    /// Watch a single run until it concludes, exiting with a failure code unless it succeeds
+    #[arg(short, long, value_name = "RUN_ID")]
+    watch: Option<u64>,
+
+    /// Write the jobs to a .json, .csv or .md file without starting the TUI
+    #[arg(long, value_name = "PATH")]
+    export: Option<PathBuf>,
+
+    /// Save the whole log of a job to the current directory without starting the TUI
+    #[arg(long, value_name = "JOB_ID")]
+    dump_logs: Option<u64>,
+
+    /// Save the logs of every job of the run of the --dump-logs job, as a zip archive
+    #[arg(long, default_value_t = false, requires = "dump_logs")]
+    whole_run: bool,
+
+    /// Print the jobs once without starting the TUI, exiting with a failure code if any failed
+    #[arg(long, default_value_t = false)]
+    once: bool,
+
+    /// How --once prints the jobs
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table, requires = "once")]
+    format: OutputFormat,
+
+    /// Color theme, cycled with T (defaults to the config file, or dark)
+    #[arg(long, value_enum, value_name = "THEME")]
+    theme: Option<ThemePreset>,
+
+    /// Poll GitHub even when another instance already polls the same repositories, rather
+    /// than showing what it fetches
+    #[arg(long, default_value_t = false)]
+    standalone: bool,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
 }
 
-fn main() -> color_eyre::Result<()> {
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Print a digest of recent runs without starting the TUI, e.g. to mail it from cron
+    Report {
+        /// Cover the runs of the past 24 hours
+        #[arg(long, required = true)]
+        daily: bool,
+    },
+    /// Check that everything lazyactions needs is set up, and print how to fix what isn't
+    Doctor,
+}
+
+fn main() -> color_eyre::Result<ExitCode> {
     color_eyre::install()?;
-    let _args = Args::parse();
-    Command::new("clear");
-    // Check for GitHub CLI installation and authentication
-    println!("Checking GitHub CLI status...");
+    let args = Args::parse();
+    // Diagnose a missing or unauthenticated GitHub CLI, or a broken config, rather than
+    // stopping at it
+    if let Some(Commands::Doctor) = args.command {
+        return Ok(doctor::run(args.repos, args.backend));
+    }
+    let config = Config::load()?;
+    if config.crash_reports {
+        crash_report::enable();
+    }
+    let result = run(args, config);
+    if let Err(e) = &result
+        && crash_report::is_enabled()
+    {
+        match crash_report::CrashReport::of_error(e).save() {
+            Ok(path) => eprintln!("A crash report was written to {}", path.display()),
+            Err(e) => eprintln!("Could not write a crash report: {:?}", e),
+        }
+    }
+    result
+}
+
+fn run(args: Args, config: Config) -> color_eyre::Result<ExitCode> {
+    // Check for GitHub CLI installation and authentication, keeping stdout for the report
+    // when one is requested as it is usually piped elsewhere
+    if args.command.is_none() && args.export.is_none() && args.dump_logs.is_none() && !args.once {
+        println!("Checking GitHub CLI status...");
+    }
     let auth_status = Command::new("gh").arg("auth").arg("status").output()?;
 
     if !auth_status.status.success() {
@@ -39,9 +180,81 @@ fn main() -> color_eyre::Result<()> {
             "GitHub CLI is not installed or not authenticated. Please install it and run 'gh auth login'."
         ));
     }
+    if let Some(Commands::Report { .. }) = args.command {
+        let gh_cli = headless_gh_cli(&args);
+        report::print_daily(&gh_cli)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Some(path) = &args.export {
+        // Fail on a file of unknown format before fetching anything
+        export::ExportFormat::from_path(path)?;
+        let gh_cli = headless_gh_cli(&args);
+        let mut jobs = gh_cli
+            .fetch_github_workflow_data(&CancelFlag::default())?
+            .jobs;
+        // Most recent first, as in the job columns
+        jobs.sort_by_key(|job| Reverse(job.started_at));
+        let exported = export::export_jobs(path, &jobs)?;
+        eprintln!("Exported {} jobs to {}", exported, path.display());
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Some(job_id) = args.dump_logs {
+        let gh_cli = headless_gh_cli(&args);
+        let repo = gh_cli
+            .repos()
+            .first()
+            .map(RepoInfo::full_name)
+            .ok_or_else(|| eyre!("No repository to save the logs of a job from"))?;
+        let (run_id, job_name) = gh_cli.fetch_job_run(&repo, job_id)?;
+        let (path, bytes, description) = if args.whole_run {
+            (
+                log_dump::run_logs_file(&repo, run_id),
+                gh_cli.download_run_logs(&repo, run_id)?,
+                format!("the logs of run {}", run_id),
+            )
+        } else {
+            (
+                log_dump::job_log_file(&repo, run_id, &job_name),
+                gh_cli.download_job_log(&repo, job_id)?,
+                format!("the log of {}", job_name),
+            )
+        };
+        let path = log_dump::save(&path, &bytes)?;
+        eprintln!("Saved {} to {}", description, path.display());
+        return Ok(ExitCode::SUCCESS);
+    }
+    if args.once {
+        let gh_cli = headless_gh_cli(&args);
+        let mut jobs = gh_cli
+            .fetch_github_workflow_data(&CancelFlag::default())?
+            .jobs;
+        jobs.sort_by_key(|job| Reverse(job.started_at));
+        let any_failed = export::print_jobs(&jobs, args.format)?;
+        return Ok(if any_failed {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        });
+    }
     println!("GitHub CLI is installed and authenticated.");
     let terminal = ratatui::init();
-    let result = App::new().run(terminal);
+    let result = App::new(args, config).run(terminal);
     ratatui::restore();
     result
 }
+
+/// Creates the client of the modes without a TUI, printing what it couldn't look up as there
+/// are no toasts to show it in.
+fn headless_gh_cli(args: &Args) -> GhCli {
+    let gh_cli = GhCli::new(
+        args.branch,
+        args.user,
+        args.latest,
+        args.repos.clone(),
+        args.backend,
+    );
+    for warning in gh_cli.startup_warnings() {
+        eprintln!("Warning: {}", warning);
+    }
+    gh_cli
+}
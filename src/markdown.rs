@@ -0,0 +1,275 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Renders markdown as styled lines, e.g. for a `Paragraph`.
+///
+/// Supports the subset of GitHub flavoured markdown commonly found in job summaries and
+/// release notes: headings, bullet, numbered and task lists, fenced code blocks, tables,
+/// block quotes, horizontal rules and inline code, bold, italic and links. Anything else is
+/// shown as plain text.
+pub fn to_lines(markdown: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut source_lines = markdown.lines().peekable();
+    while let Some(line) = source_lines.next() {
+        let trimmed = line.trim_start();
+
+        // Fenced code blocks are shown verbatim until the closing fence
+        if let Some(fence) = ["```", "~~~"]
+            .into_iter()
+            .find(|fence| trimmed.starts_with(fence))
+        {
+            let language = trimmed.trim_start_matches(fence).trim();
+            if !language.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    language.to_string(),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            for code in source_lines.by_ref() {
+                if code.trim_start().starts_with(fence) {
+                    break;
+                }
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", code),
+                    Style::default().fg(Color::LightYellow),
+                )));
+            }
+            continue;
+        }
+
+        // Consecutive rows of a table are laid out together so their columns line up
+        if trimmed.starts_with('|') {
+            let mut rows = vec![trimmed];
+            while let Some(next) = source_lines.next_if(|next| next.trim_start().starts_with('|')) {
+                rows.push(next.trim_start());
+            }
+            lines.extend(table_lines(&rows));
+            continue;
+        }
+
+        lines.push(block_line(line));
+    }
+    lines
+}
+
+/// Renders a single line that is not part of a code block or table.
+fn block_line(line: &str) -> Line<'static> {
+    let trimmed = line.trim_start();
+    let indent = " ".repeat(line.len() - trimmed.len());
+
+    // Headings
+    let hashes = trimmed.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+        let style = match hashes {
+            1 => Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            2 => Style::default()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD),
+            _ => Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        };
+        return Line::from(inline_spans(trimmed[hashes..].trim(), style));
+    }
+
+    // Horizontal rules
+    let compact: String = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.len() >= 3
+        && ['-', '*', '_']
+            .into_iter()
+            .any(|rule| compact.chars().all(|c| c == rule))
+    {
+        return Line::from(Span::styled(
+            "─".repeat(40),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    // Block quotes
+    if let Some(quote) = trimmed.strip_prefix('>') {
+        let mut spans = vec![Span::styled(
+            format!("{}│ ", indent),
+            Style::default().fg(Color::DarkGray),
+        )];
+        spans.extend(inline_spans(
+            quote.trim_start(),
+            Style::default()
+                .fg(Color::Gray)
+                .add_modifier(Modifier::ITALIC),
+        ));
+        return Line::from(spans);
+    }
+
+    // Bullet and task lists
+    if let Some(item) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| trimmed.strip_prefix(marker))
+    {
+        let (marker, item) = if let Some(item) = item.strip_prefix("[ ] ") {
+            ("☐ ", item)
+        } else if let Some(item) = item
+            .strip_prefix("[x] ")
+            .or_else(|| item.strip_prefix("[X] "))
+        {
+            ("☑ ", item)
+        } else {
+            ("• ", item)
+        };
+        let mut spans = vec![Span::styled(
+            format!("{}{}", indent, marker),
+            Style::default().fg(Color::LightBlue),
+        )];
+        spans.extend(inline_spans(item, Style::default()));
+        return Line::from(spans);
+    }
+
+    // Numbered lists
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0
+        && let Some(item) = trimmed[digits..]
+            .strip_prefix(". ")
+            .or_else(|| trimmed[digits..].strip_prefix(") "))
+    {
+        let mut spans = vec![Span::styled(
+            format!("{}{}. ", indent, &trimmed[..digits]),
+            Style::default().fg(Color::LightBlue),
+        )];
+        spans.extend(inline_spans(item, Style::default()));
+        return Line::from(spans);
+    }
+
+    let mut spans = vec![Span::raw(indent)];
+    spans.extend(inline_spans(trimmed, Style::default()));
+    Line::from(spans)
+}
+
+/// Lays out the rows of a table with aligned columns, the first row being the header.
+fn table_lines(rows: &[&str]) -> Vec<Line<'static>> {
+    let cells = |row: &str| -> Vec<String> {
+        let row = row.trim().trim_start_matches('|');
+        let row = row.strip_suffix('|').unwrap_or(row);
+        row.split('|').map(|cell| cell.trim().to_string()).collect()
+    };
+    // The row separating the header from the body only holds dashes and colons
+    let is_separator = |row: &[String]| {
+        row.iter()
+            .all(|cell| !cell.is_empty() && cell.chars().all(|c| matches!(c, '-' | ':')))
+    };
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| cells(row))
+        .filter(|row| !is_separator(row))
+        .collect();
+
+    // Widths are measured on the text as displayed, after inline markup is removed
+    let column_count = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut widths = vec![0; column_count];
+    let rendered: Vec<Vec<Vec<Span<'static>>>> = rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let style = if index == 0 {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            row.iter()
+                .enumerate()
+                .map(|(column, cell)| {
+                    let spans = inline_spans(cell, style);
+                    widths[column] = widths[column].max(spans_width(&spans));
+                    spans
+                })
+                .collect()
+        })
+        .collect();
+
+    let separator = || Span::styled(" │ ", Style::default().fg(Color::DarkGray));
+    let mut lines = Vec::new();
+    for (index, row) in rendered.into_iter().enumerate() {
+        let mut spans = Vec::new();
+        for (column, cell) in row.into_iter().enumerate() {
+            if column > 0 {
+                spans.push(separator());
+            }
+            let padding = widths[column].saturating_sub(spans_width(&cell));
+            spans.extend(cell);
+            spans.push(Span::raw(" ".repeat(padding)));
+        }
+        lines.push(Line::from(spans));
+        if index == 0 {
+            let rule = widths
+                .iter()
+                .map(|width| "─".repeat(*width))
+                .collect::<Vec<_>>()
+                .join("─┼─");
+            lines.push(Line::from(Span::styled(
+                rule,
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+    }
+    lines
+}
+
+/// The number of characters shown for a list of spans.
+fn spans_width(spans: &[Span]) -> usize {
+    spans.iter().map(|span| span.content.chars().count()).sum()
+}
+
+/// Splits text into spans for inline code, bold, italic and links, on top of `base`.
+fn inline_spans(text: &str, base: Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let styled = match c {
+            '`' => delimited(rest, "`", "`")
+                .map(|(code, len)| (code, base.fg(Color::LightYellow), len)),
+            '*' if rest.starts_with("**") => delimited(rest, "**", "**")
+                .map(|(bold, len)| (bold, base.add_modifier(Modifier::BOLD), len)),
+            '_' if rest.starts_with("__") => delimited(rest, "__", "__")
+                .map(|(bold, len)| (bold, base.add_modifier(Modifier::BOLD), len)),
+            '*' => delimited(rest, "*", "*")
+                .map(|(italic, len)| (italic, base.add_modifier(Modifier::ITALIC), len)),
+            '[' => delimited(rest, "[", "]").and_then(|(label, label_len)| {
+                let (_, url_len) = delimited(&rest[label_len..], "(", ")")?;
+                Some((
+                    label,
+                    base.fg(Color::LightBlue).add_modifier(Modifier::UNDERLINED),
+                    label_len + url_len,
+                ))
+            }),
+            _ => None,
+        };
+        match styled {
+            Some((content, style, len)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut plain), base));
+                }
+                spans.push(Span::styled(content.to_string(), style));
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+/// If `text` starts with `open` and later contains `close`, returns what is between them and
+/// the length of the whole delimited part.
+fn delimited<'a>(text: &'a str, open: &str, close: &str) -> Option<(&'a str, usize)> {
+    let inner = text.strip_prefix(open)?;
+    let end = inner.find(close).filter(|&end| end > 0)?;
+    Some((&inner[..end], open.len() + end + close.len()))
+}
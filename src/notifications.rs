@@ -0,0 +1,45 @@
+use notify_rust::Notification;
+use std::thread;
+
+use crate::gh_cli::{GithubJob, GithubWorkflowRun};
+
+/// Shows a desktop notification for a workflow run that has concluded.
+///
+/// The notification is sent from its own thread, as talking to the notification daemon can
+/// block. Failures are ignored since there may be no daemon at all, e.g. over SSH.
+pub fn notify_run_concluded(run: &GithubWorkflowRun) {
+    let summary = format!(
+        "{} {}",
+        run.name,
+        run.conclusion.as_deref().unwrap_or("completed")
+    );
+    let body = format!("{} on {} by {}", run.repo, run.head_branch, run.actor_login);
+    thread::spawn(move || {
+        let _ = Notification::new()
+            .appname("lazyactions")
+            .summary(&summary)
+            .body(&body)
+            .show();
+    });
+}
+
+/// Shows a desktop notification for a pinned job whose status changed, sent the same way as
+/// [`notify_run_concluded`].
+pub fn notify_pinned_job_changed(job: &GithubJob) {
+    let summary = format!(
+        "{} {}",
+        job.name,
+        job.conclusion.as_deref().unwrap_or(&job.status)
+    );
+    let body = format!(
+        "{} of {} on {}",
+        job.workflow_name, job.repo, job.head_branch
+    );
+    thread::spawn(move || {
+        let _ = Notification::new()
+            .appname("lazyactions")
+            .summary(&summary)
+            .body(&body)
+            .show();
+    });
+}
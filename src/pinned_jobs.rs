@@ -0,0 +1,85 @@
+use color_eyre::eyre::WrapErr;
+use std::{fs, path::PathBuf};
+
+use crate::gh_cli::GithubJob;
+
+/// The jobs pinned to the top of their column, persisted as JSON along with how each was last
+/// seen, so that they stay listed after their run drops out of the fetched ones and that a
+/// change of their status is noticed across sessions.
+#[derive(Debug, Default)]
+pub struct PinnedJobs {
+    pub entries: Vec<GithubJob>,
+    /// Where the pinned jobs are saved, `None` to keep them in memory, e.g. in tests.
+    path: Option<PathBuf>,
+}
+
+impl PinnedJobs {
+    /// Loads the pinned jobs from disk, starting with none if there are none yet or they
+    /// can't be read.
+    pub fn load() -> Self {
+        let path = pins_path();
+        let entries = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self { entries, path }
+    }
+
+    pub fn is_pinned(&self, job_id: u64) -> bool {
+        self.entries.iter().any(|pinned| pinned.id == job_id)
+    }
+
+    /// Pins a job, or unpins it if it was pinned, and returns whether it is now pinned.
+    pub fn toggle(&mut self, job: &GithubJob) -> color_eyre::Result<bool> {
+        let pinned_before = self.entries.len();
+        self.entries.retain(|pinned| pinned.id != job.id);
+        let pinned = self.entries.len() == pinned_before;
+        if pinned {
+            self.entries.push(job.clone());
+        }
+        self.save()?;
+        Ok(pinned)
+    }
+
+    /// Takes in how the pinned jobs among `jobs` are now, and returns those whose status or
+    /// conclusion changed since they were last seen, as they were before.
+    pub fn update<'a>(
+        &mut self,
+        jobs: impl Iterator<Item = &'a GithubJob>,
+    ) -> color_eyre::Result<Vec<GithubJob>> {
+        let mut changed = Vec::new();
+        for job in jobs {
+            let Some(pinned) = self.entries.iter_mut().find(|pinned| pinned.id == job.id) else {
+                continue;
+            };
+            if pinned.status != job.status || pinned.conclusion != job.conclusion {
+                changed.push(std::mem::replace(pinned, job.clone()));
+            } else {
+                *pinned = job.clone();
+            }
+        }
+        if !changed.is_empty() {
+            self.save()?;
+        }
+        Ok(changed)
+    }
+
+    fn save(&self) -> color_eyre::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err(format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(&self.entries)?;
+        fs::write(path, json).wrap_err(format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Where the pinned jobs are stored, e.g. `~/.local/share/lazyactions/pinned_jobs.json`, `None`
+/// if there is no data directory to store them in.
+fn pins_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("lazyactions").join("pinned_jobs.json"))
+}
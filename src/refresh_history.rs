@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+
+use crate::gh_cli::{GithubJob, GithubWorkflowRun};
+
+/// How many refreshes are kept to look back at.
+const MAX_PAST_REFRESHES: usize = 30;
+
+/// The jobs and runs as a refresh left them.
+#[derive(Clone, Debug)]
+pub struct PastRefresh {
+    pub fetched_at: DateTime<Utc>,
+    pub jobs: VecDeque<GithubJob>,
+    pub runs: Vec<GithubWorkflowRun>,
+}
+
+/// The latest refreshes, oldest first, kept in memory so that a state that flashed by, e.g. a
+/// job that showed as cancelled for a single refresh, can be looked at again.
+#[derive(Debug, Default)]
+pub struct RefreshHistory {
+    pub entries: VecDeque<PastRefresh>,
+}
+
+impl RefreshHistory {
+    /// Keeps the jobs and runs of a refresh, dropping the oldest one kept beyond
+    /// [`MAX_PAST_REFRESHES`].
+    pub fn record(
+        &mut self,
+        fetched_at: DateTime<Utc>,
+        jobs: &VecDeque<GithubJob>,
+        runs: &[GithubWorkflowRun],
+    ) {
+        if self.entries.len() == MAX_PAST_REFRESHES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(PastRefresh {
+            fetched_at,
+            jobs: jobs.clone(),
+            runs: runs.to_vec(),
+        });
+    }
+
+    /// Where the refresh fetched at `fetched_at` is kept, `None` once it was dropped.
+    pub fn position(&self, fetched_at: DateTime<Utc>) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.fetched_at == fetched_at)
+    }
+}
@@ -0,0 +1,124 @@
+use chrono::{TimeDelta, Utc};
+use std::collections::BTreeMap;
+
+use crate::{
+    gh_cli::{GhCli, RunDetails},
+    ui::format_elapsed,
+};
+
+/// How many of the slowest jobs a report lists.
+const SLOWEST_JOBS: usize = 5;
+
+/// Prints a plain text digest of the runs of the past day in every monitored repository:
+/// what failed, the slowest jobs and how much runner time each workflow used. Meant to be
+/// piped into mail or a chat webhook from cron.
+pub fn print_daily(gh_cli: &GhCli) -> color_eyre::Result<()> {
+    let until = Utc::now();
+    let since = until - TimeDelta::days(1);
+    for repo_info in gh_cli.repos() {
+        let runs = gh_cli.fetch_runs_since(repo_info, since)?;
+        println!(
+            "Daily report for {}, {} to {}\n",
+            repo_info.full_name(),
+            since.format("%Y-%m-%d %H:%M"),
+            until.format("%Y-%m-%d %H:%M UTC")
+        );
+        print_repo_report(&runs);
+    }
+    Ok(())
+}
+
+/// Prints the digest of the runs of a single repository.
+fn print_repo_report(runs: &[RunDetails]) {
+    let count = |conclusion: &str| {
+        runs.iter()
+            .filter(|details| details.run.conclusion.as_deref() == Some(conclusion))
+            .count()
+    };
+    let still_running = runs
+        .iter()
+        .filter(|details| details.run.status != "completed")
+        .count();
+    println!(
+        "Runs: {} ({} succeeded, {} failed, {} cancelled, {} still running)",
+        runs.len(),
+        count("success"),
+        count("failure"),
+        count("cancelled"),
+        still_running
+    );
+
+    // Only jobs that ran to the end count towards the time used
+    let now = Utc::now();
+    let finished_jobs: Vec<_> = runs
+        .iter()
+        .flat_map(|details| &details.jobs)
+        .filter(|job| job.completed_at.is_some() && job.conclusion.as_deref() != Some("skipped"))
+        .collect();
+    let total: TimeDelta = finished_jobs.iter().map(|job| job.duration(now)).sum();
+    println!(
+        "Job time: {} across {} jobs\n",
+        format_elapsed(total),
+        finished_jobs.len()
+    );
+
+    let failed_runs: Vec<_> = runs
+        .iter()
+        .filter(|details| details.run.conclusion.as_deref() == Some("failure"))
+        .collect();
+    if !failed_runs.is_empty() {
+        println!("Failures");
+        for details in failed_runs {
+            let failed_jobs: Vec<&str> = details
+                .jobs
+                .iter()
+                .filter(|job| job.conclusion.as_deref() == Some("failure"))
+                .map(|job| job.name.as_str())
+                .collect();
+            println!(
+                "  {} on {} by {}: {}\n    {}",
+                details.run.name,
+                details.run.head_branch,
+                details.run.actor_login,
+                failed_jobs.join(", "),
+                details.run.html_url
+            );
+        }
+        println!();
+    }
+
+    let mut slowest = finished_jobs.clone();
+    slowest.sort_by_key(|job| std::cmp::Reverse(job.duration(now)));
+    if !slowest.is_empty() {
+        println!("Slowest jobs");
+        for job in slowest.iter().take(SLOWEST_JOBS) {
+            println!(
+                "  {:>8}  {} in {}\n            {}",
+                format_elapsed(job.duration(now)),
+                job.name,
+                job.workflow_name,
+                job.html_url
+            );
+        }
+        println!();
+    }
+
+    let mut usage: BTreeMap<&str, (TimeDelta, usize)> = BTreeMap::new();
+    for job in &finished_jobs {
+        let (time, jobs) = usage.entry(job.workflow_name.as_str()).or_default();
+        *time += job.duration(now);
+        *jobs += 1;
+    }
+    if !usage.is_empty() {
+        println!("Usage by workflow");
+        for (workflow, (time, jobs)) in usage {
+            println!(
+                "  {:>8}  {} ({} jobs)",
+                format_elapsed(time),
+                workflow,
+                jobs
+            );
+        }
+        println!();
+    }
+}
@@ -0,0 +1,117 @@
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use std::{cmp::Reverse, collections::BTreeMap, fs, path::PathBuf};
+
+use crate::gh_cli::{BACKFILL_PAGE_SIZE, HistoryRun, WorkflowHistoryPage};
+
+/// How many pages of runs a backfill goes back at most, as the API lists no further than the
+/// first thousand results.
+pub const MAX_BACKFILL_PAGES: u64 = 10;
+
+/// The completed runs of a repository backfilled so far.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RepoHistory {
+    /// The runs, newest first.
+    pub runs: Vec<HistoryRun>,
+    /// How many pages of runs the latest backfill went back.
+    pub pages: u64,
+    /// How many completed runs the repository had as of the latest page backfilled.
+    pub total_count: u64,
+}
+
+impl RepoHistory {
+    /// How many runs a backfill goes back to.
+    pub fn target(&self) -> u64 {
+        self.total_count
+            .min(MAX_BACKFILL_PAGES * BACKFILL_PAGE_SIZE)
+    }
+
+    /// Whether the backfill went back as far as it can.
+    pub fn is_complete(&self) -> bool {
+        self.pages >= MAX_BACKFILL_PAGES
+            || (self.pages > 0 && self.pages * BACKFILL_PAGE_SIZE >= self.total_count)
+    }
+}
+
+/// The completed runs of each repository paged in by backfills, persisted as JSON so that the
+/// statistics drawn from them cover more than the latest runs from the first session on.
+#[derive(Debug, Default)]
+pub struct RunHistory {
+    /// The runs of each repository, by lowercased `owner/name`.
+    pub repos: BTreeMap<String, RepoHistory>,
+    /// Where the history is saved, `None` to keep it in memory, e.g. in tests.
+    path: Option<PathBuf>,
+}
+
+impl RunHistory {
+    /// Loads the history from disk, starting empty if there is none yet or it can't be read.
+    pub fn load() -> Self {
+        let path = history_path();
+        let repos = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self { repos, path }
+    }
+
+    pub fn repo(&self, repo: &str) -> Option<&RepoHistory> {
+        self.repos.get(&repo.to_lowercase())
+    }
+
+    /// The runs of the workflow at `path` in `repo` backfilled so far, newest first.
+    pub fn workflow_runs<'a>(
+        &'a self,
+        repo: &str,
+        path: &'a str,
+    ) -> impl Iterator<Item = &'a HistoryRun> {
+        self.repo(repo)
+            .into_iter()
+            .flat_map(|history| history.runs.iter())
+            .filter(move |entry| entry.run.path == path)
+    }
+
+    /// Takes in the `page`th page of the runs of `repo` and writes the history to disk. Runs
+    /// already kept, pushed onto a later page by those completed since, are kept once.
+    pub fn record(
+        &mut self,
+        repo: &str,
+        page: u64,
+        fetched: WorkflowHistoryPage,
+    ) -> color_eyre::Result<()> {
+        let history = self.repos.entry(repo.to_lowercase()).or_default();
+        history.pages = page;
+        history.total_count = fetched.total_count;
+        for entry in fetched.runs {
+            match history
+                .runs
+                .iter_mut()
+                .find(|kept| kept.run.id == entry.run.id)
+            {
+                Some(kept) => *kept = entry,
+                None => history.runs.push(entry),
+            }
+        }
+        // Runs are numbered in the order they were created
+        history.runs.sort_by_key(|entry| Reverse(entry.run.id));
+        self.save()
+    }
+
+    fn save(&self) -> color_eyre::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err(format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string(&self.repos)?;
+        fs::write(path, json).wrap_err(format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Where the run history is stored, e.g. `~/.local/share/lazyactions/run_history.json`, `None`
+/// if there is no data directory to store it in.
+fn history_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("lazyactions").join("run_history.json"))
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::{WrapErr, eyre};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::gh_cli::WorkflowData;
+
+/// The runs and jobs of a repository as last fetched, kept on disk so that the next start
+/// has something to show before its first fetch completes.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub saved_at: DateTime<Utc>,
+    pub data: WorkflowData,
+}
+
+impl Snapshot {
+    /// Loads the snapshot of a repository, `None` if there is none yet or it can't be read,
+    /// e.g. because an older version of lazyactions wrote it.
+    pub fn load(repo: &str) -> Option<Self> {
+        snapshot_path(repo)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+    }
+
+    /// Writes the snapshot of a repository, replacing the previous one.
+    pub fn save(&self, repo: &str) -> color_eyre::Result<()> {
+        let path =
+            snapshot_path(repo).ok_or_else(|| eyre!("Could not determine the cache directory"))?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err(format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string(self)?;
+        // The instances following this one read it whenever, so it is replaced at once rather
+        // than rewritten in place, which they could catch half-written
+        let temporary = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        fs::write(&temporary, json).wrap_err(format!("Failed to write {}", temporary.display()))?;
+        fs::rename(&temporary, &path)
+            .inspect_err(|_| {
+                let _ = fs::remove_file(&temporary);
+            })
+            .wrap_err(format!("Failed to replace {}", path.display()))
+    }
+}
+
+/// Where the snapshot of a repository is stored, e.g. `~/.cache/lazyactions/octocat/hello.json`.
+fn snapshot_path(repo: &str) -> Option<PathBuf> {
+    let repo = repo.to_ascii_lowercase();
+    let (owner, name) = repo.split_once('/')?;
+    dirs::cache_dir().map(|dir| {
+        dir.join("lazyactions")
+            .join(owner)
+            .join(format!("{}.json", name))
+    })
+}
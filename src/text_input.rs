@@ -0,0 +1,248 @@
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    style::Style,
+    text::Span,
+};
+
+/// Checks what was typed in a [`TextInput`], returning why it can't be submitted.
+pub type Validator = fn(&str) -> Result<(), String>;
+
+/// A single-line text input, used by every prompt that captures typing.
+///
+/// It handles the editing keys: characters, `Backspace`/`Delete`, `Left`/`Right`,
+/// `Home`/`End` and the `ctrl-a`, `ctrl-e`, `ctrl-u` and `ctrl-w` shortcuts of shells. The
+/// prompt owning it handles the other keys, e.g. `Up`/`Down` to go through the history of
+/// the submitted values where that makes sense.
+#[derive(Clone, Debug, Default)]
+pub struct TextInput {
+    value: String,
+    /// Where the cursor is, in characters from the start.
+    cursor: usize,
+    /// The values submitted before, oldest first.
+    history: Vec<String>,
+    /// The entry of the history shown, and what was typed before going through the history.
+    browsing: Option<(usize, String)>,
+    validator: Option<Validator>,
+}
+
+impl TextInput {
+    /// An input holding a value, with the cursor at its end.
+    pub fn new(value: impl Into<String>) -> Self {
+        let mut input = Self::default();
+        input.set_value(value);
+        input
+    }
+
+    /// Checks the value with `validator` when it is submitted.
+    pub fn with_validator(mut self, validator: Validator) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Replaces the value, moving the cursor to its end.
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.chars().count();
+        self.browsing = None;
+    }
+
+    /// Handles an editing key, returning whether the value changed. Other keys are ignored.
+    pub fn handle_key(&mut self, key_event: KeyEvent) -> bool {
+        let before = self.value.len();
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        match key_event.code {
+            KeyCode::Char('a') if ctrl => self.cursor = 0,
+            KeyCode::Char('e') if ctrl => self.cursor = self.value.chars().count(),
+            KeyCode::Char('u') if ctrl => {
+                self.value.drain(..self.byte_index(self.cursor));
+                self.cursor = 0;
+            }
+            KeyCode::Char('w') if ctrl => {
+                // Delete the word before the cursor, and the spaces after it
+                let before_cursor: Vec<char> = self.value.chars().take(self.cursor).collect();
+                let spaces = before_cursor
+                    .iter()
+                    .rev()
+                    .take_while(|c| c.is_whitespace())
+                    .count();
+                let word = before_cursor
+                    .iter()
+                    .rev()
+                    .skip(spaces)
+                    .take_while(|c| !c.is_whitespace())
+                    .count();
+                let start = self.cursor - spaces - word;
+                self.value
+                    .drain(self.byte_index(start)..self.byte_index(self.cursor));
+                self.cursor = start;
+            }
+            KeyCode::Char(_) if ctrl || key_event.modifiers.contains(KeyModifiers::ALT) => {}
+            KeyCode::Char(c) => {
+                self.value.insert(self.byte_index(self.cursor), c);
+                self.cursor += 1;
+            }
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.value.remove(self.byte_index(self.cursor));
+            }
+            KeyCode::Delete if self.cursor < self.value.chars().count() => {
+                self.value.remove(self.byte_index(self.cursor));
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.value.chars().count()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.chars().count(),
+            _ => {}
+        }
+        let changed = self.value.len() != before;
+        if changed {
+            self.browsing = None;
+        }
+        changed
+    }
+
+    /// Shows the value submitted before the one shown, returning whether there was one.
+    pub fn previous_in_history(&mut self) -> bool {
+        let index = match &self.browsing {
+            Some((0, _)) => return false,
+            Some((index, _)) => index - 1,
+            None if self.history.is_empty() => return false,
+            None => self.history.len() - 1,
+        };
+        let typed = match self.browsing.take() {
+            Some((_, typed)) => typed,
+            None => self.value.clone(),
+        };
+        self.set_value(self.history[index].clone());
+        self.browsing = Some((index, typed));
+        true
+    }
+
+    /// Shows the value submitted after the one shown, or what was typed before going through
+    /// the history, returning whether the value changed.
+    pub fn next_in_history(&mut self) -> bool {
+        let Some((index, typed)) = self.browsing.take() else {
+            return false;
+        };
+        match self.history.get(index + 1) {
+            Some(value) => {
+                let value = value.clone();
+                self.set_value(value);
+                self.browsing = Some((index + 1, typed));
+            }
+            None => self.set_value(typed),
+        }
+        true
+    }
+
+    /// Why the value can't be submitted, if it can't.
+    pub fn validate(&self) -> Result<(), String> {
+        self.validator
+            .map_or(Ok(()), |validator| validator(&self.value))
+    }
+
+    /// Validates the value and, if it is valid, adds it to the history.
+    pub fn submit(&mut self) -> Result<(), String> {
+        self.validate()?;
+        self.browsing = None;
+        if !self.value.is_empty() && self.history.last() != Some(&self.value) {
+            self.history.push(self.value.clone());
+        }
+        Ok(())
+    }
+
+    /// The value as spans, with a bar at the cursor when the input is focused.
+    pub fn spans(&self, style: Style, cursor_style: Option<Style>) -> Vec<Span<'_>> {
+        let Some(cursor_style) = cursor_style else {
+            return vec![Span::styled(self.value.as_str(), style)];
+        };
+        let (before, after) = self.value.split_at(self.byte_index(self.cursor));
+        let mut spans = vec![Span::styled(before, style), Span::styled("▏", cursor_style)];
+        if !after.is_empty() {
+            spans.push(Span::styled(after, style));
+        }
+        spans
+    }
+
+    /// The byte index of a character index of the value.
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_index)
+            .map_or(self.value.len(), |(index, _)| index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_keys(input: &mut TextInput, codes: &[KeyCode]) {
+        for code in codes {
+            input.handle_key(KeyEvent::from(*code));
+        }
+    }
+
+    #[test]
+    fn edits_at_the_cursor() {
+        let mut input = TextInput::new("mian");
+        type_keys(
+            &mut input,
+            &[
+                KeyCode::Left,
+                KeyCode::Left,
+                KeyCode::Backspace,
+                KeyCode::Right,
+                KeyCode::Char('i'),
+            ],
+        );
+        assert_eq!(input.value(), "main");
+
+        input.set_value("feature/ünïcode branch");
+        input.handle_key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::CONTROL));
+        assert_eq!(input.value(), "feature/ünïcode ");
+        type_keys(&mut input, &[KeyCode::Home, KeyCode::Delete]);
+        assert_eq!(input.value(), "eature/ünïcode ");
+    }
+
+    #[test]
+    fn goes_through_the_history_and_back_to_what_was_typed() {
+        let mut input = TextInput::default();
+        for value in ["build", "test"] {
+            input.set_value(value);
+            input.submit().expect("no validator");
+        }
+        input.set_value("li");
+        assert!(input.previous_in_history());
+        assert_eq!(input.value(), "test");
+        assert!(input.previous_in_history());
+        assert!(!input.previous_in_history());
+        assert_eq!(input.value(), "build");
+        assert!(input.next_in_history());
+        assert!(input.next_in_history());
+        assert_eq!(input.value(), "li");
+        assert!(!input.next_in_history());
+    }
+
+    #[test]
+    fn submitting_runs_the_validator() {
+        let mut input = TextInput::new(" ").with_validator(|value| {
+            if value.trim().is_empty() {
+                Err("A ref is required.".to_string())
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(input.submit(), Err("A ref is required.".to_string()));
+        input.set_value("main");
+        assert_eq!(input.submit(), Ok(()));
+    }
+}
@@ -0,0 +1,242 @@
+use clap::ValueEnum;
+use ratatui::style::{Color, Style};
+use serde::Deserialize;
+use std::{collections::HashMap, str::FromStr};
+
+/// The built-in color palettes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreset {
+    /// For dark terminals, in the terminal's own colors.
+    #[default]
+    Dark,
+    /// For light terminals.
+    Light,
+    /// The dark variant of the Solarized palette.
+    Solarized,
+}
+
+impl ThemePreset {
+    /// The preset after this one, to cycle through them.
+    pub fn next(self) -> Self {
+        match self {
+            ThemePreset::Dark => ThemePreset::Light,
+            ThemePreset::Light => ThemePreset::Solarized,
+            ThemePreset::Solarized => ThemePreset::Dark,
+        }
+    }
+
+    /// The name of the preset, as written in the config file.
+    pub fn name(self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "dark",
+            ThemePreset::Light => "light",
+            ThemePreset::Solarized => "solarized",
+        }
+    }
+}
+
+/// The parts of the UI that are colored, named in snake case in the config file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeRole {
+    Background,
+    Text,
+    Muted,
+    Accent,
+    Selection,
+    Title,
+    Group,
+    Workflow,
+    Bar,
+    Success,
+    Failure,
+    Running,
+    Warning,
+    Skipped,
+    Neutral,
+}
+
+/// A color written in the config file as a name such as `red` or `lightblue`, an ANSI index
+/// such as `208` or a hex code such as `#ff5555`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ThemeColor(Color);
+
+impl TryFrom<String> for ThemeColor {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Color::from_str(&s)
+            .map(ThemeColor)
+            .map_err(|_| format!("unknown color `{}`", s))
+    }
+}
+
+/// The `theme` section of the config file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub preset: ThemePreset,
+    /// Colors overriding those of the preset, e.g. `{ "failure": "#ff5555" }`.
+    pub colors: HashMap<ThemeRole, ThemeColor>,
+    pub focus: FocusConfig,
+}
+
+/// The `theme.focus` section of the config file: how the selected row and the focused panel
+/// stand out, for terminal themes in which reverse video is hard to tell apart.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FocusConfig {
+    /// Whether the selected row is shown in reverse video.
+    pub reversed: bool,
+    /// Whether the selected job, run or step is marked with an arrow.
+    pub marker: bool,
+    /// Whether the selected row is underlined.
+    pub underline: bool,
+    /// Whether the focused panel is drawn with a thick border, a bold one being hard to tell
+    /// from the others in many fonts.
+    pub bold_border: bool,
+}
+
+impl Default for FocusConfig {
+    fn default() -> Self {
+        Self {
+            reversed: true,
+            marker: false,
+            underline: false,
+            bold_border: false,
+        }
+    }
+}
+
+/// The colors the UI is drawn with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Theme {
+    /// Behind everything, `Reset` to keep the terminal's own.
+    pub background: Color,
+    /// Plain text.
+    pub text: Color,
+    /// Hints, placeholders and other secondary text.
+    pub muted: Color,
+    /// Panel borders and field labels.
+    pub accent: Color,
+    /// The selected row and text being typed.
+    pub selection: Color,
+    /// The app's name, the repository tabs and pull requests.
+    pub title: Color,
+    /// The headers grouping jobs in the columns.
+    pub group: Color,
+    /// Workflow names in the columns.
+    pub workflow: Color,
+    /// Behind the title line and the status bar.
+    pub bar: Color,
+    pub success: Color,
+    pub failure: Color,
+    /// Running and queued jobs.
+    pub running: Color,
+    /// Stale data, confirmations and other things that need attention.
+    pub warning: Color,
+    pub skipped: Color,
+    /// Jobs that concluded neither with success nor failure, and plain log lines.
+    pub neutral: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::preset(ThemePreset::Dark)
+    }
+}
+
+impl Theme {
+    /// Creates the theme of a preset with the configured colors applied on top.
+    pub fn new(preset: ThemePreset, colors: &HashMap<ThemeRole, ThemeColor>) -> Self {
+        let mut theme = Self::preset(preset);
+        for (role, ThemeColor(color)) in colors {
+            *theme.color_mut(*role) = *color;
+        }
+        theme
+    }
+
+    /// The colors of a built-in preset.
+    fn preset(preset: ThemePreset) -> Self {
+        match preset {
+            ThemePreset::Dark => Self {
+                background: Color::Reset,
+                text: Color::White,
+                muted: Color::DarkGray,
+                accent: Color::LightBlue,
+                selection: Color::Cyan,
+                title: Color::Magenta,
+                group: Color::LightCyan,
+                workflow: Color::LightYellow,
+                bar: Color::Black,
+                success: Color::LightGreen,
+                failure: Color::Red,
+                running: Color::Yellow,
+                warning: Color::Yellow,
+                skipped: Color::Blue,
+                neutral: Color::Gray,
+            },
+            ThemePreset::Light => Self {
+                background: Color::Rgb(0xff, 0xff, 0xff),
+                text: Color::Rgb(0x1f, 0x23, 0x28),
+                muted: Color::Rgb(0x6e, 0x77, 0x81),
+                accent: Color::Rgb(0x09, 0x69, 0xda),
+                selection: Color::Rgb(0x82, 0x50, 0xdf),
+                title: Color::Rgb(0xbf, 0x39, 0x89),
+                group: Color::Rgb(0x1b, 0x7c, 0x83),
+                workflow: Color::Rgb(0x95, 0x38, 0x00),
+                bar: Color::Rgb(0xea, 0xee, 0xf2),
+                success: Color::Rgb(0x1a, 0x7f, 0x37),
+                failure: Color::Rgb(0xcf, 0x22, 0x2e),
+                running: Color::Rgb(0x9a, 0x67, 0x00),
+                warning: Color::Rgb(0xbc, 0x4c, 0x00),
+                skipped: Color::Rgb(0x57, 0x60, 0x6a),
+                neutral: Color::Rgb(0x57, 0x60, 0x6a),
+            },
+            ThemePreset::Solarized => Self {
+                background: Color::Rgb(0x00, 0x2b, 0x36),
+                text: Color::Rgb(0x93, 0xa1, 0xa1),
+                muted: Color::Rgb(0x58, 0x6e, 0x75),
+                accent: Color::Rgb(0x26, 0x8b, 0xd2),
+                selection: Color::Rgb(0x2a, 0xa1, 0x98),
+                title: Color::Rgb(0xd3, 0x36, 0x82),
+                group: Color::Rgb(0x6c, 0x71, 0xc4),
+                workflow: Color::Rgb(0xb5, 0x89, 0x00),
+                bar: Color::Rgb(0x07, 0x36, 0x42),
+                success: Color::Rgb(0x85, 0x99, 0x00),
+                failure: Color::Rgb(0xdc, 0x32, 0x2f),
+                running: Color::Rgb(0xb5, 0x89, 0x00),
+                warning: Color::Rgb(0xcb, 0x4b, 0x16),
+                skipped: Color::Rgb(0x83, 0x94, 0x96),
+                neutral: Color::Rgb(0x83, 0x94, 0x96),
+            },
+        }
+    }
+
+    fn color_mut(&mut self, role: ThemeRole) -> &mut Color {
+        match role {
+            ThemeRole::Background => &mut self.background,
+            ThemeRole::Text => &mut self.text,
+            ThemeRole::Muted => &mut self.muted,
+            ThemeRole::Accent => &mut self.accent,
+            ThemeRole::Selection => &mut self.selection,
+            ThemeRole::Title => &mut self.title,
+            ThemeRole::Group => &mut self.group,
+            ThemeRole::Workflow => &mut self.workflow,
+            ThemeRole::Bar => &mut self.bar,
+            ThemeRole::Success => &mut self.success,
+            ThemeRole::Failure => &mut self.failure,
+            ThemeRole::Running => &mut self.running,
+            ThemeRole::Warning => &mut self.warning,
+            ThemeRole::Skipped => &mut self.skipped,
+            ThemeRole::Neutral => &mut self.neutral,
+        }
+    }
+
+    /// The style everything is drawn on top of.
+    pub fn base(&self) -> Style {
+        Style::default().fg(self.text).bg(self.background)
+    }
+}
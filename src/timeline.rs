@@ -0,0 +1,88 @@
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
+
+use crate::gh_cli::GithubJob;
+
+/// How many transitions the timeline keeps.
+const MAX_TIMELINE_ENTRIES: usize = 500;
+
+/// A change in the status of a job, noticed by a fetch.
+#[derive(Clone, Debug)]
+pub struct TimelineEntry {
+    pub observed_at: DateTime<Utc>,
+    pub job_name: String,
+    pub workflow_name: String,
+    pub repo: String,
+    pub html_url: String,
+    pub transition: Transition,
+}
+
+/// What happened to a job.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Transition {
+    Queued,
+    Started,
+    /// The job completed, with its conclusion, e.g. `failure`.
+    Concluded(String),
+}
+
+/// The status transitions of jobs observed while the app runs, newest first, so that what
+/// happened while looking elsewhere can be looked up later.
+#[derive(Debug, Default)]
+pub struct Timeline {
+    pub entries: VecDeque<TimelineEntry>,
+    /// How many entries were added since the timeline was last looked at.
+    pub unseen: usize,
+    /// The status and conclusion of each job as of the last fetch, `None` before the first.
+    last_seen: Option<HashMap<u64, (String, Option<String>)>>,
+}
+
+impl Timeline {
+    /// Records how the jobs changed since the previous fetch. The first fetch only sets what
+    /// later ones are compared with, as nothing was observed before it.
+    pub fn observe<'a>(&mut self, jobs: impl IntoIterator<Item = &'a GithubJob>) {
+        let seen: HashMap<u64, (String, Option<String>)> = jobs
+            .into_iter()
+            .map(|job| {
+                if let Some(transition) = self.transition(job) {
+                    self.push(job, transition);
+                }
+                (job.id, (job.status.clone(), job.conclusion.clone()))
+            })
+            .collect();
+        self.last_seen = Some(seen);
+    }
+
+    /// What happened to a job since the previous fetch, if anything worth noting.
+    fn transition(&self, job: &GithubJob) -> Option<Transition> {
+        let last_seen = self.last_seen.as_ref()?;
+        let previous_status = last_seen.get(&job.id).map(|(status, _)| status.as_str());
+        if previous_status == Some(job.status.as_str()) {
+            return None;
+        }
+        match (previous_status, job.status.as_str()) {
+            (None, "queued" | "waiting") => Some(Transition::Queued),
+            (_, "in_progress") => Some(Transition::Started),
+            // Skipped jobs never ran, so they would only clutter the timeline
+            (_, "completed") => job
+                .conclusion
+                .clone()
+                .filter(|conclusion| conclusion != "skipped")
+                .map(Transition::Concluded),
+            _ => None,
+        }
+    }
+
+    fn push(&mut self, job: &GithubJob, transition: Transition) {
+        self.entries.push_front(TimelineEntry {
+            observed_at: Utc::now(),
+            job_name: job.name.clone(),
+            workflow_name: job.workflow_name.clone(),
+            repo: job.repo.clone(),
+            html_url: job.html_url.clone(),
+            transition,
+        });
+        self.entries.truncate(MAX_TIMELINE_ENTRIES);
+        self.unseen += 1;
+    }
+}
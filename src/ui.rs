@@ -1,388 +1,6547 @@
-use crate::app::App;
+use crate::{
+    app::{
+        ActionsSettingsState, App, ApprovalConfirmation, BadgePopupState, BranchPickerState,
+        CancelConfirmation, ColumnLayout, ColumnRow, CommandPaletteState, DeletionConfirmation,
+        DeployViewState, DispatchField, DispatchState, ExportPrompt, ExtensionMenuState,
+        ExtensionOutputState, FlakyTestsState, FullLogConfirmation, JobGroup, JobSummaryState,
+        JsonInspectorState, LogCompareState, LogPane, LogSearch, MatrixRow, OverviewState,
+        RerunConfirmation, RunViewState, TimelineViewState, Toast, ToastLevel, TriageState,
+        UsageViewState, WatchState, WorkflowHistoryState, WorkflowSidebarState, YankMenuState,
+        is_expiring_soon, is_slow_step, matrix_name,
+    },
+    config::BranchKind,
+    event::{AppEvent, Throttle},
+    flaky_tests,
+    gh_cli::{CommitState, GithubJob, LOG_TAIL_BYTES, RefKind, SsoAuthorization},
+    gh_extensions,
+    logs::{self, LineKind, LogLine},
+    markdown,
+    theme::Theme,
+    timeline::Transition,
+    usage::UsageSummary,
+    views::View,
+};
+use chrono::{DateTime, Local, SecondsFormat, TimeDelta, Utc};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span, Text},
-    widgets::{Block, BorderType, Borders, Paragraph, Widget, Wrap},
+    widgets::{
+        Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Tabs, Widget,
+        Wrap,
+    },
+};
+use std::{
+    collections::{BTreeMap, HashSet}, // Using BTreeMap for sorted group keys
+    ops::Range,
+    time::{SystemTime, UNIX_EPOCH},
 };
-use std::collections::BTreeMap; // Using BTreeMap for sorted group keys // Assuming App struct is defined here
 
 impl Widget for &App {
     /// Renders the user interface widgets.
     fn render(self, area: Rect, buf: &mut Buffer) {
-        // Define the main layout to split the screen vertically: Header + Body
+        buf.set_style(area, self.theme.base());
+        // Split the screen into the title line, the body and the status bar
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(7), // Fixed height for header/instructions
+                Constraint::Length(1), // Title with the fetch status
                 Constraint::Min(0),    // Remaining space for job columns OR logs + details
+                Constraint::Length(1), // Key hints of the current view
             ])
             .split(area);
+        self.render_title(main_chunks[0], buf);
+        self.render_status_bar(main_chunks[2], buf);
 
-        // --- Render the header/instructions paragraph ---
-        let header_block = Block::bordered()
-            .title("lazyactions")
-            .title_alignment(Alignment::Center)
-            .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Magenta));
-
-        let header_text = format!(
-            "Showing jobs for: {} | Fetch Status: {}\n\
-             Press `Esc`, `Ctrl-C` or `q` to stop running. \n\
-             Use `Left`/`Right` to navigate columns, `Up`/`Down` for rows, `PageUp`/`PageDown` for scrolling\n\
-             Press `Enter` to toggle more job info, `Backspace` to open GitHub URL. Auto-refresh every 5 seconds.",
-            self.job_details
-                .front()
-                .map_or("N/A", |job| job.repo.as_str()),
-            self.app_state.loading_status
-        );
+        // --- Render the repository tab bar when monitoring several repositories ---
+        let body_area = if self.gh_cli.repos().len() > 1 {
+            let body_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(0)])
+                .split(main_chunks[1]);
+            self.render_repo_tabs(body_chunks[0], buf);
+            body_chunks[1]
+        } else {
+            main_chunks[1]
+        };
 
-        let header_paragraph = Paragraph::new(header_text)
-            .block(header_block)
-            .fg(Color::Cyan)
-            .bg(Color::Black)
-            .alignment(Alignment::Center)
-            .wrap(Wrap { trim: false });
+        // --- Render the API request inspector below the body when toggled ---
+        let body_area = if self.app_state.show_api_inspector {
+            let body_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(12)])
+                .split(body_area);
+            self.render_api_inspector(body_chunks[1], buf);
+            body_chunks[0]
+        } else {
+            body_area
+        };
 
-        header_paragraph.render(main_chunks[0], buf);
+        // --- Render the filter prompt below the body while a filter is typed or applied ---
+        let body_area =
+            if self.app_state.filter.editing || !self.app_state.filter.filters().query.is_empty() {
+                let body_chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(0), Constraint::Length(1)])
+                    .split(body_area);
+                self.render_filter_prompt(body_chunks[1], buf);
+                body_chunks[0]
+            } else {
+                body_area
+            };
 
-        // --- Render the main application body based on show_details ---
-        if self.app_state.show_details {
-            // If show_details is true, render the detailed logs and full details panels
-            self.render_detailed_overlay(main_chunks[1], buf);
+        // --- Render the main application body based on the view and show_details ---
+        if let Some(triage) = &self.app_state.triage {
+            self.render_triage_view(triage, body_area, buf);
         } else {
-            // Otherwise, render the three job columns
-            self.render_job_columns(main_chunks[1], buf);
+            match self.app_state.views.shown() {
+                Some(View::Timeline(timeline_view)) => {
+                    self.render_timeline(timeline_view, body_area, buf)
+                }
+                Some(View::LogCompare(compare)) => self.render_log_compare(compare, body_area, buf),
+                Some(View::Watch(watch)) => self.render_watch_dashboard(watch, body_area, buf),
+                Some(View::WorkflowHistory(history)) => {
+                    self.render_workflow_history(history, body_area, buf)
+                }
+                Some(View::FlakyTests(flaky_tests)) => {
+                    self.render_flaky_tests(flaky_tests, body_area, buf)
+                }
+                Some(View::Overview(overview)) => self.render_overview(overview, body_area, buf),
+                Some(View::Deploy(deploy_view)) => {
+                    self.render_deploy_dashboard(deploy_view, body_area, buf)
+                }
+                Some(View::Usage(usage_view)) => self.render_usage_view(usage_view, body_area, buf),
+                // If show_details is true, render the detailed logs and full details panels
+                Some(View::Runs(_)) | None if self.app_state.show_details => {
+                    self.render_detailed_overlay(body_area, buf)
+                }
+                Some(View::Runs(_)) | None => {
+                    if let Some(sidebar) = &self.app_state.workflow_sidebar {
+                        let body_chunks = Layout::default()
+                            .direction(Direction::Horizontal)
+                            .constraints([Constraint::Length(36), Constraint::Min(0)])
+                            .split(body_area);
+                        self.render_workflow_sidebar(sidebar, body_chunks[0], buf);
+                        self.render_jobs(body_chunks[1], buf);
+                    } else {
+                        // Otherwise, render the three job columns
+                        self.render_jobs(body_area, buf);
+                    }
+                }
+            }
+        }
+
+        // --- Render popups on top of everything else ---
+        if let Some(dispatch) = &self.app_state.dispatch {
+            self.render_dispatch_popup(dispatch, area, buf);
+        }
+        if let Some(picker) = &self.app_state.branch_picker {
+            self.render_branch_picker(picker, area, buf);
+        }
+        if let Some(palette) = &self.app_state.command_palette {
+            self.render_command_palette(palette, area, buf);
+        }
+        if let Some(summary) = &self.app_state.job_summary {
+            self.render_job_summary_popup(summary, area, buf);
+        }
+        if let Some(inspector) = &self.app_state.json_inspector {
+            self.render_json_inspector(inspector, area, buf);
+        }
+        if let Some(popup) = &self.app_state.actions_settings {
+            self.render_actions_settings_popup(popup, area, buf);
+        }
+        if let Some(confirmation) = &self.app_state.rerun_confirmation {
+            self.render_rerun_confirmation(confirmation, area, buf);
+        }
+        if let Some(confirmation) = &self.app_state.cancel_confirmation {
+            self.render_cancel_confirmation(confirmation, area, buf);
+        }
+        if let Some(confirmation) = &self.app_state.approval_confirmation {
+            self.render_approval_confirmation(confirmation, area, buf);
+        }
+        if let Some(confirmation) = &self.app_state.full_log_confirmation {
+            self.render_full_log_confirmation(confirmation, area, buf);
+        }
+        if let Some(authorization) = &self.app_state.sso_authorization {
+            self.render_sso_authorization(authorization, area, buf);
         }
+        if let Some(menu) = &self.app_state.extension_menu {
+            self.render_extension_menu(menu, area, buf);
+        }
+        if let Some(badges) = &self.app_state.badges {
+            self.render_badges(badges, area, buf);
+        }
+        if let Some(popup) = &self.app_state.extension_output {
+            self.render_extension_output(popup, area, buf);
+        }
+        if let Some(confirmation) = &self.app_state.deletion_confirmation {
+            self.render_deletion_confirmation(confirmation, area, buf);
+        }
+        if let Some(menu) = &self.app_state.yank_menu {
+            self.render_yank_menu(menu, area, buf);
+        }
+        if let Some(prompt) = &self.app_state.export_prompt {
+            self.render_export_prompt(prompt, area, buf);
+        }
+        render_toasts(&self.theme, &self.app_state.toasts, area, buf);
     }
 }
 
-impl App {
-    // Renders the three-column job summary layout
-    fn render_job_columns(&self, area: Rect, buf: &mut Buffer) {
-        let columns = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(33), // In Progress
-                Constraint::Percentage(34), // Concluded Success
-                Constraint::Percentage(33), // Concluded Failure
-            ])
-            .split(area);
+/// Formats a number of seconds as a short age such as `42s`, `5m`, `3h` or `2d`.
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3600 => format!("{}m", seconds / 60),
+        3600..86400 => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / 86400),
+    }
+}
 
-        self.render_job_list_column(
-            columns[0],
-            buf,
-            "In Progress",
-            &self.app_state.in_progress_jobs,
-            Color::Yellow,
-            0,
-        );
+/// Tells when something happened, as how long ago, e.g. `3m ago`, or as an ISO 8601 timestamp
+/// in the local timezone when `absolute`.
+fn format_time(at: DateTime<Utc>, now: DateTime<Utc>, absolute: bool) -> String {
+    if absolute {
+        return at
+            .with_timezone(&Local)
+            .to_rfc3339_opts(SecondsFormat::Secs, false);
+    }
+    match u64::try_from((now - at).num_seconds()).unwrap_or(0) {
+        0..5 => "just now".to_string(),
+        seconds => format!("{} ago", format_age(seconds)),
+    }
+}
 
-        self.render_job_list_column(
-            columns[1],
-            buf,
-            "Concluded Success",
-            &self.app_state.success_jobs,
-            Color::Green,
-            1,
-        );
+/// Tells how long ago the runs were last refreshed, without counting the first seconds so
+/// that the title line doesn't flicker after every refresh.
+fn format_refreshed(elapsed: TimeDelta) -> String {
+    match u64::try_from(elapsed.num_seconds()).unwrap_or(0) {
+        0..5 => "refreshed just now".to_string(),
+        seconds => format!("refreshed {} ago", format_age(seconds)),
+    }
+}
 
-        self.render_job_list_column(
-            columns[2],
-            buf,
-            "Concluded Failure",
-            &self.app_state.failure_jobs,
-            Color::Red,
-            2,
-        );
+/// Formats a duration in seconds as e.g. `42s`, `3m 05s` or `1h 20m`.
+fn format_duration(seconds: u64) -> String {
+    match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3600 => format!("{}m {:02}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60),
     }
+}
 
-    // Reusable function to render a single column of job summaries
-    fn render_job_list_column(
-        &self,
-        area: Rect,
-        buf: &mut Buffer,
-        title: &str,
-        job_indices: &BTreeMap<String, Vec<usize>>,
-        border_color: Color,
-        column_idx: usize,
-    ) {
-        let is_selected_column = self.app_state.column_index == column_idx;
-        let block =
-            Block::default()
-                .title(format!("{} ({})", title, job_indices.iter().map(|(_, v)| v.len()).sum::<usize>()))
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(border_color).add_modifier(
-                    if is_selected_column {
-                        Modifier::BOLD
-                    } else {
-                        Modifier::empty()
-                    },
-                ));
+/// Formats how long something took, or has been running for, as a duration. Clock skew can
+/// make something look like it started in the future, which shows as `0s`.
+pub fn format_elapsed(elapsed: TimeDelta) -> String {
+    format_duration(u64::try_from(elapsed.num_seconds()).unwrap_or(0))
+}
 
-        let inner_area = block.inner(area);
-        block.render(area, buf);
+/// Tells when GitHub deletes something, e.g. `in 3d`, or `None` once it did.
+fn format_expiry(expires_at: DateTime<Utc>, now: DateTime<Utc>) -> Option<String> {
+    u64::try_from((expires_at - now).num_seconds())
+        .ok()
+        .filter(|&seconds| seconds > 0)
+        .map(|seconds| format!("in {}", format_age(seconds)))
+}
 
-        if job_indices.is_empty() {
-            let no_data_text = Text::styled(
-                "No jobs in this category.",
-                Style::default().fg(Color::DarkGray),
-            );
-            let paragraph = Paragraph::new(no_data_text)
-                .alignment(Alignment::Center)
-                .wrap(Wrap { trim: false });
-            paragraph.render(inner_area, buf);
-            return;
-        }
+/// Formats a number of bytes as e.g. `512 B`, `3.4 KB` or `80.0 MB`.
+fn format_size(bytes: u64) -> String {
+    const KB: f64 = 1024.0;
+    let bytes_f = bytes as f64;
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KB", bytes_f / KB),
+        1_048_576..1_073_741_824 => format!("{:.1} MB", bytes_f / KB / KB),
+        _ => format!("{:.1} GB", bytes_f / KB / KB / KB),
+    }
+}
 
-        // Group jobs by their "tool"
+/// Explains that no runner has all of a job's `runs-on` labels.
+fn no_runner_message(labels: &[String]) -> String {
+    format!("no online runner matches [{}]", labels.join(", "))
+}
 
-        let available_height = inner_area.height as usize;
-        let mut all_column_lines: Vec<Line> = Vec::new(); // Collect all lines first
+/// The frame of the spinner shown next to running jobs and steps, advancing every second.
+fn spinner(now: DateTime<Utc>) -> &'static str {
+    const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+    FRAMES[now.timestamp().unsigned_abs() as usize % FRAMES.len()]
+}
 
-        let mut current_column_job_idx = 0; // Tracks the sequential index of jobs within the column (ignoring groups)
+/// The symbol and color used for a step or job with the given status and conclusion.
+fn status_symbol(theme: &Theme, status: &str, conclusion: Option<&str>) -> (&'static str, Color) {
+    match (status, conclusion) {
+        (_, Some("success")) => ("✓", theme.success),
+        (_, Some("failure" | "timed_out")) => ("✗", theme.failure),
+        (_, Some("skipped")) => ("↷", theme.muted),
+        (_, Some("cancelled")) => ("⊘", theme.muted),
+        ("in_progress", _) => ("⟳", theme.running),
+        // Held by an environment's protection rule until a reviewer approves it
+        ("waiting", _) => ("⏸", theme.warning),
+        _ => ("○", theme.muted),
+    }
+}
 
-        // Iterate through grouped jobs to build all lines, including group headers
-        for (tool_name, indices_in_group) in job_indices.iter() {
-            // Add group header lines
-            all_column_lines.push(Line::from(vec![
-                Span::raw("── "),
-                Span::styled(
-                    tool_name.clone(),
-                    Style::default()
-                        .fg(Color::LightCyan)
-                        .add_modifier(Modifier::BOLD)
-                        .add_modifier(Modifier::UNDERLINED),
-                ),
-                Span::raw(" ──"),
-            ]));
-            all_column_lines.push(Line::from(Span::styled(
-                "─",
-                Style::default().fg(Color::DarkGray),
-            )));
+/// The status and conclusion that sum up a group of jobs: those of its worst job, a running
+/// job counting as failed once one of its steps failed. `None` for an empty group.
+fn rollup_status<'a>(
+    jobs: impl Iterator<Item = &'a GithubJob>,
+) -> Option<(&'a str, Option<&'a str>)> {
+    let severity = |(status, conclusion): &(&str, Option<&str>)| match (*status, *conclusion) {
+        (_, Some("failure" | "timed_out" | "startup_failure")) => 7,
+        ("waiting", _) => 6,
+        ("in_progress", _) => 5,
+        (_, None) => 4,
+        (_, Some("skipped")) => 0,
+        (_, Some("success")) => 1,
+        (_, Some("cancelled")) => 3,
+        _ => 2,
+    };
+    jobs.map(|job| {
+        let step_failed = job
+            .steps
+            .iter()
+            .any(|step| matches!(step.conclusion.as_deref(), Some("failure" | "timed_out")));
+        match job.conclusion.as_deref() {
+            None if step_failed => (job.status.as_str(), Some("failure")),
+            conclusion => (job.status.as_str(), conclusion),
+        }
+    })
+    .max_by_key(severity)
+}
 
-            // Add job lines within this group
-            for &original_job_idx in indices_in_group {
-                let job = &self.job_details[original_job_idx];
-                let status_style = match job.status.as_str() {
-                    "completed" => Style::default().fg(Color::Green),
-                    "in_progress" => Style::default().fg(Color::Yellow),
-                    "queued" | "waiting" => Style::default().fg(Color::DarkGray),
-                    _ => Style::default().fg(Color::White),
-                };
+/// Shortens an ISO 8601 timestamp such as `2024-05-01T12:34:56Z` to `2024-05-01 12:34`.
+fn format_timestamp(timestamp: &str) -> String {
+    timestamp.replacen('T', " ", 1).chars().take(16).collect()
+}
 
-                let conclusion_span = if let Some(conclusion) = &job.conclusion {
-                    let conclusion_style = match conclusion.as_str() {
-                        "success" => Style::default().fg(Color::LightGreen),
-                        "failure" => Style::default().fg(Color::Red),
-                        "cancelled" => Style::default().fg(Color::DarkGray),
-                        "skipped" => Style::default().fg(Color::Blue),
-                        _ => Style::default().fg(Color::White),
-                    };
-                    Span::styled(format!(" ({})", conclusion), conclusion_style)
-                } else {
-                    Span::raw("")
-                };
+/// Shortens a commit SHA to the 7 characters GitHub shows.
+fn short_sha(sha: &str) -> String {
+    sha.chars().take(7).collect()
+}
 
-                let base_style =
-                    if is_selected_column && self.app_state.row_index == current_column_job_idx {
-                        Style::default()
-                            .fg(Color::Cyan)
-                            .add_modifier(Modifier::REVERSED)
-                    } else {
-                        Style::default().fg(Color::White)
-                    };
-                let action_part = job.name.split(" / ").last().unwrap_or(&job.name);
-                let workflow_part = job.name.as_str();
+/// What a job card says about the event that triggered the run, e.g. `scheduled`, `None` when
+/// unknown, as in jobs saved by older versions.
+fn trigger_label(event: &str) -> Option<String> {
+    match event {
+        "" => None,
+        "schedule" => Some("scheduled".to_string()),
+        "workflow_dispatch" => Some("manual".to_string()),
+        "pull_request" | "pull_request_target" => Some("pull request".to_string()),
+        event => Some(event.replace('_', " ")),
+    }
+}
 
-                // Line 1: Index, Action (or primary name), Status, Conclusion
-                all_column_lines.push(Line::from(vec![
-                    Span::styled(
-                        format!("{}. ", current_column_job_idx + 1), // Index relative to column view
-                        base_style.add_modifier(Modifier::BOLD),
-                    ),
-                    Span::styled(
-                        action_part.to_string(), // Display the parsed action/primary name
-                        base_style.add_modifier(Modifier::BOLD),
-                    ),
-                    Span::styled(" [", status_style),
-                    Span::styled(job.status.clone(), status_style),
-                    conclusion_span,
-                    Span::styled("]", status_style),
-                ]));
+/// The color used for a commit in the commit strip.
+fn commit_state_color(theme: &Theme, state: CommitState) -> Color {
+    match state {
+        CommitState::Passed => theme.success,
+        CommitState::Failed => theme.failure,
+        CommitState::Running => theme.running,
+        CommitState::Neutral => theme.muted,
+    }
+}
 
-                // Line 2: Workflow (conditionally displayed)
-                if !workflow_part.is_empty() {
-                    all_column_lines.push(Line::from(vec![
-                        Span::raw("  "), // Indent for readability
-                        Span::styled(
-                            format!("{}", workflow_part),
-                            base_style.fg(Color::LightYellow),
-                        ),
-                    ]));
-                } else {
-                    all_column_lines.push(Line::from(Span::raw("")));
-                }
+/// The color used for the state of a deployment.
+fn deployment_state_color(theme: &Theme, state: &str) -> Color {
+    match state {
+        "success" => theme.success,
+        "failure" | "error" => theme.failure,
+        "in_progress" | "queued" | "pending" => theme.running,
+        _ => theme.muted,
+    }
+}
 
-                // Line 4: Branch and Actor
-                all_column_lines.push(Line::from(vec![Span::styled(
-                    format!("  {} by {}", job.head_branch, job.actor_login),
-                    base_style
-                        .fg(Color::DarkGray)
-                        .add_modifier(Modifier::ITALIC),
-                )]));
+/// Returns a rectangle of the given size centered in `area`, clamped to fit.
+/// How many rows the lines take when wrapped to the given width.
+fn wrapped_rows(lines: &[Line], width: u16) -> usize {
+    Paragraph::new(lines.to_vec())
+        .wrap(Wrap { trim: false })
+        .line_count(width)
+}
 
-                current_column_job_idx += 1; // Increment for the next job
-                all_column_lines.push(Line::from(Span::styled(
-                    "\n",
-                    Style::default().fg(Color::DarkGray),
-                )));
-            }
+/// Renders the toasts stacked in the bottom right corner, the latest at the bottom, each
+/// wrapped to at most half the screen's width and colored by its level.
+fn render_toasts(theme: &Theme, toasts: &[Toast], area: Rect, buf: &mut Buffer) {
+    let mut bottom = area.bottom().saturating_sub(1);
+    for toast in toasts.iter().rev() {
+        let max_width = (area.width / 2).max(20);
+        let text_width = toast.message.chars().count() as u16;
+        let width = (text_width + 2).min(max_width).min(area.width);
+        let mut text_lines = text_width.div_ceil(width.saturating_sub(2).max(1));
+        if text_lines > 1 {
+            // Wrapping at word boundaries may take a line more
+            text_lines += 1;
         }
-        let scroll_offset = if is_selected_column {
-            self.app_state.scroll_offset
-        } else {
-            0 // Other columns don't scroll unless selected
+        let height = text_lines + 2;
+        if bottom < area.top() + height {
+            // The older toasts don't fit above
+            break;
+        }
+        bottom -= height;
+        let toast_area = Rect::new(
+            area.right().saturating_sub(width + 1),
+            bottom,
+            width,
+            height,
+        )
+        .intersection(area);
+        clear(theme, toast_area, buf);
+        let color = match toast.level {
+            ToastLevel::Info => theme.accent,
+            ToastLevel::Success => theme.success,
+            ToastLevel::Warning => theme.warning,
+            ToastLevel::Error => theme.failure,
         };
-
-        let start_index = scroll_offset.min(all_column_lines.len());
-        let end_index = (start_index + available_height).min(all_column_lines.len());
-
-        let visible_lines = &all_column_lines[start_index..end_index];
-
-        let paragraph = Paragraph::new(visible_lines.to_vec()).wrap(Wrap { trim: false });
-        paragraph.render(inner_area, buf);
+        Paragraph::new(toast.message.as_str())
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(color)),
+            )
+            .render(toast_area, buf);
     }
+}
 
-    /// Renders the detailed view with Job Logs and full Job Details in a horizontal split.
-    fn render_detailed_overlay(&self, area: Rect, buf: &mut Buffer) {
-        let detailed_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(70),
-                Constraint::Percentage(30), // 30% for Job Details (bottom)
-            ])
-            .split(area);
+/// Clears the area of a popup, leaving it in the theme's background and text colors.
+fn clear(theme: &Theme, area: Rect, buf: &mut Buffer) {
+    Clear.render(area, buf);
+    buf.set_style(area, theme.base());
+}
 
-        // Render Jobs
-        self.render_job_columns(detailed_chunks[0], buf);
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
 
-        // Render Job Details in the bottom panel
-        self.render_full_job_details_panel(detailed_chunks[1], buf);
+impl App {
+    /// Renders the tab bar used to switch between monitored repositories.
+    fn render_repo_tabs(&self, area: Rect, buf: &mut Buffer) {
+        let titles = self.gh_cli.repos().iter().map(|repo| repo.full_name());
+        Tabs::new(titles)
+            .block(
+                Block::bordered()
+                    .title("Repositories")
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(self.theme.title)),
+            )
+            .select(self.app_state.repo_index)
+            .style(Style::default().fg(self.theme.text))
+            .highlight_style(
+                self.selected_style(
+                    Style::default()
+                        .fg(self.theme.selection)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            )
+            .render(area, buf);
     }
-    /// Renders the full job details panel (used in detailed view).
-    fn render_full_job_details_panel(&self, area: Rect, buf: &mut Buffer) {
+
+    /// Renders the list of recent API calls with their duration, status and payload size.
+    fn render_api_inspector(&self, area: Rect, buf: &mut Buffer) {
+        let api_calls = self.gh_cli.api_calls();
         let block = Block::default()
-            .title("Job Details")
+            .title(format!("API Requests ({})", api_calls.len()))
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::LightBlue));
+            .border_style(Style::default().fg(self.theme.title));
 
-        let inner_area = block.inner(area);
-        block.render(area, buf);
+        let rows = api_calls.iter().map(|call| {
+            let status_style = if call.success {
+                Style::default().fg(self.theme.success)
+            } else {
+                Style::default().fg(self.theme.failure)
+            };
+            Row::new(vec![
+                Cell::from(format!("{}s ago", call.started.elapsed().as_secs())),
+                Cell::from(call.endpoint.clone()),
+                Cell::from(format!("{} ms", call.duration.as_millis())),
+                Cell::from(call.status.clone()).style(status_style),
+                Cell::from(format!("{:.1} KiB", call.payload_size as f64 / 1024.0)),
+            ])
+        });
 
-        let selected_job_original_index = self.get_selected_job_original_index();
-        let selected_job = selected_job_original_index.and_then(|idx| self.job_details.get(idx));
-        if let Some(job) = selected_job {
-            let mut details_text = Vec::new();
+        Table::new(
+            rows,
+            [
+                Constraint::Length(10),
+                Constraint::Min(20),
+                Constraint::Length(10),
+                Constraint::Length(14),
+                Constraint::Length(12),
+            ],
+        )
+        .header(
+            Row::new(vec!["When", "Endpoint", "Duration", "Status", "Payload"]).style(
+                Style::default()
+                    .fg(self.theme.group)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(block)
+        .render(area, buf);
+    }
 
-            details_text.push(Line::from(vec![
-                Span::styled("Name: ", Style::default().fg(Color::LightBlue)),
-                Span::raw(job.name.clone()),
-            ]));
-            details_text.push(Line::from(vec![
-                Span::styled("Repo: ", Style::default().fg(Color::LightBlue)),
-                Span::raw(job.repo.clone()),
-            ]));
-            details_text.push(Line::from(vec![
-                Span::styled("Run ID: ", Style::default().fg(Color::LightBlue)),
-                Span::raw(job.run_id.to_string()),
-            ]));
-            details_text.push(Line::from(vec![
-                Span::styled("Status: ", Style::default().fg(Color::LightBlue)),
-                Span::styled(
-                    job.status.clone(),
-                    match job.status.as_str() {
-                        "completed" => Style::default().fg(Color::Green),
-                        "in_progress" => Style::default().fg(Color::Yellow),
-                        "queued" | "waiting" => Style::default().fg(Color::DarkGray),
-                        _ => Style::default().fg(Color::White),
-                    },
-                ),
-            ]));
-            if let Some(conclusion) = &job.conclusion {
-                details_text.push(Line::from(vec![
-                    Span::styled("Conclusion: ", Style::default().fg(Color::LightBlue)),
-                    Span::styled(
-                        conclusion.clone(),
-                        match conclusion.as_str() {
-                            "success" => Style::default().fg(Color::LightGreen),
-                            "failure" => Style::default().fg(Color::Red),
-                            "cancelled" => Style::default().fg(Color::DarkGray),
-                            "skipped" => Style::default().fg(Color::Blue),
-                            _ => Style::default().fg(Color::White),
-                        },
+    /// Renders the title line: the repository shown, how fetching goes, the runners, the
+    /// API rate limit and the commit strip of the current branch.
+    fn render_title(&self, area: Rect, buf: &mut Buffer) {
+        let separator = || Span::styled(" | ", Style::default().fg(self.theme.muted));
+        let mut spans = vec![
+            Span::styled(
+                "lazyactions ",
+                Style::default()
+                    .fg(self.theme.title)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                self.active_repo()
+                    .or_else(|| self.job_details.front().map(|job| job.repo.clone()))
+                    .unwrap_or_else(|| "N/A".to_string()),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            separator(),
+        ];
+        // While offline the status would only be the latest failure to connect
+        if self.gh_cli.is_offline() {
+            spans.push(Span::styled(
+                match self.app_state.updated_at.or(self.app_state.stale_since) {
+                    Some(updated_at) => format!(
+                        "offline — showing data from {}",
+                        updated_at.with_timezone(&Local).format("%H:%M")
                     ),
-                ]));
+                    None => "offline — no data fetched yet".to_string(),
+                },
+                Style::default()
+                    .fg(self.theme.failure)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        } else {
+            spans.push(Span::raw(self.app_state.loading_status.clone()));
+            if let Some(fetched_at) = self.app_state.time_travel {
+                let back = self
+                    .refresh_history
+                    .position(fetched_at)
+                    .map_or(0, |index| self.refresh_history.entries.len() - 1 - index);
+                spans.push(separator());
+                spans.push(Span::styled(
+                    format!(
+                        "⏮ the refresh of {}, {} back",
+                        fetched_at.with_timezone(&Local).format("%H:%M:%S"),
+                        back
+                    ),
+                    Style::default()
+                        .fg(self.theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            } else if let Some(updated_at) = self.app_state.updated_at {
+                spans.push(separator());
+                spans.push(Span::styled(
+                    format_refreshed(Utc::now() - updated_at),
+                    Style::default().fg(self.theme.muted),
+                ));
             }
-            details_text.push(Line::from(vec![
-                Span::styled("Branch: ", Style::default().fg(Color::LightBlue)),
-                Span::raw(job.head_branch.clone()),
-            ]));
-            details_text.push(Line::from(vec![
-                Span::styled("Actor: ", Style::default().fg(Color::LightBlue)),
-                Span::raw(job.actor_login.clone()),
-            ]));
-            details_text.push(Line::from(vec![
-                Span::styled("URL: ", Style::default().fg(Color::LightBlue)),
-                Span::raw(job.html_url.clone()).add_modifier(Modifier::UNDERLINED),
-            ]));
+        }
+        if let Some(saved_at) = self.app_state.stale_since {
+            spans.push(Span::styled(
+                format!(
+                    " (stale, saved {} ago)",
+                    format_age((Utc::now() - saved_at).num_seconds().max(0) as u64)
+                ),
+                Style::default().fg(self.theme.warning),
+            ));
+        }
 
-            let paragraph = Paragraph::new(details_text).wrap(Wrap { trim: false });
-            paragraph.render(inner_area, buf);
+        if self.timeline.unseen > 0 {
+            spans.push(separator());
+            spans.push(Span::styled(
+                format!("{} new in the timeline", self.timeline.unseen),
+                Style::default().fg(self.theme.warning),
+            ));
+        }
+        if let Some(backfill) = &self.app_state.backfill {
+            spans.push(separator());
+            spans.push(Span::styled(
+                match self.run_history.repo(&backfill.repo) {
+                    Some(history) => format!(
+                        "backfilling history {}/{} runs",
+                        history.runs.len(),
+                        history.target()
+                    ),
+                    None => "backfilling history...".to_string(),
+                },
+                Style::default().fg(self.theme.accent),
+            ));
+        }
+        let hidden_workflows = &self.app_state.filter.filters().hidden_workflows;
+        if !hidden_workflows.is_empty() {
+            spans.push(separator());
+            spans.push(Span::styled(
+                match hidden_workflows.len() {
+                    1 => "1 workflow hidden".to_string(),
+                    hidden => format!("{} workflows hidden", hidden),
+                },
+                Style::default().fg(self.theme.warning),
+            ));
+        }
+        if self.app_state.filter.filters().hide_scheduled {
+            spans.push(separator());
+            spans.push(Span::styled(
+                "scheduled runs hidden",
+                Style::default().fg(self.theme.warning),
+            ));
+        }
+
+        let active_repo = self.active_repo();
+        let muted = self
+            .workflow_mutes
+            .entries
+            .iter()
+            .filter(|mute| {
+                active_repo
+                    .as_ref()
+                    .is_none_or(|repo| mute.repo.eq_ignore_ascii_case(repo))
+            })
+            .count();
+        if muted > 0 {
+            spans.push(separator());
+            spans.push(Span::styled(
+                match muted {
+                    1 => "1 workflow muted".to_string(),
+                    muted => format!("{} workflows muted", muted),
+                },
+                Style::default().fg(self.theme.warning),
+            ));
+        }
+
+        if let Some(branch) = self.gh_cli.branch() {
+            spans.push(separator());
+            spans.push(Span::styled(
+                format!("branch {}", branch),
+                Style::default().fg(self.theme.accent),
+            ));
+        }
+
+        if !self.runners.is_empty() {
+            spans.push(separator());
+            spans.push(Span::raw(format!(
+                "Runners: {}/{} online",
+                self.runners.iter().filter(|r| r.status == "online").count(),
+                self.runners.len()
+            )));
+        }
+
+        let rate_limit = self.gh_cli.rate_limit();
+        let rate_limit_status = match (rate_limit, Throttle::for_rate_limit(rate_limit.as_ref())) {
+            (None, _) => None,
+            (Some(rate_limit), Throttle::None) => Some(format!(
+                "API: {}/{}",
+                rate_limit.remaining, rate_limit.limit
+            )),
+            (Some(rate_limit), Throttle::Slowed) => Some(format!(
+                "API: {}/{}, polling slowed down",
+                rate_limit.remaining, rate_limit.limit
+            )),
+            (Some(rate_limit), Throttle::Paused { resets_in }) => Some(format!(
+                "API: {}/{}, polling paused for {}",
+                rate_limit.remaining,
+                rate_limit.limit,
+                format_duration(resets_in.as_secs())
+            )),
+        };
+        if let Some(rate_limit_status) = rate_limit_status {
+            spans.push(separator());
+            spans.push(Span::raw(rate_limit_status));
+        }
+
+        // The commit strip of the current branch, oldest commit first
+        let commits = self.active_commits();
+        if !commits.is_empty() {
+            spans.push(separator());
+            spans.push(Span::raw(format!("{}: ", self.gh_cli.current_branch())));
+            for commit in commits.iter().rev() {
+                spans.push(Span::styled(
+                    commit.state.symbol(),
+                    Style::default().fg(commit_state_color(&self.theme, commit.state)),
+                ));
+            }
+            if let Some(commit) = self.breaking_commit() {
+                spans.push(Span::styled(
+                    format!(" broken by {} {}", short_sha(&commit.sha), commit.title),
+                    Style::default().fg(self.theme.failure),
+                ));
+            }
+        }
+
+        Paragraph::new(Line::from(spans))
+            .fg(self.theme.selection)
+            .bg(self.theme.bar)
+            .render(area, buf);
+    }
+
+    /// Renders the keys of the actions available in the current view, as currently bound.
+    /// Hints that don't fit are left out, except for the last one, which is how to quit.
+    fn render_status_bar(&self, area: Rect, buf: &mut Buffer) {
+        let hints: Vec<(String, &str)> = self
+            .key_hints()
+            .into_iter()
+            .filter_map(|(events, label)| {
+                let keys: Vec<String> = events
+                    .iter()
+                    .filter_map(|event| self.keymap.keys_for(*event))
+                    .collect();
+                (!keys.is_empty()).then(|| (keys.join("/"), label))
+            })
+            .collect();
+        let hint_width =
+            |(keys, label): &(String, &str)| keys.chars().count() + label.chars().count() + 3;
+        let mut room = (area.width as usize).saturating_sub(hints.last().map_or(0, hint_width));
+        let mut shown: Vec<&(String, &str)> = hints[..hints.len().saturating_sub(1)]
+            .iter()
+            .take_while(|hint| {
+                let fits = hint_width(hint) <= room;
+                room = room.saturating_sub(hint_width(hint));
+                fits
+            })
+            .collect();
+        shown.extend(hints.last());
+
+        let mut spans = Vec::new();
+        for (keys, label) in shown {
+            if !spans.is_empty() {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::styled(
+                keys.clone(),
+                Style::default()
+                    .fg(self.theme.selection)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::styled(
+                format!(" {}", label),
+                Style::default().fg(self.theme.muted),
+            ));
+        }
+        Paragraph::new(Line::from(spans))
+            .bg(self.theme.bar)
+            .render(area, buf);
+    }
+
+    /// The actions offered by the current view, as the events triggering them and a label.
+    /// Popups and the triage view read keys directly and list them themselves.
+    fn key_hints(&self) -> Vec<(&'static [AppEvent], &'static str)> {
+        let state = &self.app_state;
+        if state.dispatch.is_some()
+            || state.job_summary.is_some()
+            || state.json_inspector.is_some()
+            || state.actions_settings.is_some()
+            || state.rerun_confirmation.is_some()
+            || state.cancel_confirmation.is_some()
+            || state.approval_confirmation.is_some()
+            || state.full_log_confirmation.is_some()
+            || state.sso_authorization.is_some()
+            || state.deletion_confirmation.is_some()
+            || state.extension_menu.is_some()
+            || state.badges.is_some()
+            || state.extension_output.is_some()
+            || state.yank_menu.is_some()
+            || state.export_prompt.is_some()
+            || state.triage.is_some()
+            || state.filter.editing
+            || state.workflow_sidebar.is_some()
+            || state.branch_picker.is_some()
+            || state.command_palette.is_some()
+        {
+            return Vec::new();
+        }
+
+        let scroll: (&'static [AppEvent], &'static str) =
+            (&[AppEvent::NavigateUp, AppEvent::NavigateDown], "scroll");
+        let page: (&'static [AppEvent], &'static str) =
+            (&[AppEvent::PageUp, AppEvent::PageDown], "page");
+        let quit: (&'static [AppEvent], &'static str) = (&[AppEvent::Quit], "quit");
+        let rerun: [(&'static [AppEvent], &'static str); 2] = [
+            (&[AppEvent::RerunRun], "rerun"),
+            (&[AppEvent::RerunFailedJobs], "rerun failed"),
+        ];
+
+        match state.views.shown() {
+            Some(View::LogCompare(compare)) => {
+                let mut hints = vec![scroll, page];
+                if compare.panes.iter().any(|pane| !pane.groups.is_empty()) {
+                    hints.push((&[AppEvent::ToggleDetails], "groups"));
+                }
+                hints.push((&[AppEvent::OpenFilter], "search"));
+                if let Some(search) = &compare.search {
+                    if search.editing {
+                        return Vec::new();
+                    }
+                    hints.push((&[AppEvent::NextMatch, AppEvent::PreviousMatch], "matches"));
+                }
+                if let Some(Ok(diff)) = &compare.workflow_diff
+                    && diff.changed
+                {
+                    hints.push((&[AppEvent::OpenGitHub], "compare commits"));
+                }
+                hints.extend([(&[AppEvent::Back] as &'static [AppEvent], "close"), quit]);
+                return hints;
+            }
+            Some(View::Watch(watch)) => {
+                let mut hints = vec![scroll, page, (&[AppEvent::OpenGitHub], "open run")];
+                hints.extend(rerun);
+                if watch
+                    .details
+                    .as_ref()
+                    .is_some_and(|details| details.run.status != "completed")
+                {
+                    hints.push((&[AppEvent::CancelRun], "cancel"));
+                }
+                if !watch.exit_on_conclusion {
+                    hints.push((&[AppEvent::Back], "close"));
+                }
+                hints.push(quit);
+                return hints;
+            }
+            Some(View::Timeline(_)) => {
+                return vec![
+                    (&[AppEvent::NavigateUp, AppEvent::NavigateDown], "entries"),
+                    (&[AppEvent::OpenGitHub], "open job"),
+                    (&[AppEvent::ToggleTimeline], "close"),
+                    quit,
+                ];
+            }
+            Some(View::WorkflowHistory(_)) => {
+                return vec![
+                    (&[AppEvent::NavigateUp, AppEvent::NavigateDown], "runs"),
+                    (&[AppEvent::OpenGitHub], "open run"),
+                    (&[AppEvent::ToggleWorkflowHistory], "close"),
+                    quit,
+                ];
+            }
+            Some(View::FlakyTests(_)) => {
+                return vec![
+                    (&[AppEvent::NavigateUp, AppEvent::NavigateDown], "tests"),
+                    (&[AppEvent::OpenGitHub], "open last seen"),
+                    (&[AppEvent::OpenExport], "export"),
+                    (&[AppEvent::ToggleFlakyTests], "close"),
+                    quit,
+                ];
+            }
+            Some(View::Overview(_)) => {
+                return vec![
+                    (
+                        &[AppEvent::NavigateUp, AppEvent::NavigateDown],
+                        "repositories",
+                    ),
+                    (&[AppEvent::ToggleDetails], "dashboard"),
+                    (&[AppEvent::OpenGitHub], "open actions"),
+                    (&[AppEvent::ToggleOverview], "close"),
+                    quit,
+                ];
+            }
+            Some(View::Usage(_)) => {
+                return vec![
+                    (&[AppEvent::NavigateUp, AppEvent::NavigateDown], "scroll"),
+                    (&[AppEvent::ToggleUsageView], "close"),
+                    quit,
+                ];
+            }
+            Some(View::Deploy(deploy_view)) => {
+                let mut hints = vec![
+                    (
+                        &[AppEvent::NavigateUp, AppEvent::NavigateDown] as &'static [AppEvent],
+                        "environments",
+                    ),
+                    (&[AppEvent::OpenGitHub], "open run"),
+                ];
+                let pending = deploy_view
+                    .environments
+                    .as_ref()
+                    .and_then(|environments| environments.get(deploy_view.selected))
+                    .is_some_and(|env| !env.pending_approvals.is_empty());
+                if pending {
+                    hints.push((&[AppEvent::ApproveDeployment], "approve"));
+                }
+                hints.extend([
+                    (
+                        &[AppEvent::ToggleDeployView] as &'static [AppEvent],
+                        "close",
+                    ),
+                    quit,
+                ]);
+                return hints;
+            }
+            Some(View::Runs(_)) | None => {}
+        }
+
+        let selected_job = self.job_details.get(self.current_job_index);
+        let mut hints: Vec<(&'static [AppEvent], &'static str)> = if state.show_details {
+            let mut hints: Vec<(&'static [AppEvent], &'static str)> = vec![
+                (&[AppEvent::NavigateUp, AppEvent::NavigateDown], "steps"),
+                (&[AppEvent::ToggleDetails], "close"),
+                (
+                    &[AppEvent::ToggleLinks],
+                    if state.show_links { "details" } else { "links" },
+                ),
+            ];
+            match self.selected_annotations().len() {
+                0 => {}
+                1 => hints.push((&[AppEvent::OpenAnnotation], "annotation")),
+                _ => hints.extend([
+                    (
+                        &[AppEvent::NavigateLeft, AppEvent::NavigateRight] as &'static [AppEvent],
+                        "annotations",
+                    ),
+                    (&[AppEvent::OpenAnnotation], "open annotation"),
+                ]),
+            }
+            hints
+        } else if matches!(state.views.shown(), Some(View::Runs(_))) {
+            vec![
+                (
+                    &[AppEvent::NavigateLeft, AppEvent::NavigateRight],
+                    "runs/jobs",
+                ),
+                (&[AppEvent::NavigateUp, AppEvent::NavigateDown], "rows"),
+                (&[AppEvent::ToggleDetails], "details"),
+            ]
+        } else {
+            vec![
+                (
+                    &[AppEvent::NavigateLeft, AppEvent::NavigateRight],
+                    "columns",
+                ),
+                (&[AppEvent::NavigateUp, AppEvent::NavigateDown], "rows"),
+                page,
+                (
+                    &[AppEvent::ToggleDetails],
+                    match state.selected_row() {
+                        Some(ColumnRow::Matrix(matrix)) if matrix.expanded => "collapse",
+                        Some(ColumnRow::Matrix(_)) => "expand",
+                        _ => "details",
+                    },
+                ),
+            ]
+        };
+        if state.time_travel.is_some() {
+            hints.extend([
+                (
+                    &[AppEvent::PreviousRefresh, AppEvent::NextRefresh] as &'static [AppEvent],
+                    "refreshes",
+                ),
+                (&[AppEvent::Back], "now"),
+            ]);
+        }
+        hints.extend([
+            (&[AppEvent::OpenGitHub] as &'static [AppEvent], "open"),
+            (&[AppEvent::OpenJobSummary], "summary"),
+        ]);
+        if selected_job.is_some_and(|job| state.partial_logs.contains_key(&job.id)) {
+            hints.push((&[AppEvent::LoadFullLog], "full log"));
+        }
+        if selected_job.is_some_and(|job| job.pull_request.is_some()) {
+            hints.push((&[AppEvent::OpenPullRequest], "pull request"));
+        }
+        if selected_job.is_some_and(|job| job.called_workflow.is_some()) {
+            hints.push((&[AppEvent::OpenCalledWorkflow], "called workflow"));
+        }
+        if selected_job.is_some_and(|job| job.status == "waiting") {
+            hints.push((&[AppEvent::ApproveDeployment], "approve"));
+        }
+        hints.extend(rerun);
+        hints.extend([
+            (&[AppEvent::WatchRun] as &'static [AppEvent], "watch"),
+            (&[AppEvent::CompareLogs], "compare"),
+            (&[AppEvent::CopyCommitSha], "copy sha"),
+            (&[AppEvent::OpenYankMenu], "copy"),
+        ]);
+        if let Some(job) = selected_job {
+            if self.workflow_mutes.is_muted(job) {
+                hints.push((&[AppEvent::ToggleWorkflowMute], "unmute"));
+            } else if job.conclusion.as_deref() == Some("failure") {
+                hints.push((&[AppEvent::ToggleWorkflowMute], "mute"));
+            }
+        }
+        if !state.show_details {
+            hints.push((&[AppEvent::OpenFilter], "filter"));
+            if matches!(state.views.shown(), Some(View::Runs(_))) {
+                hints.push((&[AppEvent::ToggleRunView], "columns"));
+            } else {
+                hints.extend([
+                    (&[AppEvent::ToggleRunView] as &'static [AppEvent], "runs"),
+                    (
+                        &[AppEvent::ToggleOtherJobs],
+                        if state.show_other_jobs {
+                            "failed jobs"
+                        } else {
+                            "other jobs"
+                        },
+                    ),
+                    (
+                        &[AppEvent::ToggleRecentOnly],
+                        if state.filter.filters().recent_only {
+                            "all ages"
+                        } else {
+                            "recent only"
+                        },
+                    ),
+                ]);
+            }
+            hints.extend([
+                (
+                    &[AppEvent::ToggleWorkflowSidebar] as &'static [AppEvent],
+                    "workflows",
+                ),
+                (&[AppEvent::OpenBranchPicker], "branch"),
+                (&[AppEvent::OpenExport], "export"),
+                (&[AppEvent::ToggleTimeline], "timeline"),
+                (&[AppEvent::OpenTriage], "triage"),
+                (&[AppEvent::OpenDispatch], "dispatch"),
+                (&[AppEvent::ToggleDeployView], "deployments"),
+                (&[AppEvent::ShowActionsSettings], "settings"),
+            ]);
+            if self.gh_cli.repos().len() > 1 {
+                hints.extend([
+                    (
+                        &[AppEvent::NextRepo, AppEvent::PrevRepo] as &'static [AppEvent],
+                        "repos",
+                    ),
+                    (&[AppEvent::ToggleOverview], "overview"),
+                ]);
+            }
+            let filters = state.filter.filters();
+            if !matches!(state.views.shown(), Some(View::Runs(_))) {
+                if !filters.query.is_empty() {
+                    hints.push((&[AppEvent::Back], "clear filter"));
+                } else if !filters.is_empty() {
+                    hints.push((&[AppEvent::ClearFilters], "clear filters"));
+                }
+            }
+        }
+        if self.args.debug {
+            hints.extend([
+                (
+                    &[AppEvent::ToggleApiInspector] as &'static [AppEvent],
+                    "inspector",
+                ),
+                (&[AppEvent::InspectJson], "json"),
+            ]);
+        }
+        hints.push(quit);
+        hints
+    }
+
+    /// Renders the line used to type the filter of the job columns.
+    fn render_filter_prompt(&self, area: Rect, buf: &mut Buffer) {
+        let mut spans = vec![Span::styled("/", Style::default().fg(self.theme.accent))];
+        spans.extend(
+            self.app_state.filter.prompt.spans(
+                Style::default()
+                    .fg(self.theme.selection)
+                    .add_modifier(Modifier::BOLD),
+                self.app_state
+                    .filter
+                    .editing
+                    .then(|| Style::default().fg(self.theme.selection)),
+            ),
+        );
+        if self.app_state.filter.editing {
+            spans.push(Span::styled(
+                "  matches name, workflow, branch or actor, `path:` changed files; `Enter` to apply, `Esc` to clear",
+                Style::default().fg(self.theme.muted),
+            ));
+        } else {
+            spans.push(Span::styled(
+                "  `/` to edit, `Esc` to clear",
+                Style::default().fg(self.theme.muted),
+            ));
+        }
+        // The jobs whose changed files aren't known yet are left out meanwhile
+        let fetching = self
+            .app_state
+            .changed_paths
+            .values()
+            .filter(|paths| paths.is_none())
+            .count();
+        if fetching > 0 {
+            spans.push(Span::styled(
+                format!(
+                    "  fetching the files changed by {} commit{}...",
+                    fetching,
+                    if fetching == 1 { "" } else { "s" }
+                ),
+                Style::default().fg(self.theme.running),
+            ));
+        }
+        Paragraph::new(Line::from(spans)).render(area, buf);
+    }
+
+    /// Renders the timeline of job status changes, newest first.
+    fn render_timeline(&self, timeline_view: &TimelineViewState, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title("Timeline")
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+        if self.timeline.entries.is_empty() {
+            Paragraph::new(Span::styled(
+                "No job has changed status since lazyactions started.",
+                Style::default().fg(self.theme.muted),
+            ))
+            .block(block)
+            .render(area, buf);
+            return;
+        }
+
+        let show_repo = self.gh_cli.repos().len() > 1;
+        let lines: Vec<Line> = self
+            .timeline
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let (transition, color) = match &entry.transition {
+                    Transition::Queued => ("queued".to_string(), self.theme.muted),
+                    Transition::Started => ("started".to_string(), self.theme.running),
+                    Transition::Concluded(conclusion) => (
+                        format!("→ {}", conclusion),
+                        match conclusion.as_str() {
+                            "success" => self.theme.success,
+                            "failure" => self.theme.failure,
+                            _ => self.theme.muted,
+                        },
+                    ),
+                };
+                let mut spans = vec![
+                    Span::styled(
+                        format!(
+                            "{}  ",
+                            entry.observed_at.with_timezone(&Local).format("%H:%M:%S")
+                        ),
+                        Style::default().fg(self.theme.muted),
+                    ),
+                    Span::styled(
+                        entry.job_name.clone(),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!(" in {}", entry.workflow_name),
+                        Style::default().fg(self.theme.workflow),
+                    ),
+                ];
+                if show_repo {
+                    spans.push(Span::styled(
+                        format!(" ({})", entry.repo),
+                        Style::default().fg(self.theme.muted),
+                    ));
+                }
+                spans.push(Span::styled(
+                    format!(" {}", transition),
+                    Style::default().fg(color),
+                ));
+                let line = Line::from(spans);
+                if index == timeline_view.selected {
+                    line.style(self.selected_style(Style::default()))
+                } else {
+                    line
+                }
+            })
+            .collect();
+        // Keep the selected entry on screen
+        let height = block.inner(area).height as usize;
+        let scroll = (timeline_view.selected + 1).saturating_sub(height);
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((scroll as u16, 0))
+            .render(area, buf);
+    }
+
+    /// Renders the deploy dashboard: what is deployed to each environment, how the last
+    /// deployment went and which runs are waiting for approval.
+    fn render_deploy_dashboard(&self, deploy_view: &DeployViewState, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title("Deployments")
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+
+        let Some(environments) = &deploy_view.environments else {
+            let (message, style) = match &deploy_view.error {
+                Some(error) => (error.clone(), Style::default().fg(self.theme.failure)),
+                None => (
+                    "Loading environments...".to_string(),
+                    Style::default().fg(self.theme.muted),
+                ),
+            };
+            Paragraph::new(Span::styled(message, style))
+                .block(block)
+                .wrap(Wrap { trim: false })
+                .render(area, buf);
+            return;
+        };
+        if environments.is_empty() {
+            Paragraph::new(Span::styled(
+                "This repository has no environments.",
+                Style::default().fg(self.theme.muted),
+            ))
+            .block(block)
+            .render(area, buf);
+            return;
+        }
+
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(if deploy_view.error.is_some() { 2 } else { 1 }),
+            ])
+            .split(inner_area);
+
+        let rows = environments.iter().enumerate().map(|(index, env)| {
+            let (last_deploy, state, when) = match &env.last_deploy {
+                Some(deploy) => (
+                    format!(
+                        "{} ({}) by {}",
+                        deploy.git_ref,
+                        short_sha(&deploy.sha),
+                        deploy.creator
+                    ),
+                    Cell::from(deploy.state.clone()).style(
+                        Style::default().fg(deployment_state_color(&self.theme, &deploy.state)),
+                    ),
+                    format_timestamp(&deploy.created_at),
+                ),
+                None => ("never deployed".to_string(), Cell::from(""), String::new()),
+            };
+            let pending = match env.pending_approvals.as_slice() {
+                [] => Cell::from(""),
+                [approval] => {
+                    Cell::from(format!("{} on {}", approval.run_name, approval.head_branch))
+                }
+                [approval, rest @ ..] => Cell::from(format!(
+                    "{} on {} (+{} more)",
+                    approval.run_name,
+                    approval.head_branch,
+                    rest.len()
+                )),
+            }
+            .style(Style::default().fg(self.theme.warning));
+
+            let row = Row::new(vec![
+                Cell::from(env.name.clone()).style(Style::default().add_modifier(Modifier::BOLD)),
+                Cell::from(env.deployed_ref.clone().unwrap_or_else(|| "-".to_string())),
+                Cell::from(last_deploy),
+                state,
+                Cell::from(when),
+                pending,
+            ]);
+            if index == deploy_view.selected {
+                row.style(self.selected_style(Style::default()))
+            } else {
+                row
+            }
+        });
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Length(20),
+                Constraint::Min(24),
+                Constraint::Length(12),
+                Constraint::Length(17),
+                Constraint::Min(20),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "Environment",
+                "Deployed",
+                "Last deploy",
+                "State",
+                "When",
+                "Pending approval",
+            ])
+            .style(
+                Style::default()
+                    .fg(self.theme.group)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .style(Style::default().fg(self.theme.text))
+        .render(chunks[0], buf);
+
+        let mut footer = Vec::new();
+        if let Some(error) = &deploy_view.error {
+            footer.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(self.theme.failure),
+            )));
+        }
+        footer.push(Line::from(Span::styled(
+            "`Up`/`Down` to select, `Backspace` to open the pending or last deploy run, `E` to return to jobs.",
+            Style::default().fg(self.theme.muted),
+        )));
+        Paragraph::new(footer).render(chunks[1], buf);
+    }
+
+    /// Renders the workspace overview: how the jobs of each repository are doing and the
+    /// failure each saw last.
+    fn render_overview(&self, overview: &OverviewState, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title("Workspace")
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner_area);
+
+        let count = |count: usize, color: Color| {
+            let style = if count == 0 {
+                Style::default().fg(self.theme.muted)
+            } else {
+                Style::default().fg(color)
+            };
+            Cell::from(count.to_string()).style(style)
+        };
+        let rows = self
+            .repo_overviews()
+            .into_iter()
+            .enumerate()
+            .map(|(index, repo)| {
+                let last_failure = match repo.last_failure {
+                    Some(job) => Cell::from(format!(
+                        "{} in {} on {}, {}",
+                        job.name,
+                        job.workflow_name,
+                        job.head_branch,
+                        job.completed_at
+                            .unwrap_or(job.started_at)
+                            .format("%Y-%m-%d %H:%M")
+                    ))
+                    .style(Style::default().fg(self.theme.failure)),
+                    None => Cell::from("-").style(Style::default().fg(self.theme.muted)),
+                };
+                let row = Row::new(vec![
+                    Cell::from(repo.repo).style(Style::default().add_modifier(Modifier::BOLD)),
+                    count(repo.in_progress, self.theme.running),
+                    count(repo.success, self.theme.success),
+                    count(repo.failure, self.theme.failure),
+                    count(repo.other, self.theme.neutral),
+                    last_failure,
+                ]);
+                if index == overview.selected {
+                    row.style(self.selected_style(Style::default()))
+                } else {
+                    row
+                }
+            });
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(24),
+                Constraint::Length(11),
+                Constraint::Length(7),
+                Constraint::Length(6),
+                Constraint::Length(5),
+                Constraint::Min(24),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "Repository",
+                "In progress",
+                "Success",
+                "Failed",
+                "Other",
+                "Most recent failure",
+            ])
+            .style(
+                Style::default()
+                    .fg(self.theme.group)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .style(Style::default().fg(self.theme.text))
+        .render(chunks[0], buf);
+
+        Paragraph::new(Span::styled(
+            "`Up`/`Down` to select, `Enter` to open the repository's dashboard, `O` to close.",
+            Style::default().fg(self.theme.muted),
+        ))
+        .render(chunks[1], buf);
+    }
+
+    /// Renders the runner minutes used by the runs fetched of the active repository, by workflow
+    /// and operating system, under what its owner was billed for this cycle.
+    fn render_usage_view(&self, usage_view: &UsageViewState, area: Rect, buf: &mut Buffer) {
+        let repo = self.active_repo().unwrap_or_default();
+        let block = Block::bordered()
+            .title(format!("Actions usage of {}", repo))
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(2),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(inner_area);
+
+        let muted = Style::default().fg(self.theme.muted);
+        let billing = match &usage_view.billing {
+            None => Line::styled(
+                format!("Fetching the billing of {}…", usage_view.owner),
+                muted,
+            ),
+            Some(Ok(billing)) => Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{:.0} of {:.0} included minutes",
+                        billing.total_minutes_used, billing.included_minutes
+                    ),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(
+                    " used by {} this billing cycle, {:.0} paid for.",
+                    usage_view.owner, billing.total_paid_minutes_used
+                )),
+            ]),
+            // The billing settings are only readable by the admins of the account
+            Some(Err(_)) => Line::styled(
+                format!(
+                    "The billing of {} isn't accessible, only the runs fetched are summed up.",
+                    usage_view.owner
+                ),
+                muted,
+            ),
+        };
+        Paragraph::new(billing)
+            .style(Style::default().fg(self.theme.text))
+            .render(chunks[0], buf);
+
+        let summary = UsageSummary::of(
+            self.job_details
+                .iter()
+                .filter(|job| job.repo.eq_ignore_ascii_case(&repo)),
+        );
+        let total = summary.total_minutes();
+        let mut by_os = vec![Span::raw(format!("{} min in the runs fetched: ", total))];
+        for (index, (os, minutes)) in summary.minutes_by_os.iter().enumerate() {
+            if index > 0 {
+                by_os.push(Span::raw(", "));
+            }
+            by_os.push(Span::raw(format!("{} {} min", os.name(), minutes)));
+            let multiplier = match os.multiplier() {
+                0 => " (not billed)".to_string(),
+                1 => String::new(),
+                multiplier => format!(" (×{})", multiplier),
+            };
+            by_os.push(Span::styled(multiplier, muted));
+        }
+        if summary.minutes_by_os.is_empty() {
+            by_os.push(Span::styled("no completed job", muted));
+        }
+        Paragraph::new(Line::from(by_os))
+            .style(Style::default().fg(self.theme.text))
+            .render(chunks[1], buf);
+
+        let share = |minutes: u64| {
+            (minutes * 100)
+                .checked_div(total)
+                .map_or("-".to_string(), |percent| format!("{}%", percent))
+        };
+        let scroll = usage_view
+            .scroll
+            .min(summary.workflows.len().saturating_sub(1));
+        let rows = summary.workflows.iter().skip(scroll).map(|workflow| {
+            Row::new(vec![
+                Cell::from(workflow.workflow_name.clone())
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+                Cell::from(workflow.runs.to_string()),
+                Cell::from(workflow.jobs.to_string()),
+                Cell::from(workflow.minutes.to_string()),
+                Cell::from(share(workflow.minutes)),
+                Cell::from(format_elapsed(workflow.average_run)),
+            ])
+        });
+        Table::new(
+            rows,
+            [
+                Constraint::Min(24),
+                Constraint::Length(5),
+                Constraint::Length(5),
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Length(10),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                "Workflow", "Runs", "Jobs", "Minutes", "Share", "Avg run",
+            ])
+            .style(
+                Style::default()
+                    .fg(self.theme.group)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .style(Style::default().fg(self.theme.text))
+        .render(chunks[2], buf);
+
+        Paragraph::new(Span::styled(
+            "Minutes are rounded up per job as GitHub bills them, before the multiplier of the OS.",
+            muted,
+        ))
+        .render(chunks[3], buf);
+    }
+
+    /// Renders the failed job being triaged: what it is, its summary and the end of its log.
+    fn render_triage_view(&self, triage: &TriageState, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title(format!(
+                "Triage: {} of {} failed jobs left",
+                triage.queue.len(),
+                triage.total
+            ))
+            .title_bottom(
+                Line::from(
+                    " `r` rerun, `a` acknowledge, `o` open, `n`/`p` next/previous, `Esc` to close ",
+                )
+                .centered(),
+            )
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.failure));
+
+        let Some(job) = triage.current() else {
+            Paragraph::new(Span::styled(
+                "All failed jobs have been triaged.",
+                Style::default().fg(self.theme.success),
+            ))
+            .block(block)
+            .render(area, buf);
+            return;
+        };
+
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Percentage(40),
+                Constraint::Min(0),
+            ])
+            .split(inner_area);
+
+        let failed_step = job
+            .steps
+            .iter()
+            .find(|step| step.conclusion.as_deref() == Some("failure"))
+            .map(|step| format!(" at step {}", step.name))
+            .unwrap_or_default();
+        let mut info = vec![
+            Line::from(vec![
+                Span::styled(
+                    format!("{}/{}  ", triage.position + 1, triage.queue.len()),
+                    Style::default().fg(self.theme.muted),
+                ),
+                Span::styled(
+                    job.name.clone(),
+                    Style::default()
+                        .fg(self.theme.failure)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(failed_step),
+            ]),
+            Line::from(format!(
+                "{} on {} by {}, completed {}",
+                job.workflow_name,
+                job.head_branch,
+                job.actor_login,
+                job.completed_at
+                    .map(|completed_at| completed_at.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default()
+            )),
+        ];
+        if let Some(error) = &triage.error {
+            info.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(self.theme.failure),
+            )));
+        }
+        Paragraph::new(info).render(chunks[0], buf);
+
+        let summary_block = Block::default()
+            .borders(Borders::TOP)
+            .title("Summary")
+            .border_style(Style::default().fg(self.theme.muted));
+        let summary_lines = match &triage.summary {
+            None => vec![Line::from(Span::styled(
+                "Loading summary...",
+                Style::default().fg(self.theme.muted),
+            ))],
+            Some(markdown) if markdown.trim().is_empty() => vec![Line::from(Span::styled(
+                "This job did not publish a summary.",
+                Style::default().fg(self.theme.muted),
+            ))],
+            Some(markdown) => markdown::to_lines(markdown),
+        };
+        Paragraph::new(summary_lines)
+            .block(summary_block)
+            .wrap(Wrap { trim: false })
+            .render(chunks[1], buf);
+
+        // Only as much of the end of the log as fits is shown
+        let log_block = Block::default()
+            .borders(Borders::TOP)
+            .title("End of log")
+            .border_style(Style::default().fg(self.theme.muted));
+        let visible = log_block.inner(chunks[2]).height as usize;
+        let tail: Vec<LogLine> = triage
+            .log_tail
+            .iter()
+            .flat_map(|lines| {
+                lines[lines.len().saturating_sub(visible)..]
+                    .iter()
+                    .map(|line| LogLine::parse(line))
+            })
+            .collect();
+        let log_lines: Vec<Line> = match &triage.log_tail {
+            None => vec![Line::from(Span::styled(
+                "Loading log...",
+                Style::default().fg(self.theme.muted),
+            ))],
+            Some(_) => tail
+                .iter()
+                .map(|line| {
+                    Line::from(self.log_line_spans(line, Style::default(), &[], Style::default()))
+                })
+                .collect(),
+        };
+        Paragraph::new(log_lines)
+            .block(log_block)
+            .render(chunks[2], buf);
+    }
+
+    /// Renders the runs of a workflow, newest first, with how each concluded and how long it
+    /// took.
+    fn render_workflow_history(
+        &self,
+        history: &WorkflowHistoryState,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let now = Utc::now();
+        let title = match history.total_count {
+            Some(total_count) => format!(
+                "History of {} ({} of {} runs)",
+                history.workflow_name,
+                history.runs.len(),
+                total_count
+            ),
+            None => format!("History of {}", history.workflow_name),
+        };
+        let block = Block::bordered()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner_area);
+
+        let rows = history.runs.iter().enumerate().map(|(index, entry)| {
+            let run = &entry.run;
+            let (symbol, color) =
+                status_symbol(&self.theme, &run.status, run.conclusion.as_deref());
+            let duration = entry
+                .duration(now)
+                .map(format_elapsed)
+                .unwrap_or_else(|| "-".to_string());
+            let age = entry
+                .started_at
+                .map(|started_at| {
+                    format_age(u64::try_from((now - started_at).num_seconds()).unwrap_or(0))
+                })
+                .unwrap_or_else(|| "-".to_string());
+            let row = Row::new(vec![
+                Cell::from(symbol).style(Style::default().fg(color)),
+                Cell::from(format!("#{}", entry.run_number)),
+                Cell::from(run.conclusion.clone().unwrap_or_else(|| run.status.clone()))
+                    .style(Style::default().fg(color)),
+                Cell::from(duration),
+                Cell::from(run.head_branch.clone()).style(Style::default().fg(self.theme.workflow)),
+                Cell::from(run.event.clone()).style(Style::default().fg(self.theme.muted)),
+                Cell::from(run.commit_title.clone()),
+                Cell::from(age).style(Style::default().fg(self.theme.muted)),
+            ]);
+            if index == history.selected {
+                row.style(self.selected_style(Style::default()))
+            } else {
+                row
+            }
+        });
+        let mut state = TableState::default()
+            .with_selected((!history.runs.is_empty()).then_some(history.selected));
+        ratatui::widgets::StatefulWidget::render(
+            Table::new(
+                rows,
+                [
+                    Constraint::Length(1),
+                    Constraint::Length(7),
+                    Constraint::Length(11),
+                    Constraint::Length(8),
+                    Constraint::Max(20),
+                    Constraint::Length(12),
+                    Constraint::Min(10),
+                    Constraint::Length(4),
+                ],
+            )
+            .header(
+                Row::new(vec![
+                    "",
+                    "Run",
+                    "Conclusion",
+                    "Took",
+                    "Branch",
+                    "Trigger",
+                    "Commit",
+                    "Age",
+                ])
+                .style(
+                    Style::default()
+                        .fg(self.theme.group)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            )
+            .style(Style::default().fg(self.theme.text)),
+            chunks[0],
+            buf,
+            &mut state,
+        );
+
+        // Say how often the workflow failed lately, and what is being fetched
+        let failed = history
+            .runs
+            .iter()
+            .filter(|entry| {
+                matches!(
+                    entry.run.conclusion.as_deref(),
+                    Some("failure" | "timed_out")
+                )
+            })
+            .count();
+        let mut spans = vec![Span::styled(
+            format!(
+                "{} of the {} runs listed failed.",
+                failed,
+                history.runs.len()
+            ),
+            Style::default().fg(self.theme.muted),
+        )];
+        // The runs backfilled tell how the workflow fared over a longer stretch
+        let backfilled: Vec<_> = self
+            .run_history
+            .workflow_runs(&history.repo, &history.path)
+            .collect();
+        if !backfilled.is_empty() {
+            let passed = backfilled
+                .iter()
+                .filter(|entry| entry.run.conclusion.as_deref() == Some("success"))
+                .count();
+            spans.push(Span::styled(
+                format!(
+                    " {}% of the {} runs backfilled passed.",
+                    passed * 100 / backfilled.len(),
+                    backfilled.len()
+                ),
+                Style::default().fg(self.theme.muted),
+            ));
+        }
+        if let Some(error) = &history.error {
+            spans.push(Span::styled(
+                format!(" {}", error),
+                Style::default().fg(self.theme.failure),
+            ));
+        } else if history.loading {
+            spans.push(Span::styled(
+                if history.runs.is_empty() {
+                    " Loading runs..."
+                } else {
+                    " Loading older runs..."
+                },
+                Style::default().fg(self.theme.muted),
+            ));
+        } else if history.has_older_runs() {
+            spans.push(Span::styled(
+                " Older runs load at the bottom of the list.",
+                Style::default().fg(self.theme.muted),
+            ));
+        }
+        Paragraph::new(Line::from(spans)).render(chunks[1], buf);
+    }
+
+    /// Renders the tests of a workflow suspected of being flaky, those failing most often
+    /// first, with the latest run each failed in.
+    fn render_flaky_tests(&self, flaky_tests: &FlakyTestsState, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title(format!(
+                "Suspected flaky tests of {}",
+                flaky_tests.workflow_name
+            ))
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+        let scan = match &flaky_tests.scan {
+            Some(Ok(scan)) => scan,
+            Some(Err(error)) => {
+                Paragraph::new(Span::styled(
+                    error.clone(),
+                    Style::default().fg(self.theme.failure),
+                ))
+                .block(block)
+                .wrap(Wrap { trim: false })
+                .render(area, buf);
+                return;
+            }
+            None => {
+                Paragraph::new(Span::styled(
+                    format!(
+                        "Scanning the test reports of the latest {} runs...",
+                        flaky_tests::SCANNED_RUNS
+                    ),
+                    Style::default().fg(self.theme.muted),
+                ))
+                .block(block)
+                .render(area, buf);
+                return;
+            }
+        };
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner_area);
+
+        let rows = scan.suspects.iter().enumerate().map(|(index, suspect)| {
+            let row = Row::new(vec![
+                Cell::from(suspect.name.clone()),
+                Cell::from(suspect.occurrences.to_string())
+                    .style(Style::default().fg(self.theme.warning)),
+                Cell::from(Line::from(vec![
+                    Span::raw(format!("#{} ", suspect.last_seen.id)),
+                    Span::styled(
+                        format_timestamp(&suspect.last_seen.updated_at),
+                        Style::default().fg(self.theme.muted),
+                    ),
+                ])),
+            ]);
+            if index == flaky_tests.selected {
+                row.style(self.selected_style(Style::default()))
+            } else {
+                row
+            }
+        });
+        let mut state = TableState::default()
+            .with_selected((!scan.suspects.is_empty()).then_some(flaky_tests.selected));
+        ratatui::widgets::StatefulWidget::render(
+            Table::new(
+                rows,
+                [
+                    Constraint::Min(20),
+                    Constraint::Length(8),
+                    Constraint::Length(30),
+                ],
+            )
+            .header(
+                Row::new(vec!["Test", "Failures", "Last seen"]).style(
+                    Style::default()
+                        .fg(self.theme.group)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            )
+            .style(Style::default().fg(self.theme.text)),
+            chunks[0],
+            buf,
+            &mut state,
+        );
+
+        Paragraph::new(Span::styled(
+            format!(
+                "{} failed between passes in the {} runs with test reports.",
+                match scan.suspects.len() {
+                    1 => "1 test".to_string(),
+                    count => format!("{} tests", count),
+                },
+                scan.runs_with_reports
+            ),
+            Style::default().fg(self.theme.muted),
+        ))
+        .render(chunks[1], buf);
+    }
+
+    /// Renders the dashboard of a single run: its jobs as rows with their steps below them,
+    /// with spinners and elapsed times for what is still running.
+    fn render_watch_dashboard(&self, watch: &WatchState, area: Rect, buf: &mut Buffer) {
+        let now = Utc::now();
+        let block = Block::bordered()
+            .title_bottom(
+                Line::from(" `Up`/`Down` to scroll, `Backspace` to open the run, `Esc` to close ")
+                    .centered(),
+            )
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+
+        let Some(details) = &watch.details else {
+            let (message, style) = match &watch.error {
+                Some(error) => (error.clone(), Style::default().fg(self.theme.failure)),
+                None => (
+                    "Loading run...".to_string(),
+                    Style::default().fg(self.theme.muted),
+                ),
+            };
+            Paragraph::new(Span::styled(message, style))
+                .block(block.title(format!("Watching run {}", watch.run_id)))
+                .wrap(Wrap { trim: false })
+                .render(area, buf);
+            return;
+        };
+
+        let run = &details.run;
+        let symbol_for = |status: &str, conclusion: Option<&str>| {
+            if matches!(status, "in_progress" | "queued" | "waiting" | "pending") {
+                (spinner(now), self.theme.running)
+            } else {
+                status_symbol(&self.theme, status, conclusion)
+            }
+        };
+        let (run_symbol, run_color) = symbol_for(&run.status, run.conclusion.as_deref());
+        let block = block.title(format!(
+            "Watching {} #{} on {} by {}",
+            run.name, run.id, run.head_branch, run.actor_login
+        ));
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .split(inner_area);
+
+        // The run took from its first job starting to its last one completing
+        let started_at = details.jobs.iter().map(|job| job.started_at).min();
+        let completed_at = details
+            .jobs
+            .iter()
+            .filter_map(|job| job.completed_at)
+            .max()
+            .filter(|_| run.status == "completed");
+        let run_duration = started_at
+            .map(|started_at| format_elapsed(completed_at.unwrap_or(now) - started_at))
+            .unwrap_or_default();
+        let mut status_spans = vec![
+            Span::styled(format!("{} ", run_symbol), Style::default().fg(run_color)),
+            Span::styled(
+                run.conclusion.clone().unwrap_or_else(|| run.status.clone()),
+                Style::default().fg(run_color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  {}", run_duration),
+                Style::default().fg(self.theme.muted),
+            ),
+        ];
+        if let Some(error) = &watch.error {
+            status_spans.push(Span::styled(
+                format!("  {}", error),
+                Style::default().fg(self.theme.failure),
+            ));
+        }
+        Paragraph::new(Line::from(status_spans)).render(chunks[0], buf);
+
+        let mut lines = Vec::new();
+        for job in &details.jobs {
+            let (symbol, color) = symbol_for(&job.status, job.conclusion.as_deref());
+            let duration = if job.status == "queued" {
+                String::new()
+            } else {
+                format_elapsed(job.duration(now))
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} ", symbol), Style::default().fg(color)),
+                Span::styled(
+                    job.name.clone(),
+                    Style::default()
+                        .fg(self.theme.text)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(
+                        "  {}  {}",
+                        job.conclusion.as_deref().unwrap_or(&job.status),
+                        duration
+                    ),
+                    Style::default().fg(self.theme.muted),
+                ),
+            ]));
+            for (index, step) in job.steps.iter().enumerate() {
+                let branch = if index + 1 == job.steps.len() {
+                    "  └─ "
+                } else {
+                    "  ├─ "
+                };
+                let (symbol, color) = symbol_for(&step.status, step.conclusion.as_deref());
+                lines.push(Line::from(vec![
+                    Span::styled(branch, Style::default().fg(self.theme.muted)),
+                    Span::styled(format!("{} ", symbol), Style::default().fg(color)),
+                    Span::styled(step.name.clone(), Style::default().fg(self.theme.text)),
+                    Span::styled(
+                        format!(
+                            "  {}",
+                            step.duration(now).map(format_elapsed).unwrap_or_default()
+                        ),
+                        Style::default().fg(self.theme.muted),
+                    ),
+                ]));
+            }
+        }
+        Paragraph::new(lines)
+            .scroll((watch.scroll.min(u16::MAX as usize) as u16, 0))
+            .render(chunks[1], buf);
+    }
+
+    /// Renders the logs of two jobs side by side, highlighting the lines only one of them has.
+    fn render_log_compare<'a>(&self, compare: &'a LogCompareState, area: Rect, buf: &mut Buffer) {
+        let diff_lines = self.workflow_diff_lines(compare);
+        // A changed workflow file gets a bordered pane of up to a third of the height
+        let diff_height = match &compare.workflow_diff {
+            Some(Ok(diff)) if !diff.patch.is_empty() => {
+                (diff_lines.len() as u16 + 2).min(area.height / 3)
+            }
+            _ => 1,
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(diff_height),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(area);
+        let pane_areas = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+
+        let [left, right] = &compare.panes;
+        let line_set = |pane: &'a LogPane| -> HashSet<&'a str> {
+            pane.lines
+                .iter()
+                .flatten()
+                .map(|line| line.text.as_str())
+                .collect()
+        };
+        let (left_lines, right_lines) = (line_set(left), line_set(right));
+        for (index, other_lines) in [&right_lines, &left_lines].into_iter().enumerate() {
+            self.render_log_pane(compare, index, other_lines, pane_areas[index], buf);
+        }
+
+        match &compare.search {
+            Some(search) => self.render_log_search_prompt(search, chunks[2], buf),
+            None => Paragraph::new(Span::styled(
+                "`Up`/`Down` and `PageUp`/`PageDown` scroll both logs, highlighted lines are only in one of them, `Enter` folds or unfolds the groups, `/` to search, `Esc` to close.",
+                Style::default().fg(self.theme.muted),
+            ))
+            .render(chunks[2], buf),
+        }
+
+        match &compare.workflow_diff {
+            Some(Ok(diff)) if !diff.patch.is_empty() => {
+                let hidden = diff_lines
+                    .len()
+                    .saturating_sub(chunks[0].height as usize - 2);
+                let mut block = Block::bordered()
+                    .title(format!(
+                        "{} changed between {} and {}",
+                        diff.path,
+                        short_sha(&diff.base_sha),
+                        short_sha(&diff.head_sha)
+                    ))
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(self.theme.warning));
+                if hidden > 0 {
+                    block = block.title_bottom(
+                        Line::from(format!(" {} more lines ", hidden)).right_aligned(),
+                    );
+                }
+                Paragraph::new(diff_lines)
+                    .block(block)
+                    .render(chunks[0], buf);
+            }
+            _ => Paragraph::new(diff_lines).render(chunks[0], buf),
+        }
+    }
+
+    /// The lines telling whether the workflow file changed between the compared jobs, with
+    /// the colored diff of the file when it did.
+    fn workflow_diff_lines<'a>(&self, compare: &'a LogCompareState) -> Vec<Line<'a>> {
+        let diff = match &compare.workflow_diff {
+            None => {
+                return vec![Line::from(Span::styled(
+                    "Checking whether the workflow file changed...",
+                    Style::default().fg(self.theme.muted),
+                ))];
+            }
+            Some(Err(error)) => {
+                return vec![Line::from(Span::styled(
+                    error.clone(),
+                    Style::default().fg(self.theme.failure),
+                ))];
+            }
+            Some(Ok(diff)) => diff,
+        };
+        let (base, head) = (short_sha(&diff.base_sha), short_sha(&diff.head_sha));
+        if !diff.changed {
+            let message = if base == head {
+                format!("Both jobs ran on commit {}.", head)
+            } else {
+                format!("{} is the same at {} and {}.", diff.path, base, head)
+            };
+            return vec![Line::from(Span::styled(
+                message,
+                Style::default().fg(self.theme.muted),
+            ))];
+        }
+        if diff.patch.is_empty() {
+            return vec![Line::from(Span::styled(
+                format!(
+                    "{} changed between {} and {}, too much to show here.",
+                    diff.path, base, head
+                ),
+                Style::default().fg(self.theme.warning),
+            ))];
+        }
+        diff.patch
+            .iter()
+            .map(|line| {
+                let color = if line.starts_with("@@") {
+                    self.theme.accent
+                } else if line.starts_with('+') {
+                    self.theme.success
+                } else if line.starts_with('-') {
+                    self.theme.failure
+                } else {
+                    self.theme.neutral
+                };
+                Line::from(Span::styled(line.as_str(), Style::default().fg(color)))
+            })
+            .collect()
+    }
+
+    /// Renders the line used to type the search through the compared logs.
+    fn render_log_search_prompt(&self, search: &LogSearch, area: Rect, buf: &mut Buffer) {
+        let mut spans = vec![Span::styled("/", Style::default().fg(self.theme.accent))];
+        spans.extend(
+            search.query.spans(
+                Style::default()
+                    .fg(self.theme.selection)
+                    .add_modifier(Modifier::BOLD),
+                search
+                    .editing
+                    .then(|| Style::default().fg(self.theme.selection)),
+            ),
+        );
+        let counter = match search.matches.len() {
+            _ if search.query.is_empty() => String::new(),
+            0 => "  no matches".to_string(),
+            count => format!("  match {}/{}", search.current + 1, count),
+        };
+        spans.push(Span::styled(counter, Style::default().fg(self.theme.text)));
+        spans.push(Span::styled(
+            if search.editing {
+                "  `Enter` to search, `Esc` to cancel"
+            } else {
+                "  `n`/`N` next/previous match, `/` to edit, `Esc` to clear"
+            },
+            Style::default().fg(self.theme.muted),
+        ));
+        Paragraph::new(Line::from(spans)).render(area, buf);
+    }
+
+    /// Renders side `index` of the log comparison, from the line scrolled to onwards.
+    fn render_log_pane(
+        &self,
+        compare: &LogCompareState,
+        index: usize,
+        other_lines: &HashSet<&str>,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let (pane, scroll) = (&compare.panes[index], compare.scroll);
+        let search = compare
+            .search
+            .as_ref()
+            .filter(|search| !search.query.is_empty());
+        let current_match = search.and_then(|search| search.matches.get(search.current));
+        let conclusion = pane.job.conclusion.as_deref().unwrap_or(&pane.job.status);
+        let border_color = match conclusion {
+            "success" => self.theme.success,
+            "failure" => self.theme.failure,
+            _ => self.theme.running,
+        };
+        let mut block = Block::bordered()
+            .title(format!(
+                "{} ({}){}",
+                pane.job.name,
+                conclusion,
+                if self.app_state.partial_logs.contains_key(&pane.job.id) {
+                    format!(", last {} of the log", format_size(LOG_TAIL_BYTES))
+                } else {
+                    String::new()
+                }
+            ))
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(border_color));
+
+        let lines: Vec<Line> = match (&pane.error, &pane.lines) {
+            (Some(error), _) => vec![Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(self.theme.failure),
+            ))],
+            (None, None) => vec![Line::from(Span::styled(
+                "Loading log...",
+                Style::default().fg(self.theme.muted),
+            ))],
+            (None, Some(lines)) => {
+                block = block.title_bottom(
+                    Line::from(format!(
+                        " line {}/{} ",
+                        pane.rows().get(scroll).map_or(lines.len(), |line| line + 1),
+                        lines.len()
+                    ))
+                    .right_aligned(),
+                );
+                if let Some(search) = search {
+                    let count = search
+                        .matches
+                        .iter()
+                        .filter(|(pane, _)| *pane == index)
+                        .count();
+                    let title = match current_match {
+                        Some(&(pane, _)) if pane == index => {
+                            format!(" match {}/{} ", search.current + 1, search.matches.len())
+                        }
+                        _ if count == 1 => " 1 match ".to_string(),
+                        _ => format!(" {} matches ", count),
+                    };
+                    block = block.title_bottom(Line::from(title).left_aligned());
+                }
+                let rows = pane.rows();
+                rows.iter()
+                    .skip(scroll)
+                    .take(area.height as usize)
+                    .map(|&number| {
+                        let line = &lines[number];
+                        let style = if other_lines.contains(line.text.as_str()) {
+                            Style::default()
+                        } else {
+                            Style::default().fg(self.theme.warning)
+                        };
+                        // The current match stands out from the others
+                        let (matches, match_style) = match search {
+                            Some(search) => (
+                                logs::find_matches(&line.text, search.query.value()),
+                                if current_match == Some(&(index, number)) {
+                                    Style::default()
+                                        .fg(self.theme.selection)
+                                        .add_modifier(Modifier::REVERSED | Modifier::BOLD)
+                                } else {
+                                    Style::default().add_modifier(Modifier::REVERSED)
+                                },
+                            ),
+                            None => (Vec::new(), Style::default()),
+                        };
+                        let mut spans = Vec::new();
+                        if line.kind == LineKind::GroupStart {
+                            let folded = pane.collapsed.contains(&number);
+                            spans.push(Span::styled(
+                                if folded { "▸ " } else { "▾ " },
+                                Style::default().fg(self.theme.accent),
+                            ));
+                        }
+                        spans.extend(self.log_line_spans(line, style, &matches, match_style));
+                        if let Some(group) = pane
+                            .groups
+                            .iter()
+                            .find(|group| group.header == number)
+                            .filter(|_| pane.collapsed.contains(&number))
+                        {
+                            let hidden = group.end - number - 1;
+                            spans.push(Span::styled(
+                                format!(" ({} line{})", hidden, if hidden == 1 { "" } else { "s" }),
+                                Style::default().fg(self.theme.muted),
+                            ));
+                        }
+                        Line::from(spans)
+                    })
+                    .collect()
+            }
+        };
+
+        Paragraph::new(lines)
+            .block(block)
+            .style(Style::default().fg(self.theme.text))
+            .render(area, buf);
+    }
+
+    /// The spans of a log line, styled by its workflow command and escape sequences on top of
+    /// `style`, with the byte ranges of `highlights` in `highlight_style`.
+    fn log_line_spans<'a>(
+        &self,
+        line: &'a LogLine,
+        style: Style,
+        highlights: &[Range<usize>],
+        highlight_style: Style,
+    ) -> Vec<Span<'a>> {
+        let style = style.patch(match line.kind {
+            LineKind::Error => Style::default()
+                .fg(self.theme.failure)
+                .add_modifier(Modifier::BOLD),
+            LineKind::Warning => Style::default()
+                .fg(self.theme.warning)
+                .add_modifier(Modifier::BOLD),
+            LineKind::Notice | LineKind::Command => Style::default().fg(self.theme.accent),
+            LineKind::Debug => Style::default().fg(self.theme.muted),
+            LineKind::GroupStart => Style::default().add_modifier(Modifier::BOLD),
+            LineKind::Plain | LineKind::GroupEnd => Style::default(),
+        });
+        // Cut the text wherever a style or a highlight starts or ends
+        let mut cuts: Vec<usize> = line
+            .styles
+            .iter()
+            .map(|(range, _)| range)
+            .chain(highlights)
+            .flat_map(|range| [range.start, range.end])
+            .chain([0, line.text.len()])
+            .collect();
+        cuts.sort_unstable();
+        cuts.dedup();
+        cuts.windows(2)
+            .map(|cut| {
+                let (start, end) = (cut[0], cut[1]);
+                let mut span_style = style;
+                if let Some((_, ansi)) =
+                    line.styles.iter().find(|(range, _)| range.contains(&start))
+                {
+                    span_style = span_style.patch(*ansi);
+                }
+                if highlights.iter().any(|range| range.contains(&start)) {
+                    span_style = span_style.patch(highlight_style);
+                }
+                Span::styled(&line.text[start..end], span_style)
+            })
+            .collect()
+    }
+
+    /// Renders the Actions settings of a repository.
+    /// Renders the prompt asking to confirm a rerun.
+    fn render_rerun_confirmation(
+        &self,
+        confirmation: &RerunConfirmation,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let popup_area = centered_rect(area.width.saturating_mul(3) / 5, 5, area);
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title(if confirmation.failed_only {
+                "Rerun failed jobs"
+            } else {
+                "Rerun workflow"
+            })
+            .title_bottom(Line::from(" `y`/`Enter` to rerun, `n`/`Esc` to cancel ").centered())
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.warning));
+
+        let lines = vec![
+            Line::from(format!("Rerun {}?", confirmation.description())),
+            Line::from(Span::styled(
+                confirmation.repo.clone(),
+                Style::default().fg(self.theme.muted),
+            )),
+        ];
+        Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(popup_area, buf);
+    }
+
+    /// Renders the prompt asking to confirm cancelling a run.
+    fn render_cancel_confirmation(
+        &self,
+        confirmation: &CancelConfirmation,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let popup_area = centered_rect(area.width.saturating_mul(3) / 5, 5, area);
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title("Cancel run")
+            .title_bottom(
+                Line::from(" `y`/`Enter` to cancel the run, `n`/`Esc` to keep it ").centered(),
+            )
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.warning));
+
+        let lines = vec![
+            Line::from(format!("Cancel {}?", confirmation.description())),
+            Line::from(Span::styled(
+                confirmation.repo.clone(),
+                Style::default().fg(self.theme.muted),
+            )),
+        ];
+        Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(popup_area, buf);
+    }
+
+    fn render_full_log_confirmation(
+        &self,
+        confirmation: &FullLogConfirmation,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let popup_area = centered_rect(area.width.saturating_mul(3) / 5, 5, area);
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title("Load full log")
+            .title_bottom(Line::from(" `y`/`Enter` to load, `n`/`Esc` to cancel ").centered())
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.warning));
+
+        let size = confirmation
+            .total_bytes
+            .map_or_else(String::new, |bytes| format!("{} ", format_size(bytes)));
+        let lines = vec![
+            Line::from(format!(
+                "Load the full {}log of {}?",
+                size, confirmation.job.name
+            )),
+            Line::from(Span::styled(
+                format!("Only its last {} are shown.", format_size(LOG_TAIL_BYTES)),
+                Style::default().fg(self.theme.muted),
+            )),
+        ];
+        Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(popup_area, buf);
+    }
+
+    fn render_sso_authorization(
+        &self,
+        authorization: &SsoAuthorization,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let popup_area = centered_rect(area.width.saturating_mul(3) / 5, 7, area);
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title("Single sign-on required")
+            .title_bottom(
+                Line::from(" `Enter` to authorize, `r` to retry, `Esc` to close ").centered(),
+            )
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.warning));
+
+        let lines = vec![
+            Line::from(format!(
+                "{} enforces SAML single sign-on, which the token of the GitHub CLI isn't \
+                 authorized for. Authorize it at:",
+                authorization.org
+            )),
+            Line::from(Span::styled(
+                authorization.url.clone(),
+                Style::default().fg(self.theme.accent),
+            )),
+            Line::from(Span::styled(
+                "then retry.",
+                Style::default().fg(self.theme.muted),
+            )),
+        ];
+        Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(popup_area, buf);
+    }
+
+    fn render_approval_confirmation(
+        &self,
+        confirmation: &ApprovalConfirmation,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let popup_area = centered_rect(area.width.saturating_mul(3) / 5, 5, area);
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title("Approve deployment")
+            .title_bottom(Line::from(" `y`/`Enter` to approve, `n`/`Esc` to cancel ").centered())
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.warning));
+
+        let lines = vec![
+            Line::from(format!(
+                "Approve the deployments {} waits for?",
+                confirmation.description()
+            )),
+            Line::from(Span::styled(
+                confirmation.repo.clone(),
+                Style::default().fg(self.theme.muted),
+            )),
+        ];
+        Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(popup_area, buf);
+    }
+
+    /// Renders the prompt asking to confirm deleting a run, or the older runs of its workflow
+    /// along with how old they are.
+    fn render_deletion_confirmation(
+        &self,
+        confirmation: &DeletionConfirmation,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let popup_area = centered_rect(area.width.saturating_mul(3) / 5, 5, area);
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title(if confirmation.older_than_days.is_some() {
+                "Delete older runs"
+            } else {
+                "Delete run"
+            })
+            .title_bottom(Line::from(" `y`/`Enter` to delete, `n`/`Esc` to cancel ").centered())
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.failure));
+
+        let hint = if confirmation.older_than_days.is_some() {
+            "Type the age in days, `o` to delete the run only"
+        } else {
+            "`o` to delete the older runs of the workflow instead"
+        };
+        let lines = vec![
+            Line::from(format!("Delete {}?", confirmation.description())),
+            Line::from(Span::styled(
+                confirmation.repo.clone(),
+                Style::default().fg(self.theme.muted),
+            )),
+            Line::from(Span::styled(hint, Style::default().fg(self.theme.muted))),
+        ];
+        Paragraph::new(lines)
+            .block(block)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .render(popup_area, buf);
+    }
+
+    /// Renders the menu of what to copy about the selected job, with the value of each item.
+    fn render_yank_menu(&self, menu: &YankMenuState, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(
+            area.width.saturating_mul(4) / 5,
+            menu.items.len() as u16 + 2,
+            area,
+        );
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title("Copy")
+            .title_bottom(Line::from(" key or `Enter` to copy, `Esc` to close ").centered())
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+
+        let lines: Vec<Line> = menu
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let style = if index == menu.selected {
+                    self.selected_style(Style::default().fg(self.theme.selection))
+                } else {
+                    Style::default().fg(self.theme.text)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {} ", item.key),
+                        Style::default()
+                            .fg(self.theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!("{:<16}", item.label), style),
+                    Span::styled(
+                        format!(" {}", item.value),
+                        Style::default().fg(self.theme.muted),
+                    ),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines).block(block).render(popup_area, buf);
+    }
+
+    /// Renders the status badges of the workflows with the branch they are for, the markdown of
+    /// each under its workflow's name.
+    fn render_badges(&self, badges: &BadgePopupState, area: Rect, buf: &mut Buffer) {
+        let mut branch = vec![Span::styled(
+            "Branch: ",
+            Style::default().fg(self.theme.accent),
+        )];
+        branch.extend(
+            badges.branch.spans(
+                Style::default()
+                    .fg(self.theme.selection)
+                    .add_modifier(Modifier::BOLD),
+                Some(Style::default().fg(self.theme.selection)),
+            ),
+        );
+        if badges.branch.value().trim().is_empty() {
+            branch.push(Span::styled(
+                " any branch",
+                Style::default().fg(self.theme.muted),
+            ));
+        }
+        let mut lines = vec![Line::from(branch), Line::from("")];
+        match &badges.workflows {
+            None => lines.push(Line::from(Span::styled(
+                "Fetching workflows...",
+                Style::default().fg(self.theme.muted),
+            ))),
+            Some(Err(error)) => lines.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(self.theme.failure),
+            ))),
+            Some(Ok(workflows)) if workflows.is_empty() => lines.push(Line::from(Span::styled(
+                "This repository has no workflow files.",
+                Style::default().fg(self.theme.muted),
+            ))),
+            Some(Ok(workflows)) => {
+                for (index, workflow) in workflows.iter().enumerate() {
+                    let style = if index == badges.selected {
+                        self.selected_style(Style::default().fg(self.theme.selection))
+                    } else {
+                        Style::default().fg(self.theme.workflow)
+                    };
+                    lines.push(Line::from(Span::styled(workflow.name.clone(), style)));
+                    lines.push(Line::from(Span::styled(
+                        badges.badge(workflow),
+                        Style::default().fg(self.theme.muted),
+                    )));
+                }
+            }
+        }
+        let popup_area = centered_rect(
+            area.width.saturating_mul(4) / 5,
+            lines.len() as u16 + 2,
+            area,
+        );
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title(format!("Badges of {}", badges.repo))
+            .title_bottom(
+                Line::from(" `Enter` to copy, `Tab` to copy all, `Esc` to close ").centered(),
+            )
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+        Paragraph::new(lines).block(block).render(popup_area, buf);
+    }
+
+    /// Renders the menu of the commands of the installed `gh` extensions, suggesting some to
+    /// install when none is.
+    fn render_extension_menu(&self, menu: &ExtensionMenuState, area: Rect, buf: &mut Buffer) {
+        let lines: Vec<Line> = match &menu.actions {
+            None => vec![Line::from(Span::styled(
+                "Listing the gh extensions...",
+                Style::default().fg(self.theme.muted),
+            ))],
+            Some(Err(error)) => vec![Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(self.theme.failure),
+            ))],
+            Some(Ok(actions)) if actions.is_empty() => {
+                let mut lines = vec![Line::from(
+                    "No installed gh extension has commands to offer here. Try one of:",
+                )];
+                lines.extend(gh_extensions::KNOWN_EXTENSIONS.iter().map(|(_, repo)| {
+                    Line::from(Span::styled(
+                        format!("  gh extension install {}", repo),
+                        Style::default().fg(self.theme.muted),
+                    ))
+                }));
+                lines
+            }
+            Some(Ok(actions)) => actions
+                .iter()
+                .enumerate()
+                .map(|(index, action)| {
+                    let style = if index == menu.selected {
+                        self.selected_style(Style::default().fg(self.theme.selection))
+                    } else {
+                        Style::default().fg(self.theme.text)
+                    };
+                    Line::from(vec![
+                        Span::styled(format!(" {:<42}", action.label), style),
+                        Span::styled(
+                            format!(" gh {}", action.args(&menu.repo, &menu.branch).join(" ")),
+                            Style::default().fg(self.theme.muted),
+                        ),
+                    ])
+                })
+                .collect(),
+        };
+        let popup_area = centered_rect(
+            area.width.saturating_mul(4) / 5,
+            lines.len() as u16 + 2,
+            area,
+        );
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title("gh extensions")
+            .title_bottom(Line::from(" `Enter` to run, `Esc` to close ").centered())
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+        Paragraph::new(lines).block(block).render(popup_area, buf);
+    }
+
+    /// Renders the output of a command of a `gh` extension.
+    fn render_extension_output(&self, popup: &ExtensionOutputState, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(
+            area.width.saturating_mul(4) / 5,
+            area.height.saturating_mul(4) / 5,
+            area,
+        );
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title(popup.label)
+            .title_bottom(Line::from(" `Up`/`Down` to scroll, `Esc` to close ").centered())
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+
+        let lines: Vec<Line> = match &popup.output {
+            None => vec![Line::from(Span::styled(
+                "Running...",
+                Style::default().fg(self.theme.muted),
+            ))],
+            Some(Err(error)) => vec![Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(self.theme.failure),
+            ))],
+            Some(Ok(output)) => gh_extensions::align_columns(output)
+                .into_iter()
+                .map(Line::from)
+                .collect(),
+        };
+        Paragraph::new(lines)
+            .block(block)
+            .style(Style::default().fg(self.theme.text))
+            .scroll((popup.scroll, 0))
+            .render(popup_area, buf);
+    }
+
+    fn render_export_prompt(&self, prompt: &ExportPrompt, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(area.width.saturating_mul(3) / 5, 6, area);
+        clear(&self.theme, popup_area, buf);
+
+        let jobs: usize = (0..3)
+            .map(|column| {
+                self.app_state
+                    .column_jobs(column)
+                    .values()
+                    .flatten()
+                    .flat_map(ColumnRow::jobs)
+                    .count()
+            })
+            .sum();
+        let block = Block::bordered()
+            .title(format!("Export {} jobs", jobs))
+            .title_bottom(Line::from(" `Enter` to export, `Esc` to cancel ").centered())
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+
+        let mut path = vec![Span::raw("File: ")];
+        path.extend(
+            prompt.path.spans(
+                Style::default()
+                    .fg(self.theme.selection)
+                    .add_modifier(Modifier::BOLD),
+                Some(Style::default().fg(self.theme.selection)),
+            ),
+        );
+        let lines = vec![
+            Line::from(path),
+            match &prompt.error {
+                Some(error) => Line::from(Span::styled(
+                    error.clone(),
+                    Style::default().fg(self.theme.failure),
+                )),
+                None => Line::from(Span::styled(
+                    "Written as JSON, CSV or a Markdown table for .json, .csv or .md",
+                    Style::default().fg(self.theme.muted),
+                )),
+            },
+        ];
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: true })
+            .render(popup_area, buf);
+    }
+
+    fn render_actions_settings_popup(
+        &self,
+        popup: &ActionsSettingsState,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let popup_area = centered_rect(area.width.saturating_mul(4) / 5, 14, area);
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title(format!("Actions settings of {}", popup.repo))
+            .title_bottom(Line::from(" `Esc` to close ").centered())
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+
+        let lines: Vec<Line> = match (&popup.error, &popup.settings) {
+            (Some(error), _) => vec![Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(self.theme.failure),
+            ))],
+            (None, None) => vec![Line::from(Span::styled(
+                "Loading settings...",
+                Style::default().fg(self.theme.muted),
+            ))],
+            (None, Some(settings)) => {
+                let name_width = settings
+                    .iter()
+                    .map(|setting| setting.name.len())
+                    .max()
+                    .unwrap_or(0);
+                let mut lines: Vec<Line> = settings
+                    .iter()
+                    .map(|setting| {
+                        let value = match &setting.value {
+                            Some(value) => Span::styled(value.clone(), Style::default()),
+                            None => {
+                                Span::styled("unavailable", Style::default().fg(self.theme.muted))
+                            }
+                        };
+                        Line::from(vec![
+                            Span::styled(
+                                format!("{:width$}  ", setting.name, width = name_width),
+                                Style::default().fg(self.theme.accent),
+                            ),
+                            value,
+                        ])
+                    })
+                    .collect();
+                if settings.iter().any(|setting| setting.value.is_none()) {
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled(
+                        "Unavailable settings need admin access to the repository to read.",
+                        Style::default().fg(self.theme.muted),
+                    )));
+                }
+                lines
+            }
+        };
+
+        Paragraph::new(lines)
+            .block(block)
+            .style(Style::default().fg(self.theme.text))
+            .wrap(Wrap { trim: false })
+            .render(popup_area, buf);
+    }
+
+    /// Renders the markdown summary a job published.
+    fn render_job_summary_popup(&self, summary: &JobSummaryState, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(
+            area.width.saturating_mul(4) / 5,
+            area.height.saturating_mul(4) / 5,
+            area,
+        );
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title(format!("Summary of {}", summary.job_name))
+            .title_bottom(Line::from(" `Up`/`Down` to scroll, `Esc` to close ").centered())
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+
+        let lines: Vec<Line> = match (&summary.error, &summary.markdown) {
+            (Some(error), _) => vec![Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(self.theme.failure),
+            ))],
+            (None, None) => vec![Line::from(Span::styled(
+                "Loading summary...",
+                Style::default().fg(self.theme.muted),
+            ))],
+            (None, Some(markdown)) if markdown.trim().is_empty() => vec![Line::from(Span::styled(
+                "This job did not publish a summary.",
+                Style::default().fg(self.theme.muted),
+            ))],
+            (None, Some(markdown)) => markdown::to_lines(markdown),
+        };
+
+        Paragraph::new(lines)
+            .block(block)
+            .style(Style::default().fg(self.theme.text))
+            .wrap(Wrap { trim: false })
+            .scroll((summary.scroll, 0))
+            .render(popup_area, buf);
+    }
+
+    /// Renders the popup showing the raw JSON of a job and its run, with the keys of objects
+    /// highlighted.
+    fn render_json_inspector(&self, inspector: &JsonInspectorState, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(
+            area.width.saturating_mul(4) / 5,
+            area.height.saturating_mul(4) / 5,
+            area,
+        );
+        clear(&self.theme, popup_area, buf);
+
+        let mut block = Block::bordered()
+            .title(format!("JSON of {}", inspector.job_name))
+            .title_bottom(Line::from(" `Up`/`Down` to scroll, `Esc` to close ").centered())
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+
+        let lines: Vec<Line> = match &inspector.json {
+            None => vec![Line::from(Span::styled(
+                "Loading JSON...",
+                Style::default().fg(self.theme.muted),
+            ))],
+            Some(Err(error)) => vec![Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(self.theme.failure),
+            ))],
+            Some(Ok(json)) => {
+                let line_count = json.lines().count();
+                block = block.title_bottom(
+                    Line::from(format!(
+                        " line {}/{} ",
+                        (inspector.scroll as usize + 1).min(line_count),
+                        line_count
+                    ))
+                    .right_aligned(),
+                );
+                json.lines().map(|line| self.json_line(line)).collect()
+            }
+        };
+
+        Paragraph::new(lines)
+            .block(block)
+            .style(Style::default().fg(self.theme.text))
+            .scroll((inspector.scroll, 0))
+            .render(popup_area, buf);
+    }
+
+    /// A line of pretty-printed JSON, its key in the accent color and the endpoints it came
+    /// from muted.
+    fn json_line<'a>(&self, line: &'a str) -> Line<'a> {
+        if line.starts_with("//") {
+            return Line::from(Span::styled(line, Style::default().fg(self.theme.muted)));
+        }
+        let indent = line.len() - line.trim_start().len();
+        match line
+            .trim_start()
+            .starts_with('"')
+            .then(|| line.find("\": "))
+            .flatten()
+        {
+            Some(key_end) => Line::from(vec![
+                Span::raw(&line[..indent]),
+                Span::styled(
+                    &line[indent..=key_end],
+                    Style::default().fg(self.theme.accent),
+                ),
+                Span::raw(&line[key_end + 1..]),
+            ]),
+            None => Line::from(line),
+        }
+    }
+
+    /// The branches and tags suggested below the ref of the dispatch form.
+    fn ref_suggestion_lines(
+        &self,
+        dispatch: &DispatchState,
+        selected: Option<usize>,
+    ) -> Vec<Line<'static>> {
+        let hint = |text: String, color: Color| {
+            vec![Line::from(Span::styled(
+                format!("  {}", text),
+                Style::default().fg(color),
+            ))]
+        };
+        match &dispatch.refs {
+            None => hint("Loading branches and tags...".to_string(), self.theme.muted),
+            Some(Err(error)) => hint(error.clone(), self.theme.failure),
+            Some(Ok(_)) => dispatch
+                .ref_suggestions()
+                .into_iter()
+                .enumerate()
+                .map(|(index, git_ref)| {
+                    let style = if selected == Some(index) {
+                        self.selected_style(Style::default().fg(self.theme.selection))
+                    } else {
+                        Style::default().fg(self.theme.text)
+                    };
+                    let kind = match git_ref.kind {
+                        RefKind::Branch => "branch",
+                        RefKind::Tag => "tag",
+                    };
+                    Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(git_ref.name.clone(), style),
+                        Span::styled(format!("  {}", kind), Style::default().fg(self.theme.muted)),
+                    ])
+                })
+                .collect(),
+        }
+    }
+
+    /// Renders the workflow list and dispatch form used to trigger `workflow_dispatch` runs.
+    fn render_dispatch_popup(&self, dispatch: &DispatchState, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(70, 16, area);
+        clear(&self.theme, popup_area, buf);
+
+        let title = match &dispatch.form {
+            Some(form) => format!("Dispatch {}", form.workflow.name),
+            None if dispatch.show_history => "Dispatch History".to_string(),
+            None => "Workflows".to_string(),
+        };
+        let block = Block::bordered()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+        let inner_area = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let mut lines: Vec<Line> = Vec::new();
+        if let Some(form) = &dispatch.form {
+            for (field, label, value) in [
+                (DispatchField::Ref, "Ref: ", &form.git_ref),
+                (DispatchField::Inputs, "Inputs: ", &form.inputs),
+            ] {
+                let focused = form.focused_field == field;
+                let mut spans = vec![Span::styled(label, Style::default().fg(self.theme.accent))];
+                spans.extend(value.spans(
+                    if focused {
+                        Style::default()
+                            .fg(self.theme.selection)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(self.theme.text)
+                    },
+                    focused.then(|| Style::default().fg(self.theme.selection)),
+                ));
+                lines.push(Line::from(spans));
+                if field == DispatchField::Ref && focused {
+                    lines.extend(self.ref_suggestion_lines(dispatch, form.ref_selected));
+                }
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Inputs are comma separated key=value pairs, e.g. environment=staging, dry_run=true",
+                Style::default().fg(self.theme.muted),
+            )));
+            if let Some(error) = &form.error {
+                lines.push(Line::from(Span::styled(
+                    error.clone(),
+                    Style::default().fg(self.theme.failure),
+                )));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "`Tab` to switch fields, `Up`/`Down` to pick a ref, `Enter` to dispatch, `Esc` to go back.",
+                Style::default().fg(self.theme.muted),
+            )));
+        } else if dispatch.show_history {
+            let repo = self.active_repo().unwrap_or_default();
+            let records: Vec<_> = self.dispatch_history.for_repo(&repo).collect();
+            if records.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "No dispatches sent to this repository yet.",
+                    Style::default().fg(self.theme.muted),
+                )));
+            }
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs());
+            let visible_rows = (inner_area.height as usize).saturating_sub(3).max(1);
+            let first_row = dispatch.history_selected.saturating_sub(visible_rows - 1);
+            for (index, record) in records
+                .iter()
+                .enumerate()
+                .skip(first_row)
+                .take(visible_rows)
+            {
+                let style = if index == dispatch.history_selected {
+                    self.selected_style(Style::default().fg(self.theme.selection))
+                } else {
+                    Style::default().fg(self.theme.text)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        record.workflow.name.clone(),
+                        style.add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(format!(" on {}", record.git_ref), style),
+                    Span::styled(
+                        format!(
+                            "  {}  {} ago",
+                            record.inputs_string(),
+                            format_age(now.saturating_sub(record.dispatched_at))
+                        ),
+                        style.fg(self.theme.muted),
+                    ),
+                ]));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "`Enter` to dispatch again, `e` to edit before dispatching, `h` for workflows, `Esc` to close.",
+                Style::default().fg(self.theme.muted),
+            )));
+        } else if let Some(error) = &dispatch.error {
+            lines.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(self.theme.failure),
+            )));
+        } else if let Some(workflows) = &dispatch.workflows {
+            if workflows.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "No workflows in this repository.",
+                    Style::default().fg(self.theme.muted),
+                )));
+            }
+            // Keep the selected workflow within the visible part of the list
+            let visible_rows = (inner_area.height as usize).saturating_sub(2).max(1);
+            let first_row = dispatch.selected.saturating_sub(visible_rows - 1);
+            for (index, workflow) in workflows
+                .iter()
+                .enumerate()
+                .skip(first_row)
+                .take(visible_rows)
+            {
+                let style = if index == dispatch.selected {
+                    self.selected_style(Style::default().fg(self.theme.selection))
+                } else {
+                    Style::default().fg(self.theme.text)
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(workflow.name.clone(), style.add_modifier(Modifier::BOLD)),
+                    Span::styled(format!("  {}", workflow.path), style.fg(self.theme.muted)),
+                ]));
+            }
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "`Up`/`Down` to select, `Enter` to fill in the dispatch form, `h` for history, `Esc` to close.",
+                Style::default().fg(self.theme.muted),
+            )));
+        } else {
+            lines.push(Line::from(Span::styled(
+                "Fetching workflows...",
+                Style::default().fg(self.theme.muted),
+            )));
+        }
+
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .render(inner_area, buf);
+    }
+
+    /// Renders the popup picking the branch whose runs are fetched.
+    fn render_branch_picker(&self, picker: &BranchPickerState, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(60, 16, area);
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title(format!("Branches of {}", picker.repo))
+            .title_bottom(
+                Line::from(" `Up`/`Down` to select, `Enter` to pick, `Esc` to close ").centered(),
+            )
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+        let inner_area = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let mut search = vec![Span::styled(
+            "Search: ",
+            Style::default().fg(self.theme.accent),
+        )];
+        search.extend(
+            picker.query.spans(
+                Style::default()
+                    .fg(self.theme.selection)
+                    .add_modifier(Modifier::BOLD),
+                Some(Style::default().fg(self.theme.selection)),
+            ),
+        );
+        let mut lines = vec![Line::from(search), Line::from("")];
+        match &picker.branches {
+            None => lines.push(Line::from(Span::styled(
+                "Fetching branches...",
+                Style::default().fg(self.theme.muted),
+            ))),
+            Some(Err(error)) => lines.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(self.theme.failure),
+            ))),
+            Some(Ok(_)) => {
+                let choices = picker.choices();
+                if choices.is_empty() {
+                    lines.push(Line::from(Span::styled(
+                        "No branch matches.",
+                        Style::default().fg(self.theme.muted),
+                    )));
+                }
+                // Keep the selected branch within the visible part of the list
+                let current = self.gh_cli.branch();
+                let visible_rows = (inner_area.height as usize).saturating_sub(2).max(1);
+                let first_row = picker.selected.saturating_sub(visible_rows - 1);
+                for (index, choice) in choices
+                    .iter()
+                    .enumerate()
+                    .skip(first_row)
+                    .take(visible_rows)
+                {
+                    let style = if index == picker.selected {
+                        self.selected_style(Style::default().fg(self.theme.selection))
+                    } else {
+                        Style::default().fg(self.theme.text)
+                    };
+                    let mut spans = vec![match choice {
+                        Some(branch) => Span::styled(branch.to_string(), style),
+                        None => Span::styled("All branches", style.add_modifier(Modifier::ITALIC)),
+                    }];
+                    if current.as_deref() == *choice {
+                        spans.push(Span::styled("  shown", style.fg(self.theme.muted)));
+                    }
+                    lines.push(Line::from(spans));
+                }
+            }
+        }
+
+        Paragraph::new(lines).render(inner_area, buf);
+    }
+
+    /// Renders the command palette: the search, then the commands matching it with the keys
+    /// bound to them.
+    fn render_command_palette(&self, palette: &CommandPaletteState, area: Rect, buf: &mut Buffer) {
+        let popup_area = centered_rect(60, 16, area);
+        clear(&self.theme, popup_area, buf);
+
+        let block = Block::bordered()
+            .title("Commands")
+            .title_bottom(
+                Line::from(" `Up`/`Down` to select, `Enter` to run, `Esc` to close ").centered(),
+            )
+            .title_alignment(Alignment::Center)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+        let inner_area = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let mut search = vec![Span::styled(":", Style::default().fg(self.theme.accent))];
+        search.extend(
+            palette.query.spans(
+                Style::default()
+                    .fg(self.theme.selection)
+                    .add_modifier(Modifier::BOLD),
+                Some(Style::default().fg(self.theme.selection)),
+            ),
+        );
+        let mut lines = vec![Line::from(search), Line::from("")];
+        let choices = palette.choices();
+        if choices.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No command matches.",
+                Style::default().fg(self.theme.muted),
+            )));
+        }
+        // Keep the selected command within the visible part of the list
+        let visible_rows = (inner_area.height as usize).saturating_sub(2).max(1);
+        let first_row = palette.selected.saturating_sub(visible_rows - 1);
+        for (index, (name, app_event)) in choices
+            .iter()
+            .enumerate()
+            .skip(first_row)
+            .take(visible_rows)
+        {
+            let style = if index == palette.selected {
+                self.selected_style(Style::default().fg(self.theme.selection))
+            } else {
+                Style::default().fg(self.theme.text)
+            };
+            let mut spans = vec![Span::styled(name.to_string(), style)];
+            if let Some(keys) = self.keymap.keys_for(*app_event) {
+                spans.push(Span::styled(
+                    format!("  {}", keys),
+                    style.fg(self.theme.muted),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        Paragraph::new(lines).render(inner_area, buf);
+    }
+
+    // Renders the three-column job summary layout
+    /// Renders the sidebar listing the workflows, checked when their jobs are shown.
+    fn render_workflow_sidebar(
+        &self,
+        sidebar: &WorkflowSidebarState,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let block = Block::bordered()
+            .title("Workflows")
+            .title_bottom(Line::from(" `Space` toggle `a` all `Esc` close ").centered())
+            .border_type(BorderType::Rounded)
+            .border_style(
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let workflows = self.sidebar_workflows();
+        if workflows.is_empty() {
+            Paragraph::new(Span::styled(
+                "No workflows yet.",
+                Style::default().fg(self.theme.muted),
+            ))
+            .block(block)
+            .render(area, buf);
+            return;
+        }
+
+        let lines: Vec<Line> = workflows
+            .iter()
+            .enumerate()
+            .map(|(index, workflow)| {
+                let shown = !self
+                    .app_state
+                    .filter
+                    .filters()
+                    .hidden_workflows
+                    .contains(workflow);
+                let style = if index == sidebar.selected {
+                    self.selected_style(Style::default().fg(self.theme.selection))
+                } else if shown {
+                    Style::default()
+                } else {
+                    Style::default().fg(self.theme.muted)
+                };
+                Line::from(Span::styled(
+                    format!("[{}] {}", if shown { "x" } else { " " }, workflow),
+                    style,
+                ))
+            })
+            .collect();
+        // Keep the selected workflow on screen
+        let height = block.inner(area).height as usize;
+        let scroll = (sidebar.selected + 1).saturating_sub(height);
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((scroll as u16, 0))
+            .render(area, buf);
+    }
+
+    /// Highlights the selected row of a list with the focus indicators of the config file.
+    fn selected_style(&self, style: Style) -> Style {
+        let focus = self.config.theme.focus;
+        let mut modifier = Modifier::empty();
+        if focus.reversed {
+            modifier |= Modifier::REVERSED;
+        }
+        if focus.underline {
+            modifier |= Modifier::UNDERLINED;
+        }
+        style.add_modifier(modifier)
+    }
+
+    /// What the selected job, run or step starts with, an arrow if the config file asks for
+    /// one.
+    fn selection_marker(&self) -> &'static str {
+        if self.config.theme.focus.marker {
+            "▶ "
+        } else {
+            ""
+        }
+    }
+
+    /// The border of a panel, thick while it has the focus if the config file asks for it.
+    fn focus_border(&self, focused: bool) -> BorderType {
+        if focused && self.config.theme.focus.bold_border {
+            BorderType::Thick
+        } else {
+            BorderType::Rounded
+        }
+    }
+
+    /// Renders the job columns, or the run view in their place.
+    fn render_jobs(&self, area: Rect, buf: &mut Buffer) {
+        match self.app_state.views.shown() {
+            Some(View::Runs(run_view)) => self.render_run_view(run_view, area, buf),
+            _ => self.render_job_columns(area, buf),
+        }
+    }
+
+    /// Renders the runs on the left and the jobs of the selected run on the right.
+    fn render_run_view(&self, run_view: &RunViewState, area: Rect, buf: &mut Buffer) {
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(area);
+        let now = Utc::now();
+        let pane_block = |title: String, focused: bool| {
+            Block::bordered()
+                .title(title)
+                .border_type(self.focus_border(focused))
+                .border_style(
+                    Style::default()
+                        .fg(self.theme.accent)
+                        .add_modifier(if focused {
+                            Modifier::BOLD
+                        } else {
+                            Modifier::empty()
+                        }),
+                )
+        };
+        // The selection of the pane without the focus stays visible, without standing out
+        let selected_style = |focused: bool| {
+            let style = Style::default().fg(self.theme.selection);
+            if focused {
+                self.selected_style(style)
+            } else {
+                style
+            }
+        };
+
+        let runs = self.run_view_runs();
+        let runs_block = pane_block(format!("Runs ({})", runs.len()), !run_view.jobs_focused);
+        if runs.is_empty() {
+            Paragraph::new(Span::styled(
+                "No runs fetched yet.",
+                Style::default().fg(self.theme.muted),
+            ))
+            .alignment(Alignment::Center)
+            .block(runs_block)
+            .render(panes[0], buf);
+        } else {
+            let rows = runs.iter().map(|run| {
+                let (symbol, color) =
+                    status_symbol(&self.theme, &run.status, run.conclusion.as_deref());
+                let row = Row::new(vec![
+                    Cell::from(symbol).style(Style::default().fg(color)),
+                    Cell::from(run.name.clone()).style(Style::default().fg(self.theme.workflow)),
+                    Cell::from(run.head_branch.clone())
+                        .style(Style::default().fg(self.theme.muted)),
+                    Cell::from(run.commit_title.clone()),
+                ]);
+                if Some(run.id) == run_view.run_id {
+                    row.style(selected_style(!run_view.jobs_focused))
+                } else {
+                    row
+                }
+            });
+            let mut state = TableState::default()
+                .with_selected(runs.iter().position(|run| Some(run.id) == run_view.run_id));
+            ratatui::widgets::StatefulWidget::render(
+                Table::new(
+                    rows,
+                    [
+                        Constraint::Length(1),
+                        Constraint::Max(20),
+                        Constraint::Max(20),
+                        Constraint::Min(10),
+                    ],
+                )
+                .block(runs_block)
+                .highlight_symbol(self.selection_marker())
+                .style(Style::default().fg(self.theme.text)),
+                panes[0],
+                buf,
+                &mut state,
+            );
+        }
+
+        let jobs = run_view
+            .run_id
+            .map(|run_id| self.run_jobs(run_id))
+            .unwrap_or_default();
+        // Runs with huge matrices only have their first jobs fetched until expanded
+        let total_jobs = self
+            .runs
+            .iter()
+            .find(|run| Some(run.id) == run_view.run_id)
+            .and_then(|run| run.total_jobs);
+        let mut jobs_block = match total_jobs {
+            Some(total_jobs) => pane_block(
+                format!("Jobs ({} of {})", jobs.len(), total_jobs),
+                run_view.jobs_focused,
+            ),
+            None => pane_block(format!("Jobs ({})", jobs.len()), run_view.jobs_focused),
+        };
+        if let Some(total_jobs) = total_jobs {
+            let hint = if run_view.expanding.is_some() {
+                format!(" Fetching all {} jobs... ", total_jobs)
+            } else {
+                " `Enter` on the run fetches all of them ".to_string()
+            };
+            jobs_block = jobs_block.title_bottom(Line::from(hint).right_aligned());
+        }
+        if jobs.is_empty() {
+            Paragraph::new(Span::styled(
+                "No jobs of this run are listed.",
+                Style::default().fg(self.theme.muted),
+            ))
+            .alignment(Alignment::Center)
+            .block(jobs_block)
+            .render(panes[1], buf);
+            return;
+        }
+        let rows = jobs.iter().map(|&index| {
+            let job = &self.job_details[index];
+            let (symbol, color) =
+                status_symbol(&self.theme, &job.status, job.conclusion.as_deref());
+            let row = Row::new(vec![
+                Cell::from(symbol).style(Style::default().fg(color)),
+                Cell::from(job.name.clone()),
+                Cell::from(job.conclusion.clone().unwrap_or_else(|| job.status.clone()))
+                    .style(Style::default().fg(color)),
+                Cell::from(format_elapsed(job.duration(now)))
+                    .style(Style::default().fg(self.theme.muted)),
+            ]);
+            if Some(job.id) == run_view.job_id {
+                row.style(selected_style(run_view.jobs_focused))
+            } else {
+                row
+            }
+        });
+        let mut state = TableState::default().with_selected(
+            jobs.iter()
+                .position(|&index| Some(self.job_details[index].id) == run_view.job_id),
+        );
+        ratatui::widgets::StatefulWidget::render(
+            Table::new(
+                rows,
+                [
+                    Constraint::Length(1),
+                    Constraint::Min(10),
+                    Constraint::Length(11),
+                    Constraint::Length(8),
+                ],
+            )
+            .block(jobs_block)
+            .highlight_symbol(self.selection_marker())
+            .style(Style::default().fg(self.theme.text)),
+            panes[1],
+            buf,
+            &mut state,
+        );
+    }
+
+    fn render_job_columns(&self, area: Rect, buf: &mut Buffer) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(33), // In Progress
+                Constraint::Percentage(34), // Concluded Success
+                Constraint::Percentage(33), // Concluded Failure
+            ])
+            .split(area);
+
+        self.render_job_list_column(
+            columns[0],
+            buf,
+            "In Progress",
+            &self.app_state.in_progress_jobs,
+            self.theme.running,
+            0,
+        );
+
+        self.render_job_list_column(
+            columns[1],
+            buf,
+            "Concluded Success",
+            &self.app_state.success_jobs,
+            self.theme.success,
+            1,
+        );
+
+        let (title, color) = if self.app_state.show_other_jobs {
+            ("Concluded Other", self.theme.neutral)
+        } else {
+            ("Concluded Failure", self.theme.failure)
+        };
+        let title = if self.app_state.filter.filters().recent_only {
+            format!("{}, last {}h", title, self.config.recent_hours())
+        } else {
+            title.to_string()
+        };
+        self.render_job_list_column(
+            columns[2],
+            buf,
+            &title,
+            self.app_state.column_jobs(2),
+            color,
+            2,
+        );
+    }
+
+    // Reusable function to render a single column of job summaries
+    fn render_job_list_column(
+        &self,
+        area: Rect,
+        buf: &mut Buffer,
+        title: &str,
+        job_indices: &BTreeMap<JobGroup, Vec<ColumnRow>>,
+        border_color: Color,
+        column_idx: usize,
+    ) {
+        let is_selected_column = self.app_state.column_index == column_idx;
+        // Every column keeps its own scroll offset, selected or not
+        let position = self.app_state.columns[column_idx];
+        let block =
+            Block::default()
+                .title(format!(
+                    "{} ({})",
+                    title,
+                    job_indices
+                        .values()
+                        .flatten()
+                        .map(|row| row.jobs().len())
+                        .sum::<usize>()
+                ))
+                .borders(Borders::ALL)
+                .border_type(self.focus_border(is_selected_column))
+                .border_style(Style::default().fg(border_color).add_modifier(
+                    if is_selected_column {
+                        Modifier::BOLD
+                    } else {
+                        Modifier::empty()
+                    },
+                ));
+
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        if job_indices.is_empty() {
+            let no_data_text = Text::styled(
+                "No jobs in this category.",
+                Style::default().fg(self.theme.muted),
+            );
+            let paragraph = Paragraph::new(no_data_text)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false });
+            paragraph.render(inner_area, buf);
+            return;
+        }
+
+        // Group jobs by their "tool"
+
+        let width = inner_area.width;
+        let mut all_column_lines: Vec<Line> = Vec::new(); // Collect all lines first
+        // The rows each job takes once wrapped, which is what scrolling counts in
+        let mut job_rows = Vec::new();
+        let mut row = 0;
+        // Running jobs count up with every redraw
+        let now = Utc::now();
+
+        // Failures of several kinds of branches are labeled with the kind, which they are
+        // listed by
+        let label_branches = column_idx == 2
+            && !self.app_state.show_other_jobs
+            && job_indices
+                .values()
+                .flatten()
+                .flat_map(ColumnRow::jobs)
+                .map(|&index| self.config.branches.kind(&self.job_details[index]))
+                .collect::<HashSet<_>>()
+                .len()
+                > 1;
+
+        let mut current_column_job_idx = 0; // Tracks the sequential index of jobs within the column (ignoring groups)
+
+        // Iterate through grouped jobs to build all lines, including group headers
+        for (tool_name, rows_in_group) in job_indices.iter() {
+            let group_start = row;
+            // Add group header lines, with the health of the group at a glance
+            let (symbol, color) = rollup_status(
+                rows_in_group
+                    .iter()
+                    .flat_map(ColumnRow::jobs)
+                    .map(|&index| &self.job_details[index]),
+            )
+            .map_or(("○", self.theme.muted), |(status, conclusion)| {
+                status_symbol(&self.theme, status, conclusion)
+            });
+            all_column_lines.push(Line::from(vec![
+                Span::raw("── "),
+                Span::styled(format!("{} ", symbol), Style::default().fg(color)),
+                Span::styled(
+                    tool_name.to_string(),
+                    Style::default()
+                        .fg(self.theme.group)
+                        .add_modifier(Modifier::BOLD)
+                        .add_modifier(Modifier::UNDERLINED),
+                ),
+                Span::raw(" ──"),
+            ]));
+            all_column_lines.push(Line::from(Span::styled(
+                "─",
+                Style::default().fg(self.theme.muted),
+            )));
+            row += wrapped_rows(&all_column_lines[all_column_lines.len() - 2..], width);
+
+            // The jobs of the matrix last expanded, listed below it
+            let mut matrix_jobs: &[usize] = &[];
+            // Add job lines within this group
+            for (position_in_group, column_row) in rows_in_group.iter().enumerate() {
+                // The first job of a group is shown with the group's header
+                let job_start = if position_in_group == 0 {
+                    group_start
+                } else {
+                    row
+                };
+                let first_line = all_column_lines.len();
+                let (base_style, marker) =
+                    if is_selected_column && position.row_index == current_column_job_idx {
+                        (
+                            self.selected_style(Style::default().fg(self.theme.selection)),
+                            self.selection_marker(),
+                        )
+                    } else {
+                        (Style::default().fg(self.theme.text), "")
+                    };
+                let original_job_idx = match column_row {
+                    ColumnRow::Job(index) => *index,
+                    ColumnRow::Matrix(matrix) => {
+                        all_column_lines.extend(self.matrix_lines(
+                            matrix,
+                            current_column_job_idx + 1,
+                            marker,
+                            base_style,
+                        ));
+                        if matrix.expanded {
+                            matrix_jobs = &matrix.jobs;
+                        }
+                        current_column_job_idx += 1;
+                        all_column_lines.push(Line::from(Span::styled(
+                            "\n",
+                            Style::default().fg(self.theme.muted),
+                        )));
+                        row += wrapped_rows(&all_column_lines[first_line..], width);
+                        job_rows.push(job_start..row);
+                        continue;
+                    }
+                };
+                let job = &self.job_details[original_job_idx];
+                let status_style = match job.status.as_str() {
+                    "completed" => Style::default().fg(self.theme.success),
+                    "in_progress" => Style::default().fg(self.theme.running),
+                    "queued" | "waiting" => Style::default().fg(self.theme.muted),
+                    _ => Style::default().fg(self.theme.text),
+                };
+
+                let conclusion_span = if let Some(conclusion) = &job.conclusion {
+                    let conclusion_style = match conclusion.as_str() {
+                        "success" => Style::default().fg(self.theme.success),
+                        "failure" => Style::default().fg(self.theme.failure),
+                        "cancelled" => Style::default().fg(self.theme.muted),
+                        "skipped" => Style::default().fg(self.theme.skipped),
+                        _ => Style::default().fg(self.theme.text),
+                    };
+                    Span::styled(format!(" ({})", conclusion), conclusion_style)
+                } else {
+                    Span::raw("")
+                };
+
+                let action_part = job.name.split(" / ").last().unwrap_or(&job.name);
+                let workflow_part = job.name.as_str();
+
+                // Line 1: Index, Action (or primary name), Status, Conclusion
+                // The jobs of an expanded matrix are indented below it
+                let indent = if matrix_jobs.contains(&original_job_idx) {
+                    "  "
+                } else {
+                    ""
+                };
+                let mut title_line = vec![
+                    Span::styled(
+                        format!("{}{}{}. ", indent, marker, current_column_job_idx + 1), // Index relative to column view
+                        base_style.add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        action_part.to_string(), // Display the parsed action/primary name
+                        base_style.add_modifier(Modifier::BOLD),
+                    ),
+                ];
+                if job.status == "waiting" {
+                    // Held by an environment's protection rule until a reviewer approves it
+                    title_line.extend([
+                        Span::raw(" "),
+                        Span::styled(
+                            " ⏸ waiting approval ",
+                            Style::default()
+                                .fg(self.theme.warning)
+                                .add_modifier(Modifier::REVERSED | Modifier::BOLD),
+                        ),
+                    ]);
+                } else {
+                    title_line.extend([
+                        Span::styled(" [", status_style),
+                        Span::styled(job.status.clone(), status_style),
+                        conclusion_span,
+                        Span::styled("]", status_style),
+                    ]);
+                }
+                if let Some((what, expires_at)) = self.expiring_soon(job, now) {
+                    // GitHub is about to delete what could still be downloaded
+                    title_line.extend([
+                        Span::raw(" "),
+                        Span::styled(
+                            format!(
+                                " ⌛ {} expire {} ",
+                                what,
+                                format_expiry(expires_at, now).unwrap_or_default()
+                            ),
+                            Style::default()
+                                .fg(self.theme.warning)
+                                .add_modifier(Modifier::REVERSED | Modifier::BOLD),
+                        ),
+                    ]);
+                }
+                title_line.push(Span::styled(
+                    format!(" {}", format_elapsed(job.duration(now))),
+                    base_style.fg(self.theme.muted),
+                ));
+                all_column_lines.push(Line::from(title_line));
+
+                // Line 2: Workflow (conditionally displayed)
+                if !workflow_part.is_empty() {
+                    all_column_lines.push(Line::from(vec![
+                        Span::raw("  "), // Indent for readability
+                        Span::styled(
+                            workflow_part.to_string(),
+                            base_style.fg(self.theme.workflow),
+                        ),
+                    ]));
+                } else {
+                    all_column_lines.push(Line::from(Span::raw("")));
+                }
+
+                // Line 3: The commit the job ran on, unknown in jobs saved by older versions
+                if !job.head_sha.is_empty() {
+                    all_column_lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("  {} ", short_sha(&job.head_sha)),
+                            base_style.fg(self.theme.muted),
+                        ),
+                        Span::styled(job.commit_title().to_string(), base_style),
+                    ]));
+                }
+
+                // Line 4: Branch and Actor, what triggered the run and the pull request if
+                // there is one
+                let mut branch_spans = vec![Span::styled(
+                    format!("  {} by {}", job.head_branch, job.actor_login),
+                    base_style
+                        .fg(self.theme.muted)
+                        .add_modifier(Modifier::ITALIC),
+                )];
+                // Failures are listed by how much their branch matters, which the label tells
+                // first so that narrow columns keep it
+                let branch_label = match self.config.branches.kind(job) {
+                    BranchKind::Default => Some("default branch"),
+                    BranchKind::Release => Some("release branch"),
+                    BranchKind::PullRequest | BranchKind::Other => None,
+                };
+                if let Some(label) = branch_label.filter(|_| label_branches) {
+                    branch_spans.push(Span::styled(
+                        format!(" · {}", label),
+                        base_style
+                            .fg(self.theme.warning)
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                }
+                if let Some(trigger) = trigger_label(&job.event) {
+                    branch_spans.push(Span::styled(
+                        format!(" · {}", trigger),
+                        base_style.fg(self.theme.accent),
+                    ));
+                }
+                if let Some(pull_request) = &job.pull_request {
+                    branch_spans.push(Span::styled(
+                        format!(" · #{} {}", pull_request.number, pull_request.title),
+                        base_style.fg(self.theme.title),
+                    ));
+                }
+                all_column_lines.push(Line::from(branch_spans));
+
+                // A job waiting for a runner that doesn't exist hangs silently otherwise
+                if job.lacks_runner(&self.runners) {
+                    all_column_lines.push(Line::from(Span::styled(
+                        format!("  ⚠ {}", no_runner_message(&job.labels)),
+                        base_style.fg(self.theme.failure),
+                    )));
+                }
+
+                current_column_job_idx += 1; // Increment for the next job
+                all_column_lines.push(Line::from(Span::styled(
+                    "\n",
+                    Style::default().fg(self.theme.muted),
+                )));
+                row += wrapped_rows(&all_column_lines[first_line..], width);
+                job_rows.push(job_start..row);
+            }
+        }
+
+        let layout = ColumnLayout {
+            jobs: job_rows,
+            total_rows: row,
+            viewport_rows: inner_area.height as usize,
+        };
+        // The selection stays in view even when the column got shorter since it was scrolled
+        let scroll_offset = layout.offset_showing(position.row_index, position.scroll_offset);
+        self.app_state.column_layouts.borrow_mut()[column_idx] = layout;
+
+        let paragraph = Paragraph::new(all_column_lines)
+            .wrap(Wrap { trim: false })
+            .scroll((scroll_offset.min(u16::MAX as usize) as u16, 0));
+        paragraph.render(inner_area, buf);
+    }
+
+    /// The lines of a matrix row: its name with how many of all of its jobs passed, the
+    /// commit and the branch the jobs ran on.
+    fn matrix_lines(
+        &self,
+        matrix: &MatrixRow,
+        number: usize,
+        marker: &str,
+        base_style: Style,
+    ) -> Vec<Line<'static>> {
+        // Counted over every job of the matrix, including those of the other columns
+        let siblings: Vec<&GithubJob> = self
+            .job_details
+            .iter()
+            .filter(|job| {
+                job.run_id == matrix.run_id && matrix_name(&job.name) == Some(&matrix.name)
+            })
+            .collect();
+        let passed = siblings
+            .iter()
+            .filter(|job| job.conclusion.as_deref() == Some("success"))
+            .count();
+        let (_, color) = rollup_status(siblings.iter().copied())
+            .map_or(("○", self.theme.muted), |(status, conclusion)| {
+                status_symbol(&self.theme, status, conclusion)
+            });
+        let job = &self.job_details[matrix.jobs[0]];
+        vec![
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{}{}. {} ",
+                        marker,
+                        number,
+                        if matrix.expanded { "▾" } else { "▸" }
+                    ),
+                    base_style.add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(matrix.name.clone(), base_style.add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!(" [{}/{} passed]", passed, siblings.len()),
+                    Style::default().fg(color),
+                ),
+            ]),
+            Line::from(Span::styled(
+                format!("  {} matrix jobs", matrix.jobs.len()),
+                base_style.fg(self.theme.workflow),
+            )),
+            Line::from(vec![
+                Span::styled(
+                    format!("  {} ", short_sha(&job.head_sha)),
+                    base_style.fg(self.theme.muted),
+                ),
+                Span::styled(job.commit_title().to_string(), base_style),
+            ]),
+            Line::from(Span::styled(
+                format!("  {} by {}", job.head_branch, job.actor_login),
+                base_style
+                    .fg(self.theme.muted)
+                    .add_modifier(Modifier::ITALIC),
+            )),
+        ]
+    }
+
+    /// Renders the detailed view with Job Logs and full Job Details in a horizontal split.
+    fn render_detailed_overlay(&self, area: Rect, buf: &mut Buffer) {
+        // A failed job needs room for the excerpt of its log
+        let details_percentage = if self.selected_job_failed() { 50 } else { 30 };
+        let detailed_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(100 - details_percentage),
+                Constraint::Percentage(details_percentage), // Job Details (bottom)
+            ])
+            .split(area);
+
+        // Render Jobs
+        self.render_jobs(detailed_chunks[0], buf);
+
+        // Render Job Details in the bottom panel
+        self.render_full_job_details_panel(detailed_chunks[1], buf);
+    }
+    /// Renders the full job details panel (used in detailed view).
+    fn render_full_job_details_panel(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title("Job Details")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.accent));
+
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let selected_job_original_index = self.get_selected_job_original_index();
+        let selected_job = selected_job_original_index.and_then(|idx| self.job_details.get(idx));
+        let inner_area = match selected_job {
+            Some(job) => {
+                let failed = job.conclusion.as_deref() == Some("failure");
+                // The excerpt plus its title, leaving the details at least half of the panel
+                let excerpt_rows = match self.app_state.failure_excerpts.get(&job.id) {
+                    _ if !failed => 0,
+                    Some(Some(Ok(excerpt))) => excerpt.len().max(1) + 1,
+                    _ => 2,
+                };
+                // The annotations and errors plus their title, if there are any, in a quarter
+                // of the panel
+                let annotation_lines =
+                    self.job_annotations(job).len() + self.annotation_errors(job).len();
+                let annotation_rows = match annotation_lines {
+                    0 => 0,
+                    lines => lines + 1,
+                };
+                let rows = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Min(0),
+                        Constraint::Length((annotation_rows as u16).min(inner_area.height / 4)),
+                        Constraint::Length((excerpt_rows as u16).min(inner_area.height / 2)),
+                    ])
+                    .split(inner_area);
+                self.render_annotations(job, rows[1], buf);
+                if failed {
+                    self.render_failure_excerpt(job.id, rows[2], buf);
+                }
+                rows[0]
+            }
+            None => inner_area,
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(inner_area);
+        if let Some(job) = selected_job {
+            let mut details_text = vec![
+                Line::from(vec![
+                    Span::styled("Name: ", Style::default().fg(self.theme.accent)),
+                    Span::raw(job.name.clone()),
+                ]),
+                Line::from(vec![
+                    Span::styled("Repo: ", Style::default().fg(self.theme.accent)),
+                    Span::raw(job.repo.clone()),
+                ]),
+                Line::from(vec![
+                    Span::styled("Run ID: ", Style::default().fg(self.theme.accent)),
+                    Span::raw(job.run_id.to_string()),
+                ]),
+                Line::from(vec![
+                    Span::styled("Status: ", Style::default().fg(self.theme.accent)),
+                    Span::styled(
+                        job.status.clone(),
+                        match job.status.as_str() {
+                            "completed" => Style::default().fg(self.theme.success),
+                            "in_progress" => Style::default().fg(self.theme.running),
+                            "queued" | "waiting" => Style::default().fg(self.theme.muted),
+                            _ => Style::default().fg(self.theme.text),
+                        },
+                    ),
+                ]),
+            ];
+            if let Some(conclusion) = &job.conclusion {
+                details_text.push(Line::from(vec![
+                    Span::styled("Conclusion: ", Style::default().fg(self.theme.accent)),
+                    Span::styled(
+                        conclusion.clone(),
+                        match conclusion.as_str() {
+                            "success" => Style::default().fg(self.theme.success),
+                            "failure" => Style::default().fg(self.theme.failure),
+                            "cancelled" => Style::default().fg(self.theme.muted),
+                            "skipped" => Style::default().fg(self.theme.skipped),
+                            _ => Style::default().fg(self.theme.text),
+                        },
+                    ),
+                ]));
+            }
+            // Worked out on every redraw, so that how long ago keeps up with the clock
+            let now = Utc::now();
+            let absolute = self.config.absolute_timestamps;
+            details_text.push(Line::from(vec![
+                Span::styled("Started: ", Style::default().fg(self.theme.accent)),
+                Span::raw(format_time(job.started_at, now, absolute)),
+            ]));
+            if let Some(completed_at) = job.completed_at {
+                details_text.push(Line::from(vec![
+                    Span::styled("Finished: ", Style::default().fg(self.theme.accent)),
+                    Span::raw(format_time(completed_at, now, absolute)),
+                ]));
+            }
+            details_text.push(Line::from(vec![
+                Span::styled(
+                    match (job.completed_at, job.status.as_str()) {
+                        (Some(_), _) => "Duration: ",
+                        (None, "queued") => "Queued for: ",
+                        (None, _) => "Running for: ",
+                    },
+                    Style::default().fg(self.theme.accent),
+                ),
+                Span::raw(format_elapsed(job.duration(now))),
+            ]));
+            if job.lacks_runner(&self.runners) {
+                details_text.push(Line::from(vec![
+                    Span::styled("Runner: ", Style::default().fg(self.theme.accent)),
+                    Span::styled(
+                        format!(
+                            "{}, so the job will never start",
+                            no_runner_message(&job.labels)
+                        ),
+                        Style::default().fg(self.theme.failure),
+                    ),
+                ]));
+            }
+            details_text.push(Line::from(vec![
+                Span::styled("Branch: ", Style::default().fg(self.theme.accent)),
+                Span::raw(job.head_branch.clone()),
+            ]));
+            details_text.push(Line::from(vec![
+                Span::styled("Actor: ", Style::default().fg(self.theme.accent)),
+                Span::raw(job.actor_login.clone()),
+            ]));
+            if !job.head_sha.is_empty() {
+                let mut commit = vec![
+                    Span::styled("Commit: ", Style::default().fg(self.theme.accent)),
+                    Span::raw(short_sha(&job.head_sha)),
+                ];
+                if !job.commit_author.is_empty() {
+                    commit.push(Span::raw(format!(" by {}", job.commit_author)));
+                }
+                commit.push(Span::styled(
+                    "  `Y` to copy",
+                    Style::default().fg(self.theme.muted),
+                ));
+                details_text.push(Line::from(commit));
+                // Blank lines between the title and body of a message would only take room
+                let message = job
+                    .commit_message
+                    .lines()
+                    .filter(|line| !line.trim().is_empty());
+                for (index, line) in message.enumerate() {
+                    details_text.push(Line::from(vec![
+                        Span::styled(
+                            if index == 0 { "Message: " } else { "         " },
+                            Style::default().fg(self.theme.accent),
+                        ),
+                        Span::raw(line.to_string()),
+                    ]));
+                }
+            }
+            if job.event == "workflow_dispatch" {
+                details_text.push(Line::from(vec![
+                    Span::styled("Inputs: ", Style::default().fg(self.theme.accent)),
+                    match self.dispatch_for(job) {
+                        Some(record) if record.inputs.is_empty() => Span::raw("none"),
+                        Some(record) => Span::raw(record.inputs_string()),
+                        None => Span::styled(
+                            "only known for runs dispatched from lazyactions",
+                            Style::default().fg(self.theme.muted),
+                        ),
+                    },
+                ]));
+            }
+            if let Some(Some(Ok(Some(environment)))) = self.app_state.job_environments.get(&job.id)
+            {
+                details_text.push(Line::from(vec![
+                    Span::styled("Environment: ", Style::default().fg(self.theme.accent)),
+                    Span::raw(environment.clone()),
+                ]));
+            }
+            details_text.extend(self.expiry_lines(job));
+            if let Some(pull_request) = &job.pull_request {
+                details_text.push(Line::from(vec![
+                    Span::styled("Pull request: ", Style::default().fg(self.theme.accent)),
+                    Span::raw(format!("#{} {}", pull_request.number, pull_request.title)),
+                ]));
+            }
+            if let Some(called_workflow) = &job.called_workflow {
+                let mut spans = vec![
+                    Span::styled("Called workflow: ", Style::default().fg(self.theme.accent)),
+                    Span::raw(format!(
+                        "{}/{}@{}",
+                        called_workflow.repo, called_workflow.path, called_workflow.git_ref
+                    )),
+                ];
+                if let Some(keys) = self.keymap.keys_for(AppEvent::OpenCalledWorkflow) {
+                    spans.push(Span::styled(
+                        format!("  `{}` to open", keys),
+                        Style::default().fg(self.theme.muted),
+                    ));
+                }
+                details_text.push(Line::from(spans));
+            }
+            details_text.push(Line::from(vec![
+                Span::styled("URL: ", Style::default().fg(self.theme.accent)),
+                Span::raw(job.html_url.clone()).add_modifier(Modifier::UNDERLINED),
+            ]));
+            // The environment of a job is only known once its deployment is created
+            let oidc_subject = match self.oidc_subject(job) {
+                Some(Ok(subject)) => Span::raw(subject),
+                Some(Err(e)) => Span::styled(e, Style::default().fg(self.theme.failure)),
+                None if matches!(job.status.as_str(), "queued" | "waiting") => Span::styled(
+                    "known once the job starts",
+                    Style::default().fg(self.theme.muted),
+                ),
+                None => Span::styled("Loading...", Style::default().fg(self.theme.muted)),
+            };
+            details_text.push(Line::from(vec![
+                Span::styled("OIDC subject: ", Style::default().fg(self.theme.accent)),
+                oidc_subject,
+            ]));
+
+            // The links take the width of the steps too, so that their URLs fit
+            if self.app_state.show_links {
+                Paragraph::new(self.job_link_lines(job))
+                    .wrap(Wrap { trim: false })
+                    .render(inner_area, buf);
+                return;
+            }
+            let paragraph = Paragraph::new(details_text).wrap(Wrap { trim: false });
+            paragraph.render(chunks[0], buf);
+
+            self.render_job_steps(job, chunks[1], buf);
         } else {
             let no_job_selected_text = Text::styled(
                 "No job selected. Select a job in the main view before toggling detailed view.",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(self.theme.muted),
+            );
+            let paragraph = Paragraph::new(no_job_selected_text)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false });
+            paragraph.render(inner_area, buf);
+        }
+    }
+
+    /// Warns that GitHub is about to delete the log of a completed job, and lists the
+    /// artifacts of its run with when each expires.
+    fn expiry_lines(&self, job: &GithubJob) -> Vec<Line<'static>> {
+        let now = Utc::now();
+        let mut lines = Vec::new();
+        let log_expiry = self.log_expiry(job);
+        if job.status == "completed" && is_expiring_soon(log_expiry, now) {
+            lines.push(Line::from(vec![
+                Span::styled("Log: ", Style::default().fg(self.theme.accent)),
+                Span::styled(
+                    format!(
+                        "expires {}",
+                        format_expiry(log_expiry, now).unwrap_or_default()
+                    ),
+                    Style::default()
+                        .fg(self.theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]));
+        }
+        let artifacts = self.run_artifacts(job.run_id);
+        for (index, artifact) in artifacts.iter().enumerate() {
+            let expiry = match artifact.expires_at {
+                _ if artifact.expired => {
+                    Span::styled("expired", Style::default().fg(self.theme.muted))
+                }
+                Some(expires_at) if is_expiring_soon(expires_at, now) => Span::styled(
+                    format!(
+                        "expires {}",
+                        format_expiry(expires_at, now).unwrap_or_default()
+                    ),
+                    Style::default()
+                        .fg(self.theme.warning)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Some(expires_at) => Span::styled(
+                    format_expiry(expires_at, now)
+                        .map_or_else(|| "expired".to_string(), |left| format!("expires {}", left)),
+                    Style::default().fg(self.theme.muted),
+                ),
+                None => Span::raw(""),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    if index == 0 {
+                        "Artifacts: "
+                    } else {
+                        "           "
+                    },
+                    Style::default().fg(self.theme.accent),
+                ),
+                Span::raw(format!(
+                    "{} ({}) ",
+                    artifact.name,
+                    format_size(artifact.size_in_bytes)
+                )),
+                expiry,
+            ]));
+        }
+        if let Some((what, _)) = self.expiring_soon(job, now) {
+            let key = if what == "logs" { 'l' } else { 'd' };
+            lines.push(Line::styled(
+                format!(
+                    "  `y` then `{}` copies the command downloading the {} before they are gone",
+                    key, what
+                ),
+                Style::default().fg(self.theme.muted),
+            ));
+        }
+        lines
+    }
+
+    /// Lists the links of a job, each after the number that opens it.
+    fn job_link_lines(&self, job: &GithubJob) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line> = self
+            .job_links(job)
+            .into_iter()
+            .enumerate()
+            .map(|(index, link)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", index + 1),
+                        Style::default()
+                            .fg(self.theme.accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("{:<16}", link.label),
+                        Style::default().fg(self.theme.accent),
+                    ),
+                    Span::raw(link.url).add_modifier(Modifier::UNDERLINED),
+                ])
+            })
+            .collect();
+        lines.push(Line::styled(
+            "A number to open its link, `#` or `Esc` for the details.",
+            Style::default().fg(self.theme.muted),
+        ));
+        lines
+    }
+
+    /// Whether the job selected in the columns failed.
+    fn selected_job_failed(&self) -> bool {
+        self.get_selected_job_original_index()
+            .and_then(|index| self.job_details.get(index))
+            .is_some_and(|job| job.conclusion.as_deref() == Some("failure"))
+    }
+
+    /// Why the annotations of a job, or some of those of the checks of other apps on its
+    /// commit, couldn't be fetched, with the color to show each in.
+    fn annotation_errors(&self, job: &GithubJob) -> Vec<(&str, Color)> {
+        let mut errors = Vec::new();
+        if let Some(Some(Err(error))) = self.app_state.annotations.get(&job.id) {
+            errors.push((error.as_str(), self.theme.failure));
+        }
+        match self
+            .app_state
+            .check_annotations
+            .get(&(job.repo.to_ascii_lowercase(), job.head_sha.clone()))
+        {
+            Some(Some(Ok(checks))) => errors.extend(
+                checks
+                    .errors
+                    .iter()
+                    .map(|error| (error.as_str(), self.theme.warning)),
+            ),
+            Some(Some(Err(error))) => errors.push((error.as_str(), self.theme.warning)),
+            _ => {}
+        }
+        errors
+    }
+
+    /// Renders the annotations of a job and of the checks other apps failed on its commit, each
+    /// with the file and line it is on, after why any couldn't be fetched.
+    fn render_annotations(&self, job: &GithubJob, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 {
+            return;
+        }
+        let annotations = self.job_annotations(job);
+        let errors = self.annotation_errors(job);
+        let lines: Vec<Line> = errors
+            .iter()
+            .map(|(error, color)| {
+                Line::from(Span::styled(
+                    error.lines().next().unwrap_or_default().to_string(),
+                    Style::default().fg(*color),
+                ))
+            })
+            .chain(annotations.iter().enumerate().map(|(index, annotation)| {
+                let color = match annotation.level.as_str() {
+                    "failure" => self.theme.failure,
+                    "warning" => self.theme.warning,
+                    _ => self.theme.muted,
+                };
+                let selected = index == self.app_state.selected_annotation;
+                let style = if selected {
+                    self.selected_style(Style::default())
+                } else {
+                    Style::default()
+                };
+                let message = annotation.message.lines().next().unwrap_or_default();
+                let mut spans = vec![Span::styled(
+                    format!("{} ", if selected { "›" } else { " " }),
+                    style.fg(self.theme.accent),
+                )];
+                if let Some(source) = &annotation.source {
+                    spans.push(Span::styled(
+                        format!("[{}] ", source),
+                        style.fg(self.theme.muted),
+                    ));
+                }
+                spans.push(Span::styled(
+                    format!("{}:{} ", annotation.path, annotation.start_line),
+                    style.fg(color).add_modifier(Modifier::BOLD),
+                ));
+                if let Some(title) = &annotation.title {
+                    spans.push(Span::styled(
+                        format!("{}: ", title),
+                        style.fg(self.theme.text).add_modifier(Modifier::BOLD),
+                    ));
+                }
+                spans.push(Span::styled(message.to_string(), style.fg(self.theme.text)));
+                Line::from(spans)
+            }))
+            .collect();
+        let title = match annotations.len() {
+            0 => "Annotations".to_string(),
+            count => format!("Annotations ({})", count),
+        };
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(self.theme.accent));
+        // Keep the selected annotation in view
+        let inner_height = block.inner(area).height as usize;
+        let skip =
+            (errors.len() + self.app_state.selected_annotation + 1).saturating_sub(inner_height);
+        Paragraph::new(lines.into_iter().skip(skip).collect::<Vec<_>>())
+            .block(block)
+            .render(area, buf);
+    }
+
+    /// Renders the lines around the first error in the log of a failed job.
+    fn render_failure_excerpt(&self, job_id: u64, area: Rect, buf: &mut Buffer) {
+        // How much of the log there is, while it downloads and when only its end was fetched
+        let size = match (
+            self.gh_cli.log_progress(job_id),
+            self.app_state.partial_logs.get(&job_id),
+        ) {
+            (Some(progress), _) => match progress.expected_bytes {
+                Some(expected) => format!(
+                    " (loading {} of {})",
+                    format_size(progress.loaded_bytes),
+                    format_size(expected)
+                ),
+                None => format!(" (loading {})", format_size(progress.loaded_bytes)),
+            },
+            (None, Some(Some(total_bytes))) => format!(
+                " (last {} of {})",
+                format_size(LOG_TAIL_BYTES),
+                format_size(*total_bytes)
+            ),
+            (None, Some(None)) => format!(" (last {})", format_size(LOG_TAIL_BYTES)),
+            (None, None) => String::new(),
+        };
+        let block = Block::default()
+            .title(format!("Log excerpt{}", size))
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(self.theme.accent));
+        let lines: Vec<Line> = match self.app_state.failure_excerpts.get(&job_id) {
+            Some(Some(Ok(excerpt))) if excerpt.is_empty() => vec![Line::from(Span::styled(
+                "The log is empty.",
+                Style::default().fg(self.theme.muted),
+            ))],
+            Some(Some(Ok(excerpt))) => excerpt
+                .iter()
+                .map(|line| match line.split_once("##[error]") {
+                    Some((_, error)) => Line::from(Span::styled(
+                        error.to_string(),
+                        Style::default()
+                            .fg(self.theme.failure)
+                            .add_modifier(Modifier::BOLD),
+                    )),
+                    None => Line::from(Span::styled(
+                        line.clone(),
+                        Style::default().fg(self.theme.neutral),
+                    )),
+                })
+                .collect(),
+            Some(Some(Err(error))) => vec![Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(self.theme.failure),
+            ))],
+            _ => vec![Line::from(Span::styled(
+                "Fetching log...",
+                Style::default().fg(self.theme.muted),
+            ))],
+        };
+        // Keep the end of the excerpt, where the error is, when it doesn't fit
+        let inner_height = block.inner(area).height as usize;
+        let skip = lines.len().saturating_sub(inner_height);
+        Paragraph::new(lines[skip..].to_vec())
+            .block(block)
+            .render(area, buf);
+    }
+
+    /// Renders the steps of a job as a tree with their outcome and duration.
+    fn render_job_steps(&self, job: &crate::gh_cli::GithubJob, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title(format!("Steps ({})", job.steps.len()))
+            .borders(Borders::LEFT)
+            .border_style(Style::default().fg(self.theme.accent));
+        if job.steps.is_empty() {
+            Paragraph::new(Span::styled(
+                "No steps reported yet.",
+                Style::default().fg(self.theme.muted),
+            ))
+            .block(block)
+            .render(area, buf);
+            return;
+        }
+
+        let now = Utc::now();
+        let medians = self.step_medians(job);
+        let selected_step = self
+            .app_state
+            .selected_step
+            .min(job.steps.len().saturating_sub(1));
+        // Keep the selected step within the visible part of the tree
+        let visible_rows = (area.height as usize).saturating_sub(1).max(1);
+        let first_row = selected_step.saturating_sub(visible_rows - 1);
+
+        let mut lines = Vec::new();
+        for (index, step) in job
+            .steps
+            .iter()
+            .enumerate()
+            .skip(first_row)
+            .take(visible_rows)
+        {
+            let branch = if index + 1 == job.steps.len() {
+                "└─ "
+            } else {
+                "├─ "
+            };
+            let (symbol, color) =
+                status_symbol(&self.theme, &step.status, step.conclusion.as_deref());
+            // A step far slower than it usually is points at where a regression crept in
+            let slow_median = step
+                .duration(now)
+                .zip(medians.get(&step.name))
+                .filter(|&(duration, &median)| is_slow_step(duration, median));
+            let duration = step.duration(now).map(format_elapsed).unwrap_or_default();
+            let (name_style, marker) = if index == selected_step {
+                (
+                    self.selected_style(Style::default().fg(self.theme.selection)),
+                    self.selection_marker(),
+                )
+            } else {
+                (Style::default().fg(self.theme.text), "")
+            };
+            let mut line = Line::from(vec![
+                Span::styled(branch, Style::default().fg(self.theme.muted)),
+                Span::styled(marker, Style::default().fg(self.theme.selection)),
+                Span::styled(format!("{} ", symbol), Style::default().fg(color)),
+                Span::styled(step.name.clone(), name_style),
+                Span::styled(
+                    format!("  {}  ", step.conclusion.as_deref().unwrap_or(&step.status)),
+                    Style::default().fg(self.theme.muted),
+                ),
+            ]);
+            match slow_median {
+                Some((_, median)) => line.extend([
+                    Span::styled(
+                        duration,
+                        Style::default()
+                            .fg(self.theme.warning)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("  slow, usually {}", format_elapsed(*median)),
+                        Style::default().fg(self.theme.warning),
+                    ),
+                ]),
+                None => line.push_span(Span::styled(
+                    duration,
+                    Style::default().fg(self.theme.muted),
+                )),
+            }
+            lines.push(line);
+        }
+        Paragraph::new(lines).block(block).render(area, buf);
+    }
+
+    /// Returns the original index into `self.job_details` for the currently
+    /// selected job in the UI, or None if no job is selected or the index is out of bounds.
+    pub fn get_selected_job_original_index(&self) -> Option<usize> {
+        let (job_indices_for_current_column, _) = self.get_current_column_data();
+
+        if job_indices_for_current_column.is_empty() {
+            return None;
+        }
+
+        let mut visual_job_counter = 0;
+        for (_tool_name, rows_in_group) in job_indices_for_current_column.iter() {
+            for column_row in rows_in_group {
+                if visual_job_counter == self.app_state.position().row_index {
+                    return Some(column_row.job_index());
+                }
+                visual_job_counter += 1;
+            }
+        }
+        None // No job found at the current row_index
+    }
+
+    /// Helper to get the job indices and color for the currently selected column.
+    /// This avoids duplicating logic in get_selected_job_original_index and render_job_list_column.
+    fn get_current_column_data(&self) -> (&BTreeMap<JobGroup, Vec<ColumnRow>>, Color) {
+        let color = match self.app_state.column_index {
+            0 => self.theme.running,
+            1 => self.theme.success,
+            _ if self.app_state.show_other_jobs => self.theme.neutral,
+            _ => self.theme.failure,
+        };
+        (
+            self.app_state.column_jobs(self.app_state.column_index),
+            color,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeDelta, Utc};
+    use clap::Parser;
+    use ratatui::{
+        Terminal,
+        backend::TestBackend,
+        crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    };
+    use std::{collections::HashMap, sync::Arc};
+
+    use crate::{
+        app::{
+            App, JobGroup, LogCompareState, OverviewState, ToastLevel, UsageViewState,
+            WorkflowHistoryState,
+        },
+        client::fixture::{self, FixtureClient},
+        config::Config,
+        event::{AppEvent, EventHandler, PolledEvent},
+        gh_cli::{Annotation, Artifact, CancelFlag, WorkflowData},
+        theme::FocusConfig,
+    };
+
+    const WIDTH: u16 = 100;
+
+    /// An app that has fetched `data`, with the default settings.
+    fn app_with(data: WorkflowData) -> App {
+        app_with_client(FixtureClient::new(data))
+    }
+
+    /// An app that has fetched the data of `client`, with the default settings.
+    fn app_with_client(client: FixtureClient) -> App {
+        let client = Arc::new(client);
+        let events = EventHandler::idle(client.clone());
+        let args = crate::Args::parse_from(["lazyactions"]);
+        let mut app = App::with_client(args, Config::default(), client, events);
+        refetch(&mut app);
+        // The retention of each repository, which fixtures don't serve
+        for _ in 0..app.app_state.retention_days.len() {
+            app.handle_events().expect("retention result");
+        }
+        app
+    }
+
+    /// Fetches the runs as the scheduler would on a refresh, which the idle event handler has
+    /// none of.
+    fn refetch(app: &mut App) {
+        app.events.request(|gh_cli| {
+            PolledEvent::GitHubDataFetched(
+                gh_cli
+                    .fetch_github_workflow_data(&CancelFlag::default())
+                    .map_err(|e| e.to_string()),
+            )
+        });
+        app.handle_events().expect("fetch result");
+    }
+
+    /// Handles app events as if their keys were pressed.
+    fn press(app: &mut App, app_events: &[AppEvent]) {
+        for app_event in app_events {
+            app.events.send(*app_event);
+            app.handle_events().expect("app event");
+        }
+    }
+
+    /// Two runs of the fixture repository: one that succeeded and one that failed.
+    fn concluded_runs() -> WorkflowData {
+        let passed = fixture::run(1, "completed", Some("success"));
+        let failed = fixture::run(2, "completed", Some("failure"));
+        WorkflowData {
+            jobs: vec![
+                fixture::job(11, "build", &passed, "10:00:00", Some(3)),
+                fixture::job(12, "lint", &passed, "10:01:00", Some(1)),
+                fixture::job(21, "test", &failed, "10:05:00", Some(7)),
+            ],
+            runs: vec![failed, passed],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        }
+    }
+
+    /// Renders the app on a screen `height` lines tall and returns the text of each line.
+    fn render(app: &App, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(WIDTH, height)).expect("test terminal");
+        terminal
+            .draw(|frame| frame.render_widget(app, frame.area()))
+            .expect("draw");
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                (0..WIDTH)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn no_runs() -> WorkflowData {
+        WorkflowData {
+            runs: Vec::new(),
+            jobs: Vec::new(),
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn columns_group_concluded_jobs_by_name() {
+        let app = app_with(concluded_runs());
+        assert_eq!(
+            render(&app, 18),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No jobs in this category.   ││── ✓ build ──                   ││── ✗ test ──                   │",
+                "│                               ││─                               ││─                              │",
+                "│                               ││1. build [completed (success)]  ││1. test [completed (failure)]  │",
+                "│                               ││3m 00s                          ││7m 00s                         │",
+                "│                               ││  build                         ││  test                         │",
+                "│                               ││  c0ffee1 Change number 1       ││  c0ffee2 Change number 2      │",
+                "│                               ││  main by octocat · push        ││  main by octocat · push       │",
+                "│                               ││                                ││                               │",
+                "│                               ││── ✓ lint ──                    ││                               │",
+                "│                               ││─                               ││                               │",
+                "│                               ││2. lint [completed (success)] 1m││                               │",
+                "│                               ││00s                             ││                               │",
+                "│                               ││  lint                          ││                               │",
+                "│                               ││  c0ffee1 Change number 1       ││                               │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "←/→ columns  ↑/↓ rows  PgUp/PgDn page  Enter details  Backspace open  S summary  R rerun  q quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn details_show_the_selected_job_and_its_steps() {
+        let mut app = app_with(concluded_runs());
+        press(
+            &mut app,
+            &[
+                AppEvent::NavigateRight,
+                AppEvent::NavigateRight,
+                AppEvent::ToggleDetails,
+            ],
+        );
+        assert_eq!(
+            render(&app, 20),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No jobs in this category.   ││── ✓ build ──                   ││── ✗ test ──                   │",
+                "│                               ││─                               ││─                              │",
+                "│                               ││1. build [completed (success)]  ││1. test [completed (failure)]  │",
+                "│                               ││3m 00s                          ││7m 00s                         │",
+                "│                               ││  build                         ││  test                         │",
+                "│                               ││  c0ffee1 Change number 1       ││  c0ffee2 Change number 2      │",
+                "│                               ││  main by octocat · push        ││  main by octocat · push       │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "╭Job Details───────────────────────────────────────────────────────────────────────────────────────╮",
+                "│Name: test                             │Steps (2)                                                 │",
+                "│Repo: octocat/hello                    │├─ ✗ Checkout  failure  7m 00s                            │",
+                "│Run ID: 2                              │└─ ✗ Test  failure  7m 00s                                │",
+                "│Status: completed                      │                                                          │",
+                "│Conclusion: failure                    │                                                          │",
+                "│Log excerpt───────────────────────────────────────────────────────────────────────────────────────│",
+                "│Fetching log...                                                                                   │",
+                "╰──────────────────────────────────────────────────────────────────────────────────────────────────╯",
+                "↑/↓ steps  Enter close  # links  Backspace open  S summary  R rerun  F rerun failed  q quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn details_list_the_links_of_the_job_by_number() {
+        let mut app = app_with(concluded_runs());
+        press(
+            &mut app,
+            &[
+                AppEvent::NavigateRight,
+                AppEvent::NavigateRight,
+                AppEvent::ToggleLinks,
+            ],
+        );
+        assert!(app.app_state.show_details);
+        assert_eq!(
+            render(&app, 20)[11..17],
+            [
+                "│1 Job             https://github.com/octocat/hello/actions/runs/2/job/21                          │",
+                "│2 Run             https://github.com/octocat/hello/actions/runs/2                                 │",
+                "│3 Commit          https://github.com/octocat/hello/commit/c0ffee2000000000000000000000000000000000│",
+                "│4 Workflow file                                                                                   │",
+                "│https://github.com/octocat/hello/blob/c0ffee2000000000000000000000000000000000/.github/workflows/c│",
+                "│Log excerpt───────────────────────────────────────────────────────────────────────────────────────│",
+            ]
+        );
+
+        press(&mut app, &[AppEvent::Back]);
+        assert!(app.app_state.show_details && !app.app_state.show_links);
+    }
+
+    #[test]
+    fn details_show_the_commit_of_the_job() {
+        let mut app = app_with(concluded_runs());
+        press(
+            &mut app,
+            &[AppEvent::NavigateRight, AppEvent::ToggleDetails],
+        );
+        assert_eq!(
+            render(&app, 60)[53..57],
+            [
+                "│Commit: c0ffee1 by Mona Lisa  `Y` to   │                                                          │",
+                "│copy                                   │                                                          │",
+                "│Message: Change number 1               │                                                          │",
+                "│         With a longer description.    │                                                          │",
+            ]
+        );
+    }
+
+    #[test]
+    fn details_tell_how_long_ago_a_job_ran_or_when() {
+        let run = fixture::run(1, "completed", Some("success"));
+        let mut job = fixture::job(11, "build", &run, "10:00:00", Some(2));
+        let now = Utc::now();
+        job.started_at = now - TimeDelta::minutes(3);
+        job.completed_at = Some(now - TimeDelta::seconds(70));
+        let mut app = app_with(WorkflowData {
+            jobs: vec![job],
+            runs: vec![run],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        press(
+            &mut app,
+            &[AppEvent::NavigateRight, AppEvent::ToggleDetails],
+        );
+        let lines = render(&app, 40);
+        assert!(lines.iter().any(|line| line.contains("│Started: 3m ago ")));
+        assert!(lines.iter().any(|line| line.contains("│Finished: 1m ago ")));
+
+        press(&mut app, &[AppEvent::ToggleTimestamps]);
+        let started = (now - TimeDelta::minutes(3))
+            .with_timezone(&chrono::Local)
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, false);
+        let lines = render(&app, 40);
+        assert!(
+            lines
+                .iter()
+                .any(|line| line.contains(&format!("│Started: {}", started)))
+        );
+    }
+
+    #[test]
+    fn details_flag_steps_slower_than_usual() {
+        let runs: Vec<_> = (1..=4)
+            .map(|id| fixture::run(id, "completed", Some("success")))
+            .collect();
+        let mut app = app_with(WorkflowData {
+            jobs: vec![
+                fixture::job(11, "test", &runs[0], "10:00:00", Some(2)),
+                fixture::job(21, "test", &runs[1], "11:00:00", Some(3)),
+                fixture::job(31, "test", &runs[2], "12:00:00", Some(2)),
+                fixture::job(41, "test", &runs[3], "13:00:00", Some(7)),
+            ],
+            runs,
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        press(
+            &mut app,
+            &[AppEvent::NavigateRight, AppEvent::ToggleDetails],
+        );
+        assert_eq!(
+            render(&app, 30)[23..25],
+            [
+                "│Repo: octocat/hello                    │├─ ✓ Checkout  success  7m 00s  slow, usually 2m 00s      │",
+                "│Run ID: 4                              │└─ ✓ Test  success  7m 00s  slow, usually 2m 00s          │",
+            ]
+        );
+    }
+
+    #[test]
+    fn group_headers_sum_up_the_health_of_their_jobs() {
+        let running = fixture::run(3, "in_progress", None);
+        let mut unit = fixture::job(31, "test / unit", &running, "10:00:00", None);
+        unit.steps[1].status = "completed".to_string();
+        unit.steps[1].conclusion = Some("failure".to_string());
+        let lint = fixture::job(32, "test / lint", &running, "10:01:00", None);
+        let app = app_with(WorkflowData {
+            jobs: vec![unit, lint],
+            runs: vec![running],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        // A step of a running job already failed, so the whole group shows as failing
+        assert!(render(&app, 6)[2].starts_with("│── ✗ test ──"));
+    }
+
+    #[test]
+    fn matrix_jobs_are_collapsed_until_enter_expands_them() {
+        let run = fixture::run(4, "completed", Some("success"));
+        let ubuntu = fixture::job(41, "test (ubuntu)", &run, "10:00:00", Some(2));
+        let macos = fixture::job(42, "test (macos)", &run, "10:01:00", Some(3));
+        let mut windows = fixture::job(43, "test (windows)", &run, "10:02:00", Some(4));
+        windows.conclusion = Some("failure".to_string());
+        let mut app = app_with(WorkflowData {
+            jobs: vec![ubuntu, macos, windows],
+            runs: vec![run],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        press(&mut app, &[AppEvent::NavigateRight]);
+        assert_eq!(
+            render(&app, 8),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No jobs in this category.   ││── ✓ test ──                    ││── ✗ test ──                   │",
+                "│                               ││─                               ││─                              │",
+                "│                               ││1. ▸ test [2/3 passed]          ││1. test (windows) [completed   │",
+                "│                               ││  2 matrix jobs                 ││(failure)] 4m 00s              │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "←/→ columns  ↑/↓ rows  PgUp/PgDn page  Enter expand  Backspace open  S summary  R rerun  q quit",
+            ]
+        );
+
+        press(&mut app, &[AppEvent::ToggleDetails]);
+        assert!(!app.app_state.show_details);
+        assert_eq!(
+            render(&app, 16),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No jobs in this category.   ││── ✓ test ──                    ││── ✗ test ──                   │",
+                "│                               ││─                               ││─                              │",
+                "│                               ││1. ▾ test [2/3 passed]          ││1. test (windows) [completed   │",
+                "│                               ││  2 matrix jobs                 ││(failure)] 4m 00s              │",
+                "│                               ││  c0ffee4 Change number 4       ││  test (windows)               │",
+                "│                               ││  main by octocat               ││  c0ffee4 Change number 4      │",
+                "│                               ││                                ││  main by octocat · push       │",
+                "│                               ││  2. test (macos) [completed    ││                               │",
+                "│                               ││(success)] 3m 00s               ││                               │",
+                "│                               ││  test (macos)                  ││                               │",
+                "│                               ││  c0ffee4 Change number 4       ││                               │",
+                "│                               ││  main by octocat · push        ││                               │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "←/→ columns  ↑/↓ rows  PgUp/PgDn page  Enter collapse  Backspace open  S summary  R rerun  q quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn waiting_jobs_ask_to_confirm_their_approval() {
+        let mut waiting = fixture::run(5, "waiting", None);
+        waiting.name = "Release".to_string();
+        let mut job = fixture::job(51, "deploy", &waiting, "10:00:00", None);
+        // Stop the clock of the waiting job so its elapsed time doesn't change
+        job.completed_at = Some(job.started_at + chrono::TimeDelta::minutes(4));
+        let mut app = app_with(WorkflowData {
+            jobs: vec![job],
+            runs: vec![waiting],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        assert_eq!(
+            render(&app, 8),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (1)────────────────╮╭Concluded Success (0)───────────╮╭Concluded Failure (0)──────────╮",
+                "│── ⏸ deploy ──                 ││    No jobs in this category.   ││   No jobs in this category.   │",
+                "│─                              ││                                ││                               │",
+                "│1. deploy  ⏸ waiting approval  ││                                ││                               │",
+                "│4m 00s                         ││                                ││                               │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "←/→ columns  ↑/↓ rows  PgUp/PgDn page  Enter details  Backspace open  S summary  a approve  q quit",
+            ]
+        );
+
+        press(&mut app, &[AppEvent::ApproveDeployment]);
+        assert_eq!(
+            render(&app, 8),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (1)────╭────────────────────Approve deployment────────────────────╮ilure (0)──────────╮",
+                "│── ⏸ deploy ──     │ Approve the deployments Release run 5 on main waits for? │n this category.   │",
+                "│─                  │                       octocat/hello                      │                   │",
+                "│1. deploy  ⏸ waitin│                                                          │                   │",
+                "│4m 00s             ╰────── `y`/`Enter` to approve, `n`/`Esc` to cancel ───────╯                   │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn runs_still_going_are_cancelled_once_confirmed() {
+        let running = fixture::run(3, "in_progress", None);
+        let done = fixture::run(1, "completed", Some("success"));
+        let mut job = fixture::job(31, "test", &running, "10:00:00", None);
+        job.completed_at = Some(job.started_at + chrono::TimeDelta::minutes(4));
+        let mut app = app_with(WorkflowData {
+            jobs: vec![job, fixture::job(11, "build", &done, "09:00:00", Some(3))],
+            runs: vec![running, done],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        press(&mut app, &[AppEvent::CancelRun]);
+        assert_eq!(
+            render(&app, 8),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (1)────╭────────────────────────Cancel run────────────────────────╮ilure (0)──────────╮",
+                "│── ⟳ test ──       │                     Cancel CI run 3?                     │n this category.   │",
+                "│─                  │                       octocat/hello                      │                   │",
+                "│1. test [in_progres│                                                          │                   │",
+                "│  test             ╰── `y`/`Enter` to cancel the run, `n`/`Esc` to keep it ───╯                   │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "",
+            ]
+        );
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Char('y')))
+            .expect("key");
+        app.handle_events().expect("cancel result");
+        assert!(app.app_state.cancel_confirmation.is_none());
+        let toast = app.app_state.toasts.last().expect("toast");
+        assert_eq!(
+            toast.message,
+            "Cancelling CI run 3. It will show as cancelled after the next refresh."
+        );
+
+        // A run that completed has nothing left to cancel
+        press(&mut app, &[AppEvent::NavigateRight, AppEvent::CancelRun]);
+        assert!(app.app_state.cancel_confirmation.is_none());
+        let toast = app.app_state.toasts.last().expect("toast");
+        assert_eq!(toast.message, "CI run 1 already completed.");
+    }
+
+    #[test]
+    fn runs_or_older_runs_of_their_workflow_are_deleted_once_confirmed() {
+        let mut app = app_with(concluded_runs());
+        press(&mut app, &[AppEvent::NavigateRight, AppEvent::DeleteRun]);
+        assert_eq!(
+            render(&app, 9),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No jobs in this ╭────────────────────────Delete run────────────────────────╮                   │",
+                "│                   │                     Delete CI run 1?                     │                   │",
+                "│                   │                       octocat/hello                      │pleted (failure)]  │",
+                "│                   │   `o` to delete the older runs of the workflow instead   │                   │",
+                "│                   ╰─────── `y`/`Enter` to delete, `n`/`Esc` to cancel ───────╯                   │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "",
+            ]
+        );
+
+        for code in [
+            KeyCode::Char('o'),
+            KeyCode::Backspace,
+            KeyCode::Backspace,
+            KeyCode::Char('7'),
+        ] {
+            app.handle_key_event(KeyEvent::from(code)).expect("key");
+        }
+        assert_eq!(
+            render(&app, 9)[2..6],
+            [
+                "│   No jobs in this ╭────────────────────Delete older runs─────────────────────╮                   │",
+                "│                   │         Delete every run of CI older than 7 days?        │                   │",
+                "│                   │                       octocat/hello                      │pleted (failure)]  │",
+                "│                   │     Type the age in days, `o` to delete the run only     │                   │",
+            ]
+        );
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter))
+            .expect("key");
+        app.handle_events().expect("deletion result");
+        assert!(app.app_state.deletion_confirmation.is_none());
+        let toast = app.app_state.toasts.last().expect("toast");
+        assert_eq!(toast.level, ToastLevel::Error);
+        assert!(
+            toast
+                .message
+                .starts_with("Error deleting every run of CI older than 7 days:")
+        );
+    }
+
+    #[test]
+    fn commands_of_installed_gh_extensions_run_from_their_menu() {
+        let mut app = app_with_client(FixtureClient::new(concluded_runs()).with_extension(
+            "actions-cache",
+            "Linux-cargo-3f2a\t412 MB\tmain\nLinux-node-9c1\t38 MB\tmain\n",
+        ));
+        press(&mut app, &[AppEvent::OpenExtensions]);
+        app.handle_events().expect("extensions");
+        assert_eq!(
+            render(&app, 8)[2..6],
+            [
+                "│   No job╭────────────────────────────────gh extensions─────────────────────────────────╮         │",
+                "│         │ List the Actions caches of the repository  gh actions-cache list -R octocat/h│         │",
+                "│         │ List the Actions caches of the branch      gh actions-cache list -R octocat/h│ilure)]  │",
+                "│         ╰─────────────────────── `Enter` to run, `Esc` to close ───────────────────────╯         │",
+            ]
+        );
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Down))
+            .expect("key");
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter))
+            .expect("key");
+        app.handle_events().expect("extension output");
+        assert_eq!(
+            render(&app, 8)[1..7],
+            [
+                "╭In Progre╭────────────────────List the Actions caches of the branch─────────────────────╮─────────╮",
+                "│   No job│Linux-cargo-3f2a  412 MB  main                                                │         │",
+                "│         │Linux-node-9c1    38 MB   main                                                │         │",
+                "│         │                                                                              │ilure)]  │",
+                "│         │                                                                              │         │",
+                "╰─────────╰─────────────────── `Up`/`Down` to scroll, `Esc` to close ────────────────────╯─────────╯",
+            ]
+        );
+    }
+
+    #[test]
+    fn badges_of_the_workflows_are_for_the_branch_typed() {
+        let mut data = concluded_runs();
+        let mut release = fixture::run(3, "completed", Some("success"));
+        release.name = "Release".to_string();
+        release.path = ".github/workflows/release.yml".to_string();
+        data.runs.push(release);
+        let mut app = app_with(data);
+        press(&mut app, &[AppEvent::OpenBadges]);
+        app.handle_events().expect("workflows");
+        assert_eq!(
+            render(&app, 12)[2..11],
+            [
+                "│   No job╭───────────────────────────Badges of octocat/hello────────────────────────────╮         │",
+                "│         │Branch: main▏                                                                 │         │",
+                "│         │                                                                              │ilure)]  │",
+                "│         │CI                                                                            │         │",
+                "│         │[![CI](https://github.com/octocat/hello/actions/workflows/ci.yml/badge.svg?bra│         │",
+                "│         │Release                                                                       │r 2      │",
+                "│         │[![Release](https://github.com/octocat/hello/actions/workflows/release.yml/bad│sh       │",
+                "│         ╰───────────── `Enter` to copy, `Tab` to copy all, `Esc` to close ─────────────╯         │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+            ]
+        );
+
+        let badge = |app: &App, index: usize| {
+            let badges = app.app_state.badges.as_ref().expect("badge popup");
+            match &badges.workflows {
+                Some(Ok(workflows)) => badges.badge(&workflows[index]),
+                _ => panic!("the workflows are fetched"),
+            }
+        };
+        assert_eq!(
+            badge(&app, 0),
+            "[![CI](https://github.com/octocat/hello/actions/workflows/ci.yml/badge.svg?branch=main)]\
+             (https://github.com/octocat/hello/actions/workflows/ci.yml?query=branch%3Amain)"
+        );
+
+        for code in [KeyCode::Backspace; 4] {
+            app.handle_key_event(KeyEvent::from(code)).expect("key");
+        }
+        assert_eq!(
+            badge(&app, 1),
+            "[![Release](https://github.com/octocat/hello/actions/workflows/release.yml/badge.svg)]\
+             (https://github.com/octocat/hello/actions/workflows/release.yml)"
+        );
+    }
+
+    #[test]
+    fn failures_older_than_a_day_are_hidden_on_demand() {
+        let mut data = concluded_runs();
+        let recent = fixture::run(3, "completed", Some("failure"));
+        let mut job = fixture::job(31, "deploy", &recent, "10:00:00", Some(5));
+        job.completed_at = Some(Utc::now() - TimeDelta::hours(1));
+        job.started_at = job.completed_at.expect("completed") - TimeDelta::minutes(5);
+        data.jobs.push(job);
+        data.runs.insert(0, recent);
+        let mut app = app_with(data);
+        let failed_jobs = |app: &App| -> Vec<u64> {
+            app.app_state
+                .failure_jobs
+                .values()
+                .flatten()
+                .map(|row| app.job_details[row.job_index()].id)
+                .collect()
+        };
+        assert_eq!(failed_jobs(&app), [31, 21]);
+
+        press(&mut app, &[AppEvent::ToggleRecentOnly]);
+        assert_eq!(failed_jobs(&app), [31]);
+        app.app_state.toasts.clear();
+        assert_eq!(
+            render(&app, 6)[1],
+            "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure, last 24h (1)╮"
+        );
+
+        press(&mut app, &[AppEvent::ToggleRecentOnly]);
+        assert_eq!(failed_jobs(&app), [31, 21]);
+    }
+
+    #[test]
+    fn usage_view_sums_up_the_minutes_of_each_workflow() {
+        let mut data = concluded_runs();
+        let mut job = fixture::job(22, "test-windows", &data.runs[0], "10:05:00", Some(2));
+        job.labels = vec!["windows-latest".to_string()];
+        data.jobs.push(job);
+        let mut app = app_with(data);
+
+        press(&mut app, &[AppEvent::ToggleUsageView]);
+        app.handle_events().expect("billing result");
+        assert_eq!(
+            render(&app, 11)[..],
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭──────────────────────────────────Actions usage of octocat/hello──────────────────────────────────╮",
+                "│The billing of octocat isn't accessible, only the runs fetched are summed up.                     │",
+                "│13 min in the runs fetched: Linux 11 min, Windows 2 min (×2)                                      │",
+                "│                                                                                                  │",
+                "│Workflow                                                    Runs  Jobs  Minutes  Share  Avg run   │",
+                "│CI                                                          2     4     13       100%   5m 00s    │",
+                "│                                                                                                  │",
+                "│Minutes are rounded up per job as GitHub bills them, before the multiplier of the OS.             │",
+                "╰──────────────────────────────────────────────────────────────────────────────────────────────────╯",
+                "↑/↓ scroll  U close  q quit",
+            ]
+        );
+
+        press(&mut app, &[AppEvent::ToggleUsageView]);
+        assert!(app.app_state.views.state::<UsageViewState>().is_none());
+    }
+
+    #[test]
+    fn warnings_of_a_partial_fetch_show_in_the_status_line() {
+        let mut data = concluded_runs();
+        data.warnings = vec!["Failed to fetch the jobs of CI run 3: HTTP 502".to_string()];
+        let app = app_with(data);
+        assert_eq!(
+            render(&app, 2)[0],
+            "lazyactions octocat/hello | Data updated with a warning: Failed to fetch the jobs of CI run 3: HTTP"
+        );
+    }
+
+    #[test]
+    fn columns_without_jobs_say_so() {
+        let app = app_with(no_runs());
+        assert_eq!(
+            render(&app, 6),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (0)───────────╮╭Concluded Failure (0)──────────╮",
+                "│   No jobs in this category.   ││    No jobs in this category.   ││   No jobs in this category.   │",
+                "│                               ││                                ││                               │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "←/→ columns  ↑/↓ rows  PgUp/PgDn page  Enter details  Backspace open  S summary  R rerun  q quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn run_view_without_runs_says_so() {
+        let mut app = app_with(no_runs());
+        press(&mut app, &[AppEvent::ToggleRunView]);
+        assert_eq!(
+            render(&app, 6),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭Runs (0)─────────────────────────────────────────────╮╭Jobs (0)───────────────────────────────────╮",
+                "│                No runs fetched yet.                 ││      No jobs of this run are listed.      │",
+                "│                                                     ││                                           │",
+                "╰─────────────────────────────────────────────────────╯╰───────────────────────────────────────────╯",
+                "←/→ runs/jobs  ↑/↓ rows  Enter details  Backspace open  S summary  R rerun  F rerun failed  q quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn details_without_a_selected_job_say_so() {
+        let mut app = app_with(no_runs());
+        press(&mut app, &[AppEvent::ToggleDetails]);
+        assert_eq!(
+            render(&app, 14),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (0)───────────╮╭Concluded Failure (0)──────────╮",
+                "│   No jobs in this category.   ││    No jobs in this category.   ││   No jobs in this category.   │",
+                "│                               ││                                ││                               │",
+                "│                               ││                                ││                               │",
+                "│                               ││                                ││                               │",
+                "│                               ││                                ││                               │",
+                "│                               ││                                ││                               │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "╭Job Details───────────────────────────────────────────────────────────────────────────────────────╮",
+                "│           No job selected. Select a job in the main view before toggling detailed view.          │",
+                "│                                                                                                  │",
+                "╰──────────────────────────────────────────────────────────────────────────────────────────────────╯",
+                "↑/↓ steps  Enter close  # links  Backspace open  S summary  R rerun  F rerun failed  q quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn yank_menu_lists_what_can_be_copied() {
+        let mut app = app_with(concluded_runs());
+        press(&mut app, &[AppEvent::NavigateLeft, AppEvent::OpenYankMenu]);
+        app.handle_key_event(KeyEvent::from(KeyCode::Down))
+            .expect("key");
+        assert_eq!(
+            render(&app, 12),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No job╭─────────────────────────────────────Copy─────────────────────────────────────╮         │",
+                "│         │ u the job URL      https://github.com/octocat/hello/actions/runs/2/job/21    │         │",
+                "│         │ r the run ID       2                                                         │ilure)]  │",
+                "│         │ g the gh command   gh run view 2 --repo octocat/hello                        │         │",
+                "│         │ v the view link    https://github.com/octocat/hello/actions/workflows/ci.yml?│         │",
+                "│         │ d the download     gh run download 2 --repo octocat/hello                    │r 2      │",
+                "│         │ l the log download gh run view 2 --repo octocat/hello --log > run-2.log      │sh       │",
+                "│         ╰─────────────────── key or `Enter` to copy, `Esc` to close ───────────────────╯         │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "",
+            ]
+        );
+
+        // The link to the view narrows the Actions page down to the selected column
+        assert_eq!(
+            app.app_state
+                .yank_menu
+                .as_ref()
+                .map(|menu| menu.items[3].value.as_str()),
+            Some("https://github.com/octocat/hello/actions/workflows/ci.yml?query=is%3Afailure")
+        );
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Esc))
+            .expect("key");
+        assert!(app.app_state.yank_menu.is_none());
+    }
+
+    #[test]
+    fn json_inspector_shows_the_payload_of_the_job() {
+        let mut app = app_with(concluded_runs());
+        press(&mut app, &[AppEvent::InspectJson]);
+        app.handle_events().expect("raw json");
+        for code in [KeyCode::Down, KeyCode::Down] {
+            app.handle_key_event(KeyEvent::from(code)).expect("key");
+        }
+        assert_eq!(
+            render(&app, 10),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progre╭────────────────────────────────JSON of build─────────────────────────────────╮─────────╮",
+                "│   No job│  \"id\": 11,                                                                   │         │",
+                "│         │  \"name\": \"build\",                                                            │         │",
+                "│         │  \"workflow_name\": \"CI\",                                                      │ilure)]  │",
+                "│         │  \"run_id\": 1,                                                                │         │",
+                "│         │  \"repo\": \"octocat/hello\",                                                    │         │",
+                "│         │  \"run_url\": \"https://github.com/octocat/hello/actions/runs/1\",               │r 2      │",
+                "╰─────────╰─────────────────── `Up`/`Down` to scroll, `Esc` to close ───────── line 3/43 ╯─────────╯",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn third_column_remembers_where_each_list_was_left() {
+        let failed = fixture::run(2, "completed", Some("failure"));
+        let cancelled = fixture::run(3, "completed", Some("cancelled"));
+        let mut app = app_with(WorkflowData {
+            jobs: vec![
+                fixture::job(21, "test", &failed, "10:05:00", Some(7)),
+                fixture::job(22, "lint", &failed, "10:06:00", Some(2)),
+                fixture::job(31, "deploy", &cancelled, "10:07:00", Some(1)),
+            ],
+            runs: vec![cancelled, failed],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        press(
+            &mut app,
+            &[
+                // Left from the first column wraps around to the third
+                AppEvent::NavigateLeft,
+                AppEvent::NavigateDown,
+                AppEvent::ToggleOtherJobs,
+            ],
+        );
+        assert_eq!(app.job_details[app.current_job_index].id, 31);
+        press(&mut app, &[AppEvent::ToggleOtherJobs]);
+        assert_eq!(app.app_state.position().row_index, 1);
+        assert_eq!(app.job_details[app.current_job_index].name, "test");
+    }
+
+    #[test]
+    fn earlier_refreshes_can_be_looked_at_again() {
+        let cancelled = fixture::run(2, "completed", Some("cancelled"));
+        let mut app = app_with(WorkflowData {
+            jobs: vec![fixture::job(21, "test", &cancelled, "10:05:00", Some(7))],
+            runs: vec![cancelled],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        let failed = fixture::run(2, "completed", Some("failure"));
+        app.update_github_data(WorkflowData {
+            jobs: vec![fixture::job(21, "test", &failed, "10:05:00", Some(7))],
+            runs: vec![failed],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        assert!(
+            app.app_state
+                .failure_jobs
+                .contains_key(&JobGroup::Tool("test".to_string()))
+        );
+
+        press(&mut app, &[AppEvent::PreviousRefresh]);
+        assert!(
+            app.app_state
+                .other_jobs
+                .contains_key(&JobGroup::Tool("test".to_string()))
+        );
+        assert!(app.app_state.failure_jobs.is_empty());
+        assert!(render(&app, 3)[0].ends_with(", 1 back"));
+
+        press(&mut app, &[AppEvent::Back]);
+        assert_eq!(app.app_state.time_travel, None);
+        assert!(
+            app.app_state
+                .failure_jobs
+                .contains_key(&JobGroup::Tool("test".to_string()))
+        );
+    }
+
+    #[test]
+    fn pinned_jobs_stay_on_top_and_tell_when_their_status_changes() {
+        let mut app = app_with(concluded_runs());
+        press(
+            &mut app,
+            &[
+                AppEvent::NavigateRight,
+                AppEvent::NavigateDown,
+                AppEvent::TogglePin,
+            ],
+        );
+        assert_eq!(
+            app.app_state.success_jobs.keys().next(),
+            Some(&JobGroup::Pinned)
+        );
+        assert!(render(&app, 6)[2].contains("│── ✓ pinned ──"));
+
+        // The run of the pinned job is no longer fetched, but the job stays listed
+        let failed = fixture::run(2, "completed", Some("failure"));
+        app.update_github_data(WorkflowData {
+            jobs: vec![fixture::job(21, "test", &failed, "10:05:00", Some(7))],
+            runs: vec![failed],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        assert!(app.job_details.iter().any(|job| job.name == "lint"));
+
+        let rerun = fixture::run(1, "completed", Some("failure"));
+        app.update_github_data(WorkflowData {
+            jobs: vec![fixture::job(12, "lint", &rerun, "10:01:00", Some(1))],
+            runs: vec![rerun],
+            unchanged_runs: vec![2],
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        assert_eq!(
+            app.app_state.failure_jobs.keys().next(),
+            Some(&JobGroup::Pinned)
+        );
+        assert_eq!(
+            app.app_state
+                .toasts
+                .last()
+                .map(|toast| toast.message.as_str()),
+            Some("lint went from success to failure.")
+        );
+    }
+
+    #[test]
+    fn failures_of_the_default_branch_are_listed_first() {
+        let mut feature = fixture::run(2, "completed", Some("failure"));
+        feature.head_branch = "feature".to_string();
+        let main = fixture::run(3, "completed", Some("failure"));
+        let app = app_with(WorkflowData {
+            jobs: vec![
+                fixture::job(21, "test", &feature, "10:10:00", Some(7)),
+                fixture::job(31, "test", &main, "10:05:00", Some(7)),
+            ],
+            runs: vec![feature, main],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        // The earlier failure on main comes before the later one on a feature branch
+        let lines = render(&app, 11);
+        assert!(lines[7].ends_with("│  c0ffee3 Change number 3      │"));
+        assert!(lines[8].ends_with("│  main by octocat · default    │"));
+    }
+
+    #[test]
+    fn selection_follows_its_job_into_view_after_a_refresh() {
+        let mut app = app_with(concluded_runs());
+        press(&mut app, &[AppEvent::NavigateRight, AppEvent::NavigateDown]);
+        assert_eq!(app.job_details[app.current_job_index].name, "lint");
+
+        // A job sorting above the selected one pushes it down, below the screen
+        let checked = fixture::run(3, "completed", Some("success"));
+        app.update_github_data(WorkflowData {
+            jobs: vec![fixture::job(31, "check", &checked, "10:10:00", Some(2))],
+            runs: vec![checked],
+            unchanged_runs: vec![1, 2],
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        assert_eq!(app.app_state.position().row_index, 2);
+        assert_eq!(app.job_details[app.current_job_index].name, "lint");
+        assert!(
+            render(&app, 10)
+                .iter()
+                .any(|line| line.contains("3. lint [completed (success)]"))
+        );
+    }
+
+    #[test]
+    fn new_runs_of_the_current_branch_are_followed() {
+        let mut app = app_with(concluded_runs());
+        app.config.follow_new_runs = true;
+        press(&mut app, &[AppEvent::NavigateRight, AppEvent::NavigateDown]);
+
+        let mut elsewhere = fixture::run(3, "in_progress", None);
+        elsewhere.head_branch = "feature".to_string();
+        let pushed = fixture::run(4, "in_progress", None);
+        app.update_github_data(WorkflowData {
+            jobs: vec![
+                fixture::job(31, "build", &elsewhere, "10:10:00", None),
+                fixture::job(41, "test", &pushed, "10:11:00", None),
+            ],
+            runs: vec![pushed, elsewhere],
+            unchanged_runs: vec![1, 2],
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        assert_eq!(app.app_state.column_index, 0);
+        assert_eq!(app.job_details[app.current_job_index].id, 41);
+        assert_eq!(
+            app.app_state
+                .toasts
+                .last()
+                .map(|toast| toast.message.as_str()),
+            Some("Following the new CI run on main.")
+        );
+    }
+
+    #[test]
+    fn huge_logs_show_their_end_until_loaded_in_full() {
+        let mut log = "##[error]An early error\n".to_string();
+        while log.len() < 3 * 1024 * 1024 {
+            log.push_str("Compiling a crate with very verbose output\n");
+        }
+        log.push_str("##[error]Process completed with exit code 1.\n");
+        let mut app = app_with_client(FixtureClient::new(concluded_runs()).with_log(21, log));
+        press(&mut app, &[AppEvent::NavigateLeft, AppEvent::ToggleDetails]);
+        // The job's summary and environment are fetched along with its log
+        while app.app_state.failure_excerpts[&21].is_none() {
+            app.handle_events().expect("log");
+        }
+        let lines = render(&app, 16);
+        assert!(
+            lines
+                .iter()
+                .any(|line| line.contains("Log excerpt (last 2.0 MB of 3.0 MB)"))
+        );
+        assert!(
+            lines
+                .last()
+                .is_some_and(|hints| hints.contains("f full log"))
+        );
+
+        press(&mut app, &[AppEvent::LoadFullLog]);
+        assert!(
+            render(&app, 16)
+                .iter()
+                .any(|line| line.contains("Load the full 3.0 MB log of test?"))
+        );
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter))
+            .expect("confirm");
+        while !app.app_state.partial_logs.is_empty() {
+            app.handle_events().expect("full log");
+        }
+        assert_eq!(
+            app.app_state.failure_excerpts[&21]
+                .as_ref()
+                .and_then(|excerpt| excerpt.as_ref().ok())
+                .map(|excerpt| excerpt[0].as_str()),
+            Some("##[error]An early error")
+        );
+    }
+
+    #[test]
+    fn searching_compared_logs_jumps_between_the_matches() {
+        let client = FixtureClient::new(concluded_runs())
+            .with_log(11, "Compiling\nerror: flaky network\nFinished\n")
+            .with_log(
+                21,
+                "Compiling\nRunning 3 tests\nERROR: assertion failed\nerror: 1 test failed\n",
             );
-            let paragraph = Paragraph::new(no_job_selected_text)
-                .alignment(Alignment::Center)
-                .wrap(Wrap { trim: false });
-            paragraph.render(inner_area, buf);
+        let mut app = app_with_client(client);
+        press(
+            &mut app,
+            &[
+                AppEvent::NavigateLeft,
+                AppEvent::CompareLogs,
+                AppEvent::NavigateRight,
+                AppEvent::CompareLogs,
+            ],
+        );
+        while app
+            .app_state
+            .views
+            .state::<LogCompareState>()
+            .is_some_and(|compare| compare.panes.iter().any(|pane| pane.lines.is_none()))
+        {
+            app.handle_events().expect("logs");
+        }
+        press(&mut app, &[AppEvent::OpenFilter]);
+        for c in "error".chars() {
+            app.handle_key_event(KeyEvent::from(KeyCode::Char(c)))
+                .expect("search");
         }
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter))
+            .expect("submit");
+        press(&mut app, &[AppEvent::NextMatch, AppEvent::NextMatch]);
+        assert_eq!(
+            render(&app, 12)[..],
+            [
+                "lazyactions octocat/hello | Marked test for comparison, pick another job and press `c` again. | refr",
+                "Checking whether the workflow file changed...",
+                "╭─────────────────test (failure)─────────────────╮╭────────────────build (success)─────────────────╮",
+                "│Compiling                                       ││Compiling                                       │",
+                "│Running 3 tests                                 ││error: flaky network                            │",
+                "│ERROR: assertion failed                         ││Finished                                        │",
+                "│error: 1 test failed                            ││                                                │",
+                "│                                                ││                                                │",
+                "│                                                ││                                                │",
+                "╰ match 3/3 ─────────────────────────── line 1/4 ╯╰ 1 match ───────────────────────────── line 1/3 ╯",
+                "/error  match 3/3  `n`/`N` next/previous match, `/` to edit, `Esc` to clear",
+                "↑/↓ scroll  PgUp/PgDn page  / search  n/N matches  Esc close  q quit",
+            ]
+        );
+
+        // Back clears the search, then closes the comparison
+        press(&mut app, &[AppEvent::Back]);
+        assert!(
+            app.app_state
+                .views
+                .state::<LogCompareState>()
+                .is_some_and(|compare| compare.search.is_none())
+        );
     }
 
-    /// Returns the original index into `self.job_details` for the currently
-    /// selected job in the UI, or None if no job is selected or the index is out of bounds.
-    pub fn get_selected_job_original_index(&self) -> Option<usize> {
-        let (job_indices_for_current_column, _) = self.get_current_column_data();
+    #[test]
+    fn compared_logs_fold_their_groups_but_those_with_errors() {
+        let client = FixtureClient::new(concluded_runs())
+            .with_log(
+                11,
+                "##[group]Run actions/checkout@v4\nwith:\n  fetch-depth: 1\n##[endgroup]\n\
+                 ##[group]Run cargo build\ncargo build --locked\n##[endgroup]\n\
+                 \x1b[1;32m   Compiling\x1b[0m lazyactions\n",
+            )
+            .with_log(
+                21,
+                "##[group]Run actions/checkout@v4\nwith:\n  fetch-depth: 1\n##[endgroup]\n\
+                 ##[group]Run cargo test\n##[warning]Test `flaky` is ignored\n\
+                 ##[error]Process completed with exit code 101.\n",
+            );
+        let mut app = app_with_client(client);
+        press(
+            &mut app,
+            &[
+                AppEvent::NavigateLeft,
+                AppEvent::CompareLogs,
+                AppEvent::NavigateRight,
+                AppEvent::CompareLogs,
+            ],
+        );
+        while app
+            .app_state
+            .views
+            .state::<LogCompareState>()
+            .is_some_and(|compare| compare.panes.iter().any(|pane| pane.lines.is_none()))
+        {
+            app.handle_events().expect("logs");
+        }
+        assert_eq!(
+            render(&app, 10)[..],
+            [
+                "lazyactions octocat/hello | Marked test for comparison, pick another job and press `c` again. | refr",
+                "Checking whether the workflow file changed...",
+                "╭─────────────────test (failure)─────────────────╮╭────────────────build (success)─────────────────╮",
+                "│▸ Run actions/checkout@v4 (2 lines)             ││▸ Run actions/checkout@v4 (2 lines)             │",
+                "│▾ Run cargo test                                ││▸ Run cargo build (1 line)                      │",
+                "│Test `flaky` is ignored                         ││   Compiling lazyactions                        │",
+                "│Process completed with exit code 101.           ││                                                │",
+                "╰────────────────────────────────────── line 1/7 ╯╰────────────────────────────────────── line 1/8 ╯",
+                "`Up`/`Down` and `PageUp`/`PageDown` scroll both logs, highlighted lines are only in one of them, `En",
+                "↑/↓ scroll  PgUp/PgDn page  Enter groups  / search  Esc close  q quit",
+            ]
+        );
 
-        if job_indices_for_current_column.is_empty() {
-            return None;
+        press(&mut app, &[AppEvent::ToggleDetails]);
+        assert_eq!(
+            render(&app, 10)[..],
+            [
+                "lazyactions octocat/hello | Marked test for comparison, pick another job and press `c` again. | refr",
+                "Checking whether the workflow file changed...",
+                "╭─────────────────test (failure)─────────────────╮╭────────────────build (success)─────────────────╮",
+                "│▾ Run actions/checkout@v4                       ││▾ Run actions/checkout@v4                       │",
+                "│with:                                           ││with:                                           │",
+                "│  fetch-depth: 1                                ││  fetch-depth: 1                                │",
+                "│▾ Run cargo test                                ││▾ Run cargo build                               │",
+                "╰────────────────────────────────────── line 1/7 ╯╰────────────────────────────────────── line 1/8 ╯",
+                "`Up`/`Down` and `PageUp`/`PageDown` scroll both logs, highlighted lines are only in one of them, `En",
+                "↑/↓ scroll  PgUp/PgDn page  Enter groups  / search  Esc close  q quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn path_terms_of_the_filter_match_the_files_changed_by_the_commits() {
+        let client = FixtureClient::new(concluded_runs())
+            .with_changed_paths(&format!("{:0<40}", "c0ffee1"), &["docs/usage.md"])
+            .with_changed_paths(
+                &format!("{:0<40}", "c0ffee2"),
+                &["src/main.rs", "docs/usage.md"],
+            );
+        let mut app = app_with_client(client);
+        press(&mut app, &[AppEvent::OpenFilter]);
+        for c in "path:src".chars() {
+            app.handle_key_event(KeyEvent::from(KeyCode::Char(c)))
+                .expect("filter");
+        }
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter))
+            .expect("apply");
+        assert!(
+            render(&app, 10)
+                .iter()
+                .any(|line| line.contains("fetching the files changed by 2 commits..."))
+        );
+        while app.app_state.changed_paths.values().any(Option::is_none) {
+            app.handle_events().expect("changed paths");
         }
+        assert_eq!(
+            render(&app, 10)[..],
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (0)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No jobs in this category.   ││    No jobs in this category.   ││── ✗ test ──                   │",
+                "│                               ││                                ││─                              │",
+                "│                               ││                                ││1. test [completed (failure)]  │",
+                "│                               ││                                ││7m 00s                         │",
+                "│                               ││                                ││  test                         │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "/path:src  `/` to edit, `Esc` to clear",
+                "←/→ columns  ↑/↓ rows  PgUp/PgDn page  Enter details  Backspace open  S summary  R rerun  q quit",
+            ]
+        );
 
-        let mut visual_job_counter = 0;
-        for (_tool_name, indices_in_group) in job_indices_for_current_column.iter() {
-            for &original_job_idx in indices_in_group {
-                if visual_job_counter == self.app_state.row_index {
-                    return Some(original_job_idx);
+        press(&mut app, &[AppEvent::OpenFilter]);
+        app.handle_key_event(KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL))
+            .expect("clear");
+        for c in "path:docs/*.md".chars() {
+            app.handle_key_event(KeyEvent::from(KeyCode::Char(c)))
+                .expect("filter");
+        }
+        assert_eq!(
+            render(&app, 10)[..],
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No jobs in this category.   ││── ✓ build ──                   ││── ✗ test ──                   │",
+                "│                               ││─                               ││─                              │",
+                "│                               ││1. build [completed (success)]  ││1. test [completed (failure)]  │",
+                "│                               ││3m 00s                          ││7m 00s                         │",
+                "│                               ││  build                         ││  test                         │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "/path:docs/*.md▏  matches name, workflow, branch or actor, `path:` changed files; `Enter` to apply,",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn sorting_reorders_the_jobs_within_their_group() {
+        let passed = fixture::run(1, "completed", Some("success"));
+        let mut app = app_with(WorkflowData {
+            jobs: vec![
+                fixture::job(11, "checks / build", &passed, "10:00:00", Some(3)),
+                fixture::job(12, "checks / lint", &passed, "10:01:00", Some(1)),
+                fixture::job(13, "checks / docs", &passed, "09:55:00", Some(10)),
+            ],
+            runs: vec![passed],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        press(&mut app, &[AppEvent::CycleSort]);
+        assert_eq!(
+            render(&app, 14)[..],
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (3)───────────╮╭Concluded Failure (0)──────────╮",
+                "│   No jobs in this category.   ││── ✓ checks ──                  ││   No jobs in this category.   │",
+                "│                               ││─                               ││                               │",
+                "│                               ││1. docs [completed (success)]   ││                               │",
+                "│                               ││10m 00s                         ││                               │",
+                "│                               ││  checks / docs                 ││                               │",
+                "│                               ││  c0ffee1 Change number 1       ││                               │",
+                "│                               ││  main by octocat · push        ││                               │",
+                "│                               ││                                ││                               │",
+                "│                               ││2. build [completed (success)]  ││    ╭─────────────────────────╮│",
+                "│                               ││3m 00s                          ││    │Jobs listed longest first││",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰────╰─────────────────────────╯╯",
+                "←/→ columns  ↑/↓ rows  PgUp/PgDn page  Enter details  Backspace open  S summary  R rerun  q quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn annotations_of_failed_jobs_point_at_their_lines() {
+        let annotation = |path: &str, line: u64, level: &str, message: &str| Annotation {
+            repo: fixture::REPO.to_string(),
+            head_sha: "c0ffee".to_string(),
+            path: path.to_string(),
+            start_line: line,
+            end_line: line,
+            level: level.to_string(),
+            title: None,
+            message: message.to_string(),
+            source: None,
+        };
+        let coverage = Annotation {
+            source: Some("Codecov".to_string()),
+            ..annotation(
+                "src/api.rs",
+                3,
+                "warning",
+                "Added line not covered by tests",
+            )
+        };
+        let data = concluded_runs();
+        let head_sha = data.jobs[2].head_sha.clone();
+        let client = FixtureClient::new(data)
+            .with_annotations(
+                21,
+                vec![
+                    annotation("src/lib.rs", 42, "failure", "mismatched types"),
+                    annotation("src/main.rs", 7, "warning", "unused variable: `x`"),
+                ],
+            )
+            .with_check_annotations(&head_sha, vec![coverage]);
+        let mut app = app_with_client(client);
+        press(&mut app, &[AppEvent::NavigateLeft, AppEvent::ToggleDetails]);
+        while app.app_state.annotations[&21].is_none()
+            || app
+                .app_state
+                .check_annotations
+                .values()
+                .any(Option::is_none)
+        {
+            app.handle_events().expect("annotations");
+        }
+        let lines = render(&app, 40);
+        assert!(lines.iter().any(|line| line.contains("Annotations (3)")));
+        assert!(
+            lines
+                .iter()
+                .any(|line| line.contains("› src/lib.rs:42 mismatched types"))
+        );
+        assert!(lines.iter().any(|line| {
+            line.contains("[Codecov] src/api.rs:3 Added line not covered by tests")
+        }));
+        assert!(
+            lines
+                .last()
+                .is_some_and(|hints| hints.contains("A open annotation"))
+        );
+
+        press(&mut app, &[AppEvent::NavigateRight]);
+        assert_eq!(
+            app.selected_annotations()[app.app_state.selected_annotation].html_url(),
+            "https://github.com/octocat/hello/blob/c0ffee/src/main.rs#L7"
+        );
+    }
+
+    #[test]
+    fn annotations_of_other_apps_are_fetched_once_per_commit_even_if_no_job_failed() {
+        let data = concluded_runs();
+        let head_sha = data.jobs[0].head_sha.clone();
+        let coverage = Annotation {
+            repo: fixture::REPO.to_string(),
+            head_sha: head_sha.clone(),
+            path: "src/api.rs".to_string(),
+            start_line: 3,
+            end_line: 3,
+            level: "warning".to_string(),
+            title: None,
+            message: "Added line not covered by tests".to_string(),
+            source: Some("Codecov".to_string()),
+        };
+        let client = FixtureClient::new(data)
+            .with_check_annotations(&head_sha, vec![coverage])
+            .with_check_error(
+                &head_sha,
+                "Error fetching the annotations of SonarCloud: 502",
+            );
+        let mut app = app_with_client(client);
+        let commit = (fixture::REPO.to_string(), head_sha);
+        press(&mut app, &[AppEvent::NavigateRight]);
+        for job_id in [11, 12] {
+            if job_id == 12 {
+                press(&mut app, &[AppEvent::NavigateDown]);
+            }
+            press(&mut app, &[AppEvent::ToggleDetails]);
+            assert_eq!(app.job_details[app.current_job_index].id, job_id);
+            // The second job of the commit finds them fetched already
+            if job_id == 11 {
+                while app.app_state.check_annotations[&commit].is_none() {
+                    app.handle_events().expect("check annotations");
                 }
-                visual_job_counter += 1;
             }
+            assert!(app.app_state.check_annotations[&commit].is_some());
+            let lines = render(&app, 60);
+            assert!(lines.iter().any(|line| line.contains("Annotations (1)")));
+            assert!(lines.iter().any(|line| {
+                line.contains("› [Codecov] src/api.rs:3 Added line not covered by tests")
+            }));
+            assert!(lines.iter().any(|line| {
+                line.contains("Error fetching the annotations of SonarCloud: 502")
+            }));
+            press(&mut app, &[AppEvent::ToggleDetails]);
         }
-        None // No job found at the current row_index
+        assert_eq!(app.app_state.check_annotations.len(), 1);
     }
 
-    /// Helper to get the job indices and color for the currently selected column.
-    /// This avoids duplicating logic in get_selected_job_original_index and render_job_list_column.
-    fn get_current_column_data(&self) -> (&BTreeMap<String, Vec<usize>>, Color) {
-        match self.app_state.column_index {
-            0 => (&self.app_state.in_progress_jobs, Color::Yellow),
-            1 => (&self.app_state.success_jobs, Color::Green),
-            2 => (&self.app_state.failure_jobs, Color::Red),
-            _ => (&self.app_state.in_progress_jobs, Color::White), // Should not happen
+    #[test]
+    fn logs_and_artifacts_about_to_expire_get_a_warning() {
+        let mut data = concluded_runs();
+        let now = Utc::now();
+        // GitHub keeps them 90 days by default, leaving this job's log 2 days
+        data.jobs[2].started_at = now - TimeDelta::days(88) + TimeDelta::hours(1);
+        data.jobs[2].completed_at = Some(now - TimeDelta::days(88) + TimeDelta::hours(2));
+        let artifact = |name: &str, expires_at: DateTime<Utc>, expired: bool| Artifact {
+            name: name.to_string(),
+            size_in_bytes: 3 * 1024 * 1024,
+            expires_at: Some(expires_at),
+            expired,
+        };
+        let client = FixtureClient::new(data).with_artifacts(
+            2,
+            vec![
+                artifact(
+                    "coverage",
+                    now + TimeDelta::days(5) + TimeDelta::hours(1),
+                    false,
+                ),
+                artifact("screenshots", now - TimeDelta::days(1), true),
+            ],
+        );
+        let mut app = app_with_client(client);
+        let lines = render(&app, 30);
+        assert!(lines.iter().any(|line| line.contains("logs expire in 2d")));
+
+        press(&mut app, &[AppEvent::NavigateLeft, AppEvent::ToggleDetails]);
+        while app.app_state.artifacts[&2].is_none() {
+            app.handle_events().expect("artifacts");
+        }
+        let lines = render(&app, 44);
+        assert!(
+            lines
+                .iter()
+                .any(|line| line.contains("│Log: expires in 2d"))
+        );
+        assert!(
+            lines
+                .iter()
+                .any(|line| line.contains("│Artifacts: coverage (3.0 MB) expires in"))
+        );
+    }
+
+    #[test]
+    fn focus_indicators_mark_the_selected_job_and_the_focused_column() {
+        let mut app = app_with(concluded_runs());
+        app.config.theme.focus = FocusConfig {
+            reversed: false,
+            marker: true,
+            underline: true,
+            bold_border: true,
+        };
+        press(&mut app, &[AppEvent::NavigateLeft]);
+        assert_eq!(
+            render(&app, 8)[1..7],
+            [
+                "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮┏Concluded Failure (1)━━━━━━━━━━┓",
+                "│   No jobs in this category.   ││── ✓ build ──                   │┃── ✗ test ──                   ┃",
+                "│                               ││─                               │┃─                              ┃",
+                "│                               ││1. build [completed (success)]  │┃▶ 1. test [completed (failure)]┃",
+                "│                               ││3m 00s                          │┃7m 00s                         ┃",
+                "╰───────────────────────────────╯╰────────────────────────────────╯┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛",
+            ]
+        );
+    }
+
+    #[test]
+    fn toasts_stack_in_the_corner_until_they_expire() {
+        let mut app = app_with(concluded_runs());
+        press(&mut app, &[AppEvent::CycleSort, AppEvent::CycleTheme]);
+        assert_eq!(
+            app.app_state
+                .toasts
+                .iter()
+                .map(|toast| toast.level)
+                .collect::<Vec<_>>(),
+            [ToastLevel::Info, ToastLevel::Info]
+        );
+        assert_eq!(
+            render(&app, 10)[2..9],
+            [
+                "│   No jobs in this category.   ││── ✓ build ──                   ││── ✗ test ──                   │",
+                "│                               ││─                               ││─   ╭─────────────────────────╮│",
+                "│                               ││1. build [completed (success)]  ││1. t│Jobs listed longest first││",
+                "│                               ││3m 00s                          ││7m 0╰─────────────────────────╯│",
+                "│                               ││  build                         ││  test           ╭────────────╮│",
+                "│                               ││  c0ffee1 Change number 1       ││  c0ffee2 Change │Theme: light││",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰─────────────────╰────────────╯╯",
+            ]
+        );
+
+        app.app_state.toasts[0].expires_at = std::time::Instant::now();
+        app.tick();
+        assert_eq!(
+            render(&app, 10)[2..9],
+            [
+                "│   No jobs in this category.   ││── ✓ build ──                   ││── ✗ test ──                   │",
+                "│                               ││─                               ││─                              │",
+                "│                               ││1. build [completed (success)]  ││1. test [completed (failure)]  │",
+                "│                               ││3m 00s                          ││7m 00s                         │",
+                "│                               ││  build                         ││  test           ╭────────────╮│",
+                "│                               ││  c0ffee1 Change number 1       ││  c0ffee2 Change │Theme: light││",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰─────────────────╰────────────╯╯",
+            ]
+        );
+    }
+
+    #[test]
+    fn title_line_tells_when_the_runs_were_refreshed() {
+        let mut app = app_with(concluded_runs());
+        app.app_state.updated_at = Some(Utc::now() - TimeDelta::seconds(90));
+        assert_eq!(
+            render(&app, 3)[0],
+            "lazyactions octocat/hello | Data updated. | refreshed 1m ago"
+        );
+    }
+
+    #[test]
+    fn workflow_history_lists_the_runs_of_the_workflow() {
+        let mut app = app_with(concluded_runs());
+        press(
+            &mut app,
+            &[AppEvent::NavigateLeft, AppEvent::ToggleWorkflowHistory],
+        );
+        while app
+            .app_state
+            .views
+            .state::<WorkflowHistoryState>()
+            .is_some_and(|history| history.loading)
+        {
+            app.handle_events().expect("history");
+        }
+        assert_eq!(
+            render(&app, 8),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭───────────────────────────────────History of CI (2 of 2 runs)────────────────────────────────────╮",
+                "│  Run     Conclusion  Took     Branch               Trigger      Commit                       Age │",
+                "│✗ #2      failure     -        main                 push         Change number 2              -   │",
+                "│✓ #1      success     -        main                 push         Change number 1              -   │",
+                "│1 of the 2 runs listed failed.                                                                    │",
+                "╰──────────────────────────────────────────────────────────────────────────────────────────────────╯",
+                "↑/↓ runs  Backspace open run  H close  q quit",
+            ]
+        );
+
+        press(&mut app, &[AppEvent::Back]);
+        assert!(
+            app.app_state
+                .views
+                .state::<WorkflowHistoryState>()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn backfilled_runs_tell_how_a_workflow_fared() {
+        let mut app = app_with(concluded_runs());
+        press(&mut app, &[AppEvent::ToggleBackfill]);
+        app.app_state.toasts.clear();
+        assert_eq!(
+            render(&app, 4)[0],
+            "lazyactions octocat/hello | Data updated. | refreshed just now | backfilling history..."
+        );
+
+        // Every run of the fixture fits on the first page
+        app.handle_events().expect("backfill");
+        assert!(app.app_state.backfill.is_none());
+        assert_eq!(
+            app.app_state
+                .toasts
+                .last()
+                .map(|toast| toast.message.as_str()),
+            Some("Backfilled the history of octocat/hello: 2 runs.")
+        );
+
+        press(
+            &mut app,
+            &[AppEvent::NavigateLeft, AppEvent::ToggleWorkflowHistory],
+        );
+        app.handle_events().expect("history");
+        app.app_state.toasts.clear();
+        assert_eq!(
+            render(&app, 8)[5..6],
+            [
+                "│1 of the 2 runs listed failed. 50% of the 2 runs backfilled passed.                               │",
+            ]
+        );
+    }
+
+    #[test]
+    fn tests_failing_between_passes_are_listed_as_flaky() {
+        let runs: Vec<_> = [3, 2, 1]
+            .map(|id| fixture::run(id, "completed", Some("success")))
+            .into();
+        let report = |outcome: &str| {
+            format!(
+                r#"<testsuite><testcase classname="api" name="logs_in"/><testcase classname="api" name="logs_out">{}</testcase></testsuite>"#,
+                outcome
+            )
+        };
+        let client = FixtureClient::new(WorkflowData {
+            jobs: vec![fixture::job(31, "test", &runs[0], "10:00:00", Some(2))],
+            runs,
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        })
+        .with_test_report(3, report(""))
+        .with_test_report(2, report("<failure/>"))
+        .with_test_report(1, report(""));
+        let mut app = app_with_client(client);
+        press(
+            &mut app,
+            &[AppEvent::NavigateRight, AppEvent::ToggleFlakyTests],
+        );
+        app.handle_events().expect("scan");
+        assert_eq!(
+            render(&app, 7),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭───────────────────────────────────Suspected flaky tests of CI────────────────────────────────────╮",
+                "│Test                                                       Failures Last seen                     │",
+                "│api::logs_out                                              1        #2 2026-01-05 10:10           │",
+                "│1 test failed between passes in the 3 runs with test reports.                                     │",
+                "╰──────────────────────────────────────────────────────────────────────────────────────────────────╯",
+                "↑/↓ tests  Backspace open last seen  e export  Q close  q quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn muted_workflows_fail_quietly_until_a_newer_commit_runs() {
+        let mut app = app_with(concluded_runs());
+        press(
+            &mut app,
+            &[AppEvent::NavigateLeft, AppEvent::ToggleWorkflowMute],
+        );
+        app.app_state.toasts.clear();
+        assert_eq!(
+            render(&app, 4),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now | 1 workflow muted",
+                "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure (0)──────────╮",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "←/→ columns  ↑/↓ rows  PgUp/PgDn page  Enter details  Backspace open  S summary  R rerun  q quit",
+            ]
+        );
+
+        let fixed = fixture::run(3, "in_progress", None);
+        let mut job = fixture::job(31, "test", &fixed, "10:10:00", None);
+        job.started_at = chrono::Utc::now() + chrono::TimeDelta::seconds(1);
+        app.update_github_data(WorkflowData {
+            jobs: vec![job],
+            runs: vec![fixed],
+            unchanged_runs: vec![1, 2],
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        assert!(app.workflow_mutes.entries.is_empty());
+        assert_eq!(app.app_state.failure_jobs.len(), 1);
+    }
+
+    #[test]
+    fn picking_a_branch_shows_only_its_jobs() {
+        let main = fixture::run(1, "completed", Some("success"));
+        let mut feature = fixture::run(2, "completed", Some("failure"));
+        feature.head_branch = "feature/x".to_string();
+        let mut app = app_with(WorkflowData {
+            jobs: vec![
+                fixture::job(11, "build", &main, "10:00:00", Some(3)),
+                fixture::job(21, "test", &feature, "10:05:00", Some(7)),
+            ],
+            runs: vec![feature, main],
+            unchanged_runs: Vec::new(),
+            warnings: Vec::new(),
+            fetched_runs: HashMap::new(),
+        });
+        press(&mut app, &[AppEvent::OpenBranchPicker]);
+        app.handle_events().expect("branches");
+        for code in "feat".chars().map(KeyCode::Char).chain([KeyCode::Enter]) {
+            app.handle_key_event(KeyEvent::from(code)).expect("key");
+        }
+        refetch(&mut app);
+        assert_eq!(app.gh_cli.branch().as_deref(), Some("feature/x"));
+        assert_eq!(
+            render(&app, 12),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now | branch feature/x",
+                "╭In Progress (0)────────────────╮╭Concluded Success (0)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No jobs in this category.   ││    No jobs in this category.   ││── ✗ test ──                   │",
+                "│                               ││                                ││─                              │",
+                "│                               ││                                ││1. test [completed (failure)]  │",
+                "│                               ││                                ││7m 00s                         │",
+                "│                               ││                                ││  test                         │",
+                "│                               ││                                ││  c0ffee2 Change number 2      │",
+                "│                               ││                                ││  feature/x by octocat · push  │",
+                "│                               ││                                ││                               │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "←/→ columns  ↑/↓ rows  PgUp/PgDn page  Enter details  Backspace open  S summary  R rerun  q quit",
+            ]
+        );
+    }
+
+    #[test]
+    fn overview_counts_the_jobs_of_each_repository() {
+        let mut data = concluded_runs();
+        let mut other = fixture::run(3, "in_progress", None);
+        other.repo = "octocat/world".to_string();
+        let mut job = fixture::job(31, "deploy", &other, "10:10:00", None);
+        job.repo = other.repo.clone();
+        data.jobs.push(job);
+        data.runs.push(other);
+        let mut app =
+            app_with_client(FixtureClient::new(data).with_repos(&[fixture::REPO, "octocat/world"]));
+        press(&mut app, &[AppEvent::ToggleOverview]);
+        assert_eq!(
+            render(&app, 11),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭Repositories──────────────────────────────────────────────────────────────────────────────────────╮",
+                "│ octocat/hello │ octocat/world                                                                    │",
+                "╰──────────────────────────────────────────────────────────────────────────────────────────────────╯",
+                "╭────────────────────────────────────────────Workspace─────────────────────────────────────────────╮",
+                "│Repository               In progress Success Failed Other Most recent failure                     │",
+                "│octocat/hello            0           2       1      0     test in CI on main, 2026-01-05 10:12    │",
+                "│octocat/world            1           0       0      0     -                                       │",
+                "│`Up`/`Down` to select, `Enter` to open the repository's dashboard, `O` to close.                  │",
+                "╰──────────────────────────────────────────────────────────────────────────────────────────────────╯",
+                "↑/↓ repositories  Enter dashboard  Backspace open actions  O close  q quit",
+            ]
+        );
+
+        press(&mut app, &[AppEvent::NavigateDown, AppEvent::ToggleDetails]);
+        assert!(app.app_state.views.state::<OverviewState>().is_none());
+        assert_eq!(app.active_repo().as_deref(), Some("octocat/world"));
+    }
+
+    #[test]
+    fn command_palette_runs_the_action_picked_by_name() {
+        let mut app = app_with(concluded_runs());
+        let theme = app.theme_preset;
+        press(&mut app, &[AppEvent::OpenCommandPalette]);
+        for code in "theme".chars().map(KeyCode::Char) {
+            app.handle_key_event(KeyEvent::from(code)).expect("key");
+        }
+        assert_eq!(
+            render(&app, 20),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No jobs in this ╭─────────────────────────Commands─────────────────────────╮                   │",
+                "│                   │:theme▏                                                   │                   │",
+                "│                   │                                                          │pleted (failure)]  │",
+                "│                   │Change the theme  T                                       │                   │",
+                "│                   │Approve the deployments of the run  a                     │                   │",
+                "│                   │Show the summary of the job  S                            │ange number 2      │",
+                "│                   │Show the deployments  E                                   │tocat · push       │",
+                "│                   │Show the timeline  L                                      │                   │",
+                "│                   │Switch between relative and absolute timestamps  gt       │                   │",
+                "│                   │                                                          │                   │",
+                "│                   │                                                          │                   │",
+                "│                   │                                                          │                   │",
+                "│                   │                                                          │                   │",
+                "│                   │                                                          │                   │",
+                "│                   │                                                          │                   │",
+                "│                   ╰─ `Up`/`Down` to select, `Enter` to run, `Esc` to close ──╯                   │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "",
+            ]
+        );
+
+        app.handle_key_event(KeyEvent::from(KeyCode::Enter))
+            .expect("key");
+        app.handle_events().expect("command");
+        assert!(app.app_state.command_palette.is_none());
+        assert_ne!(app.theme_preset, theme);
+    }
+
+    #[test]
+    fn refreshes_refused_by_single_sign_on_ask_for_it_to_be_authorized() {
+        let mut app = app_with(concluded_runs());
+        let refused = || {
+            PolledEvent::GitHubDataFetched(Err(
+                "Error fetching GitHub data: octo-org enforces SAML single sign-on, authorize the \
+                 token of the GitHub CLI for it at https://github.com/orgs/octo-org/sso\n\n\
+                 Location: src/gh_cli.rs"
+                    .to_string(),
+            ))
+        };
+        app.events.request(move |_| refused());
+        app.handle_events().expect("fetch result");
+        assert_eq!(
+            render(&app, 12),
+            [
+                "lazyactions octocat/hello | Error: Error fetching GitHub data: octo-org enforces SAML single sign-on",
+                "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No jobs in this ╭─────────────────Single sign-on required──────────────────╮                   │",
+                "│                   │ octo-org enforces SAML single sign-on, which the token of│                   │",
+                "│                   │   the GitHub CLI isn't authorized for. Authorize it at:  │pleted (failure)]  │",
+                "│                   │           https://github.com/orgs/octo-org/sso           │                   │",
+                "│                   │                        then retry.                       │                   │",
+                "│                   │                                                          │ange number 2      │",
+                "│                   ╰─── `Enter` to authorize, `r` to retry, `Esc` to close ───╯tocat · push       │",
+                "│                               ││                                ││                               │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "",
+            ]
+        );
+
+        // Closing the prompt keeps it from coming back on every refresh
+        app.handle_key_event(KeyEvent::from(KeyCode::Esc))
+            .expect("key");
+        app.events.request(move |_| refused());
+        app.handle_events().expect("fetch result");
+        assert!(app.app_state.sso_authorization.is_none());
+    }
+
+    #[test]
+    fn cleared_filters_can_be_restored() {
+        let mut app = app_with(concluded_runs());
+        press(&mut app, &[AppEvent::OpenFilter]);
+        for code in "lint".chars().map(KeyCode::Char).chain([KeyCode::Enter]) {
+            app.handle_key_event(KeyEvent::from(code)).expect("filter");
         }
+        let columns = "╭In Progress (0)────────────────╮╭Concluded Success (1)───────────╮╭Concluded Failure (0)──────────╮";
+        assert_eq!(render(&app, 10)[1], columns);
+
+        press(&mut app, &[AppEvent::ClearFilters]);
+        assert_eq!(
+            render(&app, 10)[1],
+            "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure (1)──────────╮"
+        );
+        press(&mut app, &[AppEvent::RestoreFilters]);
+        assert_eq!(render(&app, 10)[1], columns);
+        assert_eq!(app.app_state.filter.prompt.value(), "lint");
+    }
+
+    #[test]
+    fn scheduled_runs_are_labeled_and_can_be_hidden() {
+        let mut data = concluded_runs();
+        data.runs[0].event = "schedule".to_string();
+        for job in data.jobs.iter_mut().filter(|job| job.run_id == 1) {
+            job.event = "schedule".to_string();
+        }
+        let mut app = app_with(data);
+        assert_eq!(
+            render(&app, 12),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now",
+                "╭In Progress (0)────────────────╮╭Concluded Success (2)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No jobs in this category.   ││── ✓ build ──                   ││── ✗ test ──                   │",
+                "│                               ││─                               ││─                              │",
+                "│                               ││1. build [completed (success)]  ││1. test [completed (failure)]  │",
+                "│                               ││3m 00s                          ││7m 00s                         │",
+                "│                               ││  build                         ││  test                         │",
+                "│                               ││  c0ffee1 Change number 1       ││  c0ffee2 Change number 2      │",
+                "│                               ││  main by octocat · scheduled   ││  main by octocat · push       │",
+                "│                               ││                                ││                               │",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───────────────────────────────╯",
+                "←/→ columns  ↑/↓ rows  PgUp/PgDn page  Enter details  Backspace open  S summary  R rerun  q quit",
+            ]
+        );
+
+        press(&mut app, &[AppEvent::ToggleScheduled]);
+        assert_eq!(
+            render(&app, 12),
+            [
+                "lazyactions octocat/hello | Data updated. | refreshed just now | scheduled runs hidden",
+                "╭In Progress (0)────────────────╮╭Concluded Success (0)───────────╮╭Concluded Failure (1)──────────╮",
+                "│   No jobs in this category.   ││    No jobs in this category.   ││── ✗ test ──                   │",
+                "│                               ││                                ││─                              │",
+                "│                               ││                                ││1. test [completed (failure)]  │",
+                "│                               ││                                ││7m 00s                         │",
+                "│                               ││                                ││  test                         │",
+                "│                               ││                                ││  c0ffee2 Change number 2      │",
+                "│                               ││                                ││  m╭──────────────────────────╮│",
+                "│                               ││                                ││   │Hiding the scheduled runs.││",
+                "╰───────────────────────────────╯╰────────────────────────────────╯╰───╰──────────────────────────╯╯",
+                "←/→ columns  ↑/↓ rows  PgUp/PgDn page  Enter details  Backspace open  S summary  R rerun  q quit",
+            ]
+        );
     }
 }
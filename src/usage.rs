@@ -0,0 +1,134 @@
+use chrono::TimeDelta;
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use crate::gh_cli::GithubJob;
+
+/// The operating systems of runners, as GitHub bills their minutes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RunnerOs {
+    Linux,
+    Windows,
+    MacOs,
+    /// Runners of the repository or organization's own, whose minutes aren't billed.
+    SelfHosted,
+}
+
+impl RunnerOs {
+    /// The runners a job asked for with its `runs-on` labels, Linux when they don't say.
+    pub fn of(job: &GithubJob) -> Self {
+        let has_label = |needle: &str| {
+            job.labels
+                .iter()
+                .any(|label| label.to_ascii_lowercase().contains(needle))
+        };
+        if has_label("self-hosted") {
+            RunnerOs::SelfHosted
+        } else if has_label("windows") {
+            RunnerOs::Windows
+        } else if has_label("macos") {
+            RunnerOs::MacOs
+        } else {
+            RunnerOs::Linux
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RunnerOs::Linux => "Linux",
+            RunnerOs::Windows => "Windows",
+            RunnerOs::MacOs => "macOS",
+            RunnerOs::SelfHosted => "self-hosted",
+        }
+    }
+
+    /// How many times a minute on these runners counts against the included minutes.
+    pub fn multiplier(self) -> u64 {
+        match self {
+            RunnerOs::Linux => 1,
+            RunnerOs::Windows => 2,
+            RunnerOs::MacOs => 10,
+            RunnerOs::SelfHosted => 0,
+        }
+    }
+}
+
+/// How much a workflow used the runners in the runs fetched.
+#[derive(Debug, PartialEq, Eq)]
+pub struct WorkflowUsage {
+    pub workflow_name: String,
+    pub runs: usize,
+    pub jobs: usize,
+    /// The minutes its jobs took, each rounded up to a whole minute as GitHub bills them.
+    pub minutes: u64,
+    /// How long its runs took on average from their first job starting to their last one
+    /// completing.
+    pub average_run: TimeDelta,
+}
+
+/// The usage of the runners by the completed jobs of the runs fetched.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct UsageSummary {
+    /// The workflows, those using the most minutes first.
+    pub workflows: Vec<WorkflowUsage>,
+    /// The minutes used on each operating system.
+    pub minutes_by_os: BTreeMap<RunnerOs, u64>,
+}
+
+impl UsageSummary {
+    /// Sums up how long the completed jobs among `jobs` took, by workflow and by operating
+    /// system.
+    pub fn of<'a>(jobs: impl IntoIterator<Item = &'a GithubJob>) -> Self {
+        let mut summary = UsageSummary::default();
+        // The jobs of each run of each workflow
+        let mut runs: BTreeMap<&str, BTreeMap<u64, Vec<&GithubJob>>> = BTreeMap::new();
+        for job in jobs {
+            if job.status != "completed" || job.completed_at.is_none() {
+                continue;
+            }
+            *summary.minutes_by_os.entry(RunnerOs::of(job)).or_default() += billed_minutes(job);
+            runs.entry(&job.workflow_name)
+                .or_default()
+                .entry(job.run_id)
+                .or_default()
+                .push(job);
+        }
+        summary.workflows = runs
+            .into_iter()
+            .map(|(workflow_name, runs)| {
+                let jobs = runs.values().flatten();
+                let total_run_time: TimeDelta = runs
+                    .values()
+                    .filter_map(|jobs| {
+                        let started_at = jobs.iter().map(|job| job.started_at).min()?;
+                        let completed_at = jobs.iter().filter_map(|job| job.completed_at).max()?;
+                        Some(completed_at - started_at)
+                    })
+                    .sum();
+                WorkflowUsage {
+                    workflow_name: workflow_name.to_string(),
+                    runs: runs.len(),
+                    jobs: jobs.clone().count(),
+                    minutes: jobs.map(|job| billed_minutes(job)).sum(),
+                    average_run: total_run_time / runs.len() as i32,
+                }
+            })
+            .collect();
+        summary
+            .workflows
+            .sort_by_key(|workflow| Reverse(workflow.minutes));
+        summary
+    }
+
+    /// The minutes used across every workflow.
+    pub fn total_minutes(&self) -> u64 {
+        self.minutes_by_os.values().sum()
+    }
+}
+
+/// How long a completed job took, rounded up to a whole minute as GitHub bills it.
+fn billed_minutes(job: &GithubJob) -> u64 {
+    let seconds = job.completed_at.map_or(0, |completed_at| {
+        (completed_at - job.started_at).num_seconds()
+    });
+    u64::try_from(seconds).unwrap_or(0).div_ceil(60)
+}
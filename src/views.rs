@@ -0,0 +1,166 @@
+use std::mem;
+
+use crate::app::{
+    DeployViewState, FlakyTestsState, LogCompareState, OverviewState, RunViewState,
+    TimelineViewState, UsageViewState, WatchState, WorkflowHistoryState,
+};
+
+/// A view shown in place of the job columns.
+#[derive(Debug)]
+pub enum View {
+    Timeline(TimelineViewState),
+    WorkflowHistory(WorkflowHistoryState),
+    FlakyTests(FlakyTestsState),
+    Watch(Box<WatchState>),
+    LogCompare(Box<LogCompareState>),
+    Overview(OverviewState),
+    Deploy(DeployViewState),
+    Usage(UsageViewState),
+    Runs(RunViewState),
+}
+
+/// The state of a kind of view, found among the views open.
+pub trait ViewState: Sized {
+    fn of(view: &View) -> Option<&Self>;
+    fn of_mut(view: &mut View) -> Option<&mut Self>;
+}
+
+/// Implements [`ViewState`] for the state of each kind of view.
+macro_rules! view_states {
+    ($($variant:ident($state:ty)),* $(,)?) => {
+        $(
+            impl ViewState for $state {
+                fn of(view: &View) -> Option<&Self> {
+                    match view {
+                        View::$variant(state) => Some(state),
+                        _ => None,
+                    }
+                }
+
+                fn of_mut(view: &mut View) -> Option<&mut Self> {
+                    match view {
+                        View::$variant(state) => Some(state),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+view_states!(
+    Timeline(TimelineViewState),
+    WorkflowHistory(WorkflowHistoryState),
+    FlakyTests(FlakyTestsState),
+    Watch(WatchState),
+    LogCompare(LogCompareState),
+    Overview(OverviewState),
+    Deploy(DeployViewState),
+    Usage(UsageViewState),
+    Runs(RunViewState),
+);
+
+/// A change to the views open.
+#[derive(Debug)]
+pub enum ViewMessage {
+    /// Shows a view over those open, closing the one of its kind open before.
+    Open(View),
+    /// Closes the view shown, showing the one it was opened over.
+    Close,
+    /// Closes every view, back to the job columns.
+    CloseAll,
+}
+
+/// The views open after a change, the one shown last, with no side effects: fetching what a
+/// view shows is left to the caller.
+pub fn reduce(mut views: Vec<View>, message: ViewMessage) -> Vec<View> {
+    match message {
+        ViewMessage::Open(view) => {
+            views.retain(|open| mem::discriminant(open) != mem::discriminant(&view));
+            views.push(view);
+        }
+        ViewMessage::Close => {
+            views.pop();
+        }
+        ViewMessage::CloseAll => views.clear(),
+    }
+    views
+}
+
+/// The views open in place of the job columns, each over the one it was opened from.
+#[derive(Debug, Default)]
+pub struct ViewStack {
+    views: Vec<View>,
+}
+
+impl ViewStack {
+    /// The view shown, `None` while the job columns are.
+    pub fn shown(&self) -> Option<&View> {
+        self.views.last()
+    }
+
+    /// The state of the open view of its kind, whether it is shown or another is shown over it.
+    pub fn state<T: ViewState>(&self) -> Option<&T> {
+        self.views.iter().find_map(T::of)
+    }
+
+    pub fn state_mut<T: ViewState>(&mut self) -> Option<&mut T> {
+        self.views.iter_mut().find_map(T::of_mut)
+    }
+
+    pub fn update(&mut self, message: ViewMessage) {
+        self.views = reduce(mem::take(&mut self.views), message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(views: &[View]) -> Vec<&'static str> {
+        views
+            .iter()
+            .map(|view| match view {
+                View::Timeline(_) => "timeline",
+                View::Overview(_) => "overview",
+                View::Runs(_) => "runs",
+                _ => "other",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn closing_a_view_shows_the_one_it_was_opened_over() {
+        let views = reduce(
+            Vec::new(),
+            ViewMessage::Open(View::Runs(RunViewState::default())),
+        );
+        let views = reduce(
+            views,
+            ViewMessage::Open(View::Timeline(TimelineViewState::default())),
+        );
+        assert_eq!(kinds(&views), ["runs", "timeline"]);
+
+        let views = reduce(views, ViewMessage::Close);
+        assert_eq!(kinds(&views), ["runs"]);
+        assert!(reduce(views, ViewMessage::Close).is_empty());
+    }
+
+    #[test]
+    fn opening_a_view_again_brings_it_to_the_front() {
+        let views = [
+            View::Overview(OverviewState::default()),
+            View::Runs(RunViewState::default()),
+        ];
+        let views = reduce(
+            Vec::from(views),
+            ViewMessage::Open(View::Overview(OverviewState { selected: 2 })),
+        );
+        assert_eq!(kinds(&views), ["runs", "overview"]);
+        assert!(matches!(
+            views[1],
+            View::Overview(OverviewState { selected: 2 })
+        ));
+        assert!(reduce(views, ViewMessage::CloseAll).is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+use crate::gh_cli::GithubJob;
+
+/// A workflow whose failures on a branch are left out of the failure column until a newer
+/// commit of the branch runs, e.g. while a fix for a known breakage is on its way.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkflowMute {
+    pub repo: String,
+    pub workflow: String,
+    pub branch: String,
+    /// The commit of the failure that was muted.
+    pub head_sha: String,
+    pub muted_at: DateTime<Utc>,
+}
+
+impl WorkflowMute {
+    /// Whether a job is of the muted workflow on the muted branch.
+    fn covers(&self, job: &GithubJob) -> bool {
+        self.repo.eq_ignore_ascii_case(&job.repo)
+            && self.workflow == job.workflow_name
+            && self.branch == job.head_branch
+    }
+
+    /// Whether a job ran for a commit that landed on the branch after the mute.
+    fn is_lifted_by(&self, job: &GithubJob) -> bool {
+        self.repo.eq_ignore_ascii_case(&job.repo)
+            && self.branch == job.head_branch
+            && self.head_sha != job.head_sha
+            && job.started_at > self.muted_at
+    }
+}
+
+/// The muted workflows, persisted as JSON so that a known breakage stays muted across
+/// sessions.
+#[derive(Debug, Default)]
+pub struct WorkflowMutes {
+    pub entries: Vec<WorkflowMute>,
+    /// Where the mutes are saved, `None` to keep them in memory, e.g. in tests.
+    path: Option<PathBuf>,
+}
+
+impl WorkflowMutes {
+    /// Loads the mutes from disk, starting with none if there are none yet or they can't be
+    /// read.
+    pub fn load() -> Self {
+        let path = mutes_path();
+        let entries = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self { entries, path }
+    }
+
+    /// Whether the workflow of a job is muted on its branch.
+    pub fn is_muted(&self, job: &GithubJob) -> bool {
+        self.entries.iter().any(|mute| mute.covers(job))
+    }
+
+    /// Mutes the workflow of a job on its branch, or unmutes it if it was muted, and returns
+    /// whether it is now muted.
+    pub fn toggle(&mut self, job: &GithubJob) -> color_eyre::Result<bool> {
+        let muted_before = self.entries.len();
+        self.entries.retain(|mute| !mute.covers(job));
+        let muted = self.entries.len() == muted_before;
+        if muted {
+            self.entries.push(WorkflowMute {
+                repo: job.repo.clone(),
+                workflow: job.workflow_name.clone(),
+                branch: job.head_branch.clone(),
+                head_sha: job.head_sha.clone(),
+                muted_at: Utc::now(),
+            });
+        }
+        self.save()?;
+        Ok(muted)
+    }
+
+    /// Drops the mutes of the branches that jobs show a newer commit for, and returns them.
+    pub fn lift<'a>(
+        &mut self,
+        jobs: impl Iterator<Item = &'a GithubJob> + Clone,
+    ) -> color_eyre::Result<Vec<WorkflowMute>> {
+        let (lifted, kept): (Vec<_>, Vec<_>) = self
+            .entries
+            .drain(..)
+            .partition(|mute| jobs.clone().any(|job| mute.is_lifted_by(job)));
+        self.entries = kept;
+        if !lifted.is_empty() {
+            self.save()?;
+        }
+        Ok(lifted)
+    }
+
+    fn save(&self) -> color_eyre::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err(format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(&self.entries)?;
+        fs::write(path, json).wrap_err(format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Where the mutes are stored, e.g. `~/.local/share/lazyactions/workflow_mutes.json`, `None`
+/// if there is no data directory to store them in.
+fn mutes_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("lazyactions").join("workflow_mutes.json"))
+}